@@ -0,0 +1,163 @@
+//! `wzcc bridge`: Claude Code statusLine command that records the TTY-keyed
+//! session mapping, then runs the user's original statusLine command.
+//!
+//! Installed by `wzcc install-bridge`. Claude Code runs it every time the
+//! status line is redrawn (about every 300ms) with the session info JSON on
+//! stdin.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::Deserialize;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use super::hook::session_tty;
+use super::install_bridge::BridgeConfig;
+use crate::session_mapping::{MappingResult, SessionMapping};
+
+/// statusLine input (only the fields wzcc uses).
+#[derive(Debug, Deserialize)]
+struct StatusLineInput {
+    #[serde(default)]
+    session_id: String,
+    #[serde(default)]
+    transcript_path: PathBuf,
+    #[serde(default)]
+    cwd: String,
+}
+
+/// Record the session mapping from the statusLine JSON on stdin, then pass
+/// the same JSON to the chained statusLine command, whose output becomes the
+/// status line.
+pub fn bridge() -> Result<()> {
+    let mut input = String::new();
+    std::io::stdin()
+        .read_to_string(&mut input)
+        .context("Failed to read statusLine input")?;
+
+    // Chain even if the mapping can't be written, so the user's status line
+    // keeps working
+    let recorded = match session_tty() {
+        Some(tty) => record_status_line(&input, &tty),
+        None => Ok(()),
+    };
+    if let Some(command) = BridgeConfig::load().chain_command {
+        run_chained(&command, &input)?;
+    }
+    recorded
+}
+
+/// Write the mapping of `tty` from statusLine input JSON. Inputs without a
+/// session ID are ignored.
+fn record_status_line(input: &str, tty: &str) -> Result<()> {
+    let input: StatusLineInput =
+        serde_json::from_str(input).context("Failed to parse statusLine input")?;
+    if input.session_id.is_empty() {
+        return Ok(());
+    }
+    let previous = match SessionMapping::from_tty_with_status(tty) {
+        MappingResult::Valid(m) | MappingResult::Stale(m) => Some(m),
+        MappingResult::NotFound => None,
+    };
+    mapping_from_status_line(input, tty, previous).write()
+}
+
+/// Build the mapping, keeping the last hook event of the same session when
+/// the hook bridge is installed too.
+fn mapping_from_status_line(
+    input: StatusLineInput,
+    tty: &str,
+    previous: Option<SessionMapping>,
+) -> SessionMapping {
+    let last_event = previous
+        .filter(|m| m.session_id == input.session_id)
+        .and_then(|m| m.last_event);
+    SessionMapping {
+        session_id: input.session_id,
+        transcript_path: input.transcript_path,
+        cwd: input.cwd,
+        tty: tty.to_string(),
+        updated_at: Utc::now(),
+        last_event,
+    }
+}
+
+/// Run the chained statusLine command through `sh -c` with `input` on its
+/// stdin and stdout inherited. The input is never interpolated into the
+/// command line.
+fn run_chained(command: &str, input: &str) -> Result<()> {
+    let mut child = Command::new("/bin/sh")
+        .args(["-c", command])
+        .stdin(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run statusLine command: {}", command))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A command that doesn't read its input closes the pipe early
+        let _ = stdin.write_all(input.as_bytes());
+    }
+    let status = child
+        .wait()
+        .context("Failed to wait for statusLine command")?;
+    if !status.success() {
+        anyhow::bail!("statusLine command failed ({}): {}", status, command);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session_mapping::HookEvent;
+
+    fn input(session_id: &str) -> StatusLineInput {
+        serde_json::from_str(&format!(
+            r#"{{"session_id":"{}","transcript_path":"/tmp/it's here.jsonl","cwd":"/tmp/a \"quoted\" dir","model":{{"id":"x"}}}}"#,
+            session_id
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_mapping_from_status_line() {
+        let previous = SessionMapping {
+            session_id: "abc".to_string(),
+            transcript_path: PathBuf::from("/tmp/old.jsonl"),
+            cwd: "/tmp".to_string(),
+            tty: "ttys003".to_string(),
+            updated_at: Utc::now(),
+            last_event: Some(HookEvent {
+                name: "Stop".to_string(),
+                tool_name: None,
+                notification_type: None,
+                message: None,
+                at: Utc::now(),
+            }),
+        };
+
+        let mapping = mapping_from_status_line(input("abc"), "ttys003", Some(previous.clone()));
+        assert_eq!(mapping.cwd, "/tmp/a \"quoted\" dir");
+        assert_eq!(
+            mapping.transcript_path,
+            PathBuf::from("/tmp/it's here.jsonl")
+        );
+        assert_eq!(mapping.last_event, previous.last_event);
+
+        // A new session in the same TTY starts without the old event
+        let mapping = mapping_from_status_line(input("def"), "ttys003", Some(previous));
+        assert!(mapping.last_event.is_none());
+    }
+
+    #[test]
+    fn test_run_chained_passes_input_on_stdin() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("out.txt");
+        let command = format!("cat > '{}'", out.display());
+        run_chained(&command, r#"{"cwd":"$(touch pwned)"}"#).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&out).unwrap(),
+            r#"{"cwd":"$(touch pwned)"}"#
+        );
+        assert!(run_chained("exit 3", "").is_err());
+    }
+}
@@ -0,0 +1,177 @@
+//! `wzcc hook`: Claude Code hook handler that keeps the TTY-keyed session
+//! mapping up to date from hook events.
+//!
+//! Registered by `wzcc install-hooks` for SessionStart, UserPromptSubmit,
+//! PreToolUse, Notification, PreCompact, Stop and SessionEnd. Each event rewrites the
+//! mapping with the event that fired, so wzcc learns about prompts,
+//! permission requests and idle sessions the moment they happen instead of
+//! whenever the status line is redrawn.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::Deserialize;
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::Command;
+
+use crate::session_mapping::{HookEvent, MappingResult, SessionMapping};
+
+/// Hook input Claude Code passes on stdin (only the fields wzcc uses).
+#[derive(Debug, Deserialize)]
+struct HookInput {
+    session_id: String,
+    #[serde(default)]
+    transcript_path: PathBuf,
+    #[serde(default)]
+    cwd: String,
+    hook_event_name: String,
+    tool_name: Option<String>,
+    notification_type: Option<String>,
+    message: Option<String>,
+}
+
+/// Handle one hook event read from stdin.
+///
+/// Prints nothing: the stdout of some hooks (SessionStart, UserPromptSubmit)
+/// is added to Claude's context.
+pub fn hook() -> Result<()> {
+    let mut input = String::new();
+    std::io::stdin()
+        .read_to_string(&mut input)
+        .context("Failed to read hook input")?;
+    let tty = session_tty().context("Could not determine the session's TTY")?;
+    apply_hook_event(&input, &tty)
+}
+
+/// Update (or, at SessionEnd, remove) the mapping of `tty` from a hook
+/// input JSON.
+fn apply_hook_event(input: &str, tty: &str) -> Result<()> {
+    let input: HookInput = serde_json::from_str(input).context("Failed to parse hook input")?;
+    if input.hook_event_name == "SessionEnd" {
+        SessionMapping::remove(tty);
+        return Ok(());
+    }
+    let previous = match SessionMapping::from_tty_with_status(tty) {
+        MappingResult::Valid(m) | MappingResult::Stale(m) => Some(m),
+        MappingResult::NotFound => None,
+    };
+    mapping_from_hook(input, tty, previous).write()
+}
+
+/// Build the mapping for a hook event. A Notification keeps the tool of the
+/// session's preceding PreToolUse, which is the tool asking for permission.
+fn mapping_from_hook(
+    input: HookInput,
+    tty: &str,
+    previous: Option<SessionMapping>,
+) -> SessionMapping {
+    let now = Utc::now();
+    let tool_name = input.tool_name.or_else(|| {
+        let previous = previous.filter(|m| m.session_id == input.session_id)?;
+        let event = previous.last_event?;
+        (input.hook_event_name == "Notification" && event.name == "PreToolUse")
+            .then_some(event.tool_name)?
+    });
+    SessionMapping {
+        session_id: input.session_id,
+        transcript_path: input.transcript_path,
+        cwd: input.cwd,
+        tty: tty.to_string(),
+        updated_at: now,
+        last_event: Some(HookEvent {
+            name: input.hook_event_name,
+            tool_name,
+            notification_type: input.notification_type,
+            message: input.message,
+            at: now,
+        }),
+    }
+}
+
+/// TTY of the Claude Code session, as a mapping file name (e.g., "ttys003",
+/// "pts-0"): the parent process's (Claude Code or the shell it runs commands
+/// in), falling back to this process's own controlling terminal.
+///
+/// stdin is the input pipe, so the TTY has to come from `ps`.
+pub(super) fn session_tty() -> Option<String> {
+    let ppid = std::os::unix::process::parent_id();
+    [ppid, std::process::id()].into_iter().find_map(|pid| {
+        let output = Command::new("ps")
+            .args(["-o", "tty=", "-p", &pid.to_string()])
+            .output()
+            .ok()?;
+        parse_ps_tty(&String::from_utf8_lossy(&output.stdout))
+    })
+}
+
+/// Normalize `ps -o tty=` output. None for processes without a terminal.
+fn parse_ps_tty(output: &str) -> Option<String> {
+    let tty = output.trim();
+    if tty.is_empty() || tty == "?" || tty == "??" {
+        return None;
+    }
+    Some(tty.replace('/', "-"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ps_tty() {
+        assert_eq!(parse_ps_tty("ttys003\n"), Some("ttys003".to_string()));
+        assert_eq!(parse_ps_tty(" pts/4 \n"), Some("pts-4".to_string()));
+        assert_eq!(parse_ps_tty("?\n"), None);
+        assert_eq!(parse_ps_tty("??"), None);
+        assert_eq!(parse_ps_tty(""), None);
+    }
+
+    fn hook_input(json: &str) -> HookInput {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_mapping_from_hook() {
+        let pre_tool_use = mapping_from_hook(
+            hook_input(
+                r#"{
+                    "session_id": "abc-123",
+                    "transcript_path": "/home/me/.claude/projects/-home-me-proj/abc-123.jsonl",
+                    "cwd": "/home/me/proj",
+                    "hook_event_name": "PreToolUse",
+                    "tool_name": "Bash"
+                }"#,
+            ),
+            "pts-4",
+            None,
+        );
+        assert_eq!(pre_tool_use.session_id, "abc-123");
+        assert_eq!(pre_tool_use.cwd, "/home/me/proj");
+        assert_eq!(pre_tool_use.tty, "pts-4");
+
+        let notification = r#"{
+            "session_id": "abc-123",
+            "hook_event_name": "Notification",
+            "notification_type": "permission_prompt",
+            "message": "Claude needs your permission to use Bash"
+        }"#;
+        let mapping = mapping_from_hook(hook_input(notification), "pts-4", Some(pre_tool_use));
+        let event = mapping.last_event.unwrap();
+        assert_eq!(event.name, "Notification");
+        assert_eq!(
+            event.notification_type.as_deref(),
+            Some("permission_prompt")
+        );
+        // The tool asking for permission is carried over from PreToolUse
+        assert_eq!(event.tool_name.as_deref(), Some("Bash"));
+        assert_eq!(event.label(), "Notification Bash");
+
+        let mapping = mapping_from_hook(hook_input(notification), "pts-4", None);
+        assert!(mapping.last_event.unwrap().tool_name.is_none());
+    }
+
+    #[test]
+    fn test_apply_hook_event_rejects_invalid_input() {
+        assert!(apply_hook_event("not json", "test_hook_tty_99999").is_err());
+    }
+}
@@ -2,53 +2,77 @@
 //!
 //! This module provides commands to set up the Claude Code statusLine integration
 //! that enables accurate session tracking when multiple sessions share the same CWD.
+//! `statusLine.command` is pointed at `wzcc bridge`; a statusLine command that was
+//! configured before is kept in `~/.claude/wzcc/bridge.json` and chained.
 
 use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::fs;
-use std::os::unix::fs::PermissionsExt;
 use std::path::PathBuf;
 
-/// The bridge script content.
-/// This script is executed by Claude Code's statusLine feature and writes
-/// session information to a file that wzcc can read.
-const BRIDGE_SCRIPT: &str = r#"#!/bin/bash
-# wzcc statusLine bridge script
-# This script is called by Claude Code's statusLine feature with session info on stdin.
-# It writes the session information to a file that wzcc can read.
-
-# Read JSON input from stdin
-input=$(cat)
-
-# Get TTY name from parent process (since stdin is piped, tty command won't work)
-TTY=$(ps -o tty= -p $PPID 2>/dev/null | tr -d ' ' | tr '/' '-')
-
-# Extract session info from JSON
-SESSION_ID=$(echo "$input" | jq -r '.session_id // empty')
-TRANSCRIPT_PATH=$(echo "$input" | jq -r '.transcript_path // empty')
-CWD=$(echo "$input" | jq -r '.cwd // empty')
-
-# Only write if we have valid session info and TTY
-if [[ -n "$SESSION_ID" && -n "$TTY" ]]; then
-    mkdir -p ~/.claude/wzcc/sessions
-    cat > ~/.claude/wzcc/sessions/${TTY}.json << EOF
-{"session_id":"$SESSION_ID","transcript_path":"$TRANSCRIPT_PATH","cwd":"$CWD","tty":"$TTY","updated_at":"$(date -u +%Y-%m-%dT%H:%M:%SZ)"}
-EOF
-fi
-
-# Chain to original statusLine command if configured
-ORIGINAL_STATUSLINE="{{ORIGINAL_STATUSLINE}}"
-if [[ -n "$ORIGINAL_STATUSLINE" && "$ORIGINAL_STATUSLINE" != "{{ORIGINAL_STATUSLINE}}" && "$ORIGINAL_STATUSLINE" != "" ]]; then
-    echo "$input" | eval "$ORIGINAL_STATUSLINE"
-fi
-"#;
-
-/// Get the path to the bridge script.
+use super::{is_wzcc_command, shell_quote};
+
+/// Bridge settings stored next to the session mappings.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub(super) struct BridgeConfig {
+    /// The user's original statusLine command, run after the mapping is written
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain_command: Option<String>,
+}
+
+impl BridgeConfig {
+    /// Path of the bridge config (~/.claude/wzcc/bridge.json).
+    pub(super) fn path() -> Option<PathBuf> {
+        let home = dirs::home_dir()?;
+        Some(home.join(".claude").join("wzcc").join("bridge.json"))
+    }
+
+    /// Read the bridge config; missing or invalid files give the default.
+    pub(super) fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = Self::path().context("Could not determine home directory")?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Failed to create wzcc directory")?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(self)?)
+            .context("Failed to write bridge config")
+    }
+}
+
+/// Get the path to the legacy bash bridge script (replaced by `wzcc bridge`).
 pub fn bridge_script_path() -> Option<PathBuf> {
     let home = dirs::home_dir()?;
     Some(home.join(".claude").join("wzcc_statusline_bridge.sh"))
 }
 
+/// The original statusLine command chained by a legacy bridge script.
+fn legacy_chain_command(script: &str) -> Option<String> {
+    script.lines().find_map(|line| {
+        let value = line.strip_prefix("ORIGINAL_STATUSLINE=")?.trim_matches('"');
+        (!value.is_empty() && !value.contains("{{ORIGINAL_STATUSLINE}}")).then(|| value.to_string())
+    })
+}
+
+/// Remove the legacy bridge script, returning the command it chained.
+fn remove_legacy_script() -> Result<Option<String>> {
+    let Some(path) = bridge_script_path().filter(|p| p.exists()) else {
+        return Ok(None);
+    };
+    let chain = fs::read_to_string(&path)
+        .ok()
+        .and_then(|script| legacy_chain_command(&script));
+    fs::remove_file(&path).context("Failed to remove legacy bridge script")?;
+    println!("Removed legacy bridge script: {}", path.display());
+    Ok(chain)
+}
+
 /// Get the path to Claude's settings.json.
 pub fn claude_settings_path() -> Option<PathBuf> {
     let home = dirs::home_dir()?;
@@ -56,7 +80,7 @@ pub fn claude_settings_path() -> Option<PathBuf> {
 }
 
 /// Read and parse Claude's settings.json.
-fn read_claude_settings() -> Result<Value> {
+pub(super) fn read_claude_settings() -> Result<Value> {
     let path = claude_settings_path().context("Could not determine home directory")?;
 
     if !path.exists() {
@@ -70,7 +94,7 @@ fn read_claude_settings() -> Result<Value> {
 }
 
 /// Write Claude's settings.json.
-fn write_claude_settings(settings: &Value) -> Result<()> {
+pub(super) fn write_claude_settings(settings: &Value) -> Result<()> {
     let path = claude_settings_path().context("Could not determine home directory")?;
 
     // Ensure parent directory exists
@@ -88,11 +112,12 @@ fn write_claude_settings(settings: &Value) -> Result<()> {
 /// Install the statusLine bridge.
 ///
 /// This function:
-/// 1. Creates the bridge script at ~/.claude/wzcc_statusline_bridge.sh
-/// 2. Updates ~/.claude/settings.json to use the bridge script for statusLine.command
-/// 3. If an existing statusLine.command is configured, chains to it from the bridge
+/// 1. Updates ~/.claude/settings.json to run `wzcc bridge` as statusLine.command
+/// 2. If another statusLine.command is configured, saves it so the bridge chains to it
+/// 3. Replaces the legacy bash bridge script, keeping the command it chained
 pub fn install_bridge() -> Result<()> {
-    let bridge_path = bridge_script_path().context("Could not determine home directory")?;
+    let exe = std::env::current_exe().context("Could not determine the wzcc executable path")?;
+    let bridge_command = format!("{} bridge", shell_quote(&exe.to_string_lossy()));
 
     // Read existing settings
     let mut settings = read_claude_settings()?;
@@ -105,33 +130,19 @@ pub fn install_bridge() -> Result<()> {
         .map(|s| s.to_string());
 
     // Don't chain to ourselves if we're already installed
-    let original_command = match &existing_command {
-        Some(cmd) if cmd.contains("wzcc_statusline_bridge") => None,
-        other => other.clone(),
-    };
-
-    // Create bridge script with optional chaining
-    let script_content = if let Some(ref original) = original_command {
-        BRIDGE_SCRIPT.replace("{{ORIGINAL_STATUSLINE}}", original)
-    } else {
-        BRIDGE_SCRIPT.replace("{{ORIGINAL_STATUSLINE}}", "")
+    let legacy_chain = remove_legacy_script()?;
+    let original_command = match existing_command {
+        Some(cmd) if cmd == bridge_command || is_wzcc_command(&cmd, "bridge") => {
+            BridgeConfig::load().chain_command
+        }
+        Some(cmd) if cmd.contains("wzcc_statusline_bridge") => legacy_chain,
+        other => other,
     };
 
-    // Ensure parent directory exists
-    if let Some(parent) = bridge_path.parent() {
-        fs::create_dir_all(parent).context("Failed to create .claude directory")?;
+    BridgeConfig {
+        chain_command: original_command.clone(),
     }
-
-    // Write bridge script
-    fs::write(&bridge_path, script_content).context("Failed to write bridge script")?;
-
-    // Make script executable
-    let mut perms = fs::metadata(&bridge_path)?.permissions();
-    perms.set_mode(0o755);
-    fs::set_permissions(&bridge_path, perms).context("Failed to set script permissions")?;
-
-    // Update settings.json
-    let bridge_command = bridge_path.to_string_lossy().to_string();
+    .save()?;
 
     // Ensure statusLine object exists
     if settings.get("statusLine").is_none() {
@@ -144,7 +155,6 @@ pub fn install_bridge() -> Result<()> {
 
     println!("Bridge installed successfully!");
     println!();
-    println!("  Bridge script: {}", bridge_path.display());
     println!(
         "  statusLine.command: {}",
         settings["statusLine"]["command"]
@@ -166,37 +176,14 @@ pub fn install_bridge() -> Result<()> {
 /// Uninstall the statusLine bridge.
 ///
 /// This function:
-/// 1. Removes the bridge script from ~/.claude/wzcc_statusline_bridge.sh
-/// 2. Restores the original statusLine.command if one was chained, or removes it
+/// 1. Restores the original statusLine.command if one was chained, or removes it
+/// 2. Removes the bridge config (and the legacy bridge script, if any)
 /// 3. Cleans up the sessions directory
 pub fn uninstall_bridge() -> Result<()> {
-    let bridge_path = bridge_script_path().context("Could not determine home directory")?;
-
-    // Check if bridge script exists and extract original command
-    let original_command = if bridge_path.exists() {
-        let content = fs::read_to_string(&bridge_path).ok();
-        content.and_then(|c| {
-            // Extract the ORIGINAL_STATUSLINE value from the script
-            for line in c.lines() {
-                if line.starts_with("ORIGINAL_STATUSLINE=") {
-                    let value = line
-                        .trim_start_matches("ORIGINAL_STATUSLINE=")
-                        .trim_matches('"');
-                    if !value.is_empty() && !value.contains("{{ORIGINAL_STATUSLINE}}") {
-                        return Some(value.to_string());
-                    }
-                }
-            }
-            None
-        })
-    } else {
-        None
-    };
-
-    // Remove bridge script
-    if bridge_path.exists() {
-        fs::remove_file(&bridge_path).context("Failed to remove bridge script")?;
-        println!("Removed bridge script: {}", bridge_path.display());
+    let legacy_chain = remove_legacy_script()?;
+    let original_command = BridgeConfig::load().chain_command.or(legacy_chain);
+    if let Some(path) = BridgeConfig::path().filter(|p| p.exists()) {
+        fs::remove_file(&path).context("Failed to remove bridge config")?;
     }
 
     // Update settings.json
@@ -261,19 +248,29 @@ mod tests {
     }
 
     #[test]
-    fn test_bridge_script_content() {
-        // Verify the script contains expected elements
-        assert!(BRIDGE_SCRIPT.contains("#!/bin/bash"));
-        assert!(BRIDGE_SCRIPT.contains("jq"));
-        assert!(BRIDGE_SCRIPT.contains("session_id"));
-        assert!(BRIDGE_SCRIPT.contains("transcript_path"));
-        assert!(BRIDGE_SCRIPT.contains("{{ORIGINAL_STATUSLINE}}"));
+    fn test_legacy_chain_command() {
+        let script = "#!/bin/bash\nORIGINAL_STATUSLINE=\"npx ccusage statusline\"\nif [[ ...";
+        assert_eq!(
+            legacy_chain_command(script),
+            Some("npx ccusage statusline".to_string())
+        );
+        assert_eq!(legacy_chain_command("ORIGINAL_STATUSLINE=\"\""), None);
+        assert_eq!(
+            legacy_chain_command("ORIGINAL_STATUSLINE=\"{{ORIGINAL_STATUSLINE}}\""),
+            None
+        );
     }
 
     #[test]
-    fn test_bridge_script_replacement() {
-        let script = BRIDGE_SCRIPT.replace("{{ORIGINAL_STATUSLINE}}", "echo 'hello'");
-        assert!(script.contains("echo 'hello'"));
-        assert!(!script.contains("{{ORIGINAL_STATUSLINE}}"));
+    fn test_bridge_config_serialization() {
+        assert_eq!(
+            serde_json::from_str::<BridgeConfig>("{}").unwrap(),
+            BridgeConfig::default()
+        );
+        let config = BridgeConfig {
+            chain_command: Some("echo 'it''s'".to_string()),
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(serde_json::from_str::<BridgeConfig>(&json).unwrap(), config);
     }
 }
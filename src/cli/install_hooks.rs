@@ -0,0 +1,174 @@
+//! Install/uninstall the hook-based session bridge.
+//!
+//! An alternative to the statusLine bridge: Claude Code hooks call
+//! `wzcc hook` on session events, which writes the TTY-keyed session mapping
+//! together with the event that fired. It needs neither `jq` nor the
+//! `statusLine` setting, and its mappings don't go stale while a session
+//! sits idle.
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+
+use super::install_bridge::{claude_settings_path, read_claude_settings, write_claude_settings};
+use super::{is_wzcc_command, shell_quote};
+
+/// Hook events `wzcc hook` is registered for.
+pub const HOOK_EVENTS: [&str; 7] = [
+    "SessionStart",
+    "UserPromptSubmit",
+    "PreToolUse",
+    "Notification",
+    "PreCompact",
+    "Stop",
+    "SessionEnd",
+];
+
+/// Events whose hooks are filtered by a matcher (tool name / notification
+/// kind); `*` runs the hook for all of them.
+const MATCHER_EVENTS: [&str; 2] = ["PreToolUse", "Notification"];
+
+/// Install the hook bridge: register `<wzcc> hook` for every event in
+/// `HOOK_EVENTS` in ~/.claude/settings.json, replacing earlier wzcc hooks.
+pub fn install_hooks() -> Result<()> {
+    let exe = std::env::current_exe().context("Could not determine the wzcc executable path")?;
+    let command = format!("{} hook", shell_quote(&exe.to_string_lossy()));
+
+    let mut settings = read_claude_settings()?;
+    add_hooks(&mut settings, &command);
+    write_claude_settings(&settings)?;
+
+    let settings_path = claude_settings_path().context("Could not determine home directory")?;
+    println!("Hooks installed successfully!");
+    println!();
+    println!("  Settings: {}", settings_path.display());
+    println!("  Command: {}", command);
+    println!("  Events: {}", HOOK_EVENTS.join(", "));
+    println!();
+    println!("The statusLine bridge is no longer needed; remove it with `wzcc uninstall-bridge`.");
+    println!("Please restart your Claude Code sessions for changes to take effect.");
+
+    Ok(())
+}
+
+/// Uninstall the hook bridge: remove every wzcc hook from settings.json.
+pub fn uninstall_hooks() -> Result<()> {
+    let mut settings = read_claude_settings()?;
+    if remove_hooks(&mut settings) {
+        write_claude_settings(&settings)?;
+        println!("Removed wzcc hooks from settings");
+    } else {
+        println!("No wzcc hooks installed");
+    }
+    println!("Please restart your Claude Code sessions for changes to take effect.");
+    Ok(())
+}
+
+/// Whether a hook command runs `wzcc hook` (from any install location).
+fn is_wzcc_hook(command: &str) -> bool {
+    is_wzcc_command(command, "hook")
+}
+
+/// Register `command` for every hook event, after removing earlier wzcc
+/// hooks so reinstalling (e.g., after moving the binary) doesn't duplicate
+/// them. Other hooks are left untouched.
+fn add_hooks(settings: &mut Value, command: &str) {
+    remove_hooks(settings);
+    if !settings.is_object() {
+        *settings = json!({});
+    }
+    if !settings.get("hooks").is_some_and(Value::is_object) {
+        settings["hooks"] = json!({});
+    }
+    for event in HOOK_EVENTS {
+        let mut matcher = json!({
+            "hooks": [{ "type": "command", "command": command }],
+        });
+        if MATCHER_EVENTS.contains(&event) {
+            matcher["matcher"] = json!("*");
+        }
+        let hooks = &mut settings["hooks"][event];
+        match hooks.as_array_mut() {
+            Some(matchers) => matchers.push(matcher),
+            None => *hooks = json!([matcher]),
+        }
+    }
+}
+
+/// Remove wzcc hooks, dropping matchers, events and the `hooks` object
+/// left empty. Returns whether anything was removed.
+fn remove_hooks(settings: &mut Value) -> bool {
+    let Some(events) = settings.get_mut("hooks").and_then(Value::as_object_mut) else {
+        return false;
+    };
+    let mut removed = false;
+    for matchers in events.values_mut().filter_map(Value::as_array_mut) {
+        for matcher in matchers.iter_mut() {
+            if let Some(hooks) = matcher.get_mut("hooks").and_then(Value::as_array_mut) {
+                let before = hooks.len();
+                hooks.retain(|hook| !hook["command"].as_str().is_some_and(is_wzcc_hook));
+                removed |= hooks.len() != before;
+            }
+        }
+        matchers.retain(|m| !m["hooks"].as_array().is_some_and(Vec::is_empty));
+    }
+    events.retain(|_, matchers| !matchers.as_array().is_some_and(Vec::is_empty));
+    if events.is_empty() {
+        if let Some(root) = settings.as_object_mut() {
+            root.remove("hooks");
+        }
+    }
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_wzcc_hook() {
+        assert!(is_wzcc_hook("'/home/me/.cargo/bin/wzcc' hook"));
+        assert!(is_wzcc_hook("wzcc hook"));
+        assert!(!is_wzcc_hook("wzcc hook --verbose"));
+        assert!(!is_wzcc_hook("/usr/bin/notify-send done"));
+    }
+
+    #[test]
+    fn test_add_and_remove_hooks() {
+        let mut settings = json!({
+            "statusLine": { "command": "my-status" },
+            "hooks": {
+                "Stop": [{ "hooks": [{ "type": "command", "command": "say done" }] }]
+            }
+        });
+
+        add_hooks(&mut settings, "'/Users/John Doe/old/wzcc' hook");
+        add_hooks(&mut settings, "'/new/wzcc' hook");
+        for event in HOOK_EVENTS {
+            let commands: Vec<&str> = settings["hooks"][event]
+                .as_array()
+                .unwrap()
+                .iter()
+                .flat_map(|m| m["hooks"].as_array().unwrap())
+                .filter_map(|h| h["command"].as_str())
+                .filter(|c| is_wzcc_hook(c))
+                .collect();
+            assert_eq!(commands, vec!["'/new/wzcc' hook"], "{}", event);
+        }
+        assert_eq!(settings["hooks"]["PreToolUse"][0]["matcher"], "*");
+        assert!(settings["hooks"]["SessionStart"][0]
+            .get("matcher")
+            .is_none());
+
+        assert!(remove_hooks(&mut settings));
+        assert_eq!(
+            settings,
+            json!({
+                "statusLine": { "command": "my-status" },
+                "hooks": {
+                    "Stop": [{ "hooks": [{ "type": "command", "command": "say done" }] }]
+                }
+            })
+        );
+        assert!(!remove_hooks(&mut settings));
+    }
+}
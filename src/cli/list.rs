@@ -0,0 +1,79 @@
+//! `wzcc list`: the Claude Code sessions running in WezTerm.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex};
+
+use crate::config::Config;
+use crate::discovery::{ClaudeSession, Discovery};
+use crate::transcript::{todo_progress, Todo, TranscriptTails};
+
+/// A session as printed by `wzcc list --json`.
+#[derive(Debug, Serialize)]
+struct ListedSession<'a> {
+    pane_id: u32,
+    workspace: &'a str,
+    cwd: Option<String>,
+    title: &'a str,
+    status: &'static str,
+    session_id: Option<&'a str>,
+    git_branch: Option<&'a str>,
+    /// Latest TodoWrite list
+    todos: &'a [Todo],
+}
+
+impl<'a> From<&'a ClaudeSession> for ListedSession<'a> {
+    fn from(session: &'a ClaudeSession) -> Self {
+        Self {
+            pane_id: session.pane.pane_id,
+            workspace: &session.pane.workspace,
+            cwd: session.pane.cwd_path(),
+            title: &session.pane.title,
+            status: session.status.as_str(),
+            session_id: session.session_id.as_deref(),
+            git_branch: session.git_branch.as_deref(),
+            todos: &session.todos,
+        }
+    }
+}
+
+/// Detect sessions once and print them, as a table or as JSON.
+pub fn list(json: bool) -> Result<()> {
+    let detection = Config::load().unwrap_or_default().status.detection_config();
+    let mut discovery = Discovery::new(Arc::new(Mutex::new(TranscriptTails::default())), detection);
+    // Never cancelled, so a result is always returned
+    let (_, mut sessions) = discovery
+        .full_refresh(false, &AtomicBool::new(false))
+        .context("Session discovery was cancelled")?
+        .context("Failed to detect sessions")?;
+    sessions.sort_by_key(|s| s.pane.pane_id);
+
+    let listed: Vec<ListedSession> = sessions.iter().map(ListedSession::from).collect();
+    if json {
+        println!("{}", serde_json::to_string_pretty(&listed)?);
+        return Ok(());
+    }
+
+    if listed.is_empty() {
+        println!("No Claude Code sessions found");
+        return Ok(());
+    }
+    for session in &listed {
+        let (done, total) = todo_progress(session.todos);
+        let todos = if total == 0 {
+            "-".to_string()
+        } else {
+            format!("{}/{}", done, total)
+        };
+        println!(
+            "#{:<5} {:<12} {:<12} {:<6} {}",
+            session.pane_id,
+            session.workspace,
+            session.status,
+            todos,
+            session.cwd.as_deref().unwrap_or("")
+        );
+    }
+    Ok(())
+}
@@ -1,10 +1,22 @@
+mod bridge;
+mod hook;
 mod install_bridge;
+mod install_hooks;
 mod install_workspace_switcher;
+mod list;
+mod search;
+mod stats;
 
+pub use bridge::bridge;
+pub use hook::hook;
 pub use install_bridge::{install_bridge, uninstall_bridge};
+pub use install_hooks::{install_hooks, uninstall_hooks};
 pub use install_workspace_switcher::{
     install_workspace_switcher, switch_workspace, uninstall_workspace_switcher,
 };
+pub use list::list;
+pub use search::search;
+pub use stats::stats;
 
 use anyhow::{Context, Result};
 use std::io::Read;
@@ -361,6 +373,84 @@ fn shell_quote(s: &str) -> String {
     format!("'{}'", s.replace('\'', "'\\''"))
 }
 
+/// Whether a shell command installed in Claude Code settings runs
+/// `wzcc <subcommand>` (from any install location, quoted or not).
+fn is_wzcc_command(command: &str, subcommand: &str) -> bool {
+    let Some(words) = shell_words(command) else {
+        return false;
+    };
+    match words.as_slice() {
+        [program, sub] => {
+            let program = program.rsplit('/').next().unwrap_or(program);
+            program == "wzcc" && sub == subcommand
+        }
+        _ => false,
+    }
+}
+
+/// Split a shell command into words, undoing POSIX quoting (single quotes,
+/// double quotes and backslash escapes) the way `sh` would.
+/// Returns `None` for an unterminated quote.
+fn shell_words(command: &str) -> Option<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = command.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next()? {
+                        '\'' => break,
+                        c => word.push(c),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '\\' => match chars.next()? {
+                            c @ ('"' | '\\' | '$' | '`') => word.push(c),
+                            '\n' => {}
+                            c => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                        },
+                        c => word.push(c),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                match chars.next() {
+                    Some('\n') => {}
+                    Some(c) => word.push(c),
+                    None => word.push('\\'),
+                }
+            }
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            c => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    Some(words)
+}
+
 /// Parse pane-id from wezterm cli spawn stdout output.
 /// Expected format: a single integer, optionally followed by whitespace/newline.
 fn parse_pane_id(stdout: &str) -> Result<u32> {
@@ -396,6 +486,48 @@ mod tests {
         assert_eq!(shell_quote("$(whoami)"), "'$(whoami)'");
     }
 
+    #[test]
+    fn test_shell_words() {
+        assert_eq!(shell_words("wzcc  hook").unwrap(), ["wzcc", "hook"]);
+        assert_eq!(
+            shell_words("'/Users/John Doe/bin/wzcc' bridge").unwrap(),
+            ["/Users/John Doe/bin/wzcc", "bridge"]
+        );
+        assert_eq!(
+            shell_words(r#""/a \"b\"/wzcc" /c\ d ''"#).unwrap(),
+            ["/a \"b\"/wzcc", "/c d", ""]
+        );
+        assert!(shell_words("'unterminated").is_none());
+    }
+
+    #[test]
+    fn test_shell_words_round_trips_shell_quote() {
+        for s in ["/Users/John Doe/.cargo/bin/wzcc", "it's", "$(whoami)", ""] {
+            assert_eq!(shell_words(&shell_quote(s)).unwrap(), [s]);
+        }
+    }
+
+    #[test]
+    fn test_is_wzcc_command_with_space_in_path() {
+        let exe = "/Users/John Doe/.cargo/bin/wzcc";
+        assert!(is_wzcc_command(
+            &format!("{} bridge", shell_quote(exe)),
+            "bridge"
+        ));
+        assert!(is_wzcc_command(
+            &format!("{} hook", shell_quote(exe)),
+            "hook"
+        ));
+        assert!(!is_wzcc_command(
+            &format!("{} bridge", shell_quote(exe)),
+            "hook"
+        ));
+        assert!(!is_wzcc_command(
+            "'/Users/John Doe/bin/other' bridge",
+            "bridge"
+        ));
+    }
+
     #[test]
     fn test_parse_pane_id_valid() {
         assert_eq!(parse_pane_id("42\n").unwrap(), 42);
@@ -0,0 +1,35 @@
+//! `wzcc search`: full-text search over all Claude Code transcripts.
+
+use anyhow::{Context, Result};
+use std::sync::atomic::AtomicBool;
+
+use crate::transcript::{projects_dir, search_transcripts};
+
+/// Print the turns matching `query` in every transcript, newest first.
+pub fn search(query: &str, limit: usize) -> Result<()> {
+    let dir = projects_dir().context("Could not determine home directory")?;
+    let hits = search_transcripts(&dir, query, limit, &AtomicBool::new(false))?;
+
+    if hits.is_empty() {
+        println!("No matches for '{}' in {}", query, dir.display());
+        return Ok(());
+    }
+
+    for hit in &hits {
+        println!(
+            "{}  {}  {}",
+            hit.local_time(),
+            hit.project_name(),
+            hit.session_id
+        );
+        println!("    {}", hit.snippet);
+    }
+
+    println!(
+        "\n{} matching turn{}. Resume a session with `claude --resume <session-id>` \
+         in its directory, or open it from the TUI (F).",
+        hits.len(),
+        if hits.len() == 1 { "" } else { "s" }
+    );
+    Ok(())
+}
@@ -0,0 +1,115 @@
+//! `wzcc stats`: usage statistics over all Claude Code transcripts.
+
+use anyhow::{Context, Result};
+use std::sync::atomic::AtomicBool;
+
+use crate::transcript::{
+    collect_usage_stats, period_start, projects_dir, StatsGroup, UsageStats, UsageTotals,
+    STATS_COLUMNS,
+};
+
+/// Print usage over the last `days` days (all history if None), as tables
+/// per grouping (only `by` if given) or as JSON.
+pub fn stats(days: Option<u32>, by: Option<StatsGroup>, json: bool) -> Result<()> {
+    let dir = projects_dir().context("Could not determine home directory")?;
+    let stats = collect_usage_stats(&dir, days.map(period_start), &AtomicBool::new(false))?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+        return Ok(());
+    }
+
+    if stats.total.turns == 0 && stats.total.total_tool_calls() == 0 {
+        println!("No usage found in {}", dir.display());
+        return Ok(());
+    }
+
+    let groups: Vec<StatsGroup> = match by {
+        Some(group) => vec![group],
+        None => StatsGroup::ALL.to_vec(),
+    };
+    for group in groups {
+        print_table(&stats, group);
+        println!();
+    }
+    print_tool_calls(&stats.total);
+
+    let period = match stats.since {
+        Some(since) => format!("since {}", since),
+        None => "all history".to_string(),
+    };
+    println!(
+        "\nCosts are estimates at list prices; {}. Cache counts cache writes and reads.",
+        period
+    );
+    Ok(())
+}
+
+/// One table: a row per group, then the total.
+fn print_table(stats: &UsageStats, group: StatsGroup) {
+    let mut rows: Vec<(String, [String; 7])> = stats
+        .groups(group)
+        .iter()
+        .map(|(key, totals)| (key.clone(), totals.cells()))
+        .collect();
+    rows.push(("Total".to_string(), stats.total.cells()));
+
+    let mut widths = STATS_COLUMNS.map(str::len);
+    for (_, cells) in &rows {
+        for (width, cell) in widths.iter_mut().zip(cells) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    let key_width = rows
+        .iter()
+        .map(|(key, _)| key.chars().count())
+        .chain([group.as_str().len()])
+        .max()
+        .unwrap_or(0);
+
+    let header: Vec<String> = STATS_COLUMNS
+        .iter()
+        .zip(widths)
+        .map(|(name, w)| format!("{:>w$}", name))
+        .collect();
+    println!(
+        "{:<key_width$}  {}",
+        capitalize(group.as_str()),
+        header.join("  ")
+    );
+    let last = rows.len() - 1;
+    for (i, (key, cells)) in rows.iter().enumerate() {
+        if i == last {
+            let rule = key_width + widths.iter().map(|w| w + 2).sum::<usize>();
+            println!("{}", "─".repeat(rule));
+        }
+        let cells: Vec<String> = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, w)| format!("{:>w$}", cell))
+            .collect();
+        println!("{:<key_width$}  {}", key, cells.join("  "));
+    }
+}
+
+/// Tool calls by type, most used first.
+fn print_tool_calls(total: &UsageTotals) {
+    let mut tools: Vec<(&String, &usize)> = total.tool_calls.iter().collect();
+    if tools.is_empty() {
+        return;
+    }
+    tools.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+    let width = tools.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    println!("Tool calls");
+    for (name, count) in tools {
+        println!("  {:<width$}  {:>6}", name, count);
+    }
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) => c.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -1,7 +1,10 @@
 use anyhow::{Context, Result};
 use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use crate::transcript::DetectionConfig;
 
 /// A named command that can be spawned in a new pane.
 #[derive(Debug, Deserialize, Clone)]
@@ -12,6 +15,240 @@ pub struct SpawnCommand {
     pub command: Vec<String>,
 }
 
+/// Ordering of sessions within a group in the session list.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    /// Working directory, then pane ID (discovery order).
+    #[default]
+    Default,
+    /// Most urgent first (waiting for approval, then ready, processing, idle).
+    Status,
+    /// Most recently active first (transcript `updated_at`).
+    Activity,
+    /// Git repository root, then working directory.
+    Repo,
+    /// Git branch name.
+    Branch,
+}
+
+impl SortOrder {
+    /// All sort orders in cycling order.
+    pub const ALL: [SortOrder; 5] = [
+        SortOrder::Default,
+        SortOrder::Status,
+        SortOrder::Activity,
+        SortOrder::Repo,
+        SortOrder::Branch,
+    ];
+
+    /// Name used in config.toml and for display.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SortOrder::Default => "default",
+            SortOrder::Status => "status",
+            SortOrder::Activity => "activity",
+            SortOrder::Repo => "repo",
+            SortOrder::Branch => "branch",
+        }
+    }
+
+    /// Next sort order (wraps around).
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|o| *o == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+}
+
+/// Grouping of sessions (header rows) in the session list.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupBy {
+    /// Workspace header, then working directory header.
+    #[default]
+    Workspace,
+    /// Git repository root (linked worktrees are grouped with their main repo).
+    Repo,
+    /// Detected agent process (e.g. `claude`).
+    Agent,
+    /// No headers.
+    Flat,
+}
+
+impl GroupBy {
+    /// All groupings in cycling order.
+    pub const ALL: [GroupBy; 4] = [
+        GroupBy::Workspace,
+        GroupBy::Repo,
+        GroupBy::Agent,
+        GroupBy::Flat,
+    ];
+
+    /// Name used in config.toml and for display.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GroupBy::Workspace => "workspace",
+            GroupBy::Repo => "repo",
+            GroupBy::Agent => "agent",
+            GroupBy::Flat => "flat",
+        }
+    }
+
+    /// Next grouping (wraps around).
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|g| *g == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+}
+
+/// Session list settings (`[list]` table).
+#[derive(Debug, Deserialize, Clone, Copy, Default)]
+#[serde(default)]
+pub struct ListConfig {
+    pub sort: SortOrder,
+    pub group: GroupBy,
+}
+
+/// Built-in color theme.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeName {
+    /// For dark terminal backgrounds.
+    #[default]
+    Dark,
+    /// For light terminal backgrounds.
+    Light,
+    /// Bright colors on a dark background.
+    HighContrast,
+}
+
+impl ThemeName {
+    /// All themes in cycling order.
+    pub const ALL: [ThemeName; 3] = [ThemeName::Dark, ThemeName::Light, ThemeName::HighContrast];
+
+    /// Name used in config.toml and for display.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ThemeName::Dark => "dark",
+            ThemeName::Light => "light",
+            ThemeName::HighContrast => "high_contrast",
+        }
+    }
+
+    /// Next theme (wraps around).
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|t| *t == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+}
+
+/// Color theme settings (`[theme]` table).
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeConfig {
+    /// Built-in theme to start from.
+    pub name: ThemeName,
+    /// Syntax highlighting theme for code blocks (syntect theme name).
+    /// Defaults to one matching the built-in theme.
+    pub syntax_theme: Option<String>,
+    /// Per-role color overrides (e.g. `accent = "blue"`, `muted = "#808080"`).
+    pub colors: BTreeMap<String, String>,
+}
+
+/// Session label settings (`[labels]` table).
+#[derive(Debug, Deserialize, Clone, Copy, Default)]
+#[serde(default)]
+pub struct LabelsConfig {
+    /// Push session labels to the WezTerm tab title when they change.
+    pub sync_tab_title: bool,
+}
+
+/// Git worktree settings (`[worktree]` table).
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct WorktreeConfig {
+    /// Directory new worktrees are created under, one subdirectory per
+    /// branch. Relative paths are resolved against the main worktree root;
+    /// `~/` expands to the home directory. Defaults to `<repo>.worktrees`
+    /// next to the main worktree, so it doesn't show up in its status.
+    pub dir: Option<String>,
+}
+
+impl WorktreeConfig {
+    /// Directory for new worktrees of the repository rooted at `repo_root`.
+    pub fn resolve_dir(&self, repo_root: &Path) -> PathBuf {
+        let Some(dir) = self.dir.as_deref() else {
+            let name = repo_root
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "repo".to_string());
+            return repo_root
+                .parent()
+                .unwrap_or(repo_root)
+                .join(format!("{}.worktrees", name));
+        };
+        if let Some(rest) = dir.strip_prefix("~/") {
+            if let Some(home) = dirs::home_dir() {
+                return home.join(rest);
+            }
+        }
+        repo_root.join(dir)
+    }
+}
+
+/// Status detection settings (`[status]` table).
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct StatusConfig {
+    /// Seconds a tool may run before it is suspected of waiting for
+    /// approval. Defaults to 10.
+    pub waiting_timeout_secs: Option<u64>,
+    /// Per-tool overrides (e.g. `Bash = 30`), added to the built-in ones.
+    pub tool_timeouts: BTreeMap<String, u64>,
+}
+
+impl StatusConfig {
+    /// Transcript status detection settings with these overrides applied.
+    pub fn detection_config(&self) -> DetectionConfig {
+        let mut config = DetectionConfig::default();
+        if let Some(secs) = self.waiting_timeout_secs {
+            config.waiting_timeout_secs = secs;
+        }
+        config.tool_timeouts.extend(self.tool_timeouts.clone());
+        config
+    }
+}
+
+/// One or more keys bound to an action (`"q"` or `["q", "esc"]`).
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyList {
+    /// The key strings (e.g. `"ctrl+u"`, `"g g"`).
+    pub fn keys(&self) -> Vec<&str> {
+        match self {
+            KeyList::One(key) => vec![key.as_str()],
+            KeyList::Many(keys) => keys.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+/// Key binding overrides (`[keys.<mode>]` tables), mapping action names to keys.
+/// Actions not listed keep their default keys.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct KeysConfig {
+    pub sidebar: BTreeMap<String, KeyList>,
+    pub input: BTreeMap<String, KeyList>,
+    pub history: BTreeMap<String, KeyList>,
+    pub live_pane: BTreeMap<String, KeyList>,
+    pub terminal: BTreeMap<String, KeyList>,
+}
+
 #[derive(Debug, Deserialize, Default)]
 pub struct Config {
     /// Legacy field — kept for backward compatibility.
@@ -20,6 +257,30 @@ pub struct Config {
 
     /// Named command list. Each entry has a `name` and `command`.
     pub commands: Option<Vec<SpawnCommand>>,
+
+    /// Session list sort order and grouping.
+    #[serde(default)]
+    pub list: ListConfig,
+
+    /// Session label settings.
+    #[serde(default)]
+    pub labels: LabelsConfig,
+
+    /// Key binding overrides.
+    #[serde(default)]
+    pub keys: KeysConfig,
+
+    /// Color theme and overrides.
+    #[serde(default)]
+    pub theme: ThemeConfig,
+
+    /// Where worktrees created from the add-pane prompt go.
+    #[serde(default)]
+    pub worktree: WorktreeConfig,
+
+    /// Tool timeouts for status detection.
+    #[serde(default)]
+    pub status: StatusConfig,
 }
 
 impl Config {
@@ -99,11 +360,105 @@ impl Config {
         !cmd.command.is_empty() && !cmd.command[0].trim().is_empty()
     }
 
+    /// Persist the session list sort order and grouping to the `[list]` table
+    /// of config.toml, leaving the rest of the file (including comments) intact.
+    pub fn save_list_config(list: &ListConfig) -> Result<()> {
+        Self::save_table_keys(
+            "list",
+            &[("sort", list.sort.as_str()), ("group", list.group.as_str())],
+        )
+    }
+
+    /// Persist the theme name to the `[theme]` table of config.toml.
+    pub fn save_theme_name(name: ThemeName) -> Result<()> {
+        Self::save_table_keys("theme", &[("name", name.as_str())])
+    }
+
+    /// Set string keys of a table in config.toml (created if missing).
+    fn save_table_keys(table: &str, entries: &[(&str, &str)]) -> Result<()> {
+        let path = Self::config_path().context("Could not determine home directory")?;
+
+        let content = if path.exists() {
+            fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read config file: {}", path.display()))?
+        } else {
+            String::new()
+        };
+
+        let updated = upsert_table_keys(&content, table, entries);
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Failed to create config directory")?;
+        }
+        fs::write(&path, updated)
+            .with_context(|| format!("Failed to write config file: {}", path.display()))?;
+
+        Ok(())
+    }
+
     fn config_path() -> Option<PathBuf> {
         dirs::home_dir().map(|d| d.join(".config").join("wzcc").join("config.toml"))
     }
 }
 
+/// Set string `entries` in a TOML `[table]`, editing the text line by line so
+/// comments and formatting elsewhere in the file are preserved.
+///
+/// Existing `key = ...` lines in the table are replaced; missing keys are
+/// appended to the end of the table. The table is appended if absent.
+fn upsert_table_keys(content: &str, table: &str, entries: &[(&str, &str)]) -> String {
+    let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+    let header = format!("[{}]", table);
+
+    let start = lines.iter().position(|l| {
+        let code = l.split('#').next().unwrap_or("").trim();
+        code == header
+    });
+
+    let Some(start) = start else {
+        // Table missing: append it
+        if lines.last().is_some_and(|l| !l.trim().is_empty()) {
+            lines.push(String::new());
+        }
+        lines.push(header);
+        for (key, value) in entries {
+            lines.push(format!("{} = \"{}\"", key, value));
+        }
+        return lines.join("\n") + "\n";
+    };
+
+    // Table body ends at the next table header (or end of file)
+    let mut end = lines[start + 1..]
+        .iter()
+        .position(|l| l.trim().starts_with('['))
+        .map(|i| start + 1 + i)
+        .unwrap_or(lines.len());
+
+    for (key, value) in entries {
+        let new_line = format!("{} = \"{}\"", key, value);
+        let existing = lines[start + 1..end].iter().position(|l| {
+            l.trim()
+                .strip_prefix(key)
+                .is_some_and(|rest| rest.trim_start().starts_with('='))
+        });
+        match existing {
+            Some(i) => lines[start + 1 + i] = new_line,
+            None => {
+                // Insert after the last non-blank line of the table
+                let insert_at = lines[start + 1..end]
+                    .iter()
+                    .rposition(|l| !l.trim().is_empty())
+                    .map(|i| start + 2 + i)
+                    .unwrap_or(start + 1);
+                lines.insert(insert_at, new_line);
+                end += 1;
+            }
+        }
+    }
+
+    lines.join("\n") + "\n"
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -116,6 +471,7 @@ mod tests {
         Config {
             spawn_command,
             commands,
+            ..Default::default()
         }
     }
 
@@ -323,4 +679,202 @@ command = ["codex", "--flag"]
         assert_eq!(cmds.len(), 1);
         assert_eq!(cmds[0].name, "Claude");
     }
+
+    // --- list config tests ---
+
+    #[test]
+    fn test_parse_toml_list_config() {
+        let content = r#"
+[list]
+sort = "status"
+group = "flat"
+"#;
+        let config: Config = toml::from_str(content).unwrap();
+        assert_eq!(config.list.sort, SortOrder::Status);
+        assert_eq!(config.list.group, GroupBy::Flat);
+    }
+
+    #[test]
+    fn test_parse_toml_list_config_defaults() {
+        let config: Config = toml::from_str("[list]\nsort = \"activity\"\n").unwrap();
+        assert_eq!(config.list.sort, SortOrder::Activity);
+        assert_eq!(config.list.group, GroupBy::Workspace);
+
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.list.sort, SortOrder::Default);
+        assert_eq!(config.list.group, GroupBy::Workspace);
+    }
+
+    #[test]
+    fn test_parse_toml_list_config_invalid_value() {
+        let result: std::result::Result<Config, _> = toml::from_str("[list]\nsort = \"bogus\"\n");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_toml_labels_config() {
+        let config: Config = toml::from_str("[labels]\nsync_tab_title = true\n").unwrap();
+        assert!(config.labels.sync_tab_title);
+
+        let config: Config = toml::from_str("").unwrap();
+        assert!(!config.labels.sync_tab_title);
+    }
+
+    #[test]
+    fn test_parse_toml_worktree_config() {
+        let root = Path::new("/repo");
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(
+            config.worktree.resolve_dir(root),
+            PathBuf::from("/repo.worktrees")
+        );
+
+        let config: Config = toml::from_str("[worktree]\ndir = \".worktree\"\n").unwrap();
+        assert_eq!(
+            config.worktree.resolve_dir(root),
+            PathBuf::from("/repo/.worktree")
+        );
+
+        let config: Config = toml::from_str("[worktree]\ndir = \"../wt\"\n").unwrap();
+        assert_eq!(
+            config.worktree.resolve_dir(root),
+            PathBuf::from("/repo/../wt")
+        );
+
+        let config: Config = toml::from_str("[worktree]\ndir = \"/tmp/wt\"\n").unwrap();
+        assert_eq!(config.worktree.resolve_dir(root), PathBuf::from("/tmp/wt"));
+    }
+
+    #[test]
+    fn test_parse_toml_status_config() {
+        let config: Config = toml::from_str("").unwrap();
+        let detection = config.status.detection_config();
+        assert_eq!(detection.waiting_timeout_secs, 10);
+        assert_eq!(detection.timeout_secs(&["Task".to_string()]), 600);
+
+        let toml_str = r#"
+[status]
+waiting_timeout_secs = 20
+
+[status.tool_timeouts]
+Bash = 120
+Task = 30
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let detection = config.status.detection_config();
+        assert_eq!(detection.timeout_secs(&["Edit".to_string()]), 20);
+        assert_eq!(detection.timeout_secs(&["Bash".to_string()]), 120);
+        assert_eq!(detection.timeout_secs(&["Task".to_string()]), 30);
+        assert_eq!(detection.timeout_secs(&["WebFetch".to_string()]), 60);
+    }
+
+    #[test]
+    fn test_parse_toml_keys_config() {
+        let toml_str = r#"
+[keys.sidebar]
+quit = "Q"
+down = ["j", "ctrl+n"]
+
+[keys.live_pane]
+back = []
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(
+            config.keys.sidebar.get("quit"),
+            Some(&KeyList::One("Q".to_string()))
+        );
+        assert_eq!(
+            config.keys.sidebar.get("down").unwrap().keys(),
+            vec!["j", "ctrl+n"]
+        );
+        assert!(config.keys.live_pane.get("back").unwrap().keys().is_empty());
+        assert!(config.keys.input.is_empty());
+    }
+
+    #[test]
+    fn test_parse_toml_keys_unknown_mode() {
+        let result: std::result::Result<Config, _> =
+            toml::from_str("[keys.sidebr]\nquit = \"q\"\n");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_toml_theme_config() {
+        let toml_str = r##"
+[theme]
+name = "high_contrast"
+syntax_theme = "InspiredGitHub"
+
+[theme.colors]
+accent = "blue"
+muted = "#808080"
+"##;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.theme.name, ThemeName::HighContrast);
+        assert_eq!(config.theme.syntax_theme.as_deref(), Some("InspiredGitHub"));
+        assert_eq!(
+            config.theme.colors.get("muted").map(String::as_str),
+            Some("#808080")
+        );
+
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.theme.name, ThemeName::Dark);
+        assert!(config.theme.colors.is_empty());
+
+        let result: std::result::Result<Config, _> =
+            toml::from_str("[theme]\nname = \"solarized\"\n");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_sort_order_and_group_by_cycle() {
+        assert_eq!(SortOrder::Default.next(), SortOrder::Status);
+        assert_eq!(SortOrder::Branch.next(), SortOrder::Default);
+        assert_eq!(GroupBy::Workspace.next(), GroupBy::Repo);
+        assert_eq!(GroupBy::Flat.next(), GroupBy::Workspace);
+        assert_eq!(ThemeName::Dark.next(), ThemeName::Light);
+        assert_eq!(ThemeName::HighContrast.next(), ThemeName::Dark);
+    }
+
+    #[test]
+    fn test_upsert_table_keys_appends_missing_table() {
+        let content = "# my config\n[[commands]]\nname = \"Claude\"\ncommand = [\"claude\"]\n";
+        let updated = upsert_table_keys(content, "list", &[("sort", "status"), ("group", "flat")]);
+        assert!(updated.starts_with("# my config\n"));
+        assert!(updated.ends_with("\n[list]\nsort = \"status\"\ngroup = \"flat\"\n"));
+
+        let config: Config = toml::from_str(&updated).unwrap();
+        assert_eq!(config.list.sort, SortOrder::Status);
+        assert_eq!(config.resolved_commands()[0].name, "Claude");
+    }
+
+    #[test]
+    fn test_upsert_table_keys_replaces_existing_keys() {
+        let content =
+            "[list]\nsort = \"repo\" # keep me?\n\n[[commands]]\nname = \"X\"\ncommand = [\"x\"]\n";
+        let updated = upsert_table_keys(content, "list", &[("sort", "branch"), ("group", "agent")]);
+        let config: Config = toml::from_str(&updated).unwrap();
+        assert_eq!(config.list.sort, SortOrder::Branch);
+        assert_eq!(config.list.group, GroupBy::Agent);
+        // Key inserted inside [list], not after [[commands]]
+        let list_pos = updated.find("[list]").unwrap();
+        let group_pos = updated.find("group = ").unwrap();
+        let commands_pos = updated.find("[[commands]]").unwrap();
+        assert!(list_pos < group_pos && group_pos < commands_pos);
+        assert_eq!(config.resolved_commands()[0].name, "X");
+    }
+
+    #[test]
+    fn test_upsert_table_keys_empty_content() {
+        let updated = upsert_table_keys("", "list", &[("sort", "default")]);
+        assert_eq!(updated, "[list]\nsort = \"default\"\n");
+    }
+
+    #[test]
+    fn test_upsert_table_keys_does_not_touch_similar_keys() {
+        let content = "[list]\nsorting = \"x\"\n";
+        let updated = upsert_table_keys(content, "list", &[("sort", "status")]);
+        assert!(updated.contains("sorting = \"x\""));
+        assert!(updated.contains("sort = \"status\""));
+    }
 }
@@ -1,5 +1,6 @@
+use anyhow::{bail, Context, Result};
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::{Duration, Instant};
 
@@ -74,6 +75,381 @@ pub fn get_git_worktree_name(cwd: &str) -> Option<String> {
         .filter(|s| !s.is_empty())
 }
 
+/// Get the root directory of the repository containing cwd.
+///
+/// For linked worktrees this is the main worktree root, so that all worktrees
+/// of the same repository resolve to the same path.
+pub fn get_git_repo_root(cwd: &str) -> Option<String> {
+    let common_dir = get_git_common_dir(cwd)?;
+    let root = common_dir.parent()?.canonicalize().ok()?;
+    Some(root.to_string_lossy().to_string())
+}
+
+/// Get the git directory shared by all worktrees of the repository
+/// containing cwd (e.g. `<repo>/.git`).
+fn get_git_common_dir(cwd: &str) -> Option<PathBuf> {
+    let output = Command::new("git")
+        .args(["-C", cwd, "rev-parse", "--git-common-dir"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let common_dir = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if common_dir.is_empty() {
+        return None;
+    }
+
+    // git-common-dir may be relative to cwd (e.g. ".git")
+    Some(Path::new(cwd).join(common_dir))
+}
+
+/// Create a linked worktree for `branch` at `<dir>/<branch>`, where `cwd` is
+/// any directory of the repository.
+///
+/// An existing local branch is checked out; otherwise the branch is created
+/// from the HEAD of `cwd`. Returns the path of the new worktree.
+pub fn create_worktree(cwd: &str, dir: &Path, branch: &str) -> Result<PathBuf> {
+    let valid = Command::new("git")
+        .args(["check-ref-format", "--branch", branch])
+        .output()
+        .context("Failed to run git")?;
+    if branch.is_empty() || !valid.status.success() {
+        bail!("Invalid branch name: {:?}", branch);
+    }
+
+    let path = dir.join(branch);
+    if path.exists() {
+        bail!("{} already exists", path.display());
+    }
+
+    let branch_exists = Command::new("git")
+        .args(["-C", cwd, "rev-parse", "--verify", "--quiet"])
+        .arg(format!("refs/heads/{}", branch))
+        .output()
+        .is_ok_and(|o| o.status.success());
+
+    let mut cmd = Command::new("git");
+    cmd.args(["-C", cwd, "worktree", "add"]);
+    if branch_exists {
+        cmd.arg(&path).arg(branch);
+    } else {
+        cmd.args(["-b", branch]).arg(&path);
+    }
+    let output = cmd.output().context("Failed to run git worktree add")?;
+    if !output.status.success() {
+        bail!("{}", git_error(&output.stderr, "git worktree add failed"));
+    }
+    // Best effort: the worktree exists either way
+    let _ = exclude_worktree_dir(cwd, dir);
+    Ok(path)
+}
+
+/// Add `dir` to the repository's `info/exclude` if it lies inside the main
+/// worktree, so its worktrees aren't reported as untracked files there.
+fn exclude_worktree_dir(cwd: &str, dir: &Path) -> Result<()> {
+    let (Some(root), Some(common_dir)) = (get_git_repo_root(cwd), get_git_common_dir(cwd)) else {
+        return Ok(());
+    };
+    let dir = dir.canonicalize()?;
+    let Ok(relative) = dir.strip_prefix(&root) else {
+        return Ok(());
+    };
+    if relative.as_os_str().is_empty() {
+        return Ok(());
+    }
+
+    let pattern = format!("/{}/", relative.to_string_lossy());
+    let exclude = common_dir.join("info").join("exclude");
+    let content = std::fs::read_to_string(&exclude).unwrap_or_default();
+    if content.lines().any(|line| line.trim() == pattern) {
+        return Ok(());
+    }
+    if let Some(parent) = exclude.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let separator = if content.is_empty() || content.ends_with('\n') {
+        ""
+    } else {
+        "\n"
+    };
+    std::fs::write(&exclude, format!("{}{}{}\n", content, separator, pattern))?;
+    Ok(())
+}
+
+/// A linked worktree, as listed by `git worktree list`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Worktree {
+    pub path: PathBuf,
+    /// Checked-out branch (`None` for a detached HEAD)
+    pub branch: Option<String>,
+    /// Whether the worktree directory is gone and can be pruned
+    pub prunable: bool,
+}
+
+/// Parse `git worktree list --porcelain` output into the linked worktrees
+/// (the main worktree, listed first, is skipped).
+pub fn parse_worktree_list(output: &str) -> Vec<Worktree> {
+    output
+        .split("\n\n")
+        .filter_map(|record| {
+            let mut lines = record.lines();
+            let path = PathBuf::from(lines.next()?.strip_prefix("worktree ")?);
+            let mut worktree = Worktree {
+                path,
+                branch: None,
+                prunable: false,
+            };
+            for line in lines {
+                if let Some(branch) = line.strip_prefix("branch ") {
+                    let branch = branch.strip_prefix("refs/heads/").unwrap_or(branch);
+                    worktree.branch = Some(branch.to_string());
+                } else if line == "prunable" || line.starts_with("prunable ") {
+                    worktree.prunable = true;
+                }
+            }
+            Some(worktree)
+        })
+        .skip(1)
+        .collect()
+}
+
+/// List the linked worktrees of the repository rooted at `repo_root`.
+pub fn list_worktrees(repo_root: &str) -> Result<Vec<Worktree>> {
+    let output = Command::new("git")
+        .args(["-C", repo_root, "worktree", "list", "--porcelain"])
+        .output()
+        .context("Failed to run git worktree list")?;
+    if !output.status.success() {
+        bail!("{}", git_error(&output.stderr, "git worktree list failed"));
+    }
+    Ok(parse_worktree_list(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+/// The branch other branches are merged into: `origin/HEAD` if the remote's
+/// default branch is known, else the local `init.defaultBranch`, else the
+/// HEAD of the main worktree.
+pub fn get_default_branch(repo_root: &str) -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(["-C", repo_root])
+            .args(args)
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+
+    if let Some(remote_head) = git(&["symbolic-ref", "-q", "--short", "refs/remotes/origin/HEAD"]) {
+        return remote_head;
+    }
+    git(&["config", "init.defaultBranch"])
+        .filter(|branch| {
+            git(&[
+                "rev-parse",
+                "-q",
+                "--verify",
+                &format!("refs/heads/{}", branch),
+            ])
+            .is_some()
+        })
+        .unwrap_or_else(|| "HEAD".to_string())
+}
+
+/// Whether `branch` is merged into `base` (see `get_default_branch`).
+pub fn is_branch_merged(repo_root: &str, branch: &str, base: &str) -> bool {
+    Command::new("git")
+        .args(["-C", repo_root, "merge-base", "--is-ancestor", branch, base])
+        .output()
+        .is_ok_and(|o| o.status.success())
+}
+
+/// Remove a linked worktree, discarding uncommitted changes if `force`.
+pub fn remove_worktree(repo_root: &str, path: &Path, force: bool) -> Result<()> {
+    let mut cmd = Command::new("git");
+    cmd.args(["-C", repo_root, "worktree", "remove"]);
+    if force {
+        cmd.arg("--force");
+    }
+    let output = cmd
+        .arg(path)
+        .output()
+        .context("Failed to run git worktree remove")?;
+    if !output.status.success() {
+        bail!(
+            "{}",
+            git_error(&output.stderr, "git worktree remove failed")
+        );
+    }
+    Ok(())
+}
+
+/// Delete a local branch, even if it isn't merged.
+pub fn delete_branch(repo_root: &str, branch: &str) -> Result<()> {
+    let output = Command::new("git")
+        .args(["-C", repo_root, "branch", "-D", branch])
+        .output()
+        .context("Failed to run git branch")?;
+    if !output.status.success() {
+        bail!("{}", git_error(&output.stderr, "git branch -D failed"));
+    }
+    Ok(())
+}
+
+/// First line of git's stderr without the `fatal: `/`error: ` prefix.
+fn git_error(stderr: &[u8], fallback: &str) -> String {
+    let stderr = String::from_utf8_lossy(stderr);
+    let line = stderr
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .unwrap_or(fallback);
+    line.trim_start_matches("fatal: ")
+        .trim_start_matches("error: ")
+        .to_string()
+}
+
+/// Working tree and upstream state of a repository.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GitStatus {
+    /// Files with staged changes
+    pub staged: usize,
+    /// Files with unstaged changes
+    pub modified: usize,
+    /// Untracked files
+    pub untracked: usize,
+    /// Files with merge conflicts
+    pub conflicted: usize,
+    /// Commits ahead of / behind the upstream branch (`None` without upstream)
+    pub ahead_behind: Option<(usize, usize)>,
+    /// Number of stash entries
+    pub stashes: usize,
+    /// Id of the HEAD commit (`None` before the first commit)
+    pub head: Option<String>,
+    /// Subject of the HEAD commit (`None` before the first commit)
+    pub last_commit: Option<String>,
+}
+
+impl GitStatus {
+    /// Whether the working tree has uncommitted changes or untracked files.
+    pub fn is_dirty(&self) -> bool {
+        self.staged + self.modified + self.untracked + self.conflicted > 0
+    }
+
+    /// Compact summary (e.g. `+1 !2 ?3 ⇡1 $1`): `+` staged, `!` modified,
+    /// `?` untracked, `=` conflicted, `⇡`/`⇣` ahead/behind, `$` stashes.
+    /// Empty for a clean tree that is in sync with its upstream.
+    pub fn summary(&self) -> String {
+        let (ahead, behind) = self.ahead_behind.unwrap_or_default();
+        [
+            ("+", self.staged),
+            ("!", self.modified),
+            ("?", self.untracked),
+            ("=", self.conflicted),
+            ("⇡", ahead),
+            ("⇣", behind),
+            ("$", self.stashes),
+        ]
+        .iter()
+        .filter(|(_, n)| *n > 0)
+        .map(|(symbol, n)| format!("{}{}", symbol, n))
+        .collect::<Vec<_>>()
+        .join(" ")
+    }
+}
+
+/// Parse `git status --porcelain=v2 --branch --show-stash` output.
+/// `last_commit` is left unset.
+pub fn parse_git_status(output: &str) -> GitStatus {
+    let mut status = GitStatus::default();
+    for line in output.lines() {
+        if let Some(header) = line.strip_prefix("# ") {
+            match header.split_once(' ') {
+                Some(("branch.ab", ab)) => {
+                    // "+<ahead> -<behind>"
+                    let mut counts = ab
+                        .split_whitespace()
+                        .map(|n| n.trim_start_matches(['+', '-']).parse().unwrap_or(0));
+                    let ahead = counts.next().unwrap_or(0);
+                    let behind = counts.next().unwrap_or(0);
+                    status.ahead_behind = Some((ahead, behind));
+                }
+                Some(("stash", n)) => status.stashes = n.trim().parse().unwrap_or(0),
+                Some(("branch.oid", oid)) if oid != "(initial)" => {
+                    status.head = Some(oid.to_string())
+                }
+                _ => {}
+            }
+        } else if let Some(entry) = line.strip_prefix("1 ").or_else(|| line.strip_prefix("2 ")) {
+            // Ordinary or renamed entry: "<XY> ...", '.' means unchanged
+            let mut xy = entry.chars();
+            if xy.next().is_some_and(|x| x != '.') {
+                status.staged += 1;
+            }
+            if xy.next().is_some_and(|y| y != '.') {
+                status.modified += 1;
+            }
+        } else if line.starts_with("u ") {
+            status.conflicted += 1;
+        } else if line.starts_with("? ") {
+            status.untracked += 1;
+        }
+    }
+    status
+}
+
+/// Get the working tree state of the repository containing cwd with a
+/// single `git status` call. `last_commit` is left unset.
+pub fn get_git_status(cwd: &str) -> Option<GitStatus> {
+    // The `# stash` header needs git 2.35+; older versions just omit it
+    let output = Command::new("git")
+        .args([
+            "-C",
+            cwd,
+            "status",
+            "--porcelain=v2",
+            "--branch",
+            "--show-stash",
+        ])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(parse_git_status(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Get the subject of the HEAD commit of the repository containing cwd.
+pub fn get_last_commit(cwd: &str) -> Option<String> {
+    Command::new("git")
+        .args(["-C", cwd, "log", "-1", "--format=%s"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Get the root of the working tree containing cwd (the linked worktree's
+/// own root, unlike `get_git_repo_root`).
+fn get_git_toplevel(cwd: &str) -> Option<String> {
+    let output = Command::new("git")
+        .args(["-C", cwd, "rev-parse", "--show-toplevel"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+    let toplevel = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!toplevel.is_empty()).then_some(toplevel)
+}
+
 /// Cache for git branch lookups with TTL.
 pub struct GitBranchCache {
     entries: HashMap<String, (Option<String>, Instant)>,
@@ -133,3 +509,308 @@ impl GitWorktreeCache {
         worktree
     }
 }
+
+/// Cache for git repository root lookups with TTL.
+pub struct GitRepoRootCache {
+    entries: HashMap<String, (Option<String>, Instant)>,
+    ttl: Duration,
+}
+
+impl GitRepoRootCache {
+    pub fn new(ttl_secs: u64) -> Self {
+        Self {
+            entries: HashMap::new(),
+            ttl: Duration::from_secs(ttl_secs),
+        }
+    }
+
+    pub fn get(&mut self, cwd: &str) -> Option<String> {
+        if let Some((root, fetched_at)) = self.entries.get(cwd) {
+            if fetched_at.elapsed() < self.ttl {
+                return root.clone();
+            }
+        }
+
+        let root = get_git_repo_root(cwd);
+        self.entries
+            .insert(cwd.to_string(), (root.clone(), Instant::now()));
+        root
+    }
+}
+
+/// Cache for git status lookups with TTL.
+///
+/// Entries are keyed by working tree root, so sessions in different
+/// directories of one checkout share a single `git status` call. The HEAD
+/// commit subject is only looked up again when HEAD moves.
+pub struct GitStatusCache {
+    /// Working tree root of each cwd seen (kept until `clear`)
+    roots: HashMap<String, Option<String>>,
+    entries: HashMap<String, (Option<GitStatus>, Instant)>,
+    ttl: Duration,
+}
+
+impl GitStatusCache {
+    pub fn new(ttl_secs: u64) -> Self {
+        Self {
+            roots: HashMap::new(),
+            entries: HashMap::new(),
+            ttl: Duration::from_secs(ttl_secs),
+        }
+    }
+
+    pub fn get(&mut self, cwd: &str) -> Option<GitStatus> {
+        let root = self
+            .roots
+            .entry(cwd.to_string())
+            .or_insert_with(|| get_git_toplevel(cwd))
+            .clone()?;
+
+        let previous = match self.entries.get(&root) {
+            Some((status, fetched_at)) if fetched_at.elapsed() < self.ttl => {
+                return status.clone();
+            }
+            Some((status, _)) => status.as_ref(),
+            None => None,
+        };
+
+        let mut status = get_git_status(&root);
+        if let Some(status) = &mut status {
+            status.last_commit = match previous {
+                Some(previous) if status.head.is_some() && previous.head == status.head => {
+                    previous.last_commit.clone()
+                }
+                _ if status.head.is_some() => get_last_commit(&root),
+                _ => None,
+            };
+        }
+        self.entries.insert(root, (status.clone(), Instant::now()));
+        status
+    }
+
+    pub fn clear(&mut self) {
+        self.roots.clear();
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_git_status() {
+        let output = "\
+# branch.oid 1234567890abcdef
+# branch.head main
+# branch.upstream origin/main
+# branch.ab +2 -1
+# stash 3
+1 M. N... 100644 100644 100644 abc abc src/a.rs
+1 .M N... 100644 100644 100644 abc abc src/b.rs
+1 MM N... 100644 100644 100644 abc abc src/c.rs
+2 R. N... 100644 100644 100644 abc abc R100 new.rs\told.rs
+u UU N... 100644 100644 100644 100644 abc abc abc conflict.rs
+? notes.txt
+? tmp/
+! target/
+";
+        let status = parse_git_status(output);
+        assert_eq!(
+            status,
+            GitStatus {
+                staged: 3,
+                modified: 2,
+                untracked: 2,
+                conflicted: 1,
+                ahead_behind: Some((2, 1)),
+                stashes: 3,
+                head: Some("1234567890abcdef".to_string()),
+                last_commit: None,
+            }
+        );
+        assert!(status.is_dirty());
+        assert_eq!(status.summary(), "+3 !2 ?2 =1 ⇡2 ⇣1 $3");
+    }
+
+    /// Run git in `cwd`, ignoring the user's and the system's git config
+    /// (signing, hooks, templates) so the tests behave the same everywhere.
+    fn git(cwd: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .env("GIT_CONFIG_GLOBAL", "/dev/null")
+            .env("GIT_CONFIG_NOSYSTEM", "1")
+            .args(["-c", "commit.gpgsign=false"])
+            .args(["-c", "user.name=t", "-c", "user.email=t@t"])
+            .arg("-C")
+            .arg(cwd)
+            .args(args)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    #[test]
+    fn test_create_worktree() {
+        let repo = tempfile::tempdir().unwrap();
+        git(repo.path(), &["init", "-q"]);
+        git(
+            repo.path(),
+            &["commit", "-q", "--allow-empty", "-m", "init"],
+        );
+        git(repo.path(), &["branch", "existing"]);
+        let cwd = repo.path().to_str().unwrap();
+        let dir = repo.path().join(".worktree");
+
+        let path = create_worktree(cwd, &dir, "feat/new").unwrap();
+        assert_eq!(path, dir.join("feat/new"));
+        // A worktree directory inside the main worktree is excluded there
+        assert!(!get_git_status(cwd).unwrap().is_dirty());
+        assert_eq!(
+            get_git_branch(path.to_str().unwrap()).as_deref(),
+            Some("feat/new")
+        );
+
+        let path = create_worktree(cwd, &dir, "existing").unwrap();
+        assert_eq!(
+            get_git_branch(path.to_str().unwrap()).as_deref(),
+            Some("existing")
+        );
+
+        assert!(create_worktree(cwd, &dir, "existing").is_err());
+        assert!(create_worktree(cwd, &dir, "bad..name").is_err());
+        assert!(create_worktree(cwd, &dir, "").is_err());
+    }
+
+    #[test]
+    fn test_git_status_cache_shares_working_tree() {
+        let repo = tempfile::tempdir().unwrap();
+        git(repo.path(), &["init", "-q"]);
+        let sub = repo.path().join("src");
+        std::fs::create_dir(&sub).unwrap();
+        let mut cache = GitStatusCache::new(60);
+
+        // No commit yet
+        let status = cache.get(repo.path().to_str().unwrap()).unwrap();
+        assert_eq!(status.head, None);
+        assert_eq!(status.last_commit, None);
+
+        git(
+            repo.path(),
+            &["commit", "-q", "--allow-empty", "-m", "init"],
+        );
+        cache.clear();
+        let status = cache.get(sub.to_str().unwrap()).unwrap();
+        assert_eq!(status.last_commit.as_deref(), Some("init"));
+        assert!(status.head.is_some());
+        // Both directories resolve to one entry
+        assert_eq!(cache.get(repo.path().to_str().unwrap()), Some(status));
+        assert_eq!(cache.entries.len(), 1);
+    }
+
+    #[test]
+    fn test_default_branch_and_merged() {
+        let repo = tempfile::tempdir().unwrap();
+        git(repo.path(), &["init", "-q", "-b", "main"]);
+        git(repo.path(), &["config", "init.defaultBranch", "main"]);
+        git(
+            repo.path(),
+            &["commit", "-q", "--allow-empty", "-m", "init"],
+        );
+        git(repo.path(), &["branch", "feature"]);
+        // The main worktree is on another branch with its own commits
+        git(repo.path(), &["checkout", "-q", "-b", "wip"]);
+        git(repo.path(), &["commit", "-q", "--allow-empty", "-m", "wip"]);
+        git(repo.path(), &["branch", "wip-topic"]);
+        let root = repo.path().to_str().unwrap();
+
+        let base = get_default_branch(root);
+        assert_eq!(base, "main");
+        assert!(is_branch_merged(root, "feature", &base));
+        // Merged into the checked out branch only
+        assert!(!is_branch_merged(root, "wip-topic", &base));
+        assert!(is_branch_merged(root, "wip-topic", "HEAD"));
+
+        // The remote's default branch wins
+        git(
+            repo.path(),
+            &["update-ref", "refs/remotes/origin/wip", "wip"],
+        );
+        git(
+            repo.path(),
+            &[
+                "symbolic-ref",
+                "refs/remotes/origin/HEAD",
+                "refs/remotes/origin/wip",
+            ],
+        );
+        assert_eq!(get_default_branch(root), "origin/wip");
+    }
+
+    #[test]
+    fn test_parse_worktree_list() {
+        let output = "\
+worktree /repo
+HEAD 1111111111111111111111111111111111111111
+branch refs/heads/main
+
+worktree /repo/.worktree/feat/a
+HEAD 2222222222222222222222222222222222222222
+branch refs/heads/feat/a
+
+worktree /repo/.worktree/detached
+HEAD 3333333333333333333333333333333333333333
+detached
+prunable gitdir file points to non-existent location
+
+";
+        assert_eq!(
+            parse_worktree_list(output),
+            vec![
+                Worktree {
+                    path: PathBuf::from("/repo/.worktree/feat/a"),
+                    branch: Some("feat/a".to_string()),
+                    prunable: false,
+                },
+                Worktree {
+                    path: PathBuf::from("/repo/.worktree/detached"),
+                    branch: None,
+                    prunable: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_remove_worktree_and_branch() {
+        let repo = tempfile::tempdir().unwrap();
+        git(repo.path(), &["init", "-q"]);
+        git(
+            repo.path(),
+            &["commit", "-q", "--allow-empty", "-m", "init"],
+        );
+        let root = repo.path().to_str().unwrap();
+        let path = create_worktree(root, &repo.path().join(".worktree"), "done").unwrap();
+
+        let worktrees = list_worktrees(root).unwrap();
+        assert_eq!(worktrees.len(), 1);
+        assert_eq!(worktrees[0].branch.as_deref(), Some("done"));
+        assert!(is_branch_merged(root, "done", "HEAD"));
+
+        std::fs::write(path.join("scratch.txt"), "wip").unwrap();
+        assert!(remove_worktree(root, &path, false).is_err());
+        remove_worktree(root, &path, true).unwrap();
+        delete_branch(root, "done").unwrap();
+        assert!(list_worktrees(root).unwrap().is_empty());
+        assert!(!is_branch_merged(root, "done", "HEAD"));
+    }
+
+    #[test]
+    fn test_parse_git_status_clean_without_upstream() {
+        let output = "# branch.oid (initial)\n# branch.head main\n";
+        let status = parse_git_status(output);
+        assert_eq!(status, GitStatus::default());
+        assert!(!status.is_dirty());
+        assert_eq!(status.summary(), "");
+    }
+}
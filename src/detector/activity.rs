@@ -0,0 +1,150 @@
+//! Cross-checks for sessions the transcript reports as waiting for approval.
+//!
+//! A tool_use without a result looks the same whether Claude Code is asking
+//! for permission or the tool is still executing (e.g., a long
+//! `cargo build`). The live pane and the session's child processes tell the
+//! two apart.
+
+use crate::datasource::{ProcessInfo, ProcessTree};
+
+/// Lines at the bottom of the pane searched for Claude Code's UI.
+const TAIL_LINES: usize = 30;
+
+/// Shells the Bash tool runs commands in.
+const SHELLS: [&str; 5] = ["sh", "bash", "zsh", "fish", "dash"];
+
+/// What the bottom of a Claude Code pane shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaneActivity {
+    /// A permission prompt ("Do you want to proceed?" with numbered options)
+    PermissionPrompt,
+    /// The spinner of a running turn ("esc to interrupt")
+    Running,
+    /// Neither is visible
+    Unknown,
+}
+
+/// Classify the plain text of a pane (`wezterm cli get-text`).
+pub fn pane_activity(text: &str) -> PaneActivity {
+    let tail: Vec<&str> = text
+        .lines()
+        .rev()
+        .filter(|line| !line.trim().is_empty())
+        .take(TAIL_LINES)
+        .collect();
+    let asks = tail.iter().any(|line| line.contains("Do you want to"));
+    if asks && tail.iter().any(|line| is_yes_option(line)) {
+        PaneActivity::PermissionPrompt
+    } else if tail.iter().any(|line| line.contains("esc to interrupt")) {
+        PaneActivity::Running
+    } else {
+        PaneActivity::Unknown
+    }
+}
+
+/// Whether a line is the first option of a permission prompt
+/// (e.g., "│ ❯ 1. Yes").
+fn is_yes_option(line: &str) -> bool {
+    line.trim_start_matches(|c: char| !c.is_ascii_digit())
+        .starts_with("1. Yes")
+}
+
+/// Whether a Claude Code process on `tty` is running a tool command: a
+/// shell it started with `-c`, which is how the Bash tool runs commands.
+/// MCP servers and other helpers Claude Code starts aren't shells.
+pub fn has_running_tool(tree: &ProcessTree, tty: &str) -> bool {
+    tree.processes
+        .values()
+        .filter(|proc| proc.tty.as_deref() == Some(tty) && is_claude(proc))
+        .flat_map(|proc| tree.children.get(&proc.pid).into_iter().flatten())
+        .filter_map(|pid| tree.processes.get(pid))
+        .any(is_command_shell)
+}
+
+fn is_claude(proc: &ProcessInfo) -> bool {
+    proc.command.to_lowercase().contains("claude")
+        || proc
+            .args
+            .as_deref()
+            .is_some_and(|args| args.to_lowercase().contains("claude"))
+}
+
+/// Whether a process is a shell running a command string (`bash -c ...`).
+fn is_command_shell(proc: &ProcessInfo) -> bool {
+    let name = proc.command.rsplit('/').next().unwrap_or(&proc.command);
+    let name = name.trim_start_matches('-');
+    SHELLS.contains(&name)
+        && proc.args.as_deref().is_some_and(|args| {
+            args.split_whitespace()
+                .skip(1)
+                .any(|arg| arg.starts_with('-') && !arg.starts_with("--") && arg.contains('c'))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(pid: u32, ppid: u32, command: &str, args: &str) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            ppid,
+            tty: Some("ttys003".to_string()),
+            command: command.to_string(),
+            args: Some(args.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_pane_activity() {
+        let prompt = "\
+⏺ Bash(rm -rf target)
+╭──────────────────────────────────╮
+│ Bash command                     │
+│   rm -rf target                  │
+│ Do you want to proceed?          │
+│ ❯ 1. Yes                         │
+│   2. No, and tell Claude what to do differently (esc) │
+╰──────────────────────────────────╯
+";
+        assert_eq!(pane_activity(prompt), PaneActivity::PermissionPrompt);
+
+        let running = "\
+⏺ Bash(cargo build --release)
+  ⎿  Running…
+
+✻ Compiling… (125s · esc to interrupt)
+
+╭──────────────────────────────────╮
+│ >                                │
+╰──────────────────────────────────╯
+";
+        assert_eq!(pane_activity(running), PaneActivity::Running);
+        assert_eq!(pane_activity("$ ls\n"), PaneActivity::Unknown);
+    }
+
+    #[test]
+    fn test_has_running_tool() {
+        let mut processes = vec![
+            process(100, 1, "-zsh", "-zsh"),
+            process(200, 100, "claude", "claude"),
+            // MCP server: not a tool command
+            process(300, 200, "node", "node /opt/mcp/server.js"),
+        ];
+        assert!(!has_running_tool(
+            &ProcessTree::build(processes.clone()),
+            "ttys003"
+        ));
+
+        processes.push(process(
+            400,
+            200,
+            "/bin/zsh",
+            "/bin/zsh -c -l source ~/.claude/shell-snapshots/snapshot.sh && eval 'cargo build'",
+        ));
+        processes.push(process(500, 400, "cargo", "cargo build"));
+        let tree = ProcessTree::build(processes);
+        assert!(has_running_tool(&tree, "ttys003"));
+        assert!(!has_running_tool(&tree, "ttys004"));
+    }
+}
@@ -1,5 +1,7 @@
+pub mod activity;
 pub mod identify;
 
+pub use activity::{has_running_tool, pane_activity, PaneActivity};
 pub use identify::ClaudeCodeDetector;
 
 /// Detection reason for Claude Code
@@ -26,6 +28,19 @@ impl DetectionReason {
         }
     }
 
+    /// Agent name for grouping (basename of the detected process).
+    /// Wrapper-launched sessions are suffixed so they stay distinguishable.
+    pub fn agent_name(&self) -> String {
+        match self {
+            DetectionReason::DirectTtyMatch { process_name } => {
+                Self::basename(process_name).to_string()
+            }
+            DetectionReason::WrapperDetected { wrapper_process } => {
+                format!("{} (wrapper)", Self::basename(wrapper_process))
+            }
+        }
+    }
+
     /// Get basename from path (use only first part if spaces exist)
     fn basename(path: &str) -> &str {
         // First split by whitespace and get the first part
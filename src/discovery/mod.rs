@@ -0,0 +1,269 @@
+//! Session discovery: the Claude Code sessions running in WezTerm.
+//!
+//! Lists panes (`wezterm cli list`), builds the process tree (`ps`), detects
+//! Claude Code in each pane and gathers its transcript and git info. The TUI
+//! runs this on its refresh worker; `wzcc list` runs it once.
+
+use crate::cli::WeztermCli;
+use crate::datasource::git::{
+    GitBranchCache, GitRepoRootCache, GitStatus, GitStatusCache, GitWorktreeCache,
+};
+use crate::datasource::ProcessTree;
+use crate::datasource::{
+    PaneDataSource, ProcessDataSource, SystemProcessDataSource, WeztermDataSource,
+};
+use crate::detector::{
+    has_running_tool, pane_activity, ClaudeCodeDetector, DetectionReason, PaneActivity,
+};
+use crate::models::Pane;
+use crate::session_meta::SessionMeta;
+use crate::status_timeline::StatusTimeline;
+use crate::transcript::{
+    detect_session_info, DetectionConfig, SessionInfo, SessionStatus, StatusSource, Subagent,
+    SubagentState, Todo, TranscriptTails, WaitingPrompt,
+};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+/// Claude Code session information
+#[derive(Debug, Clone)]
+pub struct ClaudeSession {
+    pub pane: Pane,
+    pub detected: bool,
+    pub reason: DetectionReason,
+    /// Session status (Processing/Idle/WaitingForUser/Unknown)
+    pub status: SessionStatus,
+    /// Where `status` came from (hook event or transcript heuristics)
+    pub status_source: StatusSource,
+    /// Git branch name
+    pub git_branch: Option<String>,
+    /// Git worktree relative path (only set for linked worktrees)
+    pub git_worktree: Option<String>,
+    /// Git repository root (main worktree root for linked worktrees)
+    pub git_repo_root: Option<String>,
+    /// Working tree and upstream state (staged/modified/untracked counts etc.)
+    pub git_status: Option<GitStatus>,
+    /// Last user prompt (from transcript)
+    pub last_prompt: Option<String>,
+    /// Last assistant output text (from transcript)
+    pub last_output: Option<String>,
+    /// Session ID from statusLine bridge (if available)
+    pub session_id: Option<String>,
+    /// Transcript path from statusLine bridge (if available)
+    pub transcript_path: Option<PathBuf>,
+    /// Last updated time (from transcript file modification time)
+    pub updated_at: Option<SystemTime>,
+    /// Warning message to display in details
+    pub warning: Option<String>,
+    /// Parsed waiting prompt data when status is WaitingForUser.
+    pub waiting_prompt: Option<WaitingPrompt>,
+    /// Subagents of the current prompt (from Task tool calls)
+    pub subagents: Vec<Subagent>,
+    /// Latest TodoWrite list
+    pub todos: Vec<Todo>,
+    /// User-assigned label, color, tags and pin (keyed by session_id)
+    pub meta: SessionMeta,
+    /// Status changes observed by wzcc (keyed by session_id)
+    pub timeline: StatusTimeline,
+}
+
+impl ClaudeSession {
+    /// How long the session has been in its current status, if the change
+    /// into it was observed.
+    pub fn status_elapsed(&self, now: DateTime<Utc>) -> Option<std::time::Duration> {
+        let since = self
+            .timeline
+            .since()
+            .filter(|_| self.timeline.current() == Some(self.status.kind()))?;
+        (now - since).to_std().ok()
+    }
+
+    /// Subagents still running, listed beneath the session.
+    pub fn running_subagents(&self) -> impl Iterator<Item = &Subagent> {
+        self.subagents
+            .iter()
+            .filter(|s| s.state == SubagentState::Running)
+    }
+}
+
+/// Finds the Claude Code sessions running in WezTerm and reads their
+/// transcripts, keeping git info cached between refreshes.
+pub struct Discovery {
+    pane_ds: WeztermDataSource,
+    process_ds: SystemProcessDataSource,
+    detector: ClaudeCodeDetector,
+    git_branch_cache: GitBranchCache,
+    git_worktree_cache: GitWorktreeCache,
+    git_repo_root_cache: GitRepoRootCache,
+    git_status_cache: GitStatusCache,
+    tails: Arc<Mutex<TranscriptTails>>,
+    /// Tool timeouts for status detection
+    detection: DetectionConfig,
+    /// Panes of the sessions found by the last full refresh
+    panes: Vec<Pane>,
+}
+
+impl Discovery {
+    /// Transcripts are read through `tails` and their status detected with
+    /// `detection`.
+    pub fn new(tails: Arc<Mutex<TranscriptTails>>, detection: DetectionConfig) -> Self {
+        Self {
+            pane_ds: WeztermDataSource::new(),
+            process_ds: SystemProcessDataSource::new(),
+            detector: ClaudeCodeDetector::new(),
+            git_branch_cache: GitBranchCache::new(30),
+            git_worktree_cache: GitWorktreeCache::new(30),
+            git_repo_root_cache: GitRepoRootCache::new(30),
+            // Working tree state changes often, so cache it briefly
+            git_status_cache: GitStatusCache::new(10),
+            tails,
+            detection,
+            panes: Vec::new(),
+        }
+    }
+
+    /// Detect sessions and read their transcripts and git info.
+    /// Returns None if cancelled.
+    pub fn full_refresh(
+        &mut self,
+        clear_git_cache: bool,
+        cancel: &AtomicBool,
+    ) -> Option<Result<(Option<String>, Vec<ClaudeSession>)>> {
+        if clear_git_cache {
+            self.git_branch_cache.clear();
+            self.git_status_cache.clear();
+        }
+
+        // Get all panes (single call, also used to extract workspace)
+        let panes = match self.pane_ds.list_panes() {
+            Ok(panes) => panes,
+            Err(e) => return Some(Err(e)),
+        };
+        // Extract workspace from pane list (avoids redundant wezterm CLI call)
+        let workspace = extract_current_workspace(&panes);
+
+        // Build process tree once (optimization)
+        let process_tree = match self.process_ds.build_tree() {
+            Ok(tree) => tree,
+            Err(e) => return Some(Err(e)),
+        };
+        if cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        let mut sessions = Vec::new();
+        for pane in panes {
+            if cancel.load(Ordering::Relaxed) {
+                return None;
+            }
+            // Try to detect Claude Code (reusing process tree)
+            let Ok(Some(reason)) = self.detector.detect_by_tty_with_tree(&pane, &process_tree)
+            else {
+                continue;
+            };
+
+            // Get session info (uses statusLine bridge if available, falls back to CWD-based)
+            let mut info = self.session_info(&pane);
+            if info.unconfirmed_wait().is_some() {
+                cross_check_waiting(&pane, &mut info, Some(&process_tree));
+            }
+            let (git_branch, git_worktree, git_repo_root, git_status) = match pane.cwd_path() {
+                Some(cwd) => (
+                    self.git_branch_cache.get(&cwd),
+                    self.git_worktree_cache.get(&cwd),
+                    self.git_repo_root_cache.get(&cwd),
+                    self.git_status_cache.get(&cwd),
+                ),
+                None => (None, None, None, None),
+            };
+
+            sessions.push(ClaudeSession {
+                pane,
+                detected: true,
+                reason,
+                status: info.status,
+                status_source: info.status_source,
+                git_branch,
+                git_worktree,
+                git_repo_root,
+                git_status,
+                last_prompt: info.last_prompt,
+                last_output: info.last_output,
+                session_id: info.session_id,
+                transcript_path: info.transcript_path,
+                updated_at: info.updated_at,
+                warning: info.warning,
+                waiting_prompt: info.waiting_prompt,
+                subagents: info.subagents,
+                todos: info.todos,
+                meta: SessionMeta::default(),
+                timeline: StatusTimeline::default(),
+            });
+        }
+
+        self.lock_tails().retain_used();
+        self.panes = sessions.iter().map(|s| s.pane.clone()).collect();
+        Some(Ok((workspace, sessions)))
+    }
+
+    /// Re-read transcripts of known sessions. Returns None if cancelled.
+    pub fn transcript_refresh(&mut self, cancel: &AtomicBool) -> Option<Vec<(u32, SessionInfo)>> {
+        let mut infos = Vec::with_capacity(self.panes.len());
+        // Built on the first unconfirmed wait
+        let mut process_tree: Option<Option<ProcessTree>> = None;
+        for pane in &self.panes {
+            if cancel.load(Ordering::Relaxed) {
+                return None;
+            }
+            let mut info = self.session_info(pane);
+            if info.unconfirmed_wait().is_some() {
+                let tree = process_tree.get_or_insert_with(|| self.process_ds.build_tree().ok());
+                cross_check_waiting(pane, &mut info, tree.as_ref());
+            }
+            infos.push((pane.pane_id, info));
+        }
+        Some(infos)
+    }
+
+    fn session_info(&self, pane: &Pane) -> SessionInfo {
+        detect_session_info(pane, &mut self.lock_tails(), &self.detection)
+    }
+
+    fn lock_tails(&self) -> std::sync::MutexGuard<'_, TranscriptTails> {
+        // A panic while holding the lock leaves the tails usable
+        self.tails.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Tell a long-running tool from a permission prompt when the transcript
+/// can't: the live pane shows either the prompt or the spinner, and failing
+/// that, a tool command under the session's Claude process means it is
+/// executing.
+fn cross_check_waiting(pane: &Pane, info: &mut SessionInfo, tree: Option<&ProcessTree>) {
+    let activity = WeztermCli::get_text_plain(pane.pane_id)
+        .map(|text| pane_activity(&text))
+        .unwrap_or(PaneActivity::Unknown);
+    let running = match activity {
+        PaneActivity::PermissionPrompt => false,
+        PaneActivity::Running => true,
+        PaneActivity::Unknown => tree
+            .zip(pane.tty_short())
+            .is_some_and(|(tree, tty)| has_running_tool(tree, &tty)),
+    };
+    if running {
+        info.set_running_tool();
+    }
+}
+
+/// Extract current workspace from pane list.
+fn extract_current_workspace(panes: &[Pane]) -> Option<String> {
+    let current_pane_id = std::env::var("WEZTERM_PANE").ok()?.parse::<u32>().ok()?;
+    panes
+        .iter()
+        .find(|p| p.pane_id == current_pane_id)
+        .map(|p| p.workspace.clone())
+}
@@ -2,8 +2,11 @@ pub mod cli;
 pub mod config;
 pub mod datasource;
 pub mod detector;
+pub mod discovery;
 pub mod models;
 pub mod pty;
 pub mod session_mapping;
+pub mod session_meta;
+pub mod status_timeline;
 pub mod transcript;
 pub mod ui;
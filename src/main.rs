@@ -1,8 +1,10 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use wzcc::cli::{
-    install_bridge, install_workspace_switcher, uninstall_bridge, uninstall_workspace_switcher,
+    bridge, hook, install_bridge, install_hooks, install_workspace_switcher, list, search, stats,
+    uninstall_bridge, uninstall_hooks, uninstall_workspace_switcher,
 };
+use wzcc::transcript::StatsGroup;
 use wzcc::ui::App;
 
 #[derive(Parser)]
@@ -25,10 +27,48 @@ enum Commands {
     InstallBridge,
     /// Uninstall statusLine bridge
     UninstallBridge,
+    /// Install Claude Code hooks as the session bridge (alternative to the statusLine bridge)
+    InstallHooks,
+    /// Uninstall Claude Code hooks
+    UninstallHooks,
+    /// Handle a Claude Code hook event (called by Claude Code)
+    #[command(hide = true)]
+    Hook,
+    /// Record session info from statusLine input and run the chained statusLine command
+    /// (called by Claude Code)
+    #[command(hide = true)]
+    Bridge,
     /// Install workspace switcher for cross-workspace navigation
     InstallWorkspaceSwitcher,
     /// Uninstall workspace switcher
     UninstallWorkspaceSwitcher,
+    /// List the running Claude Code sessions with their status and todo progress
+    List {
+        /// Print JSON (including the todo lists) instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Search prompts and responses in all Claude Code transcripts
+    Search {
+        /// Text to search for (case-insensitive unless it contains uppercase)
+        #[arg(required = true)]
+        query: Vec<String>,
+        /// Maximum number of matching turns to show
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Show turns, tool calls, tokens, estimated cost and active time
+    Stats {
+        /// Only count the last N days (including today)
+        #[arg(short = 'd', long)]
+        days: Option<u32>,
+        /// Show only one grouping
+        #[arg(long, value_parser = ["project", "day", "model"])]
+        by: Option<String>,
+        /// Print JSON instead of tables
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 fn main() -> Result<()> {
@@ -59,12 +99,37 @@ fn main() -> Result<()> {
         Some(Commands::UninstallBridge) => {
             uninstall_bridge()?;
         }
+        Some(Commands::InstallHooks) => {
+            install_hooks()?;
+        }
+        Some(Commands::UninstallHooks) => {
+            uninstall_hooks()?;
+        }
+        Some(Commands::Bridge) => {
+            bridge()?;
+        }
+        Some(Commands::Hook) => {
+            // A failing hook must not get in Claude Code's way
+            if let Err(e) = hook() {
+                eprintln!("wzcc hook: {:#}", e);
+            }
+        }
         Some(Commands::InstallWorkspaceSwitcher) => {
             install_workspace_switcher()?;
         }
         Some(Commands::UninstallWorkspaceSwitcher) => {
             uninstall_workspace_switcher()?;
         }
+        Some(Commands::List { json }) => {
+            list(json)?;
+        }
+        Some(Commands::Search { query, limit }) => {
+            search(&query.join(" "), limit)?;
+        }
+        Some(Commands::Stats { days, by, json }) => {
+            let by = by.as_deref().and_then(StatsGroup::from_name);
+            stats(days, by, json)?;
+        }
     }
 
     Ok(())
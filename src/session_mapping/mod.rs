@@ -10,7 +10,8 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 
-/// Session mapping information written by the statusLine bridge script.
+/// Session mapping information written by the statusLine bridge script or
+/// by `wzcc hook`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionMapping {
     /// Claude Code session ID (UUID)
@@ -23,6 +24,42 @@ pub struct SessionMapping {
     pub tty: String,
     /// Last update timestamp
     pub updated_at: DateTime<Utc>,
+    /// Last Claude Code hook event (only in mappings written by `wzcc hook`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_event: Option<HookEvent>,
+}
+
+/// A Claude Code hook event recorded in a session mapping.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HookEvent {
+    /// Hook event name (e.g., "PreToolUse", "Notification", "Stop")
+    pub name: String,
+    /// Tool about to run (PreToolUse)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_name: Option<String>,
+    /// Notification kind (e.g., "permission_prompt", "idle_prompt")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notification_type: Option<String>,
+    /// Notification text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// When the hook fired
+    pub at: DateTime<Utc>,
+}
+
+impl HookEvent {
+    /// Short description for display (e.g., "PreToolUse Bash",
+    /// "Notification permission_prompt").
+    pub fn label(&self) -> String {
+        match self
+            .tool_name
+            .as_deref()
+            .or(self.notification_type.as_deref())
+        {
+            Some(detail) => format!("{} {}", self.name, detail),
+            None => self.name.clone(),
+        }
+    }
 }
 
 /// Result of reading a session mapping
@@ -30,13 +67,60 @@ pub struct SessionMapping {
 pub enum MappingResult {
     /// Valid, fresh mapping
     Valid(SessionMapping),
-    /// Mapping exists but is stale (>5 minutes old)
+    /// Mapping exists but is stale (see `SessionMapping::is_stale`)
     Stale(SessionMapping),
     /// No mapping exists for this TTY
     NotFound,
 }
 
+/// Minutes without an update after which a statusLine mapping is stale.
+const BRIDGE_MAPPING_TTL_MINUTES: i64 = 5;
+
+/// Hours without a hook event after which a hook mapping is stale.
+const HOOK_MAPPING_TTL_HOURS: i64 = 24;
+
 impl SessionMapping {
+    /// Whether the mapping is too old to trust.
+    ///
+    /// The statusLine bridge rewrites its mapping every 300ms while the
+    /// session is open, so 5 minutes without an update means the session is
+    /// gone. Hook mappings are only written on events, so an open session
+    /// may leave them untouched for a long time; they are removed at
+    /// SessionEnd and, at wzcc startup, for TTYs without a pane. A session
+    /// that crashed or was killed never sends SessionEnd, so hook mappings
+    /// also go stale a day after their last event rather than being applied
+    /// to whatever later runs on the reused TTY.
+    pub fn is_stale(&self, now: DateTime<Utc>) -> bool {
+        let age = now.signed_duration_since(self.updated_at);
+        match self.last_event {
+            None => age.num_minutes() > BRIDGE_MAPPING_TTL_MINUTES,
+            Some(_) => age.num_hours() >= HOOK_MAPPING_TTL_HOURS,
+        }
+    }
+
+    /// Write the mapping to its TTY's file atomically (temp file + rename),
+    /// so readers never see a partial record.
+    pub fn write(&self) -> Result<()> {
+        let path = Self::mapping_file_path(&self.tty)
+            .ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let tmp = path.with_extension(format!("json.{}.tmp", std::process::id()));
+        fs::write(&tmp, serde_json::to_string(self)?)?;
+        fs::rename(&tmp, &path).inspect_err(|_| {
+            let _ = fs::remove_file(&tmp);
+        })?;
+        Ok(())
+    }
+
+    /// Remove the mapping file of a TTY, if any.
+    pub fn remove(tty: &str) {
+        if let Some(path) = Self::mapping_file_path(tty) {
+            let _ = fs::remove_file(path);
+        }
+    }
+
     /// Get the sessions directory path (~/.claude/wzcc/sessions/)
     pub fn sessions_dir() -> Option<PathBuf> {
         let home = dirs::home_dir()?;
@@ -57,7 +141,7 @@ impl SessionMapping {
     ///
     /// # Returns
     /// * `Some(SessionMapping)` if a valid mapping exists and is not stale
-    /// * `None` if no mapping exists, is invalid, or is stale (>5 minutes old)
+    /// * `None` if no mapping exists, is invalid, or is stale
     pub fn from_tty(tty: &str) -> Option<Self> {
         match Self::from_tty_with_status(tty) {
             MappingResult::Valid(mapping) => Some(mapping),
@@ -72,7 +156,7 @@ impl SessionMapping {
     ///
     /// # Returns
     /// * `MappingResult::Valid(mapping)` if a valid mapping exists and is fresh
-    /// * `MappingResult::Stale(mapping)` if mapping exists but is >5 minutes old
+    /// * `MappingResult::Stale(mapping)` if mapping exists but is stale
     /// * `MappingResult::NotFound` if no mapping exists or is invalid
     pub fn from_tty_with_status(tty: &str) -> MappingResult {
         // Normalize TTY name (remove /dev/ prefix if present)
@@ -97,11 +181,7 @@ impl SessionMapping {
             Err(_) => return MappingResult::NotFound,
         };
 
-        // Check if mapping is stale (>5 minutes old)
-        // statusLine updates every 300ms, so 5 minutes without update means session is gone
-        let now = Utc::now();
-        let age = now.signed_duration_since(mapping.updated_at);
-        if age.num_minutes() > 5 {
+        if mapping.is_stale(Utc::now()) {
             return MappingResult::Stale(mapping);
         }
 
@@ -135,10 +215,7 @@ impl SessionMapping {
 
                 if let Ok(content) = fs::read_to_string(&path) {
                     if let Ok(mapping) = serde_json::from_str::<SessionMapping>(&content) {
-                        // Check staleness
-                        let now = Utc::now();
-                        let age = now.signed_duration_since(mapping.updated_at);
-                        if age.num_minutes() <= 5 {
+                        if !mapping.is_stale(Utc::now()) {
                             mappings.push(mapping);
                         } else {
                             // Remove stale mapping
@@ -152,7 +229,7 @@ impl SessionMapping {
         mappings
     }
 
-    /// Clean up stale mapping files (>5 minutes old).
+    /// Clean up stale mapping files.
     ///
     /// This is called periodically to remove mappings from sessions that
     /// have been closed without proper cleanup.
@@ -180,8 +257,7 @@ impl SessionMapping {
             // Try to read and parse the mapping
             if let Ok(content) = fs::read_to_string(&path) {
                 if let Ok(mapping) = serde_json::from_str::<SessionMapping>(&content) {
-                    let age = now.signed_duration_since(mapping.updated_at);
-                    if age.num_minutes() > 5 {
+                    if mapping.is_stale(now) {
                         // Remove stale mapping
                         let _ = fs::remove_file(&path);
                     }
@@ -309,6 +385,7 @@ mod tests {
             cwd: "/tmp/test".to_string(),
             tty: tty.to_string(),
             updated_at: Utc::now() - chrono::Duration::minutes(10),
+            last_event: None,
         };
         fs::write(&path, serde_json::to_string(&mapping).unwrap()).unwrap();
 
@@ -334,6 +411,7 @@ mod tests {
             cwd: "/Users/test/project".to_string(),
             tty: "ttys003".to_string(),
             updated_at: Utc::now(),
+            last_event: None,
         };
 
         let json = serde_json::to_string(&mapping).unwrap();
@@ -344,4 +422,51 @@ mod tests {
         assert_eq!(parsed.cwd, mapping.cwd);
         assert_eq!(parsed.tty, mapping.tty);
     }
+
+    #[test]
+    fn test_hook_mapping_expires_after_a_day() {
+        let old = Utc::now() - chrono::Duration::hours(3);
+        // Record written by the statusLine bridge script (no event)
+        let json = format!(
+            r#"{{"session_id":"s","transcript_path":"/tmp/t.jsonl","cwd":"/tmp","tty":"ttys003","updated_at":"{}"}}"#,
+            old.to_rfc3339()
+        );
+        let mut mapping: SessionMapping = serde_json::from_str(&json).unwrap();
+        assert!(mapping.last_event.is_none());
+        assert!(mapping.is_stale(Utc::now()));
+
+        mapping.last_event = Some(HookEvent {
+            name: "Stop".to_string(),
+            tool_name: None,
+            notification_type: None,
+            message: None,
+            at: old,
+        });
+        assert!(!mapping.is_stale(Utc::now()));
+        // A crashed session never sends SessionEnd
+        assert!(mapping.is_stale(Utc::now() + chrono::Duration::hours(22)));
+        let parsed: SessionMapping =
+            serde_json::from_str(&serde_json::to_string(&mapping).unwrap()).unwrap();
+        assert_eq!(parsed.last_event, mapping.last_event);
+    }
+
+    #[test]
+    fn test_write_and_remove_mapping() {
+        let tty = "test_write_tty_99999";
+        let mapping = SessionMapping {
+            session_id: "written-session-id".to_string(),
+            transcript_path: PathBuf::from("/tmp/it's \"quoted\".jsonl"),
+            cwd: "/tmp/test".to_string(),
+            tty: tty.to_string(),
+            updated_at: Utc::now(),
+            last_event: None,
+        };
+        mapping.write().unwrap();
+        let result = SessionMapping::from_tty(tty);
+        SessionMapping::remove(tty);
+
+        let read = result.expect("mapping should be readable after write");
+        assert_eq!(read.transcript_path, mapping.transcript_path);
+        assert!(SessionMapping::from_tty(tty).is_none());
+    }
 }
@@ -0,0 +1,267 @@
+//! User-assigned session metadata (labels, colors, tags, pins).
+//!
+//! Metadata is keyed by Claude Code session ID (from the statusLine bridge)
+//! and persisted in a small JSON state file so it survives wzcc restarts.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Colors that can be assigned to a session label, in cycling order.
+pub const LABEL_COLORS: [&str; 6] = ["red", "green", "yellow", "blue", "magenta", "cyan"];
+
+/// Metadata assigned to a single session by the user.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionMeta {
+    /// Display label (shown instead of the pane title)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Label color name (one of `LABEL_COLORS`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// Free-form tags (without leading `#`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Pinned sessions are kept at the top of the session list
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pinned: bool,
+}
+
+impl SessionMeta {
+    /// Whether no metadata is set (such entries are not persisted).
+    pub fn is_empty(&self) -> bool {
+        self.label.is_none() && self.color.is_none() && self.tags.is_empty() && !self.pinned
+    }
+
+    /// Set the label from user input. Blank input clears it.
+    pub fn set_label(&mut self, input: &str) {
+        let label = input.trim();
+        self.label = (!label.is_empty()).then(|| label.to_string());
+    }
+
+    /// Tags formatted for editing (e.g. "backend, urgent").
+    pub fn tags_input(&self) -> String {
+        self.tags.join(", ")
+    }
+
+    /// Advance to the next label color; wraps back to no color after the last one.
+    pub fn cycle_color(&mut self) {
+        let next = match self.color.as_deref() {
+            None => Some(0),
+            Some(current) => LABEL_COLORS
+                .iter()
+                .position(|c| *c == current)
+                .map(|i| i + 1)
+                .filter(|&i| i < LABEL_COLORS.len()),
+        };
+        self.color = next.map(|i| LABEL_COLORS[i].to_string());
+    }
+}
+
+/// Parse tags from user input.
+///
+/// Tags are separated by commas or whitespace; a leading `#` is stripped and
+/// duplicates are removed (first occurrence wins).
+pub fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for raw in input.split(|c: char| c == ',' || c.is_whitespace()) {
+        let tag = raw.trim_start_matches('#');
+        if !tag.is_empty() && !tags.iter().any(|t| t == tag) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+/// Session metadata keyed by session ID, backed by a JSON state file.
+#[derive(Debug, Default)]
+pub struct SessionMetaStore {
+    path: Option<PathBuf>,
+    entries: HashMap<String, SessionMeta>,
+}
+
+impl SessionMetaStore {
+    /// Get the state file path (~/.claude/wzcc/session_meta.json)
+    pub fn default_path() -> Option<PathBuf> {
+        let home = dirs::home_dir()?;
+        Some(home.join(".claude").join("wzcc").join("session_meta.json"))
+    }
+
+    /// Load metadata from the default state file.
+    ///
+    /// A missing file yields an empty store. An unreadable or invalid file
+    /// returns Err so the caller can show a warning.
+    pub fn load() -> Result<Self> {
+        match Self::default_path() {
+            Some(path) => Self::load_from(path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Load metadata from `path` (missing file yields an empty store).
+    pub fn load_from(path: PathBuf) -> Result<Self> {
+        let entries = if path.exists() {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", path.display()))?
+        } else {
+            HashMap::new()
+        };
+
+        Ok(Self {
+            path: Some(path),
+            entries,
+        })
+    }
+
+    /// Metadata for a session (None if nothing has been assigned).
+    pub fn get(&self, session_id: &str) -> Option<&SessionMeta> {
+        self.entries.get(session_id)
+    }
+
+    /// Modify a session's metadata and persist the store.
+    ///
+    /// Returns the updated metadata. The in-memory change is kept even if
+    /// writing the state file fails.
+    pub fn update<F>(&mut self, session_id: &str, f: F) -> Result<SessionMeta>
+    where
+        F: FnOnce(&mut SessionMeta),
+    {
+        let mut meta = self.entries.get(session_id).cloned().unwrap_or_default();
+        f(&mut meta);
+
+        if meta.is_empty() {
+            self.entries.remove(session_id);
+        } else {
+            self.entries.insert(session_id.to_string(), meta.clone());
+        }
+
+        self.save()?;
+        Ok(meta)
+    }
+
+    /// Write the store to its state file.
+    fn save(&self) -> Result<()> {
+        let path = self
+            .path
+            .as_deref()
+            .context("Could not determine home directory")?;
+        write_atomic(path, &serde_json::to_string_pretty(&self.entries)?)
+    }
+}
+
+/// Write `content` to `path` via a temporary file so readers never see a
+/// partially written state file.
+pub(crate) fn write_atomic(path: &Path, content: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, content).with_context(|| format!("Failed to write {}", tmp.display()))?;
+    fs::rename(&tmp, path).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_path() {
+        let path = SessionMetaStore::default_path().unwrap();
+        assert!(path.ends_with(".claude/wzcc/session_meta.json"));
+    }
+
+    #[test]
+    fn test_set_label_trims_and_clears() {
+        let mut meta = SessionMeta::default();
+        meta.set_label("  api refactor ");
+        assert_eq!(meta.label.as_deref(), Some("api refactor"));
+        meta.set_label("   ");
+        assert_eq!(meta.label, None);
+    }
+
+    #[test]
+    fn test_parse_tags() {
+        assert_eq!(
+            parse_tags("#backend, urgent  backend,,#"),
+            vec!["backend".to_string(), "urgent".to_string()]
+        );
+        assert!(parse_tags("  ").is_empty());
+    }
+
+    #[test]
+    fn test_cycle_color_wraps_to_none() {
+        let mut meta = SessionMeta::default();
+        meta.cycle_color();
+        assert_eq!(meta.color.as_deref(), Some(LABEL_COLORS[0]));
+        for _ in 1..LABEL_COLORS.len() {
+            meta.cycle_color();
+        }
+        assert_eq!(meta.color.as_deref(), LABEL_COLORS.last().copied());
+        meta.cycle_color();
+        assert_eq!(meta.color, None);
+    }
+
+    #[test]
+    fn test_cycle_color_from_unknown_resets() {
+        let mut meta = SessionMeta {
+            color: Some("chartreuse".to_string()),
+            ..Default::default()
+        };
+        meta.cycle_color();
+        assert_eq!(meta.color, None);
+    }
+
+    #[test]
+    fn test_serialization_skips_defaults() {
+        let meta = SessionMeta {
+            pinned: true,
+            ..Default::default()
+        };
+        assert_eq!(serde_json::to_string(&meta).unwrap(), r#"{"pinned":true}"#);
+        let parsed: SessionMeta = serde_json::from_str("{}").unwrap();
+        assert!(parsed.is_empty());
+    }
+
+    #[test]
+    fn test_store_roundtrip_and_removes_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session_meta.json");
+
+        let mut store = SessionMetaStore::load_from(path.clone()).unwrap();
+        assert!(store.get("abc").is_none());
+
+        let meta = store
+            .update("abc", |m| {
+                m.set_label("api");
+                m.pinned = true;
+            })
+            .unwrap();
+        assert_eq!(meta.label.as_deref(), Some("api"));
+
+        let reloaded = SessionMetaStore::load_from(path.clone()).unwrap();
+        assert_eq!(reloaded.get("abc"), Some(&meta));
+
+        store
+            .update("abc", |m| {
+                m.set_label("");
+                m.pinned = false;
+            })
+            .unwrap();
+        let reloaded = SessionMetaStore::load_from(path).unwrap();
+        assert!(reloaded.get("abc").is_none());
+    }
+
+    #[test]
+    fn test_load_invalid_file_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session_meta.json");
+        fs::write(&path, "not json").unwrap();
+        assert!(SessionMetaStore::load_from(path).is_err());
+    }
+}
@@ -0,0 +1,385 @@
+//! Status timelines: when each session changed status.
+//!
+//! wzcc records every status change it observes, keyed by Claude Code session
+//! ID, and persists the last day of changes in a small JSON state file so
+//! timelines and "waiting for" durations survive wzcc restarts.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use crate::session_meta::write_atomic;
+use crate::transcript::StatusKind;
+
+/// How long status changes are kept (24 hours).
+const RETENTION_SECS: i64 = 24 * 60 * 60;
+/// Changes kept per session; older ones are dropped first.
+const MAX_TRANSITIONS: usize = 500;
+/// A session unobserved for longer than this (e.g. while wzcc wasn't
+/// running) that comes back in another status gets an Unknown gap in its
+/// timeline; one still in the same status keeps it, and when it began.
+const GAP_SECS: i64 = 60;
+/// Minimum time between writes of the state file.
+const SAVE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Prefix of keys for sessions without a session ID. They are keyed by pane
+/// and not persisted, since pane IDs are reused after restarts.
+pub const PANE_KEY_PREFIX: &str = "pane:";
+
+/// A status change.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Transition {
+    pub at: DateTime<Utc>,
+    pub status: StatusKind,
+}
+
+/// The status changes of a session, oldest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusTimeline {
+    transitions: Vec<Transition>,
+    /// When the session was last observed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_seen: Option<DateTime<Utc>>,
+}
+
+impl StatusTimeline {
+    /// Record the status observed at `now`. Returns true if it changed.
+    pub fn record(&mut self, status: StatusKind, now: DateTime<Utc>) -> bool {
+        let mut changed = false;
+        if let Some(seen) = self.last_seen {
+            if now - seen > Duration::seconds(GAP_SECS)
+                && self.current() != Some(StatusKind::Unknown)
+                && self.current() != Some(status)
+            {
+                self.transitions.push(Transition {
+                    at: seen,
+                    status: StatusKind::Unknown,
+                });
+                changed = true;
+            }
+        }
+        if self.current() != Some(status) {
+            self.transitions.push(Transition { at: now, status });
+            changed = true;
+        }
+        self.last_seen = Some(now);
+        if changed {
+            self.prune(now);
+        }
+        changed
+    }
+
+    /// Drop changes before the retention window, keeping the one that was
+    /// current at its start.
+    fn prune(&mut self, now: DateTime<Utc>) {
+        let cutoff = now - Duration::seconds(RETENTION_SECS);
+        let expired = self
+            .transitions
+            .iter()
+            .take_while(|t| t.at < cutoff)
+            .count();
+        let excess = self.transitions.len().saturating_sub(MAX_TRANSITIONS);
+        self.transitions
+            .drain(..expired.saturating_sub(1).max(excess));
+    }
+
+    /// Whether the session hasn't been observed within the retention window.
+    fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.last_seen
+            .is_none_or(|seen| now - seen > Duration::seconds(RETENTION_SECS))
+    }
+
+    /// The latest recorded status.
+    pub fn current(&self) -> Option<StatusKind> {
+        self.transitions.last().map(|t| t.status)
+    }
+
+    /// When the latest recorded status began.
+    pub fn since(&self) -> Option<DateTime<Utc>> {
+        self.transitions.last().map(|t| t.at)
+    }
+
+    /// The status at `at` (None before the first recorded change).
+    pub fn status_at(&self, at: DateTime<Utc>) -> Option<StatusKind> {
+        self.transitions
+            .iter()
+            .rev()
+            .find(|t| t.at <= at)
+            .map(|t| t.status)
+    }
+
+    /// Time spent in each status between `from` and `now`, longest first.
+    pub fn durations(
+        &self,
+        from: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Vec<(StatusKind, std::time::Duration)> {
+        let mut totals: Vec<(StatusKind, Duration)> = Vec::new();
+        for (i, transition) in self.transitions.iter().enumerate() {
+            let start = transition.at.max(from);
+            let end = self
+                .transitions
+                .get(i + 1)
+                .map_or(now, |next| next.at)
+                .min(now);
+            if end <= start {
+                continue;
+            }
+            match totals
+                .iter_mut()
+                .find(|(kind, _)| *kind == transition.status)
+            {
+                Some((_, total)) => *total += end - start,
+                None => totals.push((transition.status, end - start)),
+            }
+        }
+        totals.sort_by_key(|(_, total)| std::cmp::Reverse(*total));
+        totals
+            .into_iter()
+            .map(|(kind, total)| (kind, total.to_std().unwrap_or_default()))
+            .collect()
+    }
+
+    /// The status in each of `cells` equal slices of `from..now` (sampled at
+    /// their middle), for drawing the timeline.
+    pub fn samples(
+        &self,
+        from: DateTime<Utc>,
+        now: DateTime<Utc>,
+        cells: usize,
+    ) -> Vec<Option<StatusKind>> {
+        let span = (now - from).num_milliseconds().max(0);
+        (0..cells)
+            .map(|i| {
+                let offset = span * (2 * i as i64 + 1) / (2 * cells as i64);
+                self.status_at(from + Duration::milliseconds(offset))
+            })
+            .collect()
+    }
+}
+
+/// Status timelines keyed by session ID, backed by a JSON state file.
+#[derive(Debug, Default)]
+pub struct StatusTimelineStore {
+    path: Option<PathBuf>,
+    entries: HashMap<String, StatusTimeline>,
+    /// Whether there are changes not yet written
+    unsaved: bool,
+    last_save: Option<Instant>,
+}
+
+impl StatusTimelineStore {
+    /// Get the state file path (~/.claude/wzcc/status_timeline.json)
+    pub fn default_path() -> Option<PathBuf> {
+        let home = dirs::home_dir()?;
+        Some(
+            home.join(".claude")
+                .join("wzcc")
+                .join("status_timeline.json"),
+        )
+    }
+
+    /// Load timelines from the default state file.
+    ///
+    /// A missing file yields an empty store. An unreadable or invalid file
+    /// returns Err so the caller can show a warning.
+    pub fn load() -> Result<Self> {
+        match Self::default_path() {
+            Some(path) => Self::load_from(path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Load timelines from `path` (missing file yields an empty store).
+    pub fn load_from(path: PathBuf) -> Result<Self> {
+        let entries = if path.exists() {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", path.display()))?
+        } else {
+            HashMap::new()
+        };
+
+        Ok(Self {
+            path: Some(path),
+            entries,
+            ..Default::default()
+        })
+    }
+
+    /// Timeline of a session (None if it was never observed).
+    pub fn get(&self, key: &str) -> Option<&StatusTimeline> {
+        self.entries.get(key)
+    }
+
+    /// Record the status of a session observed at `now`.
+    /// Returns true if it changed.
+    pub fn record(&mut self, key: &str, status: StatusKind, now: DateTime<Utc>) -> bool {
+        let changed = self
+            .entries
+            .entry(key.to_string())
+            .or_default()
+            .record(status, now);
+        self.unsaved |= changed && !key.starts_with(PANE_KEY_PREFIX);
+        changed
+    }
+
+    /// Write the store if it changed and wasn't written recently.
+    pub fn save_if_due(&mut self) -> Result<()> {
+        if self.unsaved && self.last_save.is_none_or(|t| t.elapsed() >= SAVE_INTERVAL) {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    /// Write the store to its state file, dropping sessions that haven't
+    /// been seen for a day.
+    pub fn save(&mut self) -> Result<()> {
+        let now = Utc::now();
+        self.entries.retain(|_, timeline| !timeline.is_expired(now));
+        self.unsaved = false;
+        self.last_save = Some(Instant::now());
+
+        let path = self
+            .path
+            .as_deref()
+            .context("Could not determine home directory")?;
+        let persisted: HashMap<&String, &StatusTimeline> = self
+            .entries
+            .iter()
+            .filter(|(key, _)| !key.starts_with(PANE_KEY_PREFIX))
+            .collect();
+        write_atomic(path, &serde_json::to_string_pretty(&persisted)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000 + secs, 0).unwrap()
+    }
+
+    #[test]
+    fn test_default_path() {
+        let path = StatusTimelineStore::default_path().unwrap();
+        assert!(path.ends_with(".claude/wzcc/status_timeline.json"));
+    }
+
+    #[test]
+    fn test_record_only_changes() {
+        let mut timeline = StatusTimeline::default();
+        assert!(timeline.record(StatusKind::Processing, at(0)));
+        assert!(!timeline.record(StatusKind::Processing, at(5)));
+        assert!(timeline.record(StatusKind::Waiting, at(10)));
+        assert!(!timeline.record(StatusKind::Waiting, at(15)));
+
+        assert_eq!(timeline.current(), Some(StatusKind::Waiting));
+        assert_eq!(timeline.since(), Some(at(10)));
+        assert_eq!(timeline.status_at(at(-1)), None);
+        assert_eq!(timeline.status_at(at(7)), Some(StatusKind::Processing));
+    }
+
+    #[test]
+    fn test_record_marks_unobserved_gap() {
+        let mut timeline = StatusTimeline::default();
+        timeline.record(StatusKind::Idle, at(0));
+        // wzcc wasn't running for 10 minutes
+        assert!(timeline.record(StatusKind::Processing, at(600)));
+        assert_eq!(timeline.status_at(at(300)), Some(StatusKind::Unknown));
+        assert_eq!(timeline.since(), Some(at(600)));
+    }
+
+    #[test]
+    fn test_record_keeps_status_unchanged_across_gap() {
+        let mut timeline = StatusTimeline::default();
+        timeline.record(StatusKind::Waiting, at(0));
+        // Still waiting after wzcc restarts: keeps waiting since the start
+        assert!(!timeline.record(StatusKind::Waiting, at(600)));
+        assert_eq!(timeline.since(), Some(at(0)));
+        assert_eq!(timeline.status_at(at(300)), Some(StatusKind::Waiting));
+    }
+
+    #[test]
+    fn test_prune_keeps_status_at_window_start() {
+        let mut timeline = StatusTimeline::default();
+        timeline.record(StatusKind::Processing, at(0));
+        timeline.record(StatusKind::Idle, at(30));
+        let day = RETENTION_SECS;
+        // Unobserved for a day: Unknown from 30s on, which is kept as the
+        // status at the start of the window
+        timeline.record(StatusKind::Processing, at(day + 40));
+        assert_eq!(timeline.transitions.len(), 2);
+        assert_eq!(timeline.status_at(at(40)), Some(StatusKind::Unknown));
+        assert_eq!(timeline.current(), Some(StatusKind::Processing));
+    }
+
+    #[test]
+    fn test_durations_and_samples() {
+        let mut timeline = StatusTimeline::default();
+        timeline.record(StatusKind::Processing, at(0));
+        timeline.record(StatusKind::Waiting, at(40));
+        timeline.record(StatusKind::Processing, at(50));
+
+        let durations = timeline.durations(at(-100), at(60));
+        assert_eq!(
+            durations,
+            vec![
+                (StatusKind::Processing, std::time::Duration::from_secs(50)),
+                (StatusKind::Waiting, std::time::Duration::from_secs(10)),
+            ]
+        );
+        // Clipped to the window
+        assert_eq!(
+            timeline.durations(at(45), at(60))[0],
+            (StatusKind::Processing, std::time::Duration::from_secs(10))
+        );
+
+        // 10s cells, sampled at -15s, -5s, 5s, ...
+        let samples = timeline.samples(at(-20), at(60), 8);
+        let processing = Some(StatusKind::Processing);
+        assert_eq!(
+            samples,
+            vec![
+                None,
+                None,
+                processing,
+                processing,
+                processing,
+                processing,
+                Some(StatusKind::Waiting),
+                processing,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_store_roundtrip_skips_pane_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("status_timeline.json");
+
+        let mut store = StatusTimelineStore::load_from(path.clone()).unwrap();
+        let now = Utc::now();
+        assert!(store.record("abc", StatusKind::Waiting, now));
+        store.record("pane:3", StatusKind::Idle, now);
+        store.save().unwrap();
+
+        let reloaded = StatusTimelineStore::load_from(path).unwrap();
+        assert_eq!(reloaded.get("abc"), store.get("abc"));
+        assert!(reloaded.get("pane:3").is_none());
+    }
+
+    #[test]
+    fn test_load_invalid_file_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("status_timeline.json");
+        fs::write(&path, "not json").unwrap();
+        assert!(StatusTimelineStore::load_from(path).is_err());
+    }
+}
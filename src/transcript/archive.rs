@@ -0,0 +1,228 @@
+//! Summaries of past sessions from their transcripts, for browsing and
+//! resuming sessions whose panes are gone.
+
+use anyhow::Result;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use super::parser::turns_from_lines;
+use super::usage::TokenUsage;
+
+/// A session transcript on disk, summarized.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchivedSession {
+    pub transcript_path: PathBuf,
+    /// Claude Code session ID (transcript file stem)
+    pub session_id: String,
+    /// Session working directory recorded in the transcript, if any
+    pub cwd: Option<String>,
+    /// Project directory name under `~/.claude/projects`
+    pub project: String,
+    /// First user prompt
+    pub first_prompt: Option<String>,
+    /// Number of conversation turns
+    pub turns: usize,
+    /// Transcript modification time
+    pub last_activity: SystemTime,
+    /// Estimated API cost in USD (None if no model had a known price)
+    pub cost_usd: Option<f64>,
+}
+
+impl ArchivedSession {
+    /// Short project name: the last component of the session cwd, falling
+    /// back to the encoded project directory name.
+    pub fn project_name(&self) -> &str {
+        self.cwd
+            .as_deref()
+            .and_then(|cwd| Path::new(cwd).file_name())
+            .and_then(|name| name.to_str())
+            .unwrap_or(&self.project)
+    }
+}
+
+/// Summarize up to `limit` transcripts, most recently active first.
+///
+/// `dirs` are project directories (`get_transcript_dir`), or `None` for
+/// every project under `projects_dir`. Unreadable files are skipped.
+pub fn list_recent_sessions(
+    projects_dir: &Path,
+    dirs: Option<&[PathBuf]>,
+    limit: usize,
+) -> Result<Vec<ArchivedSession>> {
+    let dirs: Vec<PathBuf> = match dirs {
+        Some(dirs) => dirs.to_vec(),
+        None if projects_dir.is_dir() => std::fs::read_dir(projects_dir)?
+            .flatten()
+            .map(|entry| entry.path())
+            .collect(),
+        None => Vec::new(),
+    };
+
+    let mut files: Vec<(PathBuf, SystemTime)> = Vec::new();
+    for dir in dirs {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|s| s.to_str()) != Some("jsonl") {
+                continue;
+            }
+            let modified = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .unwrap_or(SystemTime::UNIX_EPOCH);
+            files.push((path, modified));
+        }
+    }
+    files.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+
+    Ok(files
+        .into_iter()
+        .filter_map(|(path, modified)| {
+            let content = std::fs::read_to_string(&path).ok()?;
+            summarize(&path, &content, modified)
+        })
+        .take(limit)
+        .collect())
+}
+
+/// Summary of one transcript. None if it has no conversation (e.g. only
+/// snapshots or summaries).
+fn summarize(path: &Path, content: &str, modified: SystemTime) -> Option<ArchivedSession> {
+    let lines: Vec<&str> = content.lines().filter(|l| !l.trim().is_empty()).collect();
+    let turns = turns_from_lines(&lines);
+    if turns.is_empty() {
+        return None;
+    }
+
+    let cwd = lines.iter().find_map(|line| {
+        #[derive(Deserialize)]
+        struct CwdOnly {
+            cwd: Option<String>,
+        }
+        serde_json::from_str::<CwdOnly>(line).ok()?.cwd
+    });
+    let project = path
+        .parent()
+        .and_then(|p| p.file_name())
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let session_id = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    Some(ArchivedSession {
+        transcript_path: path.to_path_buf(),
+        session_id,
+        cwd,
+        project,
+        first_prompt: turns.first().map(|t| t.user_prompt.clone()),
+        turns: turns.len(),
+        last_activity: modified,
+        cost_usd: estimate_cost(&lines),
+    })
+}
+
+/// Estimated cost of the assistant responses in a transcript.
+///
+/// A response split into several content blocks is logged once per block
+/// with the same message ID and usage, so each message is counted once.
+fn estimate_cost(lines: &[&str]) -> Option<f64> {
+    #[derive(Deserialize)]
+    struct Entry {
+        message: Option<Message>,
+    }
+    #[derive(Deserialize)]
+    struct Message {
+        id: Option<String>,
+        model: Option<String>,
+        usage: Option<TokenUsage>,
+    }
+
+    let mut seen = HashSet::new();
+    let mut total = None;
+    for line in lines {
+        if !line.contains("\"usage\"") {
+            continue;
+        }
+        let Some(message) = serde_json::from_str::<Entry>(line)
+            .ok()
+            .and_then(|e| e.message)
+        else {
+            continue;
+        };
+        if let Some(id) = message.id {
+            if !seen.insert(id) {
+                continue;
+            }
+        }
+        let (Some(model), Some(usage)) = (message.model, message.usage) else {
+            continue;
+        };
+        if let Some(cost) = usage.cost_usd(&model) {
+            *total.get_or_insert(0.0) += cost;
+        }
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn user(text: &str) -> String {
+        format!(
+            r#"{{"type":"user","cwd":"/home/me/proj","message":{{"role":"user","content":"{}"}}}}"#,
+            text
+        )
+    }
+
+    fn assistant(id: &str, text: &str) -> String {
+        format!(
+            r#"{{"type":"assistant","message":{{"id":"{}","model":"claude-sonnet-4-5","content":[{{"type":"text","text":"{}"}}],"usage":{{"input_tokens":1000000,"output_tokens":100000}}}}}}"#,
+            id, text
+        )
+    }
+
+    #[test]
+    fn test_list_recent_sessions() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("-home-me-proj");
+        fs::create_dir(&project).unwrap();
+        fs::write(
+            project.join("aaaa-1111.jsonl"),
+            [
+                user("first task"),
+                assistant("msg_1", "Done."),
+                // Same message logged again for a second content block
+                assistant("msg_1", "Done."),
+                user("second task"),
+                assistant("msg_2", "Done too."),
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+        fs::write(project.join("bbbb-2222.jsonl"), r#"{"type":"summary"}"#).unwrap();
+
+        let sessions = list_recent_sessions(dir.path(), None, 10).unwrap();
+        assert_eq!(sessions.len(), 1);
+        let session = &sessions[0];
+        assert_eq!(session.session_id, "aaaa-1111");
+        assert_eq!(session.project_name(), "proj");
+        assert_eq!(session.first_prompt.as_deref(), Some("first task"));
+        assert_eq!(session.turns, 2);
+        // Two messages at $3 input + $1.5 output each
+        let cost = session.cost_usd.unwrap();
+        assert!((cost - 9.0).abs() < 1e-9, "cost = {}", cost);
+
+        let other = dir.path().join("-home-me-other");
+        assert!(list_recent_sessions(dir.path(), Some(&[other]), 10)
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -8,7 +8,9 @@
 use super::parser::{
     extract_last_assistant_text, extract_last_user_prompt, AskUserQuestionInput, TranscriptSnapshot,
 };
-use super::state::{detect_session_status_from_entries, SessionStatus};
+use super::state::{detect_status_from_entries_with_config, DetectionConfig, SessionStatus};
+use super::subagents::{subagents_from_lines, Subagent};
+use super::todos::{latest_todos, Todo};
 use anyhow::Result;
 use std::path::Path;
 
@@ -29,29 +31,79 @@ pub struct TranscriptInfo {
     pub last_prompt: Option<String>,
     pub last_output: Option<String>,
     pub waiting_prompt: Option<WaitingPrompt>,
+    /// Subagents of the current prompt
+    pub subagents: Vec<Subagent>,
+    /// Latest TodoWrite list
+    pub todos: Vec<Todo>,
+}
+
+impl TranscriptInfo {
+    /// Info with only a status (no transcript to read).
+    pub(super) fn with_status(status: SessionStatus) -> Self {
+        Self {
+            status,
+            last_prompt: None,
+            last_output: None,
+            waiting_prompt: None,
+            subagents: Vec::new(),
+            todos: Vec::new(),
+        }
+    }
 }
 
 /// Read a transcript file once and extract status, last user prompt, and
 /// last assistant text. This replaces three separate file reads with one.
 pub fn read_transcript_info(path: &Path) -> Result<TranscriptInfo> {
     let snapshot = TranscriptSnapshot::from_path(path)?;
-    let entries = snapshot.last_entries(10);
-    let status = detect_session_status_from_entries(&entries);
     let last_prompt = extract_last_user_prompt(&snapshot, usize::MAX);
     let last_output = extract_last_assistant_text(&snapshot, usize::MAX);
+    let subagents = subagents_from_lines(snapshot.raw_lines());
+    let todos = latest_todos(snapshot.raw_lines());
+    Ok(transcript_info(
+        &snapshot,
+        last_prompt,
+        last_output,
+        subagents,
+        todos,
+        &DetectionConfig::default(),
+    ))
+}
 
-    let waiting_prompt = if matches!(status, SessionStatus::WaitingForUser { .. }) {
+/// Build `TranscriptInfo` from already-extracted prompt and output text.
+/// Status is always detected afresh because tool_use timeouts depend on
+/// the current time.
+pub(super) fn transcript_info(
+    snapshot: &TranscriptSnapshot,
+    last_prompt: Option<String>,
+    last_output: Option<String>,
+    subagents: Vec<Subagent>,
+    todos: Vec<Todo>,
+    config: &DetectionConfig,
+) -> TranscriptInfo {
+    // Inline subagent entries say nothing about the main conversation
+    let mut entries = snapshot.last_entries(10);
+    entries.retain(|e| !e.is_sidechain);
+    let status = detect_status_from_entries_with_config(&entries, config);
+
+    // A tool_use still within the waiting timeout may already be asking for
+    // permission; hook events and the live pane can tell, so keep its prompt too
+    let waiting_prompt = if matches!(
+        status,
+        SessionStatus::WaitingForUser { .. } | SessionStatus::RunningTool { .. }
+    ) {
         extract_waiting_prompt(&entries)
     } else {
         None
     };
 
-    Ok(TranscriptInfo {
+    TranscriptInfo {
         status,
         last_prompt,
         last_output,
         waiting_prompt,
-    })
+        subagents,
+        todos,
+    }
 }
 
 /// Extract waiting prompt data from the last tool_use entry.
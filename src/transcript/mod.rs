@@ -3,19 +3,36 @@
 //! Claude Code stores conversation transcripts at:
 //! `~/.claude/projects/{encoded-cwd}/{session_id}.jsonl`
 
+mod archive;
 mod info;
 mod parser;
 mod path;
+mod search;
 pub mod session_info;
 mod state;
+mod stats;
+mod subagents;
+mod tail;
+mod todos;
+mod usage;
 pub mod watcher;
 
+pub use archive::{list_recent_sessions, ArchivedSession};
 pub use info::{read_transcript_info, TranscriptInfo, WaitingPrompt};
 pub use parser::{
     extract_conversation_turns, get_last_assistant_text, get_last_user_prompt,
-    AskUserQuestionInput, ConversationTurn, Question, QuestionOption, TranscriptEntry,
+    read_all_conversation_turns, AskUserQuestionInput, ConversationTurn, Question, QuestionOption,
+    TranscriptEntry,
 };
 pub use path::{encode_cwd, get_latest_transcript, get_transcript_dir};
-pub use session_info::{detect_session_info, SessionInfo};
-pub use state::{detect_session_status, SessionStatus};
+pub use search::{contains_match, find_matches, projects_dir, search_transcripts, SearchHit};
+pub use session_info::{detect_session_info, SessionInfo, StatusSource};
+pub use state::{detect_session_status, DetectionConfig, SessionStatus, StatusKind};
+pub use stats::{
+    collect_usage_stats, format_duration, format_tokens, period_start, StatsGroup, UsageStats,
+    UsageTotals, STATS_COLUMNS,
+};
+pub use subagents::{Subagent, SubagentState};
+pub use tail::{TranscriptTail, TranscriptTails};
+pub use todos::{todo_progress, Todo, TodoStatus};
 pub use watcher::TranscriptWatcher;
@@ -8,7 +8,7 @@ use std::io::{BufRead, BufReader, Seek, SeekFrom};
 use std::path::Path;
 
 /// Remove internal XML tags (system-reminder, local-command-*, command-*) from text.
-fn remove_internal_tags(text: &str) -> String {
+pub(super) fn remove_internal_tags(text: &str) -> String {
     const TAGS: &[&str] = &[
         "system-reminder",
         "local-command-stdout",
@@ -174,6 +174,18 @@ pub struct TranscriptEntry {
     pub timestamp: Option<String>,
     pub message: Option<AssistantMessage>,
     pub data: Option<ProgressData>,
+    /// Set on the synthetic assistant message of a failed API request
+    #[serde(rename = "isApiErrorMessage", default)]
+    pub is_api_error_message: bool,
+    /// Error of a system api_error entry (e.g., `{"status": 529, ...}`)
+    pub error: Option<serde_json::Value>,
+    #[serde(rename = "retryAttempt")]
+    pub retry_attempt: Option<u32>,
+    #[serde(rename = "maxRetries")]
+    pub max_retries: Option<u32>,
+    /// Set on entries of a subagent recorded inline in the main transcript
+    #[serde(rename = "isSidechain", default)]
+    pub is_sidechain: bool,
 }
 
 impl TranscriptEntry {
@@ -249,6 +261,108 @@ impl TranscriptEntry {
         self.type_ == "system" && self.subtype.as_deref() == Some("turn_duration")
     }
 
+    /// Check if this is a progress entry of a running subagent.
+    pub fn is_agent_progress(&self) -> bool {
+        self.type_ == "progress"
+            && self
+                .data
+                .as_ref()
+                .and_then(|d| d.type_.as_deref())
+                .is_some_and(|t| t == "agent_progress")
+    }
+
+    /// Check if this is a system compact_boundary (context compaction).
+    pub fn is_compact_boundary(&self) -> bool {
+        self.type_ == "system" && self.subtype.as_deref() == Some("compact_boundary")
+    }
+
+    /// Check if this is a system api_error (a failed request Claude Code
+    /// retries).
+    pub fn is_api_error(&self) -> bool {
+        self.type_ == "system" && self.subtype.as_deref() == Some("api_error")
+    }
+
+    /// Short description of an API error: an api_error entry or the
+    /// synthetic message of a failed request. None for other entries.
+    pub fn api_error_summary(&self) -> Option<String> {
+        if self.is_api_error() {
+            let status = self
+                .error
+                .as_ref()
+                .and_then(|e| e.get("status"))
+                .and_then(|s| s.as_u64());
+            let mut summary = match status {
+                Some(status) => format!("API error {}", status),
+                None => "API error".to_string(),
+            };
+            if let (Some(attempt), Some(max)) = (self.retry_attempt, self.max_retries) {
+                summary.push_str(&format!(", retry {}/{}", attempt, max));
+            }
+            return Some(summary);
+        }
+        if self.type_ != "assistant" || !self.is_api_error_message {
+            return None;
+        }
+        let text = self
+            .message
+            .as_ref()
+            .and_then(|m| m.content.iter().find_map(|c| c.text.as_deref()))
+            .unwrap_or("API error");
+        let first_line = text.lines().next().unwrap_or(text);
+        Some(truncate_with_ellipsis(first_line.to_string(), 60))
+    }
+
+    /// Whether an API error is a rate limit, usage limit or overload
+    /// (HTTP 429/529), which clears by waiting rather than by a fix.
+    pub fn is_rate_limit_error(&self) -> bool {
+        let text = if self.is_api_error() {
+            self.error
+                .as_ref()
+                .map(|e| e.to_string())
+                .unwrap_or_default()
+        } else if self.is_api_error_message {
+            self.message
+                .as_ref()
+                .map(|m| {
+                    m.content
+                        .iter()
+                        .filter_map(|c| c.text.as_deref())
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                })
+                .unwrap_or_default()
+        } else {
+            return false;
+        };
+        let text = text.to_lowercase();
+        [
+            "429",
+            "529",
+            "rate_limit",
+            "rate limit",
+            "overloaded",
+            "limit reached",
+        ]
+        .iter()
+        .any(|pattern| text.contains(pattern))
+    }
+
+    /// The `description` of a Task (subagent) tool_use.
+    pub fn subagent_description(&self) -> Option<String> {
+        self.message
+            .as_ref()?
+            .content
+            .iter()
+            .filter(|c| c.type_ == "tool_use" && c.name.as_deref().is_some_and(is_subagent_tool))
+            .find_map(|c| {
+                c.input
+                    .as_ref()?
+                    .get("description")?
+                    .as_str()
+                    .map(str::to_string)
+            })
+    }
+
     /// Check if this is a user entry with a tool_result.
     pub fn is_tool_result(&self) -> bool {
         if self.type_ != "user" {
@@ -311,6 +425,11 @@ impl TranscriptEntry {
     }
 }
 
+/// Whether a tool runs a subagent (`Task`, named `Agent` in newer versions).
+pub fn is_subagent_tool(name: &str) -> bool {
+    name == "Task" || name == "Agent"
+}
+
 /// Read the last N entries from a transcript file.
 /// Uses reverse file reading for efficiency with large files.
 pub fn read_last_entries(path: &Path, count: usize) -> Result<Vec<TranscriptEntry>> {
@@ -335,6 +454,7 @@ pub fn read_last_entries(path: &Path, count: usize) -> Result<Vec<TranscriptEntr
 
 /// Pre-read raw lines from a transcript file, shared across multiple
 /// extraction functions without re-reading the file.
+#[derive(Debug, Default)]
 pub struct TranscriptSnapshot {
     lines: Vec<String>,
 }
@@ -348,6 +468,14 @@ impl TranscriptSnapshot {
         Ok(Self { lines })
     }
 
+    /// Append lines read from the end of the file, keeping only the most
+    /// recent `max_lines`.
+    pub(super) fn push_lines(&mut self, lines: impl IntoIterator<Item = String>, max_lines: usize) {
+        self.lines.extend(lines);
+        let excess = self.lines.len().saturating_sub(max_lines);
+        self.lines.drain(..excess);
+    }
+
     /// Return true if no lines were read (empty file).
     pub fn is_empty(&self) -> bool {
         self.lines.is_empty()
@@ -403,6 +531,8 @@ pub struct UserTranscriptEntry {
     pub type_: String,
     #[serde(rename = "isMeta")]
     pub is_meta: Option<bool>,
+    #[serde(rename = "isSidechain", default)]
+    pub is_sidechain: bool,
     pub timestamp: Option<String>,
     pub message: Option<UserMessage>,
 }
@@ -421,7 +551,7 @@ pub fn extract_last_user_prompt(snapshot: &TranscriptSnapshot, max_chars: usize)
             Err(_) => continue,
         };
 
-        if entry.type_ != "user" || entry.is_meta == Some(true) {
+        if entry.type_ != "user" || entry.is_meta == Some(true) || entry.is_sidechain {
             continue;
         }
 
@@ -486,7 +616,7 @@ pub fn extract_last_assistant_text(
 
     // Search from the end for an assistant message with text content
     for entry in entries.iter().rev() {
-        if entry.type_ != "assistant" {
+        if entry.type_ != "assistant" || entry.is_sidechain {
             continue;
         }
 
@@ -533,12 +663,51 @@ pub fn extract_conversation_turns(path: &Path, max_turns: usize) -> Result<Vec<C
     // Use larger seek_multiplier for more history coverage
     let lines = read_lines_from_end(path, 100)?;
 
-    let mut turns: Vec<ConversationTurn> = Vec::new();
-    let mut current_prompt: Option<String> = None;
-    let mut current_timestamp: Option<String> = None;
-    let mut last_assistant_text = String::new();
+    let mut turns = turns_from_lines(&lines);
 
-    for line in &lines {
+    // Reverse to newest-first, then truncate
+    turns.reverse();
+    turns.truncate(max_turns);
+
+    Ok(turns)
+}
+
+/// Extract every conversation turn from a transcript file, reading the whole
+/// file (unlike `extract_conversation_turns`, which only reads the tail).
+/// Returns turns in reverse chronological order (newest first).
+pub fn read_all_conversation_turns(path: &Path) -> Result<Vec<ConversationTurn>> {
+    let content = std::fs::read_to_string(path)?;
+    let lines: Vec<&str> = content.lines().filter(|l| !l.trim().is_empty()).collect();
+    let mut turns = turns_from_lines(&lines);
+    turns.reverse();
+    Ok(turns)
+}
+
+/// Pair user prompts with the assistant's final text response.
+/// Returns turns in chronological order.
+pub(super) fn turns_from_lines<S: AsRef<str>>(lines: &[S]) -> Vec<ConversationTurn> {
+    let mut builder = TurnBuilder::default();
+    for line in lines {
+        builder.push_line(line.as_ref());
+    }
+    builder.into_turns()
+}
+
+/// Incrementally pairs user prompts with the assistant's final text
+/// response, one transcript line at a time.
+#[derive(Debug, Default)]
+pub(super) struct TurnBuilder {
+    /// Completed turns, chronological
+    turns: Vec<ConversationTurn>,
+    current_prompt: Option<String>,
+    current_timestamp: Option<String>,
+    last_assistant_text: String,
+}
+
+impl TurnBuilder {
+    /// Feed one raw JSONL line. Lines that aren't prompts or assistant text
+    /// are ignored.
+    pub(super) fn push_line(&mut self, line: &str) {
         // Quick type check to avoid unnecessary full parsing
         #[derive(Deserialize)]
         struct TypeOnly {
@@ -547,38 +716,38 @@ pub fn extract_conversation_turns(path: &Path, max_turns: usize) -> Result<Vec<C
         }
         let entry_type = match serde_json::from_str::<TypeOnly>(line) {
             Ok(t) => t.type_,
-            Err(_) => continue,
+            Err(_) => return,
         };
 
         match entry_type.as_str() {
             "user" => {
                 let entry: UserTranscriptEntry = match serde_json::from_str(line) {
                     Ok(e) => e,
-                    Err(_) => continue,
+                    Err(_) => return,
                 };
 
-                if entry.is_meta == Some(true) {
-                    continue;
+                if entry.is_meta == Some(true) || entry.is_sidechain {
+                    return;
                 }
 
                 let Some(msg) = &entry.message else {
-                    continue;
+                    return;
                 };
 
                 let text = match &msg.content {
                     UserContent::Text(s) => {
                         if s.contains("tool_result") && !s.contains('\n') {
-                            continue;
+                            return;
                         }
                         let cleaned = remove_internal_tags(s);
                         if cleaned.trim().is_empty() {
-                            continue;
+                            return;
                         }
                         cleaned
                     }
                     UserContent::Blocks(blocks) => {
                         if blocks.iter().any(|b| b.type_ == "tool_result") {
-                            continue;
+                            return;
                         }
                         let raw = blocks
                             .iter()
@@ -589,31 +758,34 @@ pub fn extract_conversation_turns(path: &Path, max_turns: usize) -> Result<Vec<C
                             .join("\n");
                         let cleaned = remove_internal_tags(&raw);
                         if cleaned.trim().is_empty() {
-                            continue;
+                            return;
                         }
                         cleaned
                     }
-                    UserContent::Empty => continue,
+                    UserContent::Empty => return,
                 };
 
                 // Save previous turn if exists
-                if let Some(prev_prompt) = current_prompt.take() {
-                    turns.push(ConversationTurn {
+                if let Some(prev_prompt) = self.current_prompt.take() {
+                    self.turns.push(ConversationTurn {
                         user_prompt: prev_prompt,
-                        assistant_response: std::mem::take(&mut last_assistant_text),
-                        timestamp: current_timestamp.take(),
+                        assistant_response: std::mem::take(&mut self.last_assistant_text),
+                        timestamp: self.current_timestamp.take(),
                     });
                 }
 
-                current_prompt = Some(text);
-                current_timestamp = entry.timestamp.clone();
-                last_assistant_text.clear();
+                self.current_prompt = Some(text);
+                self.current_timestamp = entry.timestamp.clone();
+                self.last_assistant_text.clear();
             }
             "assistant" => {
                 let entry: TranscriptEntry = match serde_json::from_str(line) {
                     Ok(e) => e,
-                    Err(_) => continue,
+                    Err(_) => return,
                 };
+                if entry.is_sidechain {
+                    return;
+                }
 
                 if let Some(msg) = &entry.message {
                     let text: String = msg
@@ -627,7 +799,7 @@ pub fn extract_conversation_turns(path: &Path, max_turns: usize) -> Result<Vec<C
 
                     if !text.is_empty() {
                         // Keep only the last assistant text for this turn
-                        last_assistant_text = text;
+                        self.last_assistant_text = text;
                     }
                 }
             }
@@ -635,20 +807,45 @@ pub fn extract_conversation_turns(path: &Path, max_turns: usize) -> Result<Vec<C
         }
     }
 
-    // Handle final turn
-    if let Some(prompt) = current_prompt {
-        turns.push(ConversationTurn {
-            user_prompt: prompt,
-            assistant_response: last_assistant_text,
-            timestamp: current_timestamp,
-        });
+    /// Drop the oldest completed turns, keeping at most `max`.
+    pub(super) fn truncate_front(&mut self, max: usize) {
+        let excess = self.turns.len().saturating_sub(max);
+        self.turns.drain(..excess);
     }
 
-    // Reverse to newest-first, then truncate
-    turns.reverse();
-    turns.truncate(max_turns);
-
-    Ok(turns)
+    /// Up to `max_turns` most recent turns, newest first, including the
+    /// turn still in progress.
+    pub(super) fn recent_turns(&self, max_turns: usize) -> Vec<ConversationTurn> {
+        let current = self.current_prompt.as_ref().map(|prompt| ConversationTurn {
+            user_prompt: prompt.clone(),
+            assistant_response: self.last_assistant_text.clone(),
+            timestamp: self.current_timestamp.clone(),
+        });
+        current
+            .into_iter()
+            .chain(self.turns.iter().rev().cloned())
+            .take(max_turns)
+            .collect()
+    }
+
+    /// Number of prompts seen so far (completed turns plus the one in
+    /// progress), as long as no turns were truncated.
+    pub(super) fn prompt_count(&self) -> usize {
+        self.turns.len() + usize::from(self.current_prompt.is_some())
+    }
+
+    /// All turns in chronological order, including the final one.
+    pub(super) fn into_turns(mut self) -> Vec<ConversationTurn> {
+        // Handle final turn
+        if let Some(prompt) = self.current_prompt {
+            self.turns.push(ConversationTurn {
+                user_prompt: prompt,
+                assistant_response: self.last_assistant_text,
+                timestamp: self.current_timestamp,
+            });
+        }
+        self.turns
+    }
 }
 
 #[cfg(test)]
@@ -976,6 +1173,28 @@ mod tests {
         assert_eq!(turns[0].assistant_response, "Fixed!");
     }
 
+    #[test]
+    fn test_extract_turns_skips_sidechain() {
+        let lines = [
+            r#"{"type":"user","message":{"content":"explore"}}"#,
+            r#"{"type":"user","isSidechain":true,"message":{"content":"search for parsers"}}"#,
+            r#"{"type":"assistant","isSidechain":true,"message":{"content":[{"type":"text","text":"Found 3 parsers"}]}}"#,
+            r#"{"type":"assistant","message":{"content":[{"type":"text","text":"Done exploring"}]}}"#,
+        ];
+        let turns = turns_from_lines(&lines);
+        assert_eq!(turns.len(), 1);
+        assert_eq!(turns[0].user_prompt, "explore");
+        assert_eq!(turns[0].assistant_response, "Done exploring");
+
+        let mut snapshot = TranscriptSnapshot::default();
+        snapshot.push_lines(lines[..3].iter().map(|l| l.to_string()), 10);
+        assert_eq!(
+            extract_last_user_prompt(&snapshot, 100).as_deref(),
+            Some("explore")
+        );
+        assert_eq!(extract_last_assistant_text(&snapshot, 100), None);
+    }
+
     #[test]
     fn test_extract_turns_max_limit() {
         let dir = tempfile::tempdir().unwrap();
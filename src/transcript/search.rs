@@ -0,0 +1,401 @@
+//! Full-text search over every Claude Code transcript on disk
+//! (`~/.claude/projects/*/*.jsonl`), including sessions that are no longer running.
+//!
+//! Queries are plain substrings matched smart-case: case-insensitive unless
+//! the query contains an uppercase letter.
+
+use anyhow::Result;
+use serde::Deserialize;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::SystemTime;
+
+use super::parser::turns_from_lines;
+
+/// Characters of context shown before a match in a snippet.
+const SNIPPET_BEFORE: usize = 30;
+/// Total snippet length in characters (excluding ellipses).
+const SNIPPET_WIDTH: usize = 100;
+
+/// A conversation turn matching a search query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchHit {
+    /// Transcript file containing the turn
+    pub transcript_path: PathBuf,
+    /// Session working directory recorded in the transcript, if any
+    pub cwd: Option<String>,
+    /// Project directory name under `~/.claude/projects`
+    pub project: String,
+    /// Claude Code session ID (transcript file stem)
+    pub session_id: String,
+    /// Turn index, newest first (as returned by `read_all_conversation_turns`)
+    pub turn_index: usize,
+    /// Timestamp of the turn's prompt (RFC 3339)
+    pub timestamp: Option<String>,
+    /// One-line excerpt around the first match
+    pub snippet: String,
+}
+
+impl SearchHit {
+    /// Short project name: the last component of the session cwd, falling
+    /// back to the encoded project directory name.
+    pub fn project_name(&self) -> &str {
+        self.cwd
+            .as_deref()
+            .and_then(|cwd| Path::new(cwd).file_name())
+            .and_then(|name| name.to_str())
+            .unwrap_or(&self.project)
+    }
+
+    /// Local `YYYY-MM-DD HH:MM` of the turn, or a placeholder of the same
+    /// width if it has no timestamp.
+    pub fn local_time(&self) -> String {
+        self.timestamp
+            .as_deref()
+            .and_then(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok())
+            .map(|dt| {
+                dt.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string()
+            })
+            .unwrap_or_else(|| "-".repeat(16))
+    }
+}
+
+/// `~/.claude/projects`, where Claude Code stores transcripts.
+pub fn projects_dir() -> Option<PathBuf> {
+    Some(dirs::home_dir()?.join(".claude").join("projects"))
+}
+
+/// Search the prompts and responses of every transcript under `projects_dir`.
+///
+/// Transcripts are scanned most recently modified first, and at most one hit
+/// is returned per turn. Scanning stops once `limit` hits have been found;
+/// the hits are returned newest first. Unreadable files are skipped.
+/// Setting `cancel` stops the scan early with the hits found so far.
+pub fn search_transcripts(
+    projects_dir: &Path,
+    query: &str,
+    limit: usize,
+    cancel: &AtomicBool,
+) -> Result<Vec<SearchHit>> {
+    if query.trim().is_empty() || !projects_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut files: Vec<(PathBuf, SystemTime)> = Vec::new();
+    for project in std::fs::read_dir(projects_dir)?.flatten() {
+        let Ok(entries) = std::fs::read_dir(project.path()) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|s| s.to_str()) != Some("jsonl") {
+                continue;
+            }
+            let modified = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .unwrap_or(SystemTime::UNIX_EPOCH);
+            files.push((path, modified));
+        }
+    }
+    files.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+
+    let mut hits = Vec::new();
+    for (path, _) in files {
+        if hits.len() >= limit || cancel.load(Ordering::Relaxed) {
+            break;
+        }
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        hits.extend(search_transcript(&path, &content, query));
+    }
+
+    // RFC 3339 timestamps from Claude Code share a format, so they sort as strings
+    hits.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    hits.truncate(limit);
+    Ok(hits)
+}
+
+/// Hits in one transcript, newest turn first.
+fn search_transcript(path: &Path, content: &str, query: &str) -> Vec<SearchHit> {
+    // Cheap pre-check on the raw JSON. Quotes and backslashes are escaped
+    // in JSON, so queries containing them skip it.
+    if !query.contains(['"', '\\']) && !contains_match(content, query) {
+        return Vec::new();
+    }
+
+    let lines: Vec<&str> = content.lines().filter(|l| !l.trim().is_empty()).collect();
+    let turns = turns_from_lines(&lines);
+    let cwd = lines.iter().find_map(|line| {
+        #[derive(Deserialize)]
+        struct CwdOnly {
+            cwd: Option<String>,
+        }
+        serde_json::from_str::<CwdOnly>(line).ok()?.cwd
+    });
+    let project = path
+        .parent()
+        .and_then(|p| p.file_name())
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let session_id = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    turns
+        .iter()
+        .rev()
+        .enumerate()
+        .filter_map(|(turn_index, turn)| {
+            let snippet = [&turn.user_prompt, &turn.assistant_response]
+                .into_iter()
+                .find_map(|text| {
+                    let range = Matches::new(text, query).next()?;
+                    Some(snippet(text, range))
+                })?;
+            Some(SearchHit {
+                transcript_path: path.to_path_buf(),
+                cwd: cwd.clone(),
+                project: project.clone(),
+                session_id: session_id.clone(),
+                turn_index,
+                timestamp: turn.timestamp.clone(),
+                snippet,
+            })
+        })
+        .collect()
+}
+
+/// Byte ranges of non-overlapping occurrences of `query` in `text`.
+/// Matching is smart-case; a blank query matches nothing.
+pub fn find_matches(text: &str, query: &str) -> Vec<Range<usize>> {
+    Matches::new(text, query).collect()
+}
+
+/// Whether `text` contains `query` (see `find_matches`). Stops at the
+/// first occurrence without allocating.
+pub fn contains_match(text: &str, query: &str) -> bool {
+    Matches::new(text, query).next().is_some()
+}
+
+/// Iterator over the matches of `find_matches`, folding char by char so byte
+/// offsets in `text` stay valid.
+struct Matches<'a> {
+    text: &'a str,
+    query: &'a str,
+    case_sensitive: bool,
+    /// Byte offset where the next match may start
+    pos: usize,
+}
+
+impl<'a> Matches<'a> {
+    fn new(text: &'a str, query: &'a str) -> Self {
+        // A blank query matches nothing
+        let pos = if query.trim().is_empty() {
+            text.len()
+        } else {
+            0
+        };
+        Self {
+            text,
+            query,
+            case_sensitive: query.chars().any(char::is_uppercase),
+            pos,
+        }
+    }
+
+    fn fold(&self, c: char) -> char {
+        if self.case_sensitive {
+            c
+        } else {
+            c.to_lowercase().next().unwrap_or(c)
+        }
+    }
+
+    /// End of a match starting at byte `start`, if there is one.
+    fn match_at(&self, start: usize) -> Option<usize> {
+        let mut chars = self.text[start..].char_indices();
+        for q in self.query.chars() {
+            let (_, c) = chars.next()?;
+            if self.fold(c) != self.fold(q) {
+                return None;
+            }
+        }
+        Some(chars.next().map_or(self.text.len(), |(i, _)| start + i))
+    }
+}
+
+impl Iterator for Matches<'_> {
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Range<usize>> {
+        while self.pos < self.text.len() {
+            let start = self.pos;
+            if let Some(end) = self.match_at(start) {
+                self.pos = end;
+                return Some(start..end);
+            }
+            let width = self.text[start..].chars().next().map_or(1, char::len_utf8);
+            self.pos = start + width;
+        }
+        None
+    }
+}
+
+/// One-line excerpt of `text` around the match at `range`, with whitespace
+/// collapsed and `…` where text was cut off.
+fn snippet(text: &str, range: Range<usize>) -> String {
+    let mut before: Vec<char> = text[..range.start]
+        .chars()
+        .rev()
+        .take(SNIPPET_BEFORE + 1)
+        .collect();
+    let cut_start = before.len() > SNIPPET_BEFORE;
+    before.truncate(SNIPPET_BEFORE);
+    before.reverse();
+
+    let rest = &text[range.start..];
+    let after_len = SNIPPET_WIDTH - before.len();
+    let cut_end = rest.chars().count() > after_len;
+
+    let excerpt: String = before
+        .into_iter()
+        .chain(rest.chars().take(after_len))
+        .collect();
+    let excerpt = excerpt.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    format!(
+        "{}{}{}",
+        if cut_start { "…" } else { "" },
+        excerpt,
+        if cut_end { "…" } else { "" }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn user(text: &str, ts: &str) -> String {
+        format!(
+            r#"{{"type":"user","cwd":"/home/me/proj","timestamp":"{}","message":{{"role":"user","content":"{}"}}}}"#,
+            ts, text
+        )
+    }
+
+    fn assistant(text: &str) -> String {
+        format!(
+            r#"{{"type":"assistant","message":{{"content":[{{"type":"text","text":"{}"}}]}}}}"#,
+            text
+        )
+    }
+
+    #[test]
+    fn test_find_matches_smart_case() {
+        assert_eq!(find_matches("Foo foo FOO", "foo"), vec![0..3, 4..7, 8..11]);
+        assert_eq!(find_matches("Foo foo FOO", "Foo"), vec![0..3]);
+        assert!(find_matches("anything", "  ").is_empty());
+    }
+
+    #[test]
+    fn test_find_matches_non_overlapping_and_multibyte() {
+        assert_eq!(find_matches("aaaa", "aa"), vec![0..2, 2..4]);
+        // "é" is two bytes; ranges are byte offsets into the original text
+        assert_eq!(find_matches("café Café", "café"), vec![0..5, 6..11]);
+        assert_eq!(find_matches("xé", "é"), vec![1..3]);
+    }
+
+    #[test]
+    fn test_contains_match() {
+        assert!(contains_match("a Needle here", "needle"));
+        assert!(!contains_match("a needle here", "Needle"));
+        assert!(!contains_match("nee", "needle"));
+        assert!(!contains_match("anything", ""));
+    }
+
+    #[test]
+    fn test_snippet_short_text_is_whole() {
+        let text = "run the\nmigration   script";
+        let range = find_matches(text, "migration")[0].clone();
+        assert_eq!(snippet(text, range), "run the migration script");
+    }
+
+    #[test]
+    fn test_snippet_long_text_is_cut() {
+        let text = format!("{}needle{}", "a".repeat(50), "b".repeat(200));
+        let range = find_matches(&text, "needle")[0].clone();
+        let s = snippet(&text, range);
+        assert!(s.starts_with('…'));
+        assert!(s.ends_with('…'));
+        assert!(s.contains(&format!("{}needle", "a".repeat(SNIPPET_BEFORE))));
+        assert_eq!(s.chars().count(), SNIPPET_WIDTH + 2);
+    }
+
+    #[test]
+    fn test_search_transcripts() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("-home-me-proj");
+        fs::create_dir(&project).unwrap();
+        fs::write(
+            project.join("aaaa-1111.jsonl"),
+            [
+                user("write the migration script", "2026-01-01T10:00:00Z"),
+                assistant("Done."),
+                user("unrelated", "2026-01-01T11:00:00Z"),
+                assistant("The Migration ran fine"),
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+        fs::write(
+            project.join("bbbb-2222.jsonl"),
+            [user("hello", "2026-02-01T10:00:00Z"), assistant("hi")].join("\n"),
+        )
+        .unwrap();
+        fs::write(project.join("notes.txt"), "migration").unwrap();
+
+        let hits =
+            search_transcripts(dir.path(), "migration", 10, &AtomicBool::new(false)).unwrap();
+        assert_eq!(hits.len(), 2);
+        // Newest first; the second turn matched in its response
+        assert_eq!(hits[0].timestamp.as_deref(), Some("2026-01-01T11:00:00Z"));
+        assert_eq!(hits[0].turn_index, 0);
+        assert_eq!(hits[0].snippet, "The Migration ran fine");
+        assert_eq!(hits[1].turn_index, 1);
+        assert_eq!(hits[1].snippet, "write the migration script");
+        assert_eq!(hits[1].session_id, "aaaa-1111");
+        assert_eq!(hits[1].cwd.as_deref(), Some("/home/me/proj"));
+        assert_eq!(hits[1].project_name(), "proj");
+
+        // Smart-case and limit
+        assert_eq!(
+            search_transcripts(dir.path(), "Migration", 10, &AtomicBool::new(false))
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(
+            search_transcripts(dir.path(), "migration", 1, &AtomicBool::new(false))
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(
+            search_transcripts(dir.path(), "zzz", 10, &AtomicBool::new(false))
+                .unwrap()
+                .is_empty()
+        );
+        // A cancelled search stops before reading any transcript
+        assert!(
+            search_transcripts(dir.path(), "migration", 10, &AtomicBool::new(true))
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
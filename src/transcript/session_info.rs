@@ -1,17 +1,33 @@
 use crate::models::Pane;
-use crate::session_mapping::{MappingResult, SessionMapping};
+use crate::session_mapping::{HookEvent, MappingResult, SessionMapping};
+use chrono::{DateTime, Utc};
 use std::path::PathBuf;
 use std::time::SystemTime;
 
 use super::info::WaitingPrompt;
+use super::parser::is_subagent_tool;
 use super::{
-    get_latest_transcript, get_transcript_dir, read_transcript_info, SessionStatus, TranscriptInfo,
+    get_latest_transcript, get_transcript_dir, DetectionConfig, SessionStatus, Subagent, Todo,
+    TranscriptInfo, TranscriptTails,
 };
 
+/// Where a session's status came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatusSource {
+    /// The last hook event, newer than the transcript (authoritative).
+    Hook { event: String, at: DateTime<Utc> },
+    /// Heuristics over the transcript from the TTY mapping.
+    Transcript,
+    /// Heuristics over the latest transcript in the pane's CWD.
+    Cwd,
+}
+
 /// Result of session info detection.
 #[derive(Debug, Clone)]
 pub struct SessionInfo {
     pub status: SessionStatus,
+    /// Where `status` came from
+    pub status_source: StatusSource,
     pub last_prompt: Option<String>,
     pub last_output: Option<String>,
     pub session_id: Option<String>,
@@ -22,8 +38,46 @@ pub struct SessionInfo {
     pub warning: Option<String>,
     /// Parsed waiting prompt data when status is WaitingForUser.
     pub waiting_prompt: Option<WaitingPrompt>,
+    /// Subagents of the current prompt
+    pub subagents: Vec<Subagent>,
+    /// Latest TodoWrite list
+    pub todos: Vec<Todo>,
+}
+
+impl SessionInfo {
+    /// Tools of a WaitingForUser status inferred from the tool_use timeout
+    /// alone, which a long-running tool can't be told apart from. None when
+    /// a hook event reported it or the tools wait for the user by design.
+    pub fn unconfirmed_wait(&self) -> Option<&[String]> {
+        let SessionStatus::WaitingForUser { tools } = &self.status else {
+            return None;
+        };
+        let confirmed = matches!(self.status_source, StatusSource::Hook { .. })
+            || tools.is_empty()
+            || tools.iter().any(|t| PROMPT_TOOLS.contains(&t.as_str()));
+        (!confirmed).then_some(tools.as_slice())
+    }
+
+    /// Report the tools of an unconfirmed wait as running since the
+    /// transcript was last written.
+    pub fn set_running_tool(&mut self) {
+        let Some(tools) = self.unconfirmed_wait() else {
+            return;
+        };
+        self.status = SessionStatus::RunningTool {
+            name: tools.join(", "),
+            elapsed: self
+                .updated_at
+                .and_then(|t| t.elapsed().ok())
+                .unwrap_or_default(),
+        };
+        self.waiting_prompt = None;
+    }
 }
 
+/// Tools that wait for the user by design, not for a permission.
+const PROMPT_TOOLS: [&str; 2] = ["AskUserQuestion", "ExitPlanMode"];
+
 /// Get file modification time.
 fn get_file_mtime(path: &PathBuf) -> Option<SystemTime> {
     std::fs::metadata(path).ok()?.modified().ok()
@@ -33,84 +87,34 @@ fn get_file_mtime(path: &PathBuf) -> Option<SystemTime> {
 ///
 /// This function tries to find session information using the TTY as the key.
 /// If a valid mapping exists, it uses the transcript_path from the mapping
-/// instead of guessing based on CWD.
-pub fn detect_session_info(pane: &Pane) -> SessionInfo {
+/// instead of guessing based on CWD. Transcripts are read through `tails`,
+/// so only lines appended since the last call are parsed. A hook event
+/// recorded in the mapping overrides the transcript heuristics.
+pub fn detect_session_info(
+    pane: &Pane,
+    tails: &mut TranscriptTails,
+    config: &DetectionConfig,
+) -> SessionInfo {
     // Try to get session mapping from TTY
     if let Some(tty) = pane.tty_short() {
         match SessionMapping::from_tty_with_status(&tty) {
             MappingResult::Valid(mapping) => {
                 // We have a valid mapping - use the transcript path from it
-                let transcript_path = mapping.transcript_path.clone();
-
-                let (status, last_prompt, last_output, updated_at, waiting_prompt) =
-                    if transcript_path.exists() {
-                        let info =
-                            read_transcript_info(&transcript_path).unwrap_or(TranscriptInfo {
-                                status: SessionStatus::Unknown,
-                                last_prompt: None,
-                                last_output: None,
-                                waiting_prompt: None,
-                            });
-                        let mtime = get_file_mtime(&transcript_path);
-                        (
-                            info.status,
-                            info.last_prompt,
-                            info.last_output,
-                            mtime,
-                            info.waiting_prompt,
-                        )
-                    } else {
-                        (SessionStatus::Ready, None, None, None, None)
-                    };
-
-                return SessionInfo {
-                    status,
-                    last_prompt,
-                    last_output,
-                    session_id: Some(mapping.session_id),
-                    transcript_path: Some(transcript_path),
-                    updated_at,
-                    warning: None,
-                    waiting_prompt,
-                };
+                return info_from_mapping(mapping, tails, config, SessionStatus::Ready, None);
             }
             MappingResult::Stale(mapping) => {
                 // Mapping exists but is stale - don't fallback to CWD
                 // This prevents showing wrong status from another session with same CWD
                 // Read transcript for actual status instead of showing Unknown
-                let transcript_path = mapping.transcript_path.clone();
-                let (status, last_prompt, last_output, updated_at, waiting_prompt) =
-                    if transcript_path.exists() {
-                        let info =
-                            read_transcript_info(&transcript_path).unwrap_or(TranscriptInfo {
-                                status: SessionStatus::Unknown,
-                                last_prompt: None,
-                                last_output: None,
-                                waiting_prompt: None,
-                            });
-                        (
-                            info.status,
-                            info.last_prompt,
-                            info.last_output,
-                            get_file_mtime(&transcript_path),
-                            info.waiting_prompt,
-                        )
-                    } else {
-                        (SessionStatus::Unknown, None, None, None, None)
-                    };
-
-                return SessionInfo {
-                    status,
-                    last_prompt,
-                    last_output,
-                    session_id: Some(mapping.session_id),
-                    transcript_path: Some(transcript_path),
-                    updated_at,
-                    warning: Some(
+                return info_from_mapping(
+                    mapping,
+                    tails,
+                    config,
+                    SessionStatus::Unknown,
+                    Some(
                         "Session info stale (statusLine not updating). Try interacting with the session.".to_string(),
                     ),
-                    waiting_prompt,
-                };
+                );
             }
             MappingResult::NotFound => {
                 // No mapping - fall through to CWD-based detection
@@ -119,61 +123,301 @@ pub fn detect_session_info(pane: &Pane) -> SessionInfo {
     }
 
     // Fallback to CWD-based detection
-    let (status, last_prompt, last_output, updated_at, waiting_prompt) =
-        detect_status_and_output_by_cwd(pane);
+    let (info, updated_at) = detect_status_and_output_by_cwd(pane, tails, config);
 
     SessionInfo {
-        status,
-        last_prompt,
-        last_output,
+        waiting_prompt: info.waiting_prompt.filter(|_| is_waiting(&info.status)),
+        status: info.status,
+        status_source: StatusSource::Cwd,
+        last_prompt: info.last_prompt,
+        last_output: info.last_output,
         session_id: None,
         transcript_path: None,
         updated_at,
         warning: None,
-        waiting_prompt,
+        subagents: info.subagents,
+        todos: info.todos,
+    }
+}
+
+/// Session info from the mapping's transcript (`missing_status` if it
+/// doesn't exist yet) and last hook event.
+fn info_from_mapping(
+    mapping: SessionMapping,
+    tails: &mut TranscriptTails,
+    config: &DetectionConfig,
+    missing_status: SessionStatus,
+    warning: Option<String>,
+) -> SessionInfo {
+    let transcript_path = mapping.transcript_path;
+    let (transcript, updated_at) = if transcript_path.exists() {
+        let info = tails
+            .read_info(&transcript_path, config)
+            .unwrap_or_else(|_| TranscriptInfo::with_status(SessionStatus::Unknown));
+        (info, get_file_mtime(&transcript_path))
+    } else {
+        (TranscriptInfo::with_status(missing_status), None)
+    };
+
+    let mut info = SessionInfo {
+        status: transcript.status,
+        status_source: StatusSource::Transcript,
+        last_prompt: transcript.last_prompt,
+        last_output: transcript.last_output,
+        session_id: Some(mapping.session_id),
+        transcript_path: Some(transcript_path),
+        updated_at,
+        warning,
+        waiting_prompt: transcript.waiting_prompt,
+        subagents: transcript.subagents,
+        todos: transcript.todos,
+    };
+    if let Some(event) = &mapping.last_event {
+        apply_hook_event(&mut info, event);
+    }
+    if !is_waiting(&info.status) {
+        info.waiting_prompt = None;
+    }
+    info
+}
+
+/// Prefer the status of a hook event over the transcript heuristics when the
+/// event is at least as new as the transcript. An older PreToolUse still
+/// rules out a permission request for its tool: that would have fired a
+/// Notification, so a tool_use past the waiting timeout is just running.
+fn apply_hook_event(info: &mut SessionInfo, event: &HookEvent) {
+    let transcript_newer = info
+        .updated_at
+        .is_some_and(|mtime| DateTime::<Utc>::from(mtime) > event.at);
+    match hook_status(event) {
+        Some(status) if !transcript_newer => {
+            if let SessionStatus::WaitingForUser { tools } = &status {
+                let tool_names: Vec<String> = tools
+                    .iter()
+                    .filter(|t| !PROMPT_TOOLS.contains(&t.as_str()))
+                    .cloned()
+                    .collect();
+                if info.waiting_prompt.is_none() && !tool_names.is_empty() {
+                    info.waiting_prompt = Some(WaitingPrompt::ToolPermission { tool_names });
+                }
+            }
+            info.status = status;
+            info.status_source = StatusSource::Hook {
+                event: event.label(),
+                at: event.at,
+            };
+        }
+        _ => {
+            if let Some(running) = running_tool(event).filter(|_| is_waiting(&info.status)) {
+                info.status = running;
+            }
+        }
+    }
+}
+
+/// The tool a PreToolUse event started, as RunningTool (SubagentRunning for
+/// Task). None for other events and for tools that wait for the user by
+/// design.
+fn running_tool(event: &HookEvent) -> Option<SessionStatus> {
+    let tool = event.tool_name.as_deref()?;
+    if event.name != "PreToolUse" || PROMPT_TOOLS.contains(&tool) {
+        return None;
+    }
+    if is_subagent_tool(tool) {
+        return Some(SessionStatus::SubagentRunning { description: None });
+    }
+    Some(SessionStatus::RunningTool {
+        name: tool.to_string(),
+        elapsed: (Utc::now() - event.at).to_std().unwrap_or_default(),
+    })
+}
+
+/// Status a hook event implies, if any.
+fn hook_status(event: &HookEvent) -> Option<SessionStatus> {
+    match event.name.as_str() {
+        "SessionStart" => Some(SessionStatus::Ready),
+        "UserPromptSubmit" => Some(SessionStatus::Processing),
+        "PreToolUse" => match event.tool_name.as_deref() {
+            Some(tool) if PROMPT_TOOLS.contains(&tool) => Some(SessionStatus::WaitingForUser {
+                tools: vec![tool.to_string()],
+            }),
+            _ => Some(running_tool(event).unwrap_or(SessionStatus::Processing)),
+        },
+        "Notification" => match event.notification_type.as_deref() {
+            Some("permission_prompt") => Some(SessionStatus::WaitingForUser {
+                tools: event.tool_name.iter().cloned().collect(),
+            }),
+            Some("idle_prompt") => Some(SessionStatus::Idle),
+            _ => None,
+        },
+        "PreCompact" => Some(SessionStatus::Compacting),
+        "Stop" => Some(SessionStatus::Idle),
+        _ => None,
     }
 }
 
+fn is_waiting(status: &SessionStatus) -> bool {
+    matches!(status, SessionStatus::WaitingForUser { .. })
+}
+
 /// Detect session info by CWD (legacy method).
 fn detect_status_and_output_by_cwd(
     pane: &Pane,
-) -> (
-    SessionStatus,
-    Option<String>,
-    Option<String>,
-    Option<SystemTime>,
-    Option<WaitingPrompt>,
-) {
+    tails: &mut TranscriptTails,
+    config: &DetectionConfig,
+) -> (TranscriptInfo, Option<SystemTime>) {
     let cwd = match pane.cwd_path() {
         Some(cwd) => cwd,
-        None => return (SessionStatus::Unknown, None, None, None, None),
+        None => return (TranscriptInfo::with_status(SessionStatus::Unknown), None),
     };
 
     let dir = match get_transcript_dir(&cwd) {
         Some(dir) => dir,
         // No transcript directory = Claude Code is running but no session yet
-        None => return (SessionStatus::Ready, None, None, None, None),
+        None => return (TranscriptInfo::with_status(SessionStatus::Ready), None),
     };
 
     let transcript_path = match get_latest_transcript(&dir) {
         Ok(Some(path)) => path,
         // No transcript file = Claude Code is running but no session yet
-        _ => return (SessionStatus::Ready, None, None, None, None),
+        _ => return (TranscriptInfo::with_status(SessionStatus::Ready), None),
     };
 
-    let info = read_transcript_info(&transcript_path).unwrap_or(TranscriptInfo {
-        status: SessionStatus::Unknown,
-        last_prompt: None,
-        last_output: None,
-        waiting_prompt: None,
-    });
-    let updated_at = get_file_mtime(&transcript_path);
-
-    (
-        info.status,
-        info.last_prompt,
-        info.last_output,
-        updated_at,
-        info.waiting_prompt,
-    )
+    let info = tails
+        .read_info(&transcript_path, config)
+        .unwrap_or_else(|_| TranscriptInfo::with_status(SessionStatus::Unknown));
+    (info, get_file_mtime(&transcript_path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn event(name: &str, tool: Option<&str>, notification: Option<&str>) -> HookEvent {
+        HookEvent {
+            name: name.to_string(),
+            tool_name: tool.map(str::to_string),
+            notification_type: notification.map(str::to_string),
+            message: None,
+            at: Utc::now(),
+        }
+    }
+
+    fn transcript_info(status: SessionStatus, updated_at: SystemTime) -> SessionInfo {
+        SessionInfo {
+            status,
+            status_source: StatusSource::Transcript,
+            last_prompt: None,
+            last_output: None,
+            session_id: Some("abc".to_string()),
+            transcript_path: None,
+            updated_at: Some(updated_at),
+            warning: None,
+            waiting_prompt: None,
+            subagents: Vec::new(),
+            todos: Vec::new(),
+        }
+    }
+
+    fn waiting(tool: &str) -> SessionStatus {
+        SessionStatus::WaitingForUser {
+            tools: vec![tool.to_string()],
+        }
+    }
+
+    #[test]
+    fn test_hook_status() {
+        assert_eq!(
+            hook_status(&event("UserPromptSubmit", None, None)),
+            Some(SessionStatus::Processing)
+        );
+        assert!(matches!(
+            hook_status(&event("PreToolUse", Some("Bash"), None)),
+            Some(SessionStatus::RunningTool { name, .. }) if name == "Bash"
+        ));
+        assert_eq!(
+            hook_status(&event("PreToolUse", Some("AskUserQuestion"), None)),
+            Some(waiting("AskUserQuestion"))
+        );
+        assert_eq!(
+            hook_status(&event(
+                "Notification",
+                Some("Bash"),
+                Some("permission_prompt")
+            )),
+            Some(waiting("Bash"))
+        );
+        assert_eq!(
+            hook_status(&event("Notification", None, Some("idle_prompt"))),
+            Some(SessionStatus::Idle)
+        );
+        assert_eq!(
+            hook_status(&event("Stop", None, None)),
+            Some(SessionStatus::Idle)
+        );
+        assert_eq!(
+            hook_status(&event("PreToolUse", Some("Task"), None)),
+            Some(SessionStatus::SubagentRunning { description: None })
+        );
+        assert_eq!(
+            hook_status(&event("PreCompact", None, None)),
+            Some(SessionStatus::Compacting)
+        );
+        assert_eq!(hook_status(&event("Notification", None, None)), None);
+    }
+
+    #[test]
+    fn test_newer_hook_event_overrides_transcript() {
+        let an_hour_ago = SystemTime::now() - Duration::from_secs(3600);
+        let mut info = transcript_info(SessionStatus::Processing, an_hour_ago);
+        apply_hook_event(
+            &mut info,
+            &event("Notification", Some("Bash"), Some("permission_prompt")),
+        );
+        assert_eq!(info.status, waiting("Bash"));
+        assert!(matches!(
+            info.status_source,
+            StatusSource::Hook { ref event, .. } if event == "Notification Bash"
+        ));
+        assert!(matches!(
+            info.waiting_prompt,
+            Some(WaitingPrompt::ToolPermission { ref tool_names }) if tool_names == &["Bash"]
+        ));
+    }
+
+    #[test]
+    fn test_older_hook_event_defers_to_transcript() {
+        let old = event("Stop", None, None);
+        let mut info = transcript_info(
+            SessionStatus::Processing,
+            SystemTime::now() + Duration::from_secs(60),
+        );
+        apply_hook_event(&mut info, &old);
+        assert_eq!(info.status, SessionStatus::Processing);
+        assert_eq!(info.status_source, StatusSource::Transcript);
+    }
+
+    #[test]
+    fn test_running_tool_is_not_waiting() {
+        // A long-running Bash tool_use passes the waiting timeout, but no
+        // permission was requested after its PreToolUse
+        let pre_tool_use = event("PreToolUse", Some("Bash"), None);
+        let mut info =
+            transcript_info(waiting("Bash"), SystemTime::now() + Duration::from_secs(60));
+        apply_hook_event(&mut info, &pre_tool_use);
+        assert!(
+            matches!(info.status, SessionStatus::RunningTool { ref name, .. } if name == "Bash")
+        );
+        assert_eq!(info.status_source, StatusSource::Transcript);
+
+        // Questions do wait for the user
+        let ask = event("PreToolUse", Some("AskUserQuestion"), None);
+        let mut info = transcript_info(
+            waiting("AskUserQuestion"),
+            SystemTime::now() + Duration::from_secs(60),
+        );
+        apply_hook_event(&mut info, &ask);
+        assert_eq!(info.status, waiting("AskUserQuestion"));
+    }
 }
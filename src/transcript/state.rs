@@ -1,9 +1,12 @@
 //! Session status detection from transcript entries.
 
-use super::parser::{read_last_entries, TranscriptEntry};
+use super::parser::{is_subagent_tool, read_last_entries, TranscriptEntry};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
+use std::time::Duration;
 
 /// Check if an entry is an internal/system entry that doesn't indicate real activity.
 fn is_internal_entry(entry: &TranscriptEntry) -> bool {
@@ -13,20 +16,63 @@ fn is_internal_entry(entry: &TranscriptEntry) -> bool {
 }
 
 /// Check if a tool_use entry has timed out and should be considered as WaitingForUser.
-/// Returns Some(SessionStatus) if status can be determined, None otherwise.
+/// Before its timeout the tool counts as running; entries without a
+/// timestamp are Processing.
 fn check_tool_use_status(entry: &TranscriptEntry, config: &DetectionConfig) -> SessionStatus {
     let tools = entry.get_tool_names();
 
-    if let Some(timestamp) = &entry.timestamp {
-        if let Ok(entry_time) = DateTime::parse_from_rfc3339(timestamp) {
-            let elapsed = Utc::now().signed_duration_since(entry_time.with_timezone(&Utc));
-            if elapsed.num_seconds() > config.waiting_timeout_secs as i64 {
-                return SessionStatus::WaitingForUser { tools };
-            }
+    let elapsed = entry
+        .timestamp
+        .as_deref()
+        .and_then(|timestamp| DateTime::parse_from_rfc3339(timestamp).ok())
+        .map(|entry_time| Utc::now().signed_duration_since(entry_time.with_timezone(&Utc)));
+    match elapsed {
+        Some(elapsed) if elapsed.num_seconds() > config.timeout_secs(&tools) as i64 => {
+            SessionStatus::WaitingForUser { tools }
         }
+        Some(_) if tools.iter().any(|t| is_subagent_tool(t)) => SessionStatus::SubagentRunning {
+            description: entry.subagent_description(),
+        },
+        Some(elapsed) if !tools.is_empty() => SessionStatus::RunningTool {
+            name: tools.join(", "),
+            elapsed: elapsed.to_std().unwrap_or_default(),
+        },
+        _ => SessionStatus::Processing,
     }
+}
+
+/// Status of an API error entry: RateLimited for rate limits and overloads,
+/// Errored for anything else. None for other entries.
+fn api_error_status(entry: &TranscriptEntry) -> Option<SessionStatus> {
+    let message = entry.api_error_summary()?;
+    Some(if entry.is_rate_limit_error() {
+        SessionStatus::RateLimited { message }
+    } else {
+        SessionStatus::Errored { message }
+    })
+}
 
-    SessionStatus::Processing
+/// Status once a turn has ended: Idle, unless the turn ended on a failed
+/// request.
+fn turn_end_status(entries: &[TranscriptEntry]) -> SessionStatus {
+    entries
+        .iter()
+        .rev()
+        .find(|e| e.type_ == "assistant")
+        .and_then(api_error_status)
+        .unwrap_or(SessionStatus::Idle)
+}
+
+/// Subagent status for its progress entries, described by the pending Task
+/// tool_use.
+fn subagent_status(entries: &[TranscriptEntry]) -> SessionStatus {
+    SessionStatus::SubagentRunning {
+        description: entries
+            .iter()
+            .rev()
+            .find(|e| e.is_tool_use())
+            .and_then(|e| e.subagent_description()),
+    }
 }
 
 /// The detected status of a Claude Code session.
@@ -36,6 +82,16 @@ pub enum SessionStatus {
     Ready,
     /// Claude is actively processing (streaming, executing tools, etc.)
     Processing,
+    /// A tool is executing (e.g., a long `cargo build`), not waiting for approval
+    RunningTool { name: String, elapsed: Duration },
+    /// A subagent (Task tool) is working, with the task's description
+    SubagentRunning { description: Option<String> },
+    /// The conversation context is being compacted
+    Compacting,
+    /// Requests are rate limited, over the usage limit or overloaded (429/529)
+    RateLimited { message: String },
+    /// The last request failed with an API error
+    Errored { message: String },
     /// Claude is idle, waiting for user input
     Idle,
     /// Claude is waiting for user action (permission approval, question response, etc.)
@@ -48,12 +104,83 @@ pub enum SessionStatus {
 impl SessionStatus {
     /// Get a short display string for the status.
     pub fn as_str(&self) -> &'static str {
+        self.kind().as_str()
+    }
+
+    /// The status without its details (tool names, messages, ...).
+    pub fn kind(&self) -> StatusKind {
+        match self {
+            SessionStatus::Ready => StatusKind::Ready,
+            SessionStatus::Processing => StatusKind::Processing,
+            SessionStatus::RunningTool { .. } => StatusKind::RunningTool,
+            SessionStatus::SubagentRunning { .. } => StatusKind::Subagent,
+            SessionStatus::Compacting => StatusKind::Compacting,
+            SessionStatus::RateLimited { .. } => StatusKind::RateLimited,
+            SessionStatus::Errored { .. } => StatusKind::Errored,
+            SessionStatus::Idle => StatusKind::Idle,
+            SessionStatus::WaitingForUser { .. } => StatusKind::Waiting,
+            SessionStatus::Unknown => StatusKind::Unknown,
+        }
+    }
+
+    /// Sort priority when ordering sessions by urgency (lower = more urgent).
+    pub fn urgency(&self) -> u8 {
+        match self {
+            SessionStatus::WaitingForUser { .. } => 0,
+            SessionStatus::Errored { .. } => 1,
+            SessionStatus::RateLimited { .. } => 2,
+            SessionStatus::Ready => 3,
+            SessionStatus::Processing
+            | SessionStatus::RunningTool { .. }
+            | SessionStatus::SubagentRunning { .. }
+            | SessionStatus::Compacting => 4,
+            SessionStatus::Idle => 5,
+            SessionStatus::Unknown => 6,
+        }
+    }
+
+    /// Whether Claude is working (shown with an animated indicator).
+    pub fn is_active(&self) -> bool {
+        matches!(
+            self,
+            SessionStatus::Processing
+                | SessionStatus::RunningTool { .. }
+                | SessionStatus::SubagentRunning { .. }
+                | SessionStatus::Compacting
+        )
+    }
+}
+
+/// A session status without its details, as recorded in status timelines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusKind {
+    Ready,
+    Processing,
+    RunningTool,
+    Subagent,
+    Compacting,
+    RateLimited,
+    Errored,
+    Idle,
+    Waiting,
+    Unknown,
+}
+
+impl StatusKind {
+    /// Get a short display string for the status.
+    pub fn as_str(self) -> &'static str {
         match self {
-            SessionStatus::Ready => "Ready",
-            SessionStatus::Processing => "Processing",
-            SessionStatus::Idle => "Idle",
-            SessionStatus::WaitingForUser { .. } => "Waiting",
-            SessionStatus::Unknown => "Unknown",
+            StatusKind::Ready => "Ready",
+            StatusKind::Processing => "Processing",
+            StatusKind::RunningTool => "Running",
+            StatusKind::Subagent => "Subagent",
+            StatusKind::Compacting => "Compacting",
+            StatusKind::RateLimited => "Rate limited",
+            StatusKind::Errored => "Error",
+            StatusKind::Idle => "Idle",
+            StatusKind::Waiting => "Waiting",
+            StatusKind::Unknown => "Unknown",
         }
     }
 }
@@ -63,16 +190,44 @@ impl SessionStatus {
 pub struct DetectionConfig {
     /// Seconds after tool_use before considering it as WaitingForUser
     pub waiting_timeout_secs: u64,
+    /// Per-tool overrides of `waiting_timeout_secs`, by tool name
+    pub tool_timeouts: HashMap<String, u64>,
 }
 
 impl Default for DetectionConfig {
     fn default() -> Self {
         Self {
             waiting_timeout_secs: 10,
+            // Subagents and web requests run long without asking for approval
+            tool_timeouts: [
+                ("Task", 600),
+                ("Agent", 600),
+                ("WebFetch", 60),
+                ("WebSearch", 60),
+            ]
+            .into_iter()
+            .map(|(tool, secs)| (tool.to_string(), secs))
+            .collect(),
         }
     }
 }
 
+impl DetectionConfig {
+    /// Waiting timeout of a tool_use calling `tools`: the longest of theirs.
+    pub fn timeout_secs(&self, tools: &[String]) -> u64 {
+        tools
+            .iter()
+            .map(|tool| {
+                self.tool_timeouts
+                    .get(tool)
+                    .copied()
+                    .unwrap_or(self.waiting_timeout_secs)
+            })
+            .max()
+            .unwrap_or(self.waiting_timeout_secs)
+    }
+}
+
 /// Detect the session status from a transcript file.
 pub fn detect_session_status(path: &Path) -> Result<SessionStatus> {
     detect_session_status_with_config(path, &DetectionConfig::default())
@@ -87,11 +242,6 @@ pub fn detect_session_status_with_config(
     Ok(detect_status_from_entries_with_config(&entries, config))
 }
 
-/// Detect session status from pre-parsed entries with default configuration.
-pub fn detect_session_status_from_entries(entries: &[TranscriptEntry]) -> SessionStatus {
-    detect_status_from_entries_with_config(entries, &DetectionConfig::default())
-}
-
 /// Detect session status from pre-parsed entries with custom configuration.
 /// This is the core logic, extracted to avoid re-reading the file when
 /// status detection is combined with other transcript queries.
@@ -106,14 +256,29 @@ pub fn detect_status_from_entries_with_config(
     // Find the last meaningful entry
     let last = entries.last().unwrap();
 
+    // API errors (retried or final)
+    if let Some(status) = api_error_status(last) {
+        return status;
+    }
+    // compact_boundary is written once compaction has finished, so it ends
+    // compaction rather than starting it (compaction in progress is only
+    // known from the PreCompact hook)
+    if last.is_compact_boundary() {
+        return SessionStatus::Idle;
+    }
+
     // Check for progress type - Processing (but not hook_progress which is just session hooks)
+    if last.is_agent_progress() {
+        return subagent_status(entries);
+    }
     if last.is_progress() && !last.is_hook_progress() {
         return SessionStatus::Processing;
     }
 
     // Check for system stop_hook_summary or turn_duration - indicates Idle
+    // (or the error the turn ended on)
     if last.is_stop_hook_summary() || last.is_turn_duration() {
-        return SessionStatus::Idle;
+        return turn_end_status(entries);
     }
 
     // For system entries (other than stop_hook_summary/turn_duration), internal entries
@@ -125,7 +290,16 @@ pub fn detect_status_from_entries_with_config(
                 continue;
             }
 
-            if entry.is_stop_hook_summary() || entry.is_turn_duration() || entry.is_end_turn() {
+            if let Some(status) = api_error_status(entry) {
+                return status;
+            }
+            if entry.is_compact_boundary() {
+                return SessionStatus::Idle;
+            }
+            if entry.is_stop_hook_summary() || entry.is_turn_duration() {
+                return turn_end_status(entries);
+            }
+            if entry.is_end_turn() {
                 return SessionStatus::Idle;
             }
             if entry.is_streaming() {
@@ -315,8 +489,8 @@ mod tests {
     }
 
     #[test]
-    fn test_detect_recent_tool_use_returns_processing() {
-        // Tool use with very recent timestamp should be Processing
+    fn test_detect_recent_tool_use_returns_running_tool() {
+        // Tool use with very recent timestamp is still running
         let now = Utc::now();
         let timestamp = now.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string();
         let entry = format!(
@@ -325,7 +499,117 @@ mod tests {
         );
         let file = create_transcript(&[&entry]);
         let status = detect_session_status(file.path()).unwrap();
-        assert_eq!(status, SessionStatus::Processing);
+        assert!(matches!(status, SessionStatus::RunningTool { name, .. } if name == "Bash"));
+    }
+
+    #[test]
+    fn test_detect_tool_use_with_per_tool_timeout() {
+        // A web request past the default timeout is still running
+        let old_time = Utc::now() - chrono::Duration::seconds(45);
+        let timestamp = old_time.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string();
+        let entry = format!(
+            r#"{{"type":"assistant","timestamp":"{}","message":{{"stop_reason":"tool_use","content":[{{"type":"tool_use","name":"WebFetch"}}]}}}}"#,
+            timestamp
+        );
+        let file = create_transcript(&[&entry]);
+        let status = detect_session_status(file.path()).unwrap();
+        assert!(matches!(
+            status,
+            SessionStatus::RunningTool { name, elapsed } if name == "WebFetch" && elapsed.as_secs() >= 45
+        ));
+
+        let mut config = DetectionConfig::default();
+        config.tool_timeouts.insert("WebFetch".to_string(), 30);
+        let status = detect_session_status_with_config(file.path(), &config).unwrap();
+        assert!(matches!(status, SessionStatus::WaitingForUser { .. }));
+    }
+
+    #[test]
+    fn test_detect_api_errors() {
+        // Overloaded request being retried
+        let file = create_transcript(&[
+            r#"{"type":"user","timestamp":"2026-01-23T16:29:00.000Z","message":{"content":"Hello"}}"#,
+            r#"{"type":"system","subtype":"api_error","level":"error","timestamp":"2026-01-23T16:29:01.000Z","error":{"status":529,"error":{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}},"retryInMs":2000,"retryAttempt":3,"maxRetries":10}"#,
+        ]);
+        let status = detect_session_status(file.path()).unwrap();
+        assert_eq!(
+            status,
+            SessionStatus::RateLimited {
+                message: "API error 529, retry 3/10".to_string()
+            }
+        );
+
+        // A failed request ends the turn with a synthetic message
+        let file = create_transcript(&[
+            r#"{"type":"user","timestamp":"2026-01-23T16:29:00.000Z","message":{"content":"Hello"}}"#,
+            r#"{"type":"assistant","isApiErrorMessage":true,"timestamp":"2026-01-23T16:29:01.000Z","message":{"stop_reason":"stop_sequence","content":[{"type":"text","text":"API Error: 500 {\"type\":\"error\",\"error\":{\"type\":\"api_error\",\"message\":\"Internal server error\"}}"}]}}"#,
+            r#"{"type":"system","subtype":"stop_hook_summary","timestamp":"2026-01-23T16:29:02.000Z"}"#,
+        ]);
+        let status = detect_session_status(file.path()).unwrap();
+        assert!(
+            matches!(status, SessionStatus::Errored { ref message } if message.starts_with("API Error: 500")),
+            "{:?}",
+            status
+        );
+
+        let file = create_transcript(&[
+            r#"{"type":"assistant","isApiErrorMessage":true,"timestamp":"2026-01-23T16:29:01.000Z","message":{"stop_reason":"stop_sequence","content":[{"type":"text","text":"5-hour limit reached ∙ resets 3pm"}]}}"#,
+        ]);
+        let status = detect_session_status(file.path()).unwrap();
+        assert!(matches!(status, SessionStatus::RateLimited { .. }));
+    }
+
+    #[test]
+    fn test_detect_compact_boundary_ends_compaction() {
+        let file = create_transcript(&[
+            r#"{"type":"user","timestamp":"2026-01-23T16:29:00.000Z","message":{"content":"Hello"}}"#,
+            r#"{"type":"system","subtype":"compact_boundary","timestamp":"2026-01-23T16:29:01.000Z","compactMetadata":{"trigger":"auto","preTokens":160000}}"#,
+        ]);
+        let status = detect_session_status(file.path()).unwrap();
+        assert_eq!(status, SessionStatus::Idle);
+
+        let file = create_transcript(&[
+            r#"{"type":"system","subtype":"compact_boundary","timestamp":"2026-01-23T16:29:01.000Z","compactMetadata":{"trigger":"manual","preTokens":160000}}"#,
+            r#"{"type":"file-history-snapshot"}"#,
+        ]);
+        let status = detect_session_status(file.path()).unwrap();
+        assert_eq!(status, SessionStatus::Idle);
+    }
+
+    #[test]
+    fn test_detect_subagent_running() {
+        let timestamp = Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string();
+        let task = format!(
+            r#"{{"type":"assistant","timestamp":"{}","message":{{"stop_reason":"tool_use","content":[{{"type":"tool_use","name":"Task","input":{{"description":"Explore the parser","subagent_type":"Explore"}}}}]}}}}"#,
+            timestamp
+        );
+        let expected = SessionStatus::SubagentRunning {
+            description: Some("Explore the parser".to_string()),
+        };
+        let file = create_transcript(&[&task]);
+        assert_eq!(detect_session_status(file.path()).unwrap(), expected);
+
+        let progress = format!(
+            r#"{{"type":"progress","timestamp":"{}","data":{{"type":"agent_progress"}}}}"#,
+            timestamp
+        );
+        let file = create_transcript(&[&task, &progress]);
+        assert_eq!(detect_session_status(file.path()).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_urgency_orders_failures_first() {
+        let errored = SessionStatus::Errored {
+            message: "API Error: 500".to_string(),
+        };
+        let rate_limited = SessionStatus::RateLimited {
+            message: "API error 429".to_string(),
+        };
+        assert!(SessionStatus::WaitingForUser { tools: vec![] }.urgency() < errored.urgency());
+        assert!(errored.urgency() < rate_limited.urgency());
+        assert!(rate_limited.urgency() < SessionStatus::Processing.urgency());
+        assert!(SessionStatus::Compacting.is_active());
+        assert!(!rate_limited.is_active());
     }
 
     #[test]
@@ -461,6 +745,7 @@ mod tests {
 
         let config = DetectionConfig {
             waiting_timeout_secs: 3,
+            ..DetectionConfig::default()
         };
         let status = detect_session_status_with_config(file.path(), &config).unwrap();
         assert!(matches!(status, SessionStatus::WaitingForUser { tools } if tools == vec!["Bash"]));
@@ -0,0 +1,504 @@
+//! Usage statistics aggregated over every transcript on disk: turns, tool
+//! calls, tokens, estimated cost and active time, grouped by project, day
+//! and model.
+
+use anyhow::Result;
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime};
+
+use super::parser::TurnBuilder;
+use super::usage::TokenUsage;
+
+/// Column headers matching `UsageTotals::cells`.
+pub const STATS_COLUMNS: [&str; 7] = [
+    "Turns", "Tools", "Input", "Output", "Cache", "Cost", "Active",
+];
+
+/// Totals for one group (or for everything).
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct UsageTotals {
+    /// User prompts
+    pub turns: usize,
+    /// Tool calls by tool name
+    pub tool_calls: BTreeMap<String, usize>,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_creation_tokens: u64,
+    pub cache_read_tokens: u64,
+    /// Estimated cost in USD (responses of unknown models count as free)
+    pub cost_usd: f64,
+    /// Time Claude spent working on turns (`turn_duration` entries), in ms
+    pub active_ms: u64,
+}
+
+impl UsageTotals {
+    /// Tool calls of every type.
+    pub fn total_tool_calls(&self) -> usize {
+        self.tool_calls.values().sum()
+    }
+
+    /// Table cells, in `STATS_COLUMNS` order.
+    pub fn cells(&self) -> [String; 7] {
+        [
+            self.turns.to_string(),
+            self.total_tool_calls().to_string(),
+            format_tokens(self.input_tokens),
+            format_tokens(self.output_tokens),
+            format_tokens(self.cache_creation_tokens + self.cache_read_tokens),
+            format!("${:.2}", self.cost_usd),
+            format_duration(Duration::from_millis(self.active_ms), 2),
+        ]
+    }
+}
+
+/// How `UsageStats` rows are grouped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsGroup {
+    Project,
+    Day,
+    Model,
+}
+
+impl StatsGroup {
+    /// All groupings in cycling order.
+    pub const ALL: [StatsGroup; 3] = [StatsGroup::Project, StatsGroup::Day, StatsGroup::Model];
+
+    /// Name used on the command line and for display.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatsGroup::Project => "project",
+            StatsGroup::Day => "day",
+            StatsGroup::Model => "model",
+        }
+    }
+
+    /// Grouping by its `as_str` name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|g| g.as_str() == name)
+    }
+
+    /// Next grouping (wraps around).
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|g| *g == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+}
+
+/// Usage aggregated over transcripts.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct UsageStats {
+    /// First day included (None: all history)
+    pub since: Option<NaiveDate>,
+    pub total: UsageTotals,
+    /// By project (session working directory, `~`-relative under home)
+    pub by_project: BTreeMap<String, UsageTotals>,
+    /// By local date (`YYYY-MM-DD`)
+    pub by_day: BTreeMap<String, UsageTotals>,
+    /// By model. Turns and active time count toward the model that answered.
+    pub by_model: BTreeMap<String, UsageTotals>,
+}
+
+impl UsageStats {
+    /// Rows of one grouping, by key.
+    pub fn groups(&self, group: StatsGroup) -> &BTreeMap<String, UsageTotals> {
+        match group {
+            StatsGroup::Project => &self.by_project,
+            StatsGroup::Day => &self.by_day,
+            StatsGroup::Model => &self.by_model,
+        }
+    }
+
+    /// Apply `f` to the total and to every group the event belongs to.
+    fn record(
+        &mut self,
+        project: &str,
+        day: &str,
+        model: Option<&str>,
+        f: impl Fn(&mut UsageTotals),
+    ) {
+        f(&mut self.total);
+        f(self.by_project.entry(project.to_string()).or_default());
+        f(self.by_day.entry(day.to_string()).or_default());
+        if let Some(model) = model {
+            f(self.by_model.entry(model.to_string()).or_default());
+        }
+    }
+}
+
+/// Aggregate every transcript under `projects_dir`, counting only entries
+/// from `since` (local date) on. Unreadable files are skipped. Setting
+/// `cancel` stops early with the transcripts read so far.
+pub fn collect_usage_stats(
+    projects_dir: &Path,
+    since: Option<NaiveDate>,
+    cancel: &AtomicBool,
+) -> Result<UsageStats> {
+    let mut stats = UsageStats {
+        since,
+        ..Default::default()
+    };
+    if !projects_dir.is_dir() {
+        return Ok(stats);
+    }
+
+    // Files last written before `since` can't contain anything newer
+    let home = dirs::home_dir();
+    let cutoff = since
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .and_then(|dt| dt.and_local_timezone(Local).earliest())
+        .map(SystemTime::from);
+
+    for project in std::fs::read_dir(projects_dir)?.flatten() {
+        let Ok(entries) = std::fs::read_dir(project.path()) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            if cancel.load(Ordering::Relaxed) {
+                return Ok(stats);
+            }
+            let path = entry.path();
+            if path.extension().and_then(|s| s.to_str()) != Some("jsonl") {
+                continue;
+            }
+            let modified = entry.metadata().and_then(|m| m.modified()).ok();
+            if let (Some(cutoff), Some(modified)) = (cutoff, modified) {
+                if modified < cutoff {
+                    continue;
+                }
+            }
+            let Ok(content) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            let project_name = project.file_name().to_string_lossy().to_string();
+            add_transcript(&mut stats, &content, &project_name, home.as_deref());
+        }
+    }
+    Ok(stats)
+}
+
+/// First day of a period of `days` days ending today (`days` of 0 counts as 1).
+pub fn period_start(days: u32) -> NaiveDate {
+    let today = Local::now().date_naive();
+    today - chrono::Days::new(u64::from(days.max(1) - 1))
+}
+
+/// Add one transcript's usage to `stats`. Its project is the session cwd,
+/// or `project_dir` (the encoded directory name) for transcripts without one.
+fn add_transcript(stats: &mut UsageStats, content: &str, project_dir: &str, home: Option<&Path>) {
+    #[derive(Deserialize)]
+    struct Entry {
+        #[serde(rename = "type")]
+        type_: Option<String>,
+        subtype: Option<String>,
+        timestamp: Option<String>,
+        cwd: Option<String>,
+        #[serde(rename = "durationMs")]
+        duration_ms: Option<u64>,
+        message: Option<Message>,
+    }
+    #[derive(Deserialize)]
+    struct Message {
+        id: Option<String>,
+        model: Option<String>,
+        usage: Option<TokenUsage>,
+        #[serde(default)]
+        content: serde_json::Value,
+    }
+
+    let lines: Vec<&str> = content.lines().filter(|l| !l.trim().is_empty()).collect();
+    let entries: Vec<Option<Entry>> = lines.iter().map(|l| serde_json::from_str(l).ok()).collect();
+    let project = entries
+        .iter()
+        .flatten()
+        .find_map(|e| e.cwd.as_deref())
+        .map(|cwd| project_key(cwd, home))
+        .unwrap_or_else(|| project_dir.to_string());
+
+    let mut turns = TurnBuilder::default();
+    let mut seen_messages = HashSet::new();
+    let mut day = String::new();
+    let mut model: Option<String> = None;
+    // Day of a prompt whose answering model isn't known yet
+    let mut pending_turn: Option<String> = None;
+
+    for (line, entry) in lines.iter().zip(entries) {
+        let prompts = turns.prompt_count();
+        turns.push_line(line);
+        let Some(entry) = entry else {
+            continue;
+        };
+        if let Some(d) = entry.timestamp.as_deref().and_then(local_day) {
+            day = d;
+        }
+        let in_range = match stats.since {
+            Some(since) => NaiveDate::parse_from_str(&day, "%Y-%m-%d").is_ok_and(|d| d >= since),
+            None => true,
+        };
+
+        if turns.prompt_count() > prompts {
+            if let Some(prev_day) = pending_turn.take() {
+                stats.record(&project, &prev_day, None, |t| t.turns += 1);
+            }
+            if in_range {
+                pending_turn = Some(day.clone());
+            }
+        }
+
+        match (entry.type_.as_deref(), entry.message) {
+            (Some("assistant"), Some(message)) => {
+                if let Some(m) = message.model.filter(|m| m != "<synthetic>") {
+                    model = Some(m);
+                }
+                if let Some(turn_day) = pending_turn.take() {
+                    stats.record(&project, &turn_day, model.as_deref(), |t| t.turns += 1);
+                }
+                if !in_range {
+                    continue;
+                }
+
+                let tools: Vec<String> = message
+                    .content
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter(|block| block["type"] == "tool_use")
+                    .filter_map(|block| block["name"].as_str().map(str::to_string))
+                    .collect();
+                for tool in &tools {
+                    stats.record(&project, &day, model.as_deref(), |t| {
+                        *t.tool_calls.entry(tool.clone()).or_default() += 1
+                    });
+                }
+
+                // A response is logged once per content block with the same usage
+                let first = message.id.is_none_or(|id| seen_messages.insert(id));
+                if let (true, Some(usage)) = (first, message.usage) {
+                    let cost = model
+                        .as_deref()
+                        .and_then(|m| usage.cost_usd(m))
+                        .unwrap_or(0.0);
+                    stats.record(&project, &day, model.as_deref(), |t| {
+                        t.input_tokens += usage.input_tokens;
+                        t.output_tokens += usage.output_tokens;
+                        t.cache_creation_tokens += usage.cache_creation_input_tokens;
+                        t.cache_read_tokens += usage.cache_read_input_tokens;
+                        t.cost_usd += cost;
+                    });
+                }
+            }
+            (Some("system"), _) if entry.subtype.as_deref() == Some("turn_duration") => {
+                if let (true, Some(ms)) = (in_range, entry.duration_ms) {
+                    stats.record(&project, &day, model.as_deref(), |t| t.active_ms += ms);
+                }
+            }
+            _ => {}
+        }
+    }
+    if let Some(turn_day) = pending_turn {
+        stats.record(&project, &turn_day, None, |t| t.turns += 1);
+    }
+}
+
+/// Project key of a session cwd: the full path, shortened to `~/...` under
+/// `home`, so projects with the same directory name stay apart.
+fn project_key(cwd: &str, home: Option<&Path>) -> String {
+    match home.and_then(|home| Path::new(cwd).strip_prefix(home).ok()) {
+        Some(rel) if rel.as_os_str().is_empty() => "~".to_string(),
+        Some(rel) => format!("~/{}", rel.display()),
+        None => cwd.to_string(),
+    }
+}
+
+/// Local `YYYY-MM-DD` of an RFC 3339 timestamp.
+fn local_day(timestamp: &str) -> Option<String> {
+    let dt = DateTime::parse_from_rfc3339(timestamp).ok()?;
+    Some(dt.with_timezone(&Local).format("%Y-%m-%d").to_string())
+}
+
+/// Compact token count (`950`, `12.3k`, `4.5M`).
+pub fn format_tokens(tokens: u64) -> String {
+    match tokens {
+        0..1_000 => tokens.to_string(),
+        1_000..1_000_000 => format!("{:.1}k", tokens as f64 / 1_000.0),
+        _ => format!("{:.1}M", tokens as f64 / 1_000_000.0),
+    }
+}
+
+/// Format a duration with its `units` largest units (at least one): e.g.
+/// "5s", "2m", "1h", "3d" with one unit and "42s", "4m12s", "1h05m",
+/// "2d03h" with two.
+pub fn format_duration(duration: Duration, units: usize) -> String {
+    const UNITS: [(u64, &str); 4] = [(86400, "d"), (3600, "h"), (60, "m"), (1, "s")];
+    let secs = duration.as_secs();
+    let largest = UNITS
+        .iter()
+        .position(|(size, _)| secs >= *size)
+        .unwrap_or(UNITS.len() - 1);
+    UNITS[largest..]
+        .iter()
+        .take(units.max(1))
+        .enumerate()
+        .map(|(i, (size, suffix))| match i {
+            0 => format!("{}{}", secs / size, suffix),
+            _ => format!("{:02}{}", secs % UNITS[largest + i - 1].0 / size, suffix),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn user(text: &str, ts: &str) -> String {
+        user_in("/home/me/proj", text, ts)
+    }
+
+    fn user_in(cwd: &str, text: &str, ts: &str) -> String {
+        format!(
+            r#"{{"type":"user","cwd":"{}","timestamp":"{}","message":{{"role":"user","content":"{}"}}}}"#,
+            cwd, ts, text
+        )
+    }
+
+    fn tool_use(id: &str, tool: &str, ts: &str) -> String {
+        format!(
+            r#"{{"type":"assistant","timestamp":"{}","message":{{"id":"{}","model":"claude-sonnet-4-5","content":[{{"type":"tool_use","name":"{}","input":{{}}}}],"usage":{{"input_tokens":1000,"output_tokens":100}}}}}}"#,
+            ts, id, tool
+        )
+    }
+
+    fn duration(ms: u64, ts: &str) -> String {
+        format!(
+            r#"{{"type":"system","subtype":"turn_duration","durationMs":{},"timestamp":"{}"}}"#,
+            ms, ts
+        )
+    }
+
+    #[test]
+    fn test_collect_usage_stats() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("-home-me-proj");
+        fs::create_dir(&project).unwrap();
+        fs::write(
+            project.join("aaaa-1111.jsonl"),
+            [
+                user("fix the bug", "2026-01-01T12:00:00Z"),
+                tool_use("msg_1", "Read", "2026-01-01T12:00:01Z"),
+                // Second content block of the same response: usage counted once
+                tool_use("msg_1", "Grep", "2026-01-01T12:00:01Z"),
+                tool_use("msg_2", "Read", "2026-01-01T12:00:02Z"),
+                duration(90_000, "2026-01-01T12:01:30Z"),
+                user("and the tests", "2026-01-03T12:00:00Z"),
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+
+        let stats = collect_usage_stats(dir.path(), None, &AtomicBool::new(false)).unwrap();
+        let total = &stats.total;
+        assert_eq!(total.turns, 2);
+        assert_eq!(total.total_tool_calls(), 3);
+        assert_eq!(total.tool_calls["Read"], 2);
+        assert_eq!(total.input_tokens, 2000);
+        assert_eq!(total.output_tokens, 200);
+        assert_eq!(total.active_ms, 90_000);
+        assert!((total.cost_usd - 0.009).abs() < 1e-9);
+
+        assert_eq!(
+            stats.by_project.keys().collect::<Vec<_>>(),
+            vec![&project_key("/home/me/proj", dirs::home_dir().as_deref())]
+        );
+        assert_eq!(stats.by_day.len(), 2);
+        // The unanswered prompt has no model
+        assert_eq!(stats.by_model["claude-sonnet-4-5"].turns, 1);
+
+        let since = NaiveDate::from_ymd_opt(2026, 1, 3);
+        let stats = collect_usage_stats(dir.path(), since, &AtomicBool::new(false)).unwrap();
+        assert_eq!(stats.total.turns, 1);
+        assert_eq!(stats.total.total_tool_calls(), 0);
+    }
+
+    #[test]
+    fn test_projects_with_same_name_stay_apart() {
+        let mut stats = UsageStats::default();
+        let home = Some(Path::new("/home/me"));
+        for cwd in ["/home/me/work/api", "/home/me/oss/api", "/srv/api"] {
+            let content = user_in(cwd, "hi", "2026-01-01T12:00:00Z");
+            add_transcript(&mut stats, &content, "-encoded", home);
+        }
+        add_transcript(&mut stats, "", "-home-me-empty", home);
+        assert_eq!(
+            stats.by_project.keys().collect::<Vec<_>>(),
+            vec!["/srv/api", "~/oss/api", "~/work/api"]
+        );
+        assert_eq!(project_key("/home/me", home), "~");
+        assert_eq!(project_key("/home/meow/x", home), "/home/meow/x");
+    }
+
+    #[test]
+    fn test_format_duration_seconds() {
+        assert_eq!(format_duration(Duration::from_secs(0), 1), "0s");
+        assert_eq!(format_duration(Duration::from_secs(1), 1), "1s");
+        assert_eq!(format_duration(Duration::from_secs(30), 1), "30s");
+        assert_eq!(format_duration(Duration::from_secs(59), 1), "59s");
+    }
+
+    #[test]
+    fn test_format_duration_minutes() {
+        assert_eq!(format_duration(Duration::from_secs(60), 1), "1m");
+        assert_eq!(format_duration(Duration::from_secs(90), 1), "1m");
+        assert_eq!(format_duration(Duration::from_secs(300), 1), "5m");
+        assert_eq!(format_duration(Duration::from_secs(3599), 1), "59m");
+    }
+
+    #[test]
+    fn test_format_duration_hours() {
+        assert_eq!(format_duration(Duration::from_secs(3600), 1), "1h");
+        assert_eq!(format_duration(Duration::from_secs(7200), 1), "2h");
+        assert_eq!(format_duration(Duration::from_secs(86399), 1), "23h");
+    }
+
+    #[test]
+    fn test_format_duration_days() {
+        assert_eq!(format_duration(Duration::from_secs(86400), 1), "1d");
+        assert_eq!(format_duration(Duration::from_secs(172800), 1), "2d");
+    }
+
+    #[test]
+    fn test_format_duration_two_units() {
+        assert_eq!(format_duration(Duration::from_secs(42), 2), "42s");
+        assert_eq!(format_duration(Duration::from_secs(252), 2), "4m12s");
+        assert_eq!(format_duration(Duration::from_secs(3900), 2), "1h05m");
+        assert_eq!(format_duration(Duration::from_secs(183600), 2), "2d03h");
+        // Lower units are zero-padded; a third unit when asked for
+        assert_eq!(format_duration(Duration::from_secs(3600), 2), "1h00m");
+        assert_eq!(format_duration(Duration::from_secs(3903), 3), "1h05m03s");
+    }
+
+    #[test]
+    fn test_format_tokens_and_active() {
+        assert_eq!(format_tokens(950), "950");
+        assert_eq!(format_tokens(12_345), "12.3k");
+        assert_eq!(format_tokens(4_500_000), "4.5M");
+        let active = |ms| UsageTotals {
+            active_ms: ms,
+            ..Default::default()
+        };
+        assert_eq!(active(45_000).cells()[6], "45s");
+        assert_eq!(active(720_000).cells()[6], "12m00s");
+        assert_eq!(active(11_100_000).cells()[6], "3h05m");
+    }
+
+    #[test]
+    fn test_stats_group_cycle() {
+        assert_eq!(StatsGroup::Project.next(), StatsGroup::Day);
+        assert_eq!(StatsGroup::Model.next(), StatsGroup::Project);
+        assert_eq!(StatsGroup::from_name("day"), Some(StatsGroup::Day));
+        assert_eq!(StatsGroup::from_name("week"), None);
+    }
+}
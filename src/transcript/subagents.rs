@@ -0,0 +1,376 @@
+//! Subagent tree reconstruction.
+//!
+//! A `Task` (`Agent` in newer versions) tool call starts a subagent. Its work
+//! is recorded as `agent_progress` entries pointing back at the call through
+//! `parentToolUseID` (or, in older versions, as inline `isSidechain` entries),
+//! and it ends with the `tool_result` of the call. `SubagentTracker` follows
+//! this one line at a time, like `TurnBuilder`.
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::time::Duration;
+
+use super::parser::{is_subagent_tool, remove_internal_tags};
+
+/// Progress of a subagent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubagentState {
+    Running,
+    Completed,
+    /// Ended with an error tool_result (including interruptions)
+    Failed,
+}
+
+/// A subagent started by a `Task` tool call of the main conversation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Subagent {
+    /// ID of the tool_use that started it
+    pub id: String,
+    pub description: String,
+    /// `subagent_type` of the call (e.g., "Explore")
+    pub agent_type: Option<String>,
+    pub state: SubagentState,
+    /// Tools the subagent has called so far
+    pub tool_count: usize,
+    pub started_at: Option<DateTime<Utc>>,
+    /// Total running time, once finished
+    pub duration: Option<Duration>,
+}
+
+impl Subagent {
+    /// Running time: the total once finished, otherwise the time since it
+    /// started.
+    pub fn elapsed(&self, now: DateTime<Utc>) -> Option<Duration> {
+        self.duration
+            .or_else(|| (now - self.started_at?).to_std().ok())
+    }
+}
+
+/// The parts of a transcript line subagent tracking needs. Content blocks
+/// are parsed leniently: a Task result's `content` is an array, which
+/// `ContentBlock` can't hold.
+#[derive(Debug, Deserialize)]
+struct SubagentLine {
+    #[serde(rename = "type")]
+    type_: String,
+    timestamp: Option<String>,
+    #[serde(rename = "isSidechain", default)]
+    is_sidechain: bool,
+    #[serde(rename = "isMeta", default)]
+    is_meta: bool,
+    #[serde(rename = "parentToolUseID")]
+    parent_tool_use_id: Option<String>,
+    message: Option<LineMessage>,
+    data: Option<LineProgress>,
+    /// Summary of a finished tool (for Task: `totalDurationMs`,
+    /// `totalToolUseCount`, ...)
+    #[serde(rename = "toolUseResult")]
+    tool_use_result: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LineMessage {
+    #[serde(default)]
+    content: LineContent,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(untagged)]
+enum LineContent {
+    #[default]
+    Empty,
+    Text(String),
+    Blocks(Vec<LineBlock>),
+}
+
+#[derive(Debug, Deserialize)]
+struct LineBlock {
+    #[serde(rename = "type")]
+    type_: String,
+    id: Option<String>,
+    name: Option<String>,
+    input: Option<serde_json::Value>,
+    text: Option<String>,
+    tool_use_id: Option<String>,
+    is_error: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LineProgress {
+    #[serde(rename = "type")]
+    type_: Option<String>,
+    /// The subagent's own transcript entry
+    message: Option<Box<SubagentLine>>,
+}
+
+impl SubagentLine {
+    fn timestamp(&self) -> Option<DateTime<Utc>> {
+        let ts = self.timestamp.as_deref()?;
+        DateTime::parse_from_rfc3339(ts)
+            .ok()
+            .map(|t| t.with_timezone(&Utc))
+    }
+
+    fn blocks(&self) -> &[LineBlock] {
+        match self.message.as_ref().map(|m| &m.content) {
+            Some(LineContent::Blocks(blocks)) => blocks,
+            _ => &[],
+        }
+    }
+
+    /// Number of tool_use blocks of an assistant entry.
+    fn tool_use_count(&self) -> usize {
+        if self.type_ != "assistant" {
+            return 0;
+        }
+        self.blocks()
+            .iter()
+            .filter(|b| b.type_ == "tool_use")
+            .count()
+    }
+
+    /// Whether this is a prompt typed by the user (not a tool_result, meta
+    /// entry or command output).
+    fn is_prompt(&self) -> bool {
+        if self.type_ != "user" || self.is_meta || self.is_sidechain {
+            return false;
+        }
+        let text = match self.message.as_ref().map(|m| &m.content) {
+            Some(LineContent::Text(s)) => s.clone(),
+            Some(LineContent::Blocks(blocks)) => {
+                if blocks.iter().any(|b| b.type_ == "tool_result") {
+                    return false;
+                }
+                blocks
+                    .iter()
+                    .filter_map(|b| b.text.as_deref())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+            _ => return false,
+        };
+        let text = remove_internal_tags(&text);
+        !text.is_empty() && !text.starts_with("[Request interrupted")
+    }
+}
+
+/// Incrementally reconstructs the subagents of the current prompt.
+#[derive(Debug, Default)]
+pub(super) struct SubagentTracker {
+    /// Subagents in the order they were started
+    subagents: Vec<Subagent>,
+}
+
+impl SubagentTracker {
+    /// Feed one raw JSONL line.
+    pub(super) fn push_line(&mut self, line: &str) {
+        let Ok(entry) = serde_json::from_str::<SubagentLine>(line) else {
+            return;
+        };
+
+        if entry.type_ == "progress" {
+            let Some(data) = &entry.data else {
+                return;
+            };
+            if data.type_.as_deref() != Some("agent_progress") {
+                return;
+            }
+            let count = data.message.as_ref().map_or(0, |m| m.tool_use_count());
+            if let Some(subagent) = entry
+                .parent_tool_use_id
+                .as_deref()
+                .and_then(|id| self.find(id))
+            {
+                subagent.tool_count += count;
+            }
+            return;
+        }
+
+        if entry.is_sidechain {
+            // Inline sidechain: attribute to the latest running subagent
+            let count = entry.tool_use_count();
+            if let Some(subagent) = self
+                .subagents
+                .iter_mut()
+                .rev()
+                .find(|s| s.state == SubagentState::Running)
+            {
+                subagent.tool_count += count;
+            }
+            return;
+        }
+
+        match entry.type_.as_str() {
+            "assistant" => {
+                for block in entry.blocks() {
+                    if block.type_ != "tool_use"
+                        || !block.name.as_deref().is_some_and(is_subagent_tool)
+                    {
+                        continue;
+                    }
+                    let Some(id) = &block.id else {
+                        continue;
+                    };
+                    let input = |key: &str| {
+                        block
+                            .input
+                            .as_ref()
+                            .and_then(|i| i.get(key))
+                            .and_then(|v| v.as_str())
+                            .map(str::to_string)
+                    };
+                    let description = input("description");
+                    self.subagents.push(Subagent {
+                        id: id.clone(),
+                        description: description.unwrap_or_else(|| "subagent".to_string()),
+                        agent_type: input("subagent_type"),
+                        state: SubagentState::Running,
+                        tool_count: 0,
+                        started_at: entry.timestamp(),
+                        duration: None,
+                    });
+                }
+            }
+            "user" => {
+                if entry.is_prompt() {
+                    // A new prompt: finished subagents belong to the last one
+                    self.subagents.retain(|s| s.state == SubagentState::Running);
+                    return;
+                }
+                let finished_at = entry.timestamp();
+                for block in entry.blocks() {
+                    if block.type_ != "tool_result" {
+                        continue;
+                    }
+                    let Some(subagent) = block.tool_use_id.as_deref().and_then(|id| self.find(id))
+                    else {
+                        continue;
+                    };
+                    subagent.state = if block.is_error == Some(true) {
+                        SubagentState::Failed
+                    } else {
+                        SubagentState::Completed
+                    };
+                    let summary = entry.tool_use_result.as_ref();
+                    let total =
+                        |key: &str| summary.and_then(|r| r.get(key)).and_then(|v| v.as_u64());
+                    if let Some(count) = total("totalToolUseCount") {
+                        subagent.tool_count = subagent.tool_count.max(count as usize);
+                    }
+                    subagent.duration = total("totalDurationMs")
+                        .map(Duration::from_millis)
+                        .or_else(|| (finished_at? - subagent.started_at?).to_std().ok());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Subagents of the current prompt (plus any still running from an
+    /// earlier one), in the order they were started.
+    pub(super) fn subagents(&self) -> &[Subagent] {
+        &self.subagents
+    }
+
+    fn find(&mut self, id: &str) -> Option<&mut Subagent> {
+        self.subagents.iter_mut().find(|s| s.id == id)
+    }
+}
+
+/// Reconstruct the subagents of the current prompt from raw lines.
+pub(super) fn subagents_from_lines<S: AsRef<str>>(lines: &[S]) -> Vec<Subagent> {
+    let mut tracker = SubagentTracker::default();
+    for line in lines {
+        tracker.push_line(line.as_ref());
+    }
+    tracker.subagents
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task_call(id: &str, description: &str, ts: &str) -> String {
+        format!(
+            r#"{{"type":"assistant","timestamp":"{ts}","message":{{"stop_reason":"tool_use","content":[{{"type":"tool_use","id":"{id}","name":"Task","input":{{"description":"{description}","subagent_type":"Explore","prompt":"..."}}}}]}}}}"#
+        )
+    }
+
+    fn progress(parent: &str, tools: usize) -> String {
+        let blocks = vec![r#"{"type":"tool_use","name":"Grep","input":{}}"#; tools].join(",");
+        format!(
+            r#"{{"type":"progress","parentToolUseID":"{parent}","data":{{"type":"agent_progress","message":{{"type":"assistant","message":{{"content":[{blocks}]}}}}}}}}"#
+        )
+    }
+
+    fn task_result(id: &str, is_error: bool, ts: &str, summary: &str) -> String {
+        format!(
+            r#"{{"type":"user","timestamp":"{ts}","message":{{"role":"user","content":[{{"type":"tool_result","tool_use_id":"{id}","is_error":{is_error},"content":[{{"type":"text","text":"done"}}]}}]}},"toolUseResult":{summary}}}"#
+        )
+    }
+
+    #[test]
+    fn test_parallel_subagents() {
+        let lines = vec![
+            r#"{"type":"user","message":{"role":"user","content":"explore the repo"}}"#.to_string(),
+            task_call("t1", "Find parsers", "2026-01-01T00:00:00Z"),
+            task_call("t2", "Find tests", "2026-01-01T00:00:01Z"),
+            task_call("t3", "Find docs", "2026-01-01T00:00:02Z"),
+            progress("t1", 2),
+            progress("t2", 1),
+            progress("t1", 1),
+            task_result(
+                "t1",
+                false,
+                "2026-01-01T00:01:00Z",
+                r#"{"status":"completed","totalDurationMs":59000,"totalToolUseCount":4}"#,
+            ),
+            task_result("t3", true, "2026-01-01T00:00:32Z", r#""Error: failed""#),
+        ];
+        let subagents = subagents_from_lines(&lines);
+        assert_eq!(subagents.len(), 3);
+
+        assert_eq!(subagents[0].description, "Find parsers");
+        assert_eq!(subagents[0].agent_type.as_deref(), Some("Explore"));
+        assert_eq!(subagents[0].state, SubagentState::Completed);
+        assert_eq!(subagents[0].tool_count, 4);
+        assert_eq!(subagents[0].duration, Some(Duration::from_secs(59)));
+
+        assert_eq!(subagents[1].state, SubagentState::Running);
+        assert_eq!(subagents[1].tool_count, 1);
+        assert_eq!(subagents[1].duration, None);
+
+        // Without a summary the duration comes from the timestamps
+        assert_eq!(subagents[2].state, SubagentState::Failed);
+        assert_eq!(subagents[2].duration, Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn test_new_prompt_drops_finished_subagents() {
+        let mut tracker = SubagentTracker::default();
+        tracker.push_line(&task_call("t1", "Old", "2026-01-01T00:00:00Z"));
+        tracker.push_line(&task_call("t2", "Still running", "2026-01-01T00:00:00Z"));
+        tracker.push_line(&task_result("t1", false, "2026-01-01T00:00:10Z", "{}"));
+
+        // Command output and interruptions aren't prompts
+        tracker.push_line(r#"{"type":"user","message":{"content":"<local-command-stdout>ok</local-command-stdout>"}}"#);
+        assert_eq!(tracker.subagents().len(), 2);
+
+        tracker.push_line(r#"{"type":"user","message":{"content":"next task"}}"#);
+        let remaining: Vec<&str> = tracker.subagents().iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(remaining, vec!["t2"]);
+    }
+
+    #[test]
+    fn test_inline_sidechain_counts_tools() {
+        let mut tracker = SubagentTracker::default();
+        tracker.push_line(&task_call("t1", "Search", "2026-01-01T00:00:00Z"));
+        // Sidechain prompt isn't a new prompt of the main conversation
+        tracker.push_line(
+            r#"{"type":"user","isSidechain":true,"message":{"content":"search for x"}}"#,
+        );
+        tracker.push_line(r#"{"type":"assistant","isSidechain":true,"message":{"content":[{"type":"tool_use","name":"Grep"},{"type":"tool_use","name":"Read"}]}}"#);
+        assert_eq!(tracker.subagents().len(), 1);
+        assert_eq!(tracker.subagents()[0].tool_count, 2);
+    }
+}
@@ -0,0 +1,341 @@
+//! Incremental transcript reading.
+//!
+//! A `TranscriptTail` remembers how far into a transcript it has read and
+//! only parses lines appended since the last update, so a session that is
+//! streaming into a huge transcript doesn't cost a reread on every change.
+//! Truncated or replaced files are detected and read again from the end.
+
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+use std::fs::{File, Metadata};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use super::info::{transcript_info, TranscriptInfo};
+use super::parser::{
+    extract_last_assistant_text, extract_last_user_prompt, ConversationTurn, TranscriptSnapshot,
+    TurnBuilder,
+};
+use super::state::DetectionConfig;
+use super::subagents::SubagentTracker;
+use super::todos::{todos_from_line, Todo};
+
+/// Bytes read from the end of a transcript when it is first opened.
+const INITIAL_READ_BYTES: u64 = 10 * 1024 * 1024;
+/// Raw lines kept for status and prompt/output extraction
+/// (the last user prompt is searched for within this many lines).
+const TAIL_LINES: usize = 200;
+/// Completed conversation turns kept for the history view.
+const MAX_TURNS: usize = 200;
+
+/// Read position and parsed state of one transcript file.
+#[derive(Debug)]
+pub struct TranscriptTail {
+    path: PathBuf,
+    /// Byte offset just past the last line parsed
+    offset: u64,
+    /// Identity of the file read so far, to detect rotation
+    file_id: Option<u64>,
+    /// Most recent raw lines
+    snapshot: TranscriptSnapshot,
+    /// Conversation turns parsed so far
+    turns: TurnBuilder,
+    /// Subagents of the current prompt
+    subagents: SubagentTracker,
+    /// Latest TodoWrite list
+    todos: Vec<Todo>,
+    last_prompt: Option<String>,
+    last_output: Option<String>,
+}
+
+impl TranscriptTail {
+    /// Create a tail for `path`. Nothing is read until `update`.
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            offset: 0,
+            file_id: None,
+            snapshot: TranscriptSnapshot::default(),
+            turns: TurnBuilder::default(),
+            subagents: SubagentTracker::default(),
+            todos: Vec::new(),
+            last_prompt: None,
+            last_output: None,
+        }
+    }
+
+    /// Parse lines appended since the last update.
+    /// Returns true if any new lines were read.
+    pub fn update(&mut self) -> Result<bool> {
+        let file = File::open(&self.path)?;
+        let metadata = file.metadata()?;
+        let len = metadata.len();
+        let id = file_id(&metadata);
+
+        if len < self.offset || (self.offset > 0 && id != self.file_id) {
+            // Truncated or replaced: start over from the end
+            *self = Self::new(&self.path);
+        }
+        self.file_id = id;
+        if len == self.offset {
+            return Ok(false);
+        }
+
+        let mut reader = BufReader::new(file);
+        let mut start = self.offset;
+        if start == 0 && len > INITIAL_READ_BYTES {
+            start = len - INITIAL_READ_BYTES;
+            reader.seek(SeekFrom::Start(start))?;
+            // Skip the partial line we seeked into
+            let mut skip = Vec::new();
+            start += reader.read_until(b'\n', &mut skip)? as u64;
+        } else {
+            reader.seek(SeekFrom::Start(start))?;
+        }
+
+        let mut buf = Vec::new();
+        reader.take(len - start).read_to_end(&mut buf)?;
+        let consumed = complete_len(&buf);
+        self.offset = start + consumed as u64;
+
+        let lines: Vec<String> = String::from_utf8_lossy(&buf[..consumed])
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(str::to_string)
+            .collect();
+        if lines.is_empty() {
+            return Ok(false);
+        }
+
+        for line in &lines {
+            self.turns.push_line(line);
+            self.subagents.push_line(line);
+            if let Some(todos) = todos_from_line(line) {
+                self.todos = todos;
+            }
+        }
+        self.turns.truncate_front(MAX_TURNS);
+        self.snapshot.push_lines(lines, TAIL_LINES);
+        self.last_prompt = extract_last_user_prompt(&self.snapshot, usize::MAX);
+        self.last_output = extract_last_assistant_text(&self.snapshot, usize::MAX);
+        Ok(true)
+    }
+
+    /// Status, last prompt and last output as of the last update.
+    pub fn info(&self, config: &DetectionConfig) -> TranscriptInfo {
+        transcript_info(
+            &self.snapshot,
+            self.last_prompt.clone(),
+            self.last_output.clone(),
+            self.subagents.subagents().to_vec(),
+            self.todos.clone(),
+            config,
+        )
+    }
+
+    /// Up to `max_turns` most recent conversation turns, newest first.
+    pub fn recent_turns(&self, max_turns: usize) -> Vec<ConversationTurn> {
+        self.turns.recent_turns(max_turns)
+    }
+}
+
+/// Length of `buf` up to and including its last complete line. A trailing
+/// line without a newline counts only if it is already valid JSON;
+/// otherwise it is still being written and is read again next time.
+fn complete_len(buf: &[u8]) -> usize {
+    let last_newline = buf.iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1);
+    let rest = &buf[last_newline..];
+    if !rest.is_empty() && serde_json::from_slice::<serde::de::IgnoredAny>(rest).is_ok() {
+        buf.len()
+    } else {
+        last_newline
+    }
+}
+
+#[cfg(unix)]
+fn file_id(metadata: &Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.ino())
+}
+
+#[cfg(not(unix))]
+fn file_id(_metadata: &Metadata) -> Option<u64> {
+    None
+}
+
+/// Tails of every transcript the TUI is showing, keyed by path.
+#[derive(Debug, Default)]
+pub struct TranscriptTails {
+    tails: HashMap<PathBuf, TranscriptTail>,
+    /// Paths read since the last `retain_used`
+    used: HashSet<PathBuf>,
+}
+
+impl TranscriptTails {
+    /// The tail for `path`, brought up to date. Tails of files that can no
+    /// longer be read are dropped.
+    fn updated(&mut self, path: &Path) -> Result<&TranscriptTail> {
+        self.used.insert(path.to_path_buf());
+        let tail = self
+            .tails
+            .entry(path.to_path_buf())
+            .or_insert_with(|| TranscriptTail::new(path));
+        if let Err(e) = tail.update() {
+            self.tails.remove(path);
+            return Err(e);
+        }
+        Ok(&self.tails[path])
+    }
+
+    /// Status, last prompt and last output of the transcript at `path`.
+    pub fn read_info(&mut self, path: &Path, config: &DetectionConfig) -> Result<TranscriptInfo> {
+        Ok(self.updated(path)?.info(config))
+    }
+
+    /// Up to `max_turns` most recent turns of the transcript at `path`,
+    /// newest first.
+    pub fn read_turns(&mut self, path: &Path, max_turns: usize) -> Result<Vec<ConversationTurn>> {
+        Ok(self.updated(path)?.recent_turns(max_turns))
+    }
+
+    /// Forget tails that weren't read since the last call (their sessions
+    /// went away or moved to another transcript).
+    pub fn retain_used(&mut self) {
+        let used = std::mem::take(&mut self.used);
+        self.tails.retain(|path, _| used.contains(path));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcript::SessionStatus;
+    use std::io::Write;
+
+    fn user(text: &str) -> String {
+        format!(
+            r#"{{"type":"user","message":{{"role":"user","content":"{}"}}}}"#,
+            text
+        )
+    }
+
+    fn assistant(text: &str) -> String {
+        format!(
+            r#"{{"type":"assistant","message":{{"stop_reason":"end_turn","content":[{{"type":"text","text":"{}"}}]}}}}"#,
+            text
+        )
+    }
+
+    fn append(path: &Path, text: &str) {
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(path)
+            .unwrap();
+        file.write_all(text.as_bytes()).unwrap();
+    }
+
+    #[test]
+    fn test_update_reads_only_appended_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s.jsonl");
+        append(&path, &format!("{}\n{}\n", user("first"), assistant("one")));
+
+        let mut tail = TranscriptTail::new(&path);
+        assert!(tail.update().unwrap());
+        assert!(!tail.update().unwrap());
+        assert_eq!(
+            tail.info(&DetectionConfig::default())
+                .last_prompt
+                .as_deref(),
+            Some("first")
+        );
+
+        append(&path, &format!("{}\n", user("second")));
+        assert!(tail.update().unwrap());
+        let info = tail.info(&DetectionConfig::default());
+        assert_eq!(info.last_prompt.as_deref(), Some("second"));
+        assert_eq!(info.last_output.as_deref(), Some("one"));
+
+        let turns = tail.recent_turns(10);
+        assert_eq!(turns.len(), 2);
+        assert_eq!(turns[0].user_prompt, "second");
+        assert_eq!(turns[1].assistant_response, "one");
+    }
+
+    #[test]
+    fn test_update_waits_for_partial_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s.jsonl");
+        let line = assistant("done");
+        let (head, rest) = line.split_at(20);
+        append(&path, &format!("{}\n{}", user("hi"), head));
+
+        let mut tail = TranscriptTail::new(&path);
+        assert!(tail.update().unwrap());
+        assert_eq!(tail.info(&DetectionConfig::default()).last_output, None);
+
+        append(&path, &format!("{}\n", rest));
+        assert!(tail.update().unwrap());
+        let info = tail.info(&DetectionConfig::default());
+        assert_eq!(info.last_output.as_deref(), Some("done"));
+        assert_eq!(info.status, SessionStatus::Idle);
+    }
+
+    #[test]
+    fn test_update_rereads_truncated_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s.jsonl");
+        append(
+            &path,
+            &format!("{}\n{}\n", user("old prompt"), assistant("old")),
+        );
+
+        let mut tail = TranscriptTail::new(&path);
+        tail.update().unwrap();
+
+        std::fs::write(&path, format!("{}\n", user("new"))).unwrap();
+        assert!(tail.update().unwrap());
+        assert_eq!(
+            tail.info(&DetectionConfig::default())
+                .last_prompt
+                .as_deref(),
+            Some("new")
+        );
+        assert_eq!(tail.info(&DetectionConfig::default()).last_output, None);
+        assert_eq!(tail.recent_turns(10).len(), 1);
+    }
+
+    #[test]
+    fn test_tails_drop_unreadable_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s.jsonl");
+        append(&path, &format!("{}\n", user("hi")));
+
+        let mut tails = TranscriptTails::default();
+        assert_eq!(tails.read_turns(&path, 10).unwrap().len(), 1);
+        std::fs::remove_file(&path).unwrap();
+        assert!(tails.read_info(&path, &DetectionConfig::default()).is_err());
+        assert!(tails.tails.is_empty());
+    }
+
+    #[test]
+    fn test_tails_retain_used() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.jsonl");
+        let b = dir.path().join("b.jsonl");
+        append(&a, &format!("{}\n", user("a")));
+        append(&b, &format!("{}\n", user("b")));
+
+        let mut tails = TranscriptTails::default();
+        tails.read_info(&a, &DetectionConfig::default()).unwrap();
+        tails.read_info(&b, &DetectionConfig::default()).unwrap();
+        tails.retain_used();
+        assert_eq!(tails.tails.len(), 2);
+
+        tails.read_info(&a, &DetectionConfig::default()).unwrap();
+        tails.retain_used();
+        assert!(tails.tails.contains_key(&a));
+        assert!(!tails.tails.contains_key(&b));
+    }
+}
@@ -0,0 +1,101 @@
+//! TodoWrite task lists.
+//!
+//! Each `TodoWrite` tool call replaces the whole list, so the latest call of
+//! the main conversation is the session's current list.
+
+use serde::{Deserialize, Serialize};
+
+use super::parser::TranscriptEntry;
+
+/// Progress of a todo item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoStatus {
+    Pending,
+    InProgress,
+    Completed,
+}
+
+/// An item of a TodoWrite list.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Todo {
+    pub content: String,
+    pub status: TodoStatus,
+    /// Present-tense form shown while the item is in progress
+    #[serde(
+        rename = "activeForm",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub active_form: Option<String>,
+}
+
+/// Completed and total item counts.
+pub fn todo_progress(todos: &[Todo]) -> (usize, usize) {
+    let done = todos
+        .iter()
+        .filter(|t| t.status == TodoStatus::Completed)
+        .count();
+    (done, todos.len())
+}
+
+/// The list written by a TodoWrite call on this line, if any.
+pub(super) fn todos_from_line(line: &str) -> Option<Vec<Todo>> {
+    // Skip the full parse for the vast majority of lines
+    if !line.contains("\"TodoWrite\"") {
+        return None;
+    }
+    let entry: TranscriptEntry = serde_json::from_str(line).ok()?;
+    if entry.type_ != "assistant" || entry.is_sidechain {
+        return None;
+    }
+    entry
+        .message?
+        .content
+        .into_iter()
+        .rev()
+        .filter(|c| c.type_ == "tool_use" && c.name.as_deref() == Some("TodoWrite"))
+        .find_map(|c| serde_json::from_value(c.input?.get("todos")?.clone()).ok())
+}
+
+/// The latest list written in `lines` (empty if none).
+pub(super) fn latest_todos<S: AsRef<str>>(lines: &[S]) -> Vec<Todo> {
+    lines
+        .iter()
+        .rev()
+        .find_map(|line| todos_from_line(line.as_ref()))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TODO_WRITE: &str = r#"{"type":"assistant","message":{"stop_reason":"tool_use","content":[{"type":"tool_use","id":"t1","name":"TodoWrite","input":{"todos":[{"content":"Write parser","status":"completed","activeForm":"Writing parser"},{"content":"Add tests","status":"in_progress","activeForm":"Adding tests"},{"content":"Update README","status":"pending","activeForm":"Updating README"}]}}]}}"#;
+
+    #[test]
+    fn test_todos_from_line() {
+        let todos = todos_from_line(TODO_WRITE).unwrap();
+        assert_eq!(todos.len(), 3);
+        assert_eq!(todos[1].status, TodoStatus::InProgress);
+        assert_eq!(todos[1].active_form.as_deref(), Some("Adding tests"));
+        assert_eq!(todo_progress(&todos), (1, 3));
+
+        assert!(todos_from_line(r#"{"type":"assistant","message":{"content":[]}}"#).is_none());
+        // A subagent's own list isn't the session's
+        let sidechain = TODO_WRITE.replace(
+            r#""type":"assistant","#,
+            r#""type":"assistant","isSidechain":true,"#,
+        );
+        assert!(todos_from_line(&sidechain).is_none());
+    }
+
+    #[test]
+    fn test_latest_todos() {
+        let cleared = r#"{"type":"assistant","message":{"content":[{"type":"tool_use","name":"TodoWrite","input":{"todos":[]}}]}}"#;
+        let reply = r#"{"type":"assistant","message":{"content":[{"type":"text","text":"Done"}]}}"#;
+        assert_eq!(latest_todos(&[TODO_WRITE, reply]).len(), 3);
+        assert!(latest_todos(&[TODO_WRITE, cleared, reply]).is_empty());
+        assert!(latest_todos(&[reply]).is_empty());
+    }
+}
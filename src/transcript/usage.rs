@@ -0,0 +1,79 @@
+//! Token usage of API responses and its estimated cost.
+
+use serde::Deserialize;
+
+/// Token usage of one API response (`message.usage` in a transcript).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct TokenUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_creation_input_tokens: u64,
+    pub cache_read_input_tokens: u64,
+}
+
+impl TokenUsage {
+    /// Estimated cost in USD at `model`'s list price, or None for models
+    /// without a known price.
+    pub fn cost_usd(&self, model: &str) -> Option<f64> {
+        let (input, output) = model_price(model)?;
+        Some(
+            (self.input_tokens as f64 * input
+                + self.cache_creation_input_tokens as f64 * input * 1.25
+                + self.cache_read_input_tokens as f64 * input * 0.1
+                + self.output_tokens as f64 * output)
+                / 1_000_000.0,
+        )
+    }
+}
+
+/// (input, output) list price in USD per million tokens.
+fn model_price(model: &str) -> Option<(f64, f64)> {
+    if model.contains("opus") {
+        // Opus 4.5 and later are priced lower than earlier Opus models
+        let legacy = model.contains("3-opus")
+            || model.contains("opus-4-1")
+            || model.starts_with("claude-opus-4-2025");
+        Some(if legacy { (15.0, 75.0) } else { (5.0, 25.0) })
+    } else if model.contains("sonnet") {
+        Some((3.0, 15.0))
+    } else if model.contains("haiku") {
+        Some(if model.contains("3-haiku") {
+            (0.25, 1.25)
+        } else if model.contains("3-5-haiku") {
+            (0.8, 4.0)
+        } else {
+            (1.0, 5.0)
+        })
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_model_price() {
+        assert_eq!(model_price("claude-opus-4-6"), Some((5.0, 25.0)));
+        assert_eq!(model_price("claude-opus-4-1-20250805"), Some((15.0, 75.0)));
+        assert_eq!(model_price("claude-opus-4-20250514"), Some((15.0, 75.0)));
+        assert_eq!(model_price("claude-haiku-4-5-20251001"), Some((1.0, 5.0)));
+        assert_eq!(model_price("<synthetic>"), None);
+    }
+
+    #[test]
+    fn test_cost_usd() {
+        let usage = TokenUsage {
+            input_tokens: 1_000_000,
+            output_tokens: 100_000,
+            cache_creation_input_tokens: 1_000_000,
+            cache_read_input_tokens: 1_000_000,
+        };
+        // 3 + 1.5 + 3.75 + 0.3
+        let cost = usage.cost_usd("claude-sonnet-4-5").unwrap();
+        assert!((cost - 8.55).abs() < 1e-9, "cost = {}", cost);
+        assert_eq!(usage.cost_usd("unknown"), None);
+    }
+}
@@ -1,18 +1,22 @@
 use crate::cli::{switch_workspace, WeztermCli};
-use crate::config::{Config, SpawnCommand};
-use crate::datasource::git::{GitBranchCache, GitWorktreeCache};
-use crate::datasource::{
-    PaneDataSource, ProcessDataSource, SystemProcessDataSource, WeztermDataSource,
+use crate::config::{
+    Config, GroupBy, ListConfig, SortOrder, SpawnCommand, ThemeConfig, WorktreeConfig,
 };
-use crate::detector::ClaudeCodeDetector;
+use crate::datasource::git::Worktree;
+use crate::datasource::{PaneDataSource, WeztermDataSource};
 use crate::session_mapping::SessionMapping;
-use crate::transcript::{ConversationTurn, TranscriptWatcher};
+use crate::session_meta::{parse_tags, SessionMeta, SessionMetaStore};
+use crate::status_timeline::{StatusTimelineStore, PANE_KEY_PREFIX};
+use crate::transcript::{
+    ArchivedSession, ConversationTurn, DetectionConfig, SearchHit, SessionInfo, SessionStatus,
+    StatsGroup, TranscriptTails, TranscriptWatcher, UsageStats,
+};
 use crate::ui::terminal_session::TerminalSession;
 use anyhow::Result;
 use crossterm::{
     event::{
         DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
-        KeyCode, KeyModifiers, MouseButton, MouseEventKind,
+        KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEventKind,
     },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
@@ -23,29 +27,60 @@ use ratatui::{
     widgets::ListState,
     Terminal,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::io;
 use std::process::Command;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant, SystemTime};
 
-use super::event::{
-    is_down_key, is_enter_key, is_quit_key, is_refresh_key, is_up_key, Event, EventHandler,
-};
+use super::event::{Event, EventHandler};
+use super::fuzzy::matches_all_terms;
+use super::history_search::HistorySearch;
 use super::input_buffer::InputBuffer;
+use super::keymap::{Action, KeyBinding, KeyLookup, KeyMode, Keymap};
+use super::refresh_worker::{RefreshRequest, RefreshUpdate, RefreshWorker};
 use super::render::{
-    render_answer_select, render_command_select, render_details, render_footer, render_list,
-    render_slash_complete, DetailMode, DetailsRenderCtx, LivePaneLinesCache,
+    render_answer_select, render_command_select, render_details, render_footer, render_help,
+    render_list, render_palette, render_plan, render_recent_sessions, render_slash_complete,
+    render_stats, render_transcript_search, render_worktrees, DetailMode, DetailsRenderCtx,
+    ListFilterCtx, ListRenderCtx, LivePaneLinesCache,
 };
-use super::session::ClaudeSession;
+use super::scan_worker::{ScanKind, ScanRequest, ScanUpdate, ScanWorker};
+use super::session::{first_new_header_level, status_display, ClaudeSession};
 use super::slash_commands::SlashCommand;
+use super::theme::Theme;
 use super::toast::Toast;
 
 #[path = "app/actions.rs"]
 mod actions;
+#[path = "app/appearance.rs"]
+mod appearance;
+#[path = "app/filter.rs"]
+mod filter;
+#[path = "app/meta.rs"]
+mod meta;
 #[path = "app/navigation.rs"]
 mod navigation;
+#[path = "app/ordering.rs"]
+mod ordering;
+#[path = "app/palette.rs"]
+mod palette;
+#[path = "app/plan.rs"]
+mod plan;
+#[path = "app/recent.rs"]
+mod recent;
 #[path = "app/runtime.rs"]
 mod runtime;
+#[path = "app/search.rs"]
+mod search;
+#[path = "app/stats.rs"]
+mod stats;
+#[path = "app/timeline.rs"]
+mod timeline;
+#[path = "app/transcript_search.rs"]
+mod transcript_search;
+#[path = "app/worktrees.rs"]
+mod worktrees;
 
 /// Debounce interval (ms) for transcript file refreshes.
 /// 200ms keeps the status responsive while coalescing burst writes during streaming.
@@ -76,6 +111,39 @@ pub(super) enum SplitDirection {
     Tab,
 }
 
+/// Which session metadata field is being edited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum MetaField {
+    Label,
+    Tags,
+}
+
+/// State for the session label/tags prompt.
+pub(super) struct MetaEditState {
+    pub session_id: String,
+    pub pane_id: u32,
+    pub field: MetaField,
+    pub buffer: String,
+}
+
+/// Where a new pane is being added, pending direction selection.
+pub(super) struct AddPaneTarget {
+    pub pane_id: u32,
+    pub cwd: String,
+    pub window_id: u32,
+    /// Branch of the worktree just created at `cwd`, if any
+    pub worktree_branch: Option<String>,
+}
+
+/// State for the worktree branch prompt (`w` in the add-pane prompt).
+pub(super) struct WorktreePromptState {
+    pub pane_id: u32,
+    /// Working directory of the selected session (any directory of the repo)
+    pub cwd: String,
+    pub window_id: u32,
+    pub buffer: String,
+}
+
 /// Context saved after direction selection, pending command choice.
 pub(super) struct AddPaneContext {
     pub pane_id: u32,
@@ -86,22 +154,36 @@ pub(super) struct AddPaneContext {
 
 /// TUI application
 pub struct App {
-    /// Claude Code session list
+    /// Claude Code session list (visible view after applying the list filter)
     sessions: Vec<ClaudeSession>,
+    /// All detected sessions, before applying the list filter
+    all_sessions: Vec<ClaudeSession>,
     /// List selection state
     list_state: ListState,
-    /// Data sources
+    /// Pane data source (startup mapping cleanup; discovery runs on the refresh worker)
     pane_ds: WeztermDataSource,
-    process_ds: SystemProcessDataSource,
-    detector: ClaudeCodeDetector,
+    /// Background session discovery and transcript parsing (started by `run`)
+    refresh_worker: Option<RefreshWorker>,
+    /// Background transcript history scans (started on first use)
+    scan_worker: Option<ScanWorker>,
     /// Dirty flag (needs redraw)
     dirty: bool,
     /// Refreshing flag
     refreshing: bool,
     /// Needs full redraw (to prevent artifacts on selection change)
     needs_full_redraw: bool,
-    /// 'g' key pressed state (for gg sequence)
-    pending_g: bool,
+    /// First key of a pending two-key sequence (e.g. `g` of `gg`)
+    pending_key: Option<KeyBinding>,
+    /// Key bindings (defaults plus `[keys]` overrides from config.toml)
+    keymap: Keymap,
+    /// Color theme used by all rendering
+    theme: Theme,
+    /// `[theme]` config the theme was built from (reused when cycling)
+    theme_config: ThemeConfig,
+    /// Key binding help overlay (`?`), if open
+    help: Option<HelpState>,
+    /// Command palette (Ctrl-P), if open
+    palette: Option<PaletteState>,
     /// Previous last_output snapshot (for change detection)
     prev_last_outputs: Vec<Option<String>>,
     /// Last click time and index (for double click detection)
@@ -110,12 +192,28 @@ pub struct App {
     list_area: Option<Rect>,
     /// File watcher for transcript changes
     transcript_watcher: Option<TranscriptWatcher>,
+    /// Incremental readers of the sessions' transcripts (shared with the refresh worker)
+    transcript_tails: Arc<Mutex<TranscriptTails>>,
     /// Animation frame counter for Processing status indicator (0-3)
     animation_frame: u8,
     /// Current workspace name (for detecting cross-workspace jumps)
     current_workspace: String,
     /// Details panel width percentage (default: 65, range: 20-80)
     details_width_percent: u16,
+    /// Session list filter query (fuzzy, whitespace-separated terms)
+    filter_query: String,
+    /// Whether the session list filter query is being edited (`/`)
+    filter_editing: bool,
+    /// Session list sort order and grouping (persisted in config.toml)
+    list_config: ListConfig,
+    /// User-assigned session labels/colors/tags/pins (persisted state file)
+    session_meta: SessionMetaStore,
+    /// Status changes of every session (persisted state file)
+    status_timelines: StatusTimelineStore,
+    /// Session label/tags prompt (`n` / `#`)
+    meta_edit: Option<MetaEditState>,
+    /// Push session labels to the WezTerm tab title
+    sync_tab_title: bool,
     /// Input mode (for sending prompts to sessions)
     input_mode: bool,
     /// Input buffer with cursor management
@@ -132,8 +230,14 @@ pub struct App {
     toast: Option<Toast>,
     /// Kill confirmation mode (stores pane_id and display label)
     kill_confirm: Option<(u32, String)>,
-    /// Add pane mode: stores (pane_id, cwd, window_id) for split direction selection
-    add_pane_pending: Option<(u32, String, u32)>,
+    /// Add pane mode: where to add the pane, pending split direction selection
+    add_pane_pending: Option<AddPaneTarget>,
+    /// Branch name prompt for a new worktree to add the pane in
+    worktree_prompt: Option<WorktreePromptState>,
+    /// Where new worktrees are created
+    worktree_config: WorktreeConfig,
+    /// Tool timeouts for status detection (`[status]` config)
+    detection_config: DetectionConfig,
     /// Pending command selection (after direction chosen, before command chosen)
     command_select_pending: Option<AddPaneContext>,
     /// ListState for command selector navigation
@@ -154,6 +258,21 @@ pub struct App {
     history_timestamps: Vec<Option<SystemTime>>,
     /// Cached rendered lines for history detail view: ((text_hash, width), lines)
     cached_history_lines: Option<((u64, usize), Vec<ratatui::text::Line<'static>>)>,
+    /// Search within the history views (`/`, `n`/`N`)
+    history_search: HistorySearch,
+    /// Label of the transcript shown in history mode when it isn't the
+    /// selected session's (opened from transcript search)
+    history_source: Option<String>,
+    /// Cross-session transcript search popup (`F`), if open
+    transcript_search: Option<TranscriptSearchState>,
+    /// Recent sessions popup (`R`), if open
+    recent_sessions: Option<RecentSessionsState>,
+    /// Worktree cleanup popup (`W`), if open
+    worktrees: Option<WorktreesState>,
+    /// Usage statistics popup (`U`), if open
+    stats_view: Option<StatsViewState>,
+    /// Plan view of an ExitPlanMode approval (`P`), if open
+    plan_view: Option<PlanViewState>,
     /// Cached rendered lines for details preview: ((text_hash, width), lines)
     cached_preview_lines: Option<((u64, usize), Vec<ratatui::text::Line<'static>>)>,
     /// Scroll offset within summary detail view (line-level)
@@ -172,10 +291,6 @@ pub struct App {
     last_live_pane_fetch: Instant,
     /// Consecutive poll failure count (circuit-breaker)
     live_pane_poll_failures: u32,
-    /// Git branch cache (30s TTL)
-    git_branch_cache: GitBranchCache,
-    /// Git worktree cache (30s TTL)
-    git_worktree_cache: GitWorktreeCache,
     /// Last time a transcript-only refresh was performed (for debouncing)
     last_transcript_refresh: Instant,
     /// Whether a transcript refresh is pending (trailing-edge debounce)
@@ -184,12 +299,119 @@ pub struct App {
     answer_select_pending: Option<AnswerSelectState>,
     /// ListState for answer selector navigation
     answer_select_state: ListState,
-    /// Embedded terminal session (independent from sidebar selection)
-    terminal_session: Option<TerminalSession>,
+    /// Embedded terminal tabs (independent from sidebar selection)
+    terminal_tabs: Vec<TerminalSession>,
+    /// Index of the shown tab in `terminal_tabs`
+    active_terminal_tab: usize,
     /// Which pane has keyboard focus
     focus_pane: FocusPane,
 }
 
+/// State of the key binding help overlay.
+pub(super) struct HelpState {
+    /// Mode whose bindings are listed
+    pub mode: KeyMode,
+    /// First visible line
+    pub scroll: usize,
+}
+
+/// State of the command palette.
+pub(super) struct PaletteState {
+    /// Fuzzy search query
+    pub query: String,
+    /// Actions matching the query, best first
+    pub matches: Vec<Action>,
+    /// Selection within `matches`
+    pub list_state: ListState,
+}
+
+/// State of the cross-session transcript search popup.
+pub(super) struct TranscriptSearchState {
+    /// Search query
+    pub query: String,
+    /// Whether the query is being edited (otherwise the results are focused)
+    pub editing: bool,
+    /// Whether a search is running on the scan worker
+    pub searching: bool,
+    /// Hits of the last search (None until a search has run)
+    pub results: Option<Vec<SearchHit>>,
+    /// Selection within `results`
+    pub list_state: ListState,
+}
+
+/// State of the recent sessions popup.
+pub(super) struct RecentSessionsState {
+    /// Working directory whose project is listed (None: all projects)
+    pub project_cwd: Option<String>,
+    /// Whether every project is listed rather than `project_cwd`'s
+    pub all_projects: bool,
+    /// Sessions, most recently active first
+    pub sessions: Vec<ArchivedSession>,
+    /// IDs of sessions that are still running in a pane
+    pub live_ids: HashSet<String>,
+    /// Selection within `sessions`
+    pub list_state: ListState,
+}
+
+/// A linked worktree listed in the worktree cleanup popup.
+pub(super) struct WorktreeRow {
+    /// Main worktree root of the repository
+    pub repo_root: String,
+    pub worktree: Worktree,
+    /// Pane of a live session working in the worktree
+    pub session_pane: Option<u32>,
+    /// Whether the branch is merged into the repository's default branch
+    pub merged: bool,
+    /// Whether the worktree has uncommitted changes or untracked files
+    pub dirty: bool,
+}
+
+/// Removal awaiting confirmation in the worktree cleanup popup.
+pub(super) struct WorktreeRemoval {
+    /// Index into `WorktreesState::rows`
+    pub index: usize,
+    /// Also delete the worktree's branch
+    pub delete_branch: bool,
+}
+
+/// State of the worktree cleanup popup.
+pub(super) struct WorktreesState {
+    /// Linked worktrees of every repository with a session
+    pub rows: Vec<WorktreeRow>,
+    /// Selection within `rows`
+    pub list_state: ListState,
+    /// Removal awaiting `y`
+    pub confirm: Option<WorktreeRemoval>,
+    /// Whether the rows are being (re)loaded on the scan worker
+    pub loading: bool,
+}
+
+/// State of the usage statistics popup.
+pub(super) struct StatsViewState {
+    /// How rows are grouped
+    pub group: StatsGroup,
+    /// Period in days ending today (None: all history)
+    pub days: Option<u32>,
+    /// Aggregated usage (None while loading)
+    pub stats: Option<UsageStats>,
+    /// First visible row
+    pub scroll: usize,
+}
+
+/// State of the plan view.
+pub(super) struct PlanViewState {
+    /// Pane of the session whose plan is shown
+    pub pane_id: u32,
+    /// Session label or pane title
+    pub title: String,
+    /// Plan text (markdown) from the ExitPlanMode input
+    pub plan: String,
+    /// First visible line (clamped when rendered)
+    pub scroll: usize,
+    /// Open the plan in the editor on the next loop iteration
+    pub edit_requested: bool,
+}
+
 /// An option in the answer selection popup.
 pub(super) struct AnswerOption {
     pub label: String,
@@ -234,26 +456,77 @@ impl App {
             Err(e) => (Config::default(), Some(format!("Config warning: {}", e))),
         };
 
-        let toast = config_warning.map(Toast::error);
+        let (session_meta, meta_warning) = match SessionMetaStore::load() {
+            Ok(store) => (store, None),
+            Err(e) => (
+                SessionMetaStore::default(),
+                Some(format!("Session metadata warning: {}", e)),
+            ),
+        };
+
+        let (status_timelines, timeline_warning) = match StatusTimelineStore::load() {
+            Ok(store) => (store, None),
+            Err(e) => (
+                StatusTimelineStore::default(),
+                Some(format!("Status timeline warning: {}", e)),
+            ),
+        };
+
+        let (keymap, keymap_warning) = match Keymap::from_config(&config.keys) {
+            Ok(k) => (k, None),
+            Err(e) => (
+                Keymap::default(),
+                Some(format!("Key binding warning: {}", e)),
+            ),
+        };
+
+        let (theme, theme_warning) = match Theme::from_config(&config.theme) {
+            Ok(t) => (t, None),
+            Err(e) => (
+                Theme::builtin(config.theme.name),
+                Some(format!("Theme warning: {}", e)),
+            ),
+        };
+
+        let toast = config_warning
+            .or(keymap_warning)
+            .or(theme_warning)
+            .or(meta_warning)
+            .or(timeline_warning)
+            .map(Toast::error);
         let resolved_commands = config.resolved_commands();
 
         Self {
             sessions: Vec::new(),
+            all_sessions: Vec::new(),
             list_state,
             pane_ds: WeztermDataSource::new(),
-            process_ds: SystemProcessDataSource::new(),
-            detector: ClaudeCodeDetector::new(),
+            refresh_worker: None,
+            scan_worker: None,
             dirty: true,
             refreshing: false,
             needs_full_redraw: true,
-            pending_g: false,
+            pending_key: None,
+            keymap,
+            theme,
+            theme_config: config.theme.clone(),
+            help: None,
+            palette: None,
             prev_last_outputs: Vec::new(),
             last_click: None,
             list_area: None,
             transcript_watcher: None,
+            transcript_tails: Arc::new(Mutex::new(TranscriptTails::default())),
             animation_frame: 0,
             current_workspace: String::new(),
             details_width_percent: 65,
+            filter_query: String::new(),
+            filter_editing: false,
+            list_config: config.list,
+            session_meta,
+            status_timelines,
+            meta_edit: None,
+            sync_tab_title: config.labels.sync_tab_title,
             input_mode: false,
             input_buffer: InputBuffer::new(),
             slash_commands: Vec::new(),
@@ -263,6 +536,9 @@ impl App {
             toast,
             kill_confirm: None,
             add_pane_pending: None,
+            worktree_prompt: None,
+            worktree_config: config.worktree.clone(),
+            detection_config: config.status.detection_config(),
             command_select_pending: None,
             command_select_state: ListState::default(),
             resolved_commands,
@@ -273,6 +549,13 @@ impl App {
             history_scroll_offset: 0,
             history_timestamps: Vec::new(),
             cached_history_lines: None,
+            history_search: HistorySearch::default(),
+            history_source: None,
+            transcript_search: None,
+            recent_sessions: None,
+            worktrees: None,
+            stats_view: None,
+            plan_view: None,
             cached_preview_lines: None,
             summary_scroll_offset: 0,
             live_pane_bytes: None,
@@ -282,13 +565,12 @@ impl App {
             cached_live_pane_lines: None,
             last_live_pane_fetch: Instant::now(),
             live_pane_poll_failures: 0,
-            git_branch_cache: GitBranchCache::new(30),
-            git_worktree_cache: GitWorktreeCache::new(30),
             last_transcript_refresh: Instant::now(),
             pending_transcript_refresh: false,
             answer_select_pending: None,
             answer_select_state: ListState::default(),
-            terminal_session: None,
+            terminal_tabs: Vec::new(),
+            active_terminal_tab: 0,
             focus_pane: FocusPane::Sidebar,
         }
     }
@@ -312,7 +594,7 @@ impl App {
     /// Update watched directories based on current sessions.
     fn update_watched_dirs(&mut self) -> Result<()> {
         let cwds: Vec<String> = self
-            .sessions
+            .all_sessions
             .iter()
             .filter_map(|s| s.pane.cwd_path())
             .collect();
@@ -331,27 +613,25 @@ impl App {
             .is_some_and(|w| w.drain_changes())
     }
 
-    /// Extract current workspace from pane list (avoids redundant wezterm CLI call)
-    fn extract_current_workspace(panes: &[crate::models::Pane]) -> Option<String> {
-        let current_pane_id = std::env::var("WEZTERM_PANE").ok()?.parse::<u32>().ok()?;
-        panes
-            .iter()
-            .find(|p| p.pane_id == current_pane_id)
-            .map(|p| p.workspace.clone())
-    }
-
     /// Apply duplicate CWD guard: clear last_prompt/last_output for sessions
     /// that share the same CWD without statusLine bridge mapping.
     fn apply_duplicate_cwd_guard(&mut self) {
-        apply_duplicate_cwd_guard(&mut self.sessions);
+        apply_duplicate_cwd_guard(&mut self.all_sessions);
     }
 
-    /// Lightweight refresh: only re-read transcript data for known sessions.
-    /// Does NOT call wezterm CLI, ps, or git. Only re-reads transcript files.
-    fn refresh_transcripts(&mut self) {
-        for session in &mut self.sessions {
-            let info = crate::transcript::detect_session_info(&session.pane);
+    /// Apply fresh transcript info of known sessions (lightweight refresh:
+    /// the worker only re-read transcripts, not wezterm CLI, ps, or git).
+    fn apply_transcript_infos(&mut self, infos: Vec<(u32, SessionInfo)>) {
+        for (pane_id, info) in infos {
+            let Some(session) = self
+                .all_sessions
+                .iter_mut()
+                .find(|s| s.pane.pane_id == pane_id)
+            else {
+                continue;
+            };
             session.status = info.status;
+            session.status_source = info.status_source;
             session.last_prompt = info.last_prompt;
             session.last_output = info.last_output;
             session.updated_at = info.updated_at;
@@ -359,9 +639,20 @@ impl App {
             session.session_id = info.session_id;
             session.transcript_path = info.transcript_path;
             session.waiting_prompt = info.waiting_prompt;
+            session.subagents = info.subagents;
+            session.todos = info.todos;
         }
         self.apply_duplicate_cwd_guard();
-        self.dirty = true;
+        self.apply_status_timelines();
+        if self.apply_session_meta() {
+            // A pane switched to a (un)pinned session: keep pinned sessions on top
+            sort_sessions(
+                &mut self.all_sessions,
+                &self.current_workspace,
+                self.list_config,
+            );
+        }
+        self.rebuild_visible_sessions();
     }
 
     /// Check if enough time has passed for a debounced transcript refresh.
@@ -378,8 +669,136 @@ impl App {
         }
     }
 
-    /// Refresh session list
-    pub fn refresh(&mut self) -> Result<()> {
+    /// Start the refresh worker and wait for the initial session list.
+    fn start_refresh_worker(&mut self) -> Result<()> {
+        let worker =
+            RefreshWorker::spawn(self.transcript_tails.clone(), self.detection_config.clone());
+        worker.request(RefreshRequest::Full {
+            clear_git_cache: false,
+        });
+        match worker.wait() {
+            Some(RefreshUpdate::Error(e)) => anyhow::bail!(e),
+            Some(update) => self.apply_refresh_update(update)?,
+            None => anyhow::bail!("Refresh worker stopped"),
+        }
+        self.refresh_worker = Some(worker);
+        Ok(())
+    }
+
+    /// Queue a refresh of the session list on the refresh worker.
+    /// The result is applied by `apply_refresh_updates`.
+    pub(super) fn refresh(&mut self) {
+        self.request_refresh(RefreshRequest::Full {
+            clear_git_cache: false,
+        });
+    }
+
+    fn request_refresh(&self, request: RefreshRequest) {
+        if let Some(worker) = &self.refresh_worker {
+            worker.request(request);
+        }
+    }
+
+    /// Apply updates published by the refresh worker since the last call.
+    fn apply_refresh_updates(&mut self) -> Result<()> {
+        let updates = self
+            .refresh_worker
+            .as_ref()
+            .map(|w| w.updates())
+            .unwrap_or_default();
+        for update in updates {
+            self.apply_refresh_update(update)?;
+        }
+        Ok(())
+    }
+
+    /// Queue a background scan, starting the scan worker on first use.
+    fn request_scan(&mut self, request: ScanRequest) {
+        self.scan_worker
+            .get_or_insert_with(ScanWorker::spawn)
+            .request(request);
+    }
+
+    /// Abandon the scan of `kind` in progress.
+    fn cancel_scan(&self, kind: ScanKind) {
+        if let Some(worker) = &self.scan_worker {
+            worker.cancel(kind);
+        }
+    }
+
+    /// Apply results published by the scan worker since the last call.
+    fn apply_scan_updates(&mut self) {
+        let updates = self
+            .scan_worker
+            .as_ref()
+            .map(|w| w.updates())
+            .unwrap_or_default();
+        for update in updates {
+            match update {
+                ScanUpdate::Search { query, hits } => self.apply_transcript_search(query, hits),
+                ScanUpdate::Stats { since, stats } => self.apply_stats(since, stats),
+                ScanUpdate::Worktrees { worktrees, errors } => {
+                    self.apply_worktree_scan(worktrees, errors)
+                }
+            }
+        }
+    }
+
+    fn apply_refresh_update(&mut self, update: RefreshUpdate) -> Result<()> {
+        let previous: HashMap<u32, SessionStatus> = self
+            .all_sessions
+            .iter()
+            .map(|s| (s.pane.pane_id, s.status.clone()))
+            .collect();
+        match update {
+            RefreshUpdate::Sessions {
+                workspace,
+                sessions,
+            } => {
+                self.apply_sessions(workspace, sessions);
+                self.update_watched_dirs()?;
+            }
+            RefreshUpdate::Transcripts(infos) => self.apply_transcript_infos(infos),
+            RefreshUpdate::Error(e) => {
+                self.refreshing = false;
+                self.toast = Some(Toast::error(format!("Refresh failed: {}", e)));
+            }
+        }
+        self.notify_failed_sessions(&previous);
+        self.dirty = true;
+
+        // Check for actual changes in output
+        let current_outputs: Vec<Option<String>> = self
+            .sessions
+            .iter()
+            .map(|s| s.last_output.clone())
+            .collect();
+        if current_outputs != self.prev_last_outputs {
+            self.needs_full_redraw = true;
+            self.prev_last_outputs = current_outputs;
+        }
+        Ok(())
+    }
+
+    /// Toast sessions that just hit an API error or rate limit, which would
+    /// otherwise sit unnoticed in the list.
+    fn notify_failed_sessions(&mut self, previous: &HashMap<u32, SessionStatus>) {
+        let failed: Vec<String> = self
+            .all_sessions
+            .iter()
+            .filter(|s| newly_failed(previous.get(&s.pane.pane_id), &s.status))
+            .map(|s| {
+                let title = s.meta.label.as_ref().unwrap_or(&s.pane.title);
+                format!("{}: {}", title, status_display(&s.status, &self.theme).1)
+            })
+            .collect();
+        if !failed.is_empty() {
+            self.toast = Some(Toast::error(failed.join(" · ")));
+        }
+    }
+
+    /// Replace the session list with freshly detected sessions.
+    fn apply_sessions(&mut self, workspace: Option<String>, sessions: Vec<ClaudeSession>) {
         // Preserve currently selected pane_id
         let selected_pane_id = self
             .list_state
@@ -387,72 +806,31 @@ impl App {
             .and_then(|i| self.sessions.get(i))
             .map(|s| s.pane.pane_id);
 
-        // Get all panes (single call, also used to extract workspace)
-        let panes = self.pane_ds.list_panes()?;
-
-        // Extract workspace from pane list (avoids redundant wezterm CLI call)
-        self.current_workspace = Self::extract_current_workspace(&panes)
-            .unwrap_or_else(|| self.current_workspace.clone());
-
-        // Build process tree once (optimization)
-        let process_tree = self.process_ds.build_tree()?;
-
-        self.sessions = panes
-            .into_iter()
-            .filter_map(|pane| {
-                // Try to detect Claude Code (reusing process tree)
-                let reason = self
-                    .detector
-                    .detect_by_tty_with_tree(&pane, &process_tree)
-                    .ok()??;
-
-                // Get session info (uses statusLine bridge if available, falls back to CWD-based)
-                let session_info = crate::transcript::detect_session_info(&pane);
-
-                // Keep only detected sessions (git_branch filled below)
-                Some(ClaudeSession {
-                    pane,
-                    detected: true,
-                    reason,
-                    status: session_info.status,
-                    git_branch: None,
-                    git_worktree: None,
-                    last_prompt: session_info.last_prompt,
-                    last_output: session_info.last_output,
-                    session_id: session_info.session_id,
-                    transcript_path: session_info.transcript_path,
-                    updated_at: session_info.updated_at,
-                    warning: session_info.warning,
-                    waiting_prompt: session_info.waiting_prompt,
-                })
-            })
-            .collect();
-
-        // Fill in git branches and worktree info with caching (separate loop to avoid borrow issues)
-        for session in &mut self.sessions {
-            if let Some(cwd) = session.pane.cwd_path() {
-                session.git_branch = self.git_branch_cache.get(&cwd);
-                session.git_worktree = self.git_worktree_cache.get(&cwd);
-            }
+        if let Some(workspace) = workspace {
+            self.current_workspace = workspace;
         }
+        self.all_sessions = sessions;
+        self.refreshing = false;
 
         // Apply duplicate CWD guard
         self.apply_duplicate_cwd_guard();
 
-        // Sort by workspace → cwd → pane_id (current workspace first)
-        sort_sessions(&mut self.sessions, &self.current_workspace);
+        // Attach user-assigned labels/tags/pins
+        self.apply_session_meta();
 
-        // Maintain selection position (reselect if same pane_id exists)
-        if !self.sessions.is_empty() {
-            let new_index = selected_pane_id
-                .and_then(|id| self.sessions.iter().position(|s| s.pane.pane_id == id))
-                .unwrap_or(0);
-            self.list_state.select(Some(new_index));
-        } else {
-            self.list_state.select(None);
-        }
+        // Record status changes
+        self.apply_status_timelines();
 
-        self.dirty = true;
+        // Sort pinned sessions first, then by group (workspace → cwd by default),
+        // then by the configured order
+        sort_sessions(
+            &mut self.all_sessions,
+            &self.current_workspace,
+            self.list_config,
+        );
+
+        // Apply list filter and maintain selection position (reselect if same pane_id exists)
+        self.rebuild_visible_sessions_with_selection(selected_pane_id);
 
         // If in live pane view, check if the selected session's pane_id changed
         if self.detail_mode == DetailMode::LivePane {
@@ -465,11 +843,17 @@ impl App {
                 self.exit_live_pane_view();
             }
         }
-
-        Ok(())
     }
 }
 
+/// Whether a known session changed into an error or rate limit status.
+fn newly_failed(previous: Option<&SessionStatus>, status: &SessionStatus) -> bool {
+    matches!(
+        status,
+        SessionStatus::Errored { .. } | SessionStatus::RateLimited { .. }
+    ) && previous.is_some_and(|p| std::mem::discriminant(p) != std::mem::discriminant(status))
+}
+
 /// Apply duplicate CWD guard: clear last_prompt/last_output for sessions
 /// that share the same CWD without statusLine bridge mapping.
 fn apply_duplicate_cwd_guard(sessions: &mut [ClaudeSession]) {
@@ -498,71 +882,112 @@ fn apply_duplicate_cwd_guard(sessions: &mut [ClaudeSession]) {
 
 /// Calculate session index from list display row.
 /// Returns the session corresponding to the clicked row, considering group headers.
-fn row_to_session_index(sessions: &[ClaudeSession], row: usize) -> Option<usize> {
+fn row_to_session_index(
+    sessions: &[ClaudeSession],
+    group_by: GroupBy,
+    row: usize,
+) -> Option<usize> {
     let mut current_row = 0;
-    let mut current_ws: Option<String> = None;
-    let mut current_cwd: Option<String> = None;
+    let mut prev_headers = None;
 
     for (session_idx, session) in sessions.iter().enumerate() {
-        let ws = &session.pane.workspace;
-        let cwd = session.pane.cwd_path().unwrap_or_default();
-
-        // Workspace header row
-        if current_ws.as_ref() != Some(ws) {
-            current_ws = Some(ws.clone());
-            current_cwd = None;
-            if current_row == row {
-                return None; // header click
-            }
-            current_row += 1;
-        }
+        let headers = session.group_headers(group_by);
+        let level = first_new_header_level(prev_headers.as_deref(), &headers);
 
-        // CWD header row
-        if current_cwd.as_ref() != Some(&cwd) {
-            current_cwd = Some(cwd.clone());
-            if current_row == row {
-                return None; // header click
-            }
-            current_row += 1;
+        // Group header rows
+        let header_rows = headers.len() - level;
+        if row < current_row + header_rows {
+            return None; // header click
         }
+        current_row += header_rows;
 
-        // Session row
-        if current_row == row {
+        // Session row, followed by its running subagents
+        let rows = session.list_rows();
+        if row < current_row + rows {
             return Some(session_idx);
         }
-        current_row += 1;
+        current_row += rows;
+        prev_headers = Some(headers);
     }
 
     None
 }
 
-/// Sort sessions: current workspace first, then by workspace name, CWD, pane_id.
-fn sort_sessions(sessions: &mut [ClaudeSession], current_workspace: &str) {
+/// Sort sessions so that pinned sessions come first and each group is
+/// contiguous (current workspace first when grouping by workspace), then by
+/// the configured order within a group.
+fn sort_sessions(sessions: &mut [ClaudeSession], current_workspace: &str, list: ListConfig) {
     sessions.sort_by(|a, b| {
-        let ws_a_is_current = a.pane.workspace == current_workspace;
-        let ws_b_is_current = b.pane.workspace == current_workspace;
-        match (ws_a_is_current, ws_b_is_current) {
-            (true, false) => std::cmp::Ordering::Less,
-            (false, true) => std::cmp::Ordering::Greater,
-            _ => {
-                let ws_a = &a.pane.workspace;
-                let ws_b = &b.pane.workspace;
-                let cwd_a = a.pane.cwd_path().unwrap_or_default();
-                let cwd_b = b.pane.cwd_path().unwrap_or_default();
-                ws_a.cmp(ws_b)
-                    .then(cwd_a.cmp(&cwd_b))
-                    .then(a.pane.pane_id.cmp(&b.pane.pane_id))
-            }
-        }
+        b.meta
+            .pinned
+            .cmp(&a.meta.pinned)
+            .then_with(|| compare_groups(a, b, current_workspace, list.group))
+            .then_with(|| compare_within_group(a, b, list.sort))
+            .then(a.pane.pane_id.cmp(&b.pane.pane_id))
     });
 }
 
+/// Order of groups in the session list.
+fn compare_groups(
+    a: &ClaudeSession,
+    b: &ClaudeSession,
+    current_workspace: &str,
+    group_by: GroupBy,
+) -> std::cmp::Ordering {
+    match group_by {
+        GroupBy::Workspace => {
+            let ws_a_is_current = a.pane.workspace == current_workspace;
+            let ws_b_is_current = b.pane.workspace == current_workspace;
+            ws_b_is_current
+                .cmp(&ws_a_is_current)
+                .then_with(|| a.pane.workspace.cmp(&b.pane.workspace))
+                .then_with(|| a.pane.cwd_path().cmp(&b.pane.cwd_path()))
+        }
+        GroupBy::Repo => cmp_none_last(&a.git_repo_root, &b.git_repo_root),
+        GroupBy::Agent => a.reason.agent_name().cmp(&b.reason.agent_name()),
+        GroupBy::Flat => std::cmp::Ordering::Equal,
+    }
+}
+
+/// Order of sessions within a group.
+fn compare_within_group(
+    a: &ClaudeSession,
+    b: &ClaudeSession,
+    sort: SortOrder,
+) -> std::cmp::Ordering {
+    let by_cwd = || a.pane.cwd_path().cmp(&b.pane.cwd_path());
+    match sort {
+        SortOrder::Default => by_cwd(),
+        SortOrder::Status => a.status.urgency().cmp(&b.status.urgency()),
+        // Most recent first; sessions without activity go last
+        SortOrder::Activity => match (a.updated_at, b.updated_at) {
+            (Some(ta), Some(tb)) => tb.cmp(&ta),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        },
+        SortOrder::Repo => cmp_none_last(&a.git_repo_root, &b.git_repo_root).then_with(by_cwd),
+        SortOrder::Branch => cmp_none_last(&a.git_branch, &b.git_branch).then_with(by_cwd),
+    }
+}
+
+/// Compare optional strings with `None` sorted after any value.
+fn cmp_none_last(a: &Option<String>, b: &Option<String>) -> std::cmp::Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.cmp(b),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::detector::DetectionReason;
     use crate::models::Pane;
-    use crate::transcript::SessionStatus;
+    use crate::status_timeline::StatusTimeline;
+    use crate::transcript::{SessionStatus, StatusKind, StatusSource, Subagent, SubagentState};
 
     fn make_pane(pane_id: u32, workspace: &str, cwd: &str) -> Pane {
         Pane {
@@ -587,8 +1012,11 @@ mod tests {
                 process_name: "claude".to_string(),
             },
             status: SessionStatus::Idle,
+            status_source: StatusSource::Transcript,
             git_branch: None,
             git_worktree: None,
+            git_repo_root: None,
+            git_status: None,
             last_prompt: Some("test prompt".to_string()),
             last_output: Some("test output".to_string()),
             session_id: None,
@@ -596,6 +1024,10 @@ mod tests {
             updated_at: None,
             warning: None,
             waiting_prompt: None,
+            subagents: Vec::new(),
+            todos: Vec::new(),
+            meta: SessionMeta::default(),
+            timeline: StatusTimeline::default(),
         }
     }
 
@@ -622,10 +1054,14 @@ mod tests {
     fn test_row_to_session_single_session() {
         // Layout: row 0 = workspace header, row 1 = cwd header, row 2 = session
         let sessions = vec![make_session(1, "default", "/home/user/project")];
-        assert_eq!(row_to_session_index(&sessions, 0), None); // workspace header
-        assert_eq!(row_to_session_index(&sessions, 1), None); // cwd header
-        assert_eq!(row_to_session_index(&sessions, 2), Some(0)); // session
-        assert_eq!(row_to_session_index(&sessions, 3), None); // out of bounds
+        assert_eq!(row_to_session_index(&sessions, GroupBy::Workspace, 0), None); // workspace header
+        assert_eq!(row_to_session_index(&sessions, GroupBy::Workspace, 1), None); // cwd header
+        assert_eq!(
+            row_to_session_index(&sessions, GroupBy::Workspace, 2),
+            Some(0)
+        ); // session
+        assert_eq!(row_to_session_index(&sessions, GroupBy::Workspace, 3), None);
+        // out of bounds
     }
 
     #[test]
@@ -636,10 +1072,218 @@ mod tests {
             make_session(1, "default", "/home/user/project"),
             make_session(2, "default", "/home/user/project"),
         ];
-        assert_eq!(row_to_session_index(&sessions, 0), None);
-        assert_eq!(row_to_session_index(&sessions, 1), None);
-        assert_eq!(row_to_session_index(&sessions, 2), Some(0));
-        assert_eq!(row_to_session_index(&sessions, 3), Some(1));
+        assert_eq!(row_to_session_index(&sessions, GroupBy::Workspace, 0), None);
+        assert_eq!(row_to_session_index(&sessions, GroupBy::Workspace, 1), None);
+        assert_eq!(
+            row_to_session_index(&sessions, GroupBy::Workspace, 2),
+            Some(0)
+        );
+        assert_eq!(
+            row_to_session_index(&sessions, GroupBy::Workspace, 3),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_row_to_session_with_running_subagents() {
+        // row 2 = session 0, rows 3-4 = its running subagents, row 5 = session 1
+        let mut busy = make_session(1, "default", "/home/user/project");
+        busy.subagents = [
+            SubagentState::Running,
+            SubagentState::Completed,
+            SubagentState::Running,
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, state)| Subagent {
+            id: format!("t{}", i),
+            description: "explore".to_string(),
+            agent_type: None,
+            state,
+            tool_count: 0,
+            started_at: None,
+            duration: None,
+        })
+        .collect();
+        let sessions = vec![busy, make_session(2, "default", "/home/user/project")];
+        for row in 2..=4 {
+            assert_eq!(
+                row_to_session_index(&sessions, GroupBy::Workspace, row),
+                Some(0)
+            );
+        }
+        assert_eq!(
+            row_to_session_index(&sessions, GroupBy::Workspace, 5),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_worktree_outside_repo_root_in_use() {
+        use crate::datasource::git::Worktree;
+        // `[worktree] dir` may point outside the main worktree (e.g. /tmp/wt)
+        let root = tempfile::tempdir().unwrap();
+        let repo = root.path().join("repo");
+        let worktree = root.path().join("wt").join("feature");
+        std::fs::create_dir_all(repo.join("src")).unwrap();
+        std::fs::create_dir_all(worktree.join("src")).unwrap();
+
+        let mut app = App::new();
+        let mut s = make_session(5, "default", &worktree.join("src").to_string_lossy());
+        s.git_repo_root = Some(repo.to_string_lossy().to_string());
+        app.all_sessions = vec![s];
+        assert_eq!(app.worktree_session_pane(&worktree), Some(5));
+        assert_eq!(app.worktree_session_pane(&root.path().join("wt")), Some(5));
+        assert_eq!(app.worktree_session_pane(&repo), None);
+
+        // A stale row without the session is re-checked before removal
+        app.worktrees = Some(WorktreesState {
+            rows: vec![WorktreeRow {
+                repo_root: repo.to_string_lossy().to_string(),
+                worktree: Worktree {
+                    path: worktree.clone(),
+                    branch: Some("feature".into()),
+                    prunable: false,
+                },
+                session_pane: None,
+                merged: false,
+                dirty: true,
+            }],
+            list_state: ListState::default(),
+            confirm: Some(WorktreeRemoval {
+                index: 0,
+                delete_branch: false,
+            }),
+            loading: false,
+        });
+        app.confirm_worktree_removal();
+        assert!(app.toast.unwrap().message.contains("in use by Pane 5"));
+        assert!(worktree.exists());
+    }
+
+    #[test]
+    fn test_apply_worktree_scan() {
+        use crate::datasource::git::Worktree;
+        use crate::ui::scan_worker::WorktreeStatus;
+        let status = |path: &str| WorktreeStatus {
+            repo_root: "/repo".to_string(),
+            worktree: Worktree {
+                path: std::path::PathBuf::from(path),
+                branch: None,
+                prunable: false,
+            },
+            merged: false,
+            dirty: false,
+        };
+
+        let mut app = App::new();
+        app.all_sessions = vec![make_session(3, "default", "/wt/b/src")];
+        app.open_worktrees();
+        app.cancel_scan(ScanKind::Worktrees);
+        assert!(app.worktrees.as_ref().unwrap().loading);
+
+        app.apply_worktree_scan(vec![status("/wt/a"), status("/wt/b")], Vec::new());
+        let state = app.worktrees.as_mut().unwrap();
+        assert!(!state.loading);
+        assert_eq!(state.list_state.selected(), Some(0));
+        assert_eq!(state.rows[1].session_pane, Some(3));
+        state.list_state.select(Some(1));
+        state.confirm = Some(WorktreeRemoval {
+            index: 1,
+            delete_branch: false,
+        });
+
+        // A reload with fewer rows clamps the selection and drops the confirmation
+        app.apply_worktree_scan(vec![status("/wt/a")], vec!["/other: boom".to_string()]);
+        let state = app.worktrees.as_ref().unwrap();
+        assert_eq!(state.list_state.selected(), Some(0));
+        assert!(state.confirm.is_none());
+        assert!(app.toast.unwrap().message.contains("/other: boom"));
+    }
+
+    #[test]
+    fn test_apply_stats_ignores_other_periods() {
+        use crate::transcript::period_start;
+        let mut app = App::new();
+        app.stats_view = Some(StatsViewState {
+            group: StatsGroup::Project,
+            days: Some(30),
+            stats: None,
+            scroll: 0,
+        });
+
+        // Usage of the period shown before `d` was pressed is dropped
+        app.apply_stats(Some(period_start(7)), Ok(UsageStats::default()));
+        assert!(app.stats_view.as_ref().unwrap().stats.is_none());
+
+        app.apply_stats(Some(period_start(30)), Ok(UsageStats::default()));
+        assert!(app.stats_view.as_ref().unwrap().stats.is_some());
+    }
+
+    #[test]
+    fn test_tab_index_after_close() {
+        use actions::tab_index_after_close;
+        // Closing the shown tab shows the next one, or the one before the last
+        assert_eq!(tab_index_after_close(1, 1, 2), 1);
+        assert_eq!(tab_index_after_close(2, 2, 2), 1);
+        // Closing another tab keeps the shown one
+        assert_eq!(tab_index_after_close(2, 0, 2), 1);
+        assert_eq!(tab_index_after_close(0, 1, 2), 0);
+        // Closing the only tab
+        assert_eq!(tab_index_after_close(0, 0, 0), 0);
+    }
+
+    #[test]
+    fn test_apply_transcript_search_ignores_abandoned_results() {
+        let mut app = App::new();
+        app.transcript_search = Some(TranscriptSearchState {
+            query: "new".to_string(),
+            editing: false,
+            searching: true,
+            results: None,
+            list_state: ListState::default(),
+        });
+
+        // Results of an earlier query are dropped
+        app.apply_transcript_search("old".to_string(), Ok(Vec::new()));
+        let state = app.transcript_search.as_ref().unwrap();
+        assert!(state.searching);
+        assert!(state.results.is_none());
+
+        app.apply_transcript_search("new".to_string(), Ok(Vec::new()));
+        let state = app.transcript_search.as_ref().unwrap();
+        assert!(!state.searching);
+        assert_eq!(state.results.as_ref().map(Vec::len), Some(0));
+
+        // A late result after the search was abandoned is dropped too
+        app.edit_transcript_search();
+        app.apply_transcript_search("new".to_string(), Err("boom".to_string()));
+        assert!(app.toast.is_none());
+    }
+
+    #[test]
+    fn test_show_terminal_without_tabs() {
+        let mut app = App::new();
+        app.show_terminal();
+        assert_eq!(app.detail_mode, DetailMode::Summary);
+        assert!(app.toast.unwrap().message.contains("No terminal tabs"));
+    }
+
+    #[test]
+    fn test_apply_status_timelines_records_changes() {
+        let mut app = App::new();
+        app.all_sessions = vec![make_session(7, "default", "/home/user/project")];
+        app.apply_status_timelines();
+        let timeline = &app.all_sessions[0].timeline;
+        assert_eq!(timeline.current(), Some(StatusKind::Idle));
+
+        app.all_sessions[0].status = SessionStatus::WaitingForUser { tools: vec![] };
+        app.apply_status_timelines();
+        let session = &app.all_sessions[0];
+        assert_eq!(session.timeline.current(), Some(StatusKind::Waiting));
+        assert!(session.status_elapsed(chrono::Utc::now()).is_some());
+        // Sessions without an ID are tracked by pane
+        assert!(app.status_timelines.get("pane:7").is_some());
     }
 
     #[test]
@@ -650,11 +1294,17 @@ mod tests {
             make_session(1, "default", "/home/user/project-a"),
             make_session(2, "default", "/home/user/project-b"),
         ];
-        assert_eq!(row_to_session_index(&sessions, 0), None); // ws header
-        assert_eq!(row_to_session_index(&sessions, 1), None); // cwd1 header
-        assert_eq!(row_to_session_index(&sessions, 2), Some(0));
-        assert_eq!(row_to_session_index(&sessions, 3), None); // cwd2 header
-        assert_eq!(row_to_session_index(&sessions, 4), Some(1));
+        assert_eq!(row_to_session_index(&sessions, GroupBy::Workspace, 0), None); // ws header
+        assert_eq!(row_to_session_index(&sessions, GroupBy::Workspace, 1), None); // cwd1 header
+        assert_eq!(
+            row_to_session_index(&sessions, GroupBy::Workspace, 2),
+            Some(0)
+        );
+        assert_eq!(row_to_session_index(&sessions, GroupBy::Workspace, 3), None); // cwd2 header
+        assert_eq!(
+            row_to_session_index(&sessions, GroupBy::Workspace, 4),
+            Some(1)
+        );
     }
 
     #[test]
@@ -665,18 +1315,53 @@ mod tests {
             make_session(1, "work", "/home/user/project"),
             make_session(2, "personal", "/home/user/hobby"),
         ];
-        assert_eq!(row_to_session_index(&sessions, 0), None); // ws1 header
-        assert_eq!(row_to_session_index(&sessions, 1), None); // cwd header
-        assert_eq!(row_to_session_index(&sessions, 2), Some(0));
-        assert_eq!(row_to_session_index(&sessions, 3), None); // ws2 header
-        assert_eq!(row_to_session_index(&sessions, 4), None); // cwd header
-        assert_eq!(row_to_session_index(&sessions, 5), Some(1));
+        assert_eq!(row_to_session_index(&sessions, GroupBy::Workspace, 0), None); // ws1 header
+        assert_eq!(row_to_session_index(&sessions, GroupBy::Workspace, 1), None); // cwd header
+        assert_eq!(
+            row_to_session_index(&sessions, GroupBy::Workspace, 2),
+            Some(0)
+        );
+        assert_eq!(row_to_session_index(&sessions, GroupBy::Workspace, 3), None); // ws2 header
+        assert_eq!(row_to_session_index(&sessions, GroupBy::Workspace, 4), None); // cwd header
+        assert_eq!(
+            row_to_session_index(&sessions, GroupBy::Workspace, 5),
+            Some(1)
+        );
     }
 
     #[test]
     fn test_row_to_session_empty() {
         let sessions: Vec<ClaudeSession> = vec![];
-        assert_eq!(row_to_session_index(&sessions, 0), None);
+        assert_eq!(row_to_session_index(&sessions, GroupBy::Workspace, 0), None);
+    }
+
+    #[test]
+    fn test_row_to_session_flat_has_no_headers() {
+        let sessions = vec![
+            make_session(1, "work", "/home/user/a"),
+            make_session(2, "personal", "/home/user/b"),
+        ];
+        assert_eq!(row_to_session_index(&sessions, GroupBy::Flat, 0), Some(0));
+        assert_eq!(row_to_session_index(&sessions, GroupBy::Flat, 1), Some(1));
+        assert_eq!(row_to_session_index(&sessions, GroupBy::Flat, 2), None);
+    }
+
+    #[test]
+    fn test_row_to_session_grouped_by_repo() {
+        // row 0 = repo-a header, row 1 = session 0, row 2 = session 1,
+        // row 3 = no-repo header, row 4 = session 2
+        let mut sessions = vec![
+            make_session(1, "work", "/src/repo-a"),
+            make_session(2, "other", "/src/repo-a/.worktree/feat"),
+            make_session(3, "work", "/tmp"),
+        ];
+        sessions[0].git_repo_root = Some("/src/repo-a".to_string());
+        sessions[1].git_repo_root = Some("/src/repo-a".to_string());
+        assert_eq!(row_to_session_index(&sessions, GroupBy::Repo, 0), None);
+        assert_eq!(row_to_session_index(&sessions, GroupBy::Repo, 1), Some(0));
+        assert_eq!(row_to_session_index(&sessions, GroupBy::Repo, 2), Some(1));
+        assert_eq!(row_to_session_index(&sessions, GroupBy::Repo, 3), None);
+        assert_eq!(row_to_session_index(&sessions, GroupBy::Repo, 4), Some(2));
     }
 
     // --- apply_duplicate_cwd_guard tests ---
@@ -754,7 +1439,7 @@ mod tests {
             make_session(1, "other", "/tmp"),
             make_session(2, "current", "/tmp"),
         ];
-        sort_sessions(&mut sessions, "current");
+        sort_sessions(&mut sessions, "current", ListConfig::default());
         assert_eq!(sessions[0].pane.workspace, "current");
         assert_eq!(sessions[1].pane.workspace, "other");
     }
@@ -766,7 +1451,7 @@ mod tests {
             make_session(1, "alpha", "/home/a"),
             make_session(2, "alpha", "/home/a"),
         ];
-        sort_sessions(&mut sessions, "none");
+        sort_sessions(&mut sessions, "none", ListConfig::default());
         assert_eq!(sessions[0].pane.pane_id, 1);
         assert_eq!(sessions[1].pane.pane_id, 2);
         assert_eq!(sessions[2].pane.pane_id, 3);
@@ -779,7 +1464,7 @@ mod tests {
             make_session(2, "alpha", "/tmp"),
             make_session(3, "current", "/tmp"),
         ];
-        sort_sessions(&mut sessions, "current");
+        sort_sessions(&mut sessions, "current", ListConfig::default());
         assert_eq!(sessions[0].pane.workspace, "current"); // current first
         assert_eq!(sessions[1].pane.workspace, "alpha"); // then alphabetical
         assert_eq!(sessions[2].pane.workspace, "beta");
@@ -792,12 +1477,261 @@ mod tests {
             make_session(2, "ws", "/home/project"),
             make_session(8, "ws", "/home/project"),
         ];
-        sort_sessions(&mut sessions, "ws");
+        sort_sessions(&mut sessions, "ws", ListConfig::default());
         assert_eq!(sessions[0].pane.pane_id, 2);
         assert_eq!(sessions[1].pane.pane_id, 5);
         assert_eq!(sessions[2].pane.pane_id, 8);
     }
 
+    fn list_config(sort: SortOrder, group: GroupBy) -> ListConfig {
+        ListConfig { sort, group }
+    }
+
+    #[test]
+    fn test_sort_by_status_urgency_within_group() {
+        let mut sessions = vec![
+            make_session(1, "ws", "/home/project"),
+            make_session(2, "ws", "/home/project"),
+            make_session(3, "ws", "/home/project"),
+        ];
+        sessions[0].status = SessionStatus::Processing;
+        sessions[1].status = SessionStatus::WaitingForUser { tools: vec![] };
+        sessions[2].status = SessionStatus::Ready;
+        sort_sessions(
+            &mut sessions,
+            "ws",
+            list_config(SortOrder::Status, GroupBy::Workspace),
+        );
+        assert_eq!(sessions[0].pane.pane_id, 2); // waiting first
+        assert_eq!(sessions[1].pane.pane_id, 3);
+        assert_eq!(sessions[2].pane.pane_id, 1);
+    }
+
+    #[test]
+    fn test_sort_by_status_flat_ignores_workspace() {
+        let mut sessions = vec![
+            make_session(1, "current", "/a"),
+            make_session(2, "other", "/b"),
+        ];
+        sessions[1].status = SessionStatus::WaitingForUser { tools: vec![] };
+        sort_sessions(
+            &mut sessions,
+            "current",
+            list_config(SortOrder::Status, GroupBy::Flat),
+        );
+        assert_eq!(sessions[0].pane.pane_id, 2);
+        assert_eq!(sessions[1].pane.pane_id, 1);
+    }
+
+    #[test]
+    fn test_sort_by_activity_most_recent_first() {
+        let now = SystemTime::now();
+        let mut sessions = vec![
+            make_session(1, "ws", "/a"),
+            make_session(2, "ws", "/b"),
+            make_session(3, "ws", "/c"),
+        ];
+        sessions[0].updated_at = Some(now - Duration::from_secs(600));
+        sessions[2].updated_at = Some(now);
+        sort_sessions(
+            &mut sessions,
+            "ws",
+            list_config(SortOrder::Activity, GroupBy::Flat),
+        );
+        assert_eq!(sessions[0].pane.pane_id, 3);
+        assert_eq!(sessions[1].pane.pane_id, 1);
+        assert_eq!(sessions[2].pane.pane_id, 2); // no activity goes last
+    }
+
+    #[test]
+    fn test_sort_by_branch_none_last() {
+        let mut sessions = vec![
+            make_session(1, "ws", "/a"),
+            make_session(2, "ws", "/b"),
+            make_session(3, "ws", "/c"),
+        ];
+        sessions[1].git_branch = Some("main".to_string());
+        sessions[2].git_branch = Some("develop".to_string());
+        sort_sessions(
+            &mut sessions,
+            "ws",
+            list_config(SortOrder::Branch, GroupBy::Flat),
+        );
+        assert_eq!(sessions[0].pane.pane_id, 3);
+        assert_eq!(sessions[1].pane.pane_id, 2);
+        assert_eq!(sessions[2].pane.pane_id, 1);
+    }
+
+    #[test]
+    fn test_group_by_repo_keeps_groups_contiguous() {
+        let mut sessions = vec![
+            make_session(1, "ws", "/src/b"),
+            make_session(2, "ws", "/tmp"),
+            make_session(3, "ws", "/src/a"),
+            make_session(4, "ws", "/src/b/.worktree/x"),
+        ];
+        sessions[0].git_repo_root = Some("/src/b".to_string());
+        sessions[2].git_repo_root = Some("/src/a".to_string());
+        sessions[3].git_repo_root = Some("/src/b".to_string());
+        sort_sessions(
+            &mut sessions,
+            "ws",
+            list_config(SortOrder::Default, GroupBy::Repo),
+        );
+        let ids: Vec<u32> = sessions.iter().map(|s| s.pane.pane_id).collect();
+        assert_eq!(ids, vec![3, 1, 4, 2]); // a, b (two worktrees), then no repo
+    }
+
+    #[test]
+    fn test_pinned_sessions_sort_first_regardless_of_order() {
+        let mut sessions = vec![
+            make_session(1, "current", "/a"),
+            make_session(2, "other", "/b"),
+            make_session(3, "current", "/c"),
+        ];
+        sessions[0].status = SessionStatus::WaitingForUser { tools: vec![] };
+        sessions[1].meta.pinned = true;
+        for group in GroupBy::ALL {
+            sort_sessions(
+                &mut sessions,
+                "current",
+                list_config(SortOrder::Status, group),
+            );
+            assert_eq!(sessions[0].pane.pane_id, 2, "group {:?}", group);
+            assert_eq!(sessions[1].pane.pane_id, 1, "group {:?}", group);
+        }
+    }
+
+    #[test]
+    fn test_row_to_session_pinned_header() {
+        // Pinned: "📌 Pinned", Session 2; then Workspace, CWD, Session 1
+        let mut sessions = vec![make_session(2, "ws", "/b"), make_session(1, "ws", "/a")];
+        sessions[0].meta.pinned = true;
+
+        assert_eq!(row_to_session_index(&sessions, GroupBy::Workspace, 0), None);
+        assert_eq!(
+            row_to_session_index(&sessions, GroupBy::Workspace, 1),
+            Some(0)
+        );
+        assert_eq!(row_to_session_index(&sessions, GroupBy::Workspace, 2), None);
+        assert_eq!(row_to_session_index(&sessions, GroupBy::Workspace, 3), None);
+        assert_eq!(
+            row_to_session_index(&sessions, GroupBy::Workspace, 4),
+            Some(1)
+        );
+
+        // Flat: no headers, pinned session is simply first
+        assert_eq!(row_to_session_index(&sessions, GroupBy::Flat, 0), Some(0));
+        assert_eq!(row_to_session_index(&sessions, GroupBy::Flat, 1), Some(1));
+    }
+
+    // --- session list filter tests ---
+
+    #[test]
+    fn test_filter_matches_fields() {
+        let mut s = make_session(1, "work", "/home/user/api-server");
+        s.git_branch = Some("feature/login".to_string());
+        s.git_worktree = Some("wt-login".to_string());
+
+        assert!(filter::session_matches_filter(&s, "api"));
+        assert!(filter::session_matches_filter(&s, "wrk"));
+        assert!(filter::session_matches_filter(&s, "flogin"));
+        assert!(filter::session_matches_filter(&s, "wt-l"));
+        assert!(filter::session_matches_filter(&s, "pane-1"));
+        assert!(filter::session_matches_filter(&s, "idle"));
+        assert!(filter::session_matches_filter(&s, "test prompt"));
+        assert!(!filter::session_matches_filter(&s, "zzz"));
+    }
+
+    #[test]
+    fn test_filter_matches_label_and_tags() {
+        let mut s = make_session(1, "work", "/tmp");
+        s.meta.label = Some("payments refactor".to_string());
+        s.meta.tags = vec!["urgent".to_string()];
+
+        assert!(filter::session_matches_filter(&s, "payref"));
+        assert!(filter::session_matches_filter(&s, "urgent"));
+        assert!(!filter::session_matches_filter(&s, "backlog"));
+    }
+
+    #[test]
+    fn test_filter_requires_all_terms() {
+        let mut s = make_session(1, "work", "/home/user/api-server");
+        s.git_branch = Some("main".to_string());
+        assert!(filter::session_matches_filter(&s, "api main"));
+        assert!(!filter::session_matches_filter(&s, "api zzz"));
+    }
+
+    #[test]
+    fn test_filter_empty_query_keeps_all() {
+        let sessions = vec![
+            make_session(1, "work", "/home/user/a"),
+            make_session(2, "work", "/home/user/b"),
+        ];
+        assert_eq!(filter::filter_sessions(&sessions, "").len(), 2);
+        assert_eq!(filter::filter_sessions(&sessions, "  ").len(), 2);
+    }
+
+    #[test]
+    fn test_filter_narrows_visible_sessions_and_keeps_selection() {
+        let mut app = App::new();
+        app.all_sessions = vec![
+            make_session(1, "work", "/home/user/alpha"),
+            make_session(2, "work", "/home/user/beta"),
+            make_session(3, "work", "/home/user/gamma"),
+        ];
+        app.rebuild_visible_sessions_with_selection(Some(3));
+        assert_eq!(app.sessions.len(), 3);
+        assert_eq!(app.list_state.selected(), Some(2));
+
+        for c in "gam".chars() {
+            app.filter_push_char(c);
+        }
+        // Only the gamma session is visible, and it stays selected at index 0
+        assert_eq!(app.sessions.len(), 1);
+        assert_eq!(app.sessions[0].pane.pane_id, 3);
+        assert_eq!(app.list_state.selected(), Some(0));
+        // Quick select [1] and row lookup both map to the filtered session
+        assert_eq!(app.row_to_session_index(2), Some(0));
+        assert_eq!(app.row_to_session_index(3), None);
+
+        app.clear_filter();
+        assert_eq!(app.sessions.len(), 3);
+        assert_eq!(app.list_state.selected(), Some(2));
+        assert!(!app.filter_editing);
+    }
+
+    #[test]
+    fn test_newly_failed() {
+        let errored = SessionStatus::Errored {
+            message: "API Error: 500".to_string(),
+        };
+        assert!(newly_failed(Some(&SessionStatus::Processing), &errored));
+        assert!(!newly_failed(Some(&errored), &errored));
+        // Sessions seen for the first time don't toast
+        assert!(!newly_failed(None, &errored));
+        assert!(!newly_failed(
+            Some(&SessionStatus::Processing),
+            &SessionStatus::Idle
+        ));
+    }
+
+    #[test]
+    fn test_filter_no_match_clears_selection() {
+        let mut app = App::new();
+        app.all_sessions = vec![make_session(1, "work", "/home/user/alpha")];
+        app.rebuild_visible_sessions_with_selection(None);
+        app.filter_push_str("zzz");
+        assert!(app.sessions.is_empty());
+        assert_eq!(app.list_state.selected(), None);
+
+        app.filter_backspace();
+        app.filter_backspace();
+        app.filter_backspace();
+        assert_eq!(app.sessions.len(), 1);
+        assert_eq!(app.list_state.selected(), Some(0));
+    }
+
     // --- PlanApproval answer selection tests ---
 
     use crate::transcript::WaitingPrompt;
@@ -903,6 +1837,75 @@ mod tests {
         app.toast.expect("toast should be set").message
     }
 
+    #[test]
+    fn test_open_plan_view() {
+        let mut app = App::new();
+        app.sessions = vec![make_waiting_session(
+            42,
+            WaitingPrompt::PlanApproval {
+                plan: "# Plan\n\n1. Step".into(),
+            },
+        )];
+        app.list_state.select(Some(0));
+
+        app.open_plan_view();
+        let state = app.plan_view.as_ref().expect("plan view should be set");
+        assert_eq!(state.pane_id, 42);
+        assert_eq!(state.plan, "# Plan\n\n1. Step");
+
+        app.plan_scroll(-3);
+        assert_eq!(app.plan_view.as_ref().unwrap().scroll, 0);
+        app.plan_half_page(true);
+        assert_eq!(app.plan_view.as_ref().unwrap().scroll, 10);
+    }
+
+    #[test]
+    fn test_open_plan_view_without_plan_shows_error() {
+        let mut app = App::new();
+        app.sessions = vec![make_session(42, "default", "/home/user/project")];
+        app.list_state.select(Some(0));
+
+        app.open_plan_view();
+        assert!(app.plan_view.is_none());
+        assert!(app.toast.is_some());
+    }
+
+    #[test]
+    fn test_approve_plan_rechecks_prompt() {
+        let mut app = App::new();
+        let mut s = make_session(42, "default", "/home/user/project");
+        s.status = SessionStatus::WaitingForUser {
+            tools: vec!["Bash".to_string()],
+        };
+        s.waiting_prompt = Some(WaitingPrompt::ToolPermission {
+            tool_names: vec!["Bash".to_string()],
+        });
+        app.sessions = vec![s];
+        app.list_state.select(Some(0));
+        app.plan_view = Some(PlanViewState {
+            pane_id: 42,
+            title: "stale plan".into(),
+            plan: String::new(),
+            scroll: 0,
+            edit_requested: false,
+        });
+
+        app.approve_plan(0);
+        assert!(app.plan_view.is_none());
+        let toast = app.toast.as_ref().expect("toast should be set");
+        assert!(toast.message.contains("Prompt type changed"));
+    }
+
+    #[test]
+    fn test_plan_feedback() {
+        use plan::plan_feedback;
+        assert_eq!(plan_feedback("# Plan\n", "# Plan"), None);
+        assert_eq!(plan_feedback("# Plan", "  \n"), None);
+        let feedback = plan_feedback("# Plan\n1. A", "# Plan\n1. B\n").unwrap();
+        assert!(feedback.starts_with("Please revise the plan"));
+        assert!(feedback.ends_with("1. B"));
+    }
+
     #[test]
     fn test_ask_popup_on_tool_permission_session_shows_error() {
         let msg = assert_prompt_mismatch(
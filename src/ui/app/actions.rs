@@ -1,4 +1,7 @@
 use super::*;
+use crate::datasource::git::{create_worktree, get_git_repo_root};
+use crate::ui::history_search::HitJump;
+use std::path::Path;
 
 impl App {
     pub(super) fn enter_input_mode(&mut self) {
@@ -34,6 +37,16 @@ impl App {
         self.needs_full_redraw = true;
     }
 
+    /// Clear the input buffer (Ctrl+U); nothing to redraw if it is already empty.
+    pub(super) fn clear_input(&mut self) {
+        if !self.input_buffer.is_empty() {
+            self.input_buffer.clear();
+            self.slash_complete_active = false;
+            self.slash_filtered.clear();
+            self.dirty = true;
+        }
+    }
+
     /// Extract the slash command prefix from the current line at cursor position.
     /// Returns the text after `/` if the current line starts with `/` and has no spaces.
     pub(super) fn slash_prefix(&self) -> Option<&str> {
@@ -170,7 +183,7 @@ impl App {
             match WeztermCli::kill_pane(pane_id) {
                 Ok(()) => {
                     self.toast = Some(Toast::success(format!("Killed Pane {}", pane_id)));
-                    self.refresh()?;
+                    self.refresh();
                     self.update_watched_dirs()?;
                 }
                 Err(e) => {
@@ -205,7 +218,12 @@ impl App {
                     }
                 };
                 let window_id = session.pane.window_id;
-                self.add_pane_pending = Some((pane_id, cwd, window_id));
+                self.add_pane_pending = Some(AddPaneTarget {
+                    pane_id,
+                    cwd,
+                    window_id,
+                    worktree_branch: None,
+                });
                 self.dirty = true;
             }
         }
@@ -216,7 +234,13 @@ impl App {
     pub(super) fn select_command_or_spawn(&mut self, direction: SplitDirection) -> Result<()> {
         // Must .take() to clear the direction-selection state so the
         // add_pane_pending key handler no longer intercepts events.
-        if let Some((pane_id, cwd, window_id)) = self.add_pane_pending.take() {
+        if let Some(AddPaneTarget {
+            pane_id,
+            cwd,
+            window_id,
+            ..
+        }) = self.add_pane_pending.take()
+        {
             if self.resolved_commands.len() <= 1 {
                 // Single command (or default): spawn immediately
                 let cmd = &self.resolved_commands[0];
@@ -233,7 +257,7 @@ impl App {
                 match result {
                     Ok(new_pane_id) => {
                         self.toast = Some(Toast::success(format!("Added Pane {}", new_pane_id)));
-                        self.refresh()?;
+                        self.refresh();
                         self.update_watched_dirs()?;
                     }
                     Err(e) => {
@@ -276,7 +300,7 @@ impl App {
                 match result {
                     Ok(new_pane_id) => {
                         self.toast = Some(Toast::success(format!("Added Pane {}", new_pane_id)));
-                        self.refresh()?;
+                        self.refresh();
                         self.update_watched_dirs()?;
                     }
                     Err(e) => {
@@ -304,6 +328,68 @@ impl App {
         self.dirty = true;
     }
 
+    /// Switch from direction selection to the worktree branch prompt (`w`).
+    pub(super) fn start_worktree_prompt(&mut self) {
+        if let Some(target) = self.add_pane_pending.take() {
+            self.worktree_prompt = Some(WorktreePromptState {
+                pane_id: target.pane_id,
+                cwd: target.cwd,
+                window_id: target.window_id,
+                buffer: String::new(),
+            });
+            self.dirty = true;
+        }
+    }
+
+    /// Close the worktree branch prompt without creating anything (Esc).
+    pub(super) fn cancel_worktree_prompt(&mut self) {
+        self.worktree_prompt = None;
+        self.dirty = true;
+    }
+
+    /// Edit the worktree branch prompt buffer.
+    pub(super) fn worktree_prompt_buffer(&mut self, f: impl FnOnce(&mut String)) {
+        if let Some(prompt) = &mut self.worktree_prompt {
+            f(&mut prompt.buffer);
+            self.dirty = true;
+        }
+    }
+
+    /// Create the worktree for the entered branch (Enter), then go back to
+    /// direction selection with the worktree as the new pane's cwd.
+    pub(super) fn confirm_worktree_prompt(&mut self) {
+        let Some(prompt) = self.worktree_prompt.take() else {
+            return;
+        };
+        self.dirty = true;
+        let branch = prompt.buffer.trim();
+        if branch.is_empty() {
+            return;
+        }
+
+        let Some(repo_root) = get_git_repo_root(&prompt.cwd) else {
+            self.toast = Some(Toast::error(format!(
+                "Not a git repository: {}",
+                prompt.cwd
+            )));
+            return;
+        };
+        let dir = self.worktree_config.resolve_dir(Path::new(&repo_root));
+        match create_worktree(&prompt.cwd, &dir, branch) {
+            Ok(path) => {
+                self.add_pane_pending = Some(AddPaneTarget {
+                    pane_id: prompt.pane_id,
+                    cwd: path.to_string_lossy().to_string(),
+                    window_id: prompt.window_id,
+                    worktree_branch: Some(branch.to_string()),
+                });
+            }
+            Err(e) => {
+                self.toast = Some(Toast::error(format!("Failed to create worktree: {}", e)));
+            }
+        }
+    }
+
     /// Copy text to system clipboard.
     /// Uses pbcopy on macOS, xclip or xsel on Linux.
     pub(super) fn copy_to_clipboard(text: &str) -> Result<()> {
@@ -392,27 +478,14 @@ impl App {
         if let Some(i) = self.list_state.selected() {
             if let Some(session) = self.sessions.get(i) {
                 if let Some(path) = &session.transcript_path {
-                    match crate::transcript::extract_conversation_turns(path, 50) {
+                    let turns = self
+                        .transcript_tails
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .read_turns(path, 50);
+                    match turns {
                         Ok(turns) if !turns.is_empty() => {
-                            // Pre-parse timestamps once to avoid per-frame parsing
-                            self.history_timestamps = turns
-                                .iter()
-                                .map(|t| {
-                                    t.timestamp.as_ref().and_then(|ts| {
-                                        chrono::DateTime::parse_from_rfc3339(ts)
-                                            .ok()
-                                            .map(|dt| dt.into())
-                                    })
-                                })
-                                .collect();
-                            self.history_turns = turns;
-                            self.history_list_state.select(Some(0));
-                            self.history_index = 0;
-                            self.history_scroll_offset = 0;
-                            self.detail_mode = DetailMode::HistoryList;
-                            self.pending_g = false;
-                            self.dirty = true;
-                            self.needs_full_redraw = true;
+                            self.show_history(turns, None);
                         }
                         Ok(_) => {
                             self.toast = Some(Toast::error("No conversation history".to_string()));
@@ -431,6 +504,31 @@ impl App {
         }
     }
 
+    /// Switch the details panel to the history list for `turns` (newest
+    /// first). `source` labels history that isn't the selected session's.
+    pub(super) fn show_history(&mut self, turns: Vec<ConversationTurn>, source: Option<String>) {
+        // Pre-parse timestamps once to avoid per-frame parsing
+        self.history_timestamps = turns
+            .iter()
+            .map(|t| {
+                t.timestamp.as_ref().and_then(|ts| {
+                    chrono::DateTime::parse_from_rfc3339(ts)
+                        .ok()
+                        .map(|dt| dt.into())
+                })
+            })
+            .collect();
+        self.history_turns = turns;
+        self.history_source = source;
+        self.history_list_state.select(Some(0));
+        self.history_index = 0;
+        self.history_scroll_offset = 0;
+        self.detail_mode = DetailMode::HistoryList;
+        self.pending_key = None;
+        self.dirty = true;
+        self.needs_full_redraw = true;
+    }
+
     /// Exit history mode entirely (back to normal)
     pub(super) fn exit_history_mode(&mut self) {
         self.detail_mode = DetailMode::Summary;
@@ -440,7 +538,9 @@ impl App {
         self.history_index = 0;
         self.history_scroll_offset = 0;
         self.history_timestamps.clear();
-        self.pending_g = false;
+        self.history_search = HistorySearch::default();
+        self.history_source = None;
+        self.pending_key = None;
         self.dirty = true;
         self.needs_full_redraw = true;
     }
@@ -451,8 +551,12 @@ impl App {
             if i < self.history_turns.len() {
                 self.history_index = i;
                 self.history_scroll_offset = 0;
+                self.history_search.reset_position();
+                if self.history_search.is_active() {
+                    self.history_search.pending = Some(HitJump::First);
+                }
                 self.detail_mode = DetailMode::HistoryDetail;
-                self.pending_g = false;
+                self.pending_key = None;
                 self.dirty = true;
                 self.needs_full_redraw = true;
             }
@@ -464,7 +568,8 @@ impl App {
         self.detail_mode = DetailMode::HistoryList;
         self.history_list_state.select(Some(self.history_index));
         self.history_scroll_offset = 0;
-        self.pending_g = false;
+        self.history_search.reset_position();
+        self.pending_key = None;
         self.dirty = true;
         self.needs_full_redraw = true;
     }
@@ -488,7 +593,7 @@ impl App {
                 // Force immediate first fetch
                 self.last_live_pane_fetch =
                     Instant::now() - Duration::from_secs(LIVE_PANE_COOLDOWN_SECS + 1);
-                self.pending_g = false;
+                self.pending_key = None;
                 self.dirty = true;
                 self.needs_full_redraw = true;
             }
@@ -509,7 +614,7 @@ impl App {
         self.live_pane_follow_tail = true;
         self.cached_live_pane_lines = None;
         self.live_pane_poll_failures = 0;
-        self.pending_g = false;
+        self.pending_key = None;
         self.dirty = true;
         self.needs_full_redraw = true;
     }
@@ -638,37 +743,7 @@ impl App {
 
         match waiting {
             WaitingPrompt::PlanApproval { .. } => {
-                self.answer_select_pending = Some(AnswerSelectState {
-                    pane_id,
-                    title: "Plan Approval (ExitPlanMode)".into(),
-                    prompt_kind: AnswerPromptKind::PlanApproval,
-                    options: vec![
-                        AnswerOption {
-                            label: "Yes, clear & bypass".into(),
-                            description: Some("Clear context and bypass permissions".into()),
-                            keystroke: "1".into(),
-                            enter_input_after: false,
-                        },
-                        AnswerOption {
-                            label: "Yes, bypass".into(),
-                            description: Some("Bypass permissions".into()),
-                            keystroke: "2".into(),
-                            enter_input_after: false,
-                        },
-                        AnswerOption {
-                            label: "Yes, approve edits".into(),
-                            description: Some("Manually approve edits".into()),
-                            keystroke: "3".into(),
-                            enter_input_after: false,
-                        },
-                        AnswerOption {
-                            label: "Tell Claude".into(),
-                            description: Some("Type feedback for Claude".into()),
-                            keystroke: "4".into(),
-                            enter_input_after: true,
-                        },
-                    ],
-                });
+                self.answer_select_pending = Some(plan_approval_select(pane_id));
                 self.answer_select_state.select(Some(0));
                 self.dirty = true;
             }
@@ -785,7 +860,10 @@ impl App {
                     match WeztermCli::send_keystroke(state.pane_id, &option.keystroke) {
                         Ok(()) => {
                             if option.enter_input_after {
-                                // Enter i-mode so user can type follow-up text
+                                // Enter i-mode so user can type follow-up text, for
+                                // the answered session rather than whichever is
+                                // selected by now
+                                self.select_pane(state.pane_id);
                                 self.enter_input_mode();
                             } else {
                                 self.toast = Some(Toast::success(format!(
@@ -815,10 +893,9 @@ impl App {
 
     // --- Embedded Terminal Actions ---
 
-    /// Enter embedded terminal mode: spawn claude in a PTY.
+    /// Open the selected session in a terminal tab: spawn claude in a PTY,
+    /// or switch to the tab already running the session.
     pub(super) fn enter_terminal_mode(&mut self) {
-        use crate::pty::PtyHandle;
-        use crate::ui::terminal_session::TerminalSession;
         use std::path::PathBuf;
 
         let selected_session = self
@@ -840,16 +917,42 @@ impl App {
         // Get session_id for --resume (if available)
         let session_id = selected_session.and_then(|s| s.session_id.clone());
 
+        self.spawn_terminal(cwd, title, session_id);
+    }
+
+    /// Start `claude` (with `--resume <session_id>` if given) in a new
+    /// embedded terminal tab. A session that is already open in a tab (or,
+    /// without a session id, a fresh tab in the same cwd) is switched to
+    /// instead of being started twice.
+    pub(super) fn spawn_terminal(
+        &mut self,
+        cwd: std::path::PathBuf,
+        title: String,
+        session_id: Option<String>,
+    ) {
+        use crate::pty::PtyHandle;
+        use crate::ui::terminal_session::TerminalSession;
+
         // Clear all transient UI state (state invariant)
         self.input_mode = false;
         self.input_buffer.clear();
         self.kill_confirm = None;
         self.add_pane_pending = None;
+        self.worktree_prompt = None;
         self.command_select_pending = None;
         self.answer_select_pending = None;
         self.slash_complete_active = false;
         self.slash_filtered.clear();
 
+        if let Some(index) = self
+            .terminal_tabs
+            .iter()
+            .position(|ts| is_same_terminal(ts, &cwd, session_id.as_deref()))
+        {
+            self.show_terminal_tab(index);
+            return;
+        }
+
         // Default size (will be updated on first render via viewport resize detection)
         let (cols, rows) = crossterm::terminal::size().unwrap_or((80, 24));
         // Approximate: use 60% width for terminal, subtract borders
@@ -866,17 +969,15 @@ impl App {
         match PtyHandle::spawn("claude", &args, &cwd, term_cols, term_rows) {
             Ok(pty_handle) => {
                 let parser = vt100::Parser::new(term_rows, term_cols, 1000);
-                self.terminal_session = Some(TerminalSession {
+                self.terminal_tabs.push(TerminalSession {
                     cwd,
                     title,
+                    session_id,
                     pty_handle,
                     vt100_parser: parser,
                     viewport_rect: None,
                 });
-                self.detail_mode = DetailMode::Terminal;
-                self.focus_pane = FocusPane::Terminal;
-                self.dirty = true;
-                self.needs_full_redraw = true;
+                self.show_terminal_tab(self.terminal_tabs.len() - 1);
             }
             Err(e) => {
                 self.toast = Some(Toast::error(format!("Failed to spawn terminal: {}", e)));
@@ -885,9 +986,33 @@ impl App {
         }
     }
 
-    /// Exit embedded terminal mode: kill PTY and return to Summary.
+    /// Bring back the hidden terminal tabs, showing the last active one.
+    pub(super) fn show_terminal(&mut self) {
+        if self.terminal_tabs.is_empty() {
+            self.toast = Some(Toast::error("No terminal tabs open".to_string()));
+            self.dirty = true;
+            return;
+        }
+        self.show_terminal_tab(self.active_terminal_tab.min(self.terminal_tabs.len() - 1));
+    }
+
+    /// Show terminal tab `index` with keyboard focus.
+    fn show_terminal_tab(&mut self, index: usize) {
+        self.active_terminal_tab = index;
+        self.detail_mode = DetailMode::Terminal;
+        self.focus_pane = FocusPane::Terminal;
+        self.dirty = true;
+        self.needs_full_redraw = true;
+    }
+
+    /// The shown terminal tab.
+    fn active_terminal(&mut self) -> Option<&mut TerminalSession> {
+        self.terminal_tabs.get_mut(self.active_terminal_tab)
+    }
+
+    /// Leave embedded terminal mode and return to Summary. The tabs keep
+    /// running in the background.
     pub(super) fn exit_terminal_mode(&mut self) {
-        self.terminal_session = None; // Drop sends shutdown signal
         self.detail_mode = DetailMode::Summary;
         self.focus_pane = FocusPane::Sidebar;
         self.summary_scroll_offset = 0;
@@ -895,9 +1020,43 @@ impl App {
         self.needs_full_redraw = true;
     }
 
+    /// Close the shown terminal tab (killing its PTY); leaves terminal mode
+    /// after the last one.
+    pub(super) fn close_terminal_tab(&mut self) {
+        if self.active_terminal_tab < self.terminal_tabs.len() {
+            self.remove_terminal_tab(self.active_terminal_tab);
+        }
+    }
+
+    /// Remove tab `index` (Drop sends the shutdown signal), keeping the
+    /// shown tab when another one is removed.
+    fn remove_terminal_tab(&mut self, index: usize) {
+        self.terminal_tabs.remove(index);
+        self.active_terminal_tab =
+            tab_index_after_close(self.active_terminal_tab, index, self.terminal_tabs.len());
+        if self.terminal_tabs.is_empty() && self.detail_mode == DetailMode::Terminal {
+            self.exit_terminal_mode();
+        }
+        self.dirty = true;
+        self.needs_full_redraw = true;
+    }
+
+    /// Switch to the next (`delta` > 0) or previous terminal tab, wrapping
+    /// around.
+    pub(super) fn switch_terminal_tab(&mut self, delta: isize) {
+        let len = self.terminal_tabs.len();
+        if len < 2 {
+            return;
+        }
+        self.active_terminal_tab =
+            (self.active_terminal_tab as isize + delta).rem_euclid(len as isize) as usize;
+        self.dirty = true;
+        self.needs_full_redraw = true;
+    }
+
     /// Toggle focus between sidebar and embedded terminal.
     pub(super) fn toggle_terminal_focus(&mut self) {
-        if self.terminal_session.is_some() {
+        if !self.terminal_tabs.is_empty() {
             self.focus_pane = match self.focus_pane {
                 FocusPane::Sidebar => FocusPane::Terminal,
                 FocusPane::Terminal => FocusPane::Sidebar,
@@ -910,7 +1069,7 @@ impl App {
     pub(super) fn send_key_to_pty(&mut self, key: &crossterm::event::KeyEvent) {
         let bytes = key_event_to_bytes(key);
         if !bytes.is_empty() {
-            if let Some(ref mut ts) = self.terminal_session {
+            if let Some(ts) = self.active_terminal() {
                 let _ = ts.pty_handle.write(&bytes);
             }
         }
@@ -918,7 +1077,7 @@ impl App {
 
     /// Send pasted text to PTY wrapped in bracketed paste markers.
     pub(super) fn send_paste_to_pty(&mut self, text: &str) {
-        if let Some(ref mut ts) = self.terminal_session {
+        if let Some(ts) = self.active_terminal() {
             let mut buf = Vec::new();
             buf.extend_from_slice(b"\x1b[200~"); // begin bracketed paste
             buf.extend_from_slice(text.as_bytes());
@@ -927,50 +1086,49 @@ impl App {
         }
     }
 
-    /// Poll PTY output and feed to vt100 parser. Returns true if any output received.
+    /// Poll the PTY output of every tab and feed it to the tab's vt100
+    /// parser. Returns true if any output received.
     pub(super) fn poll_pty_output(&mut self) -> bool {
         use crate::pty::PtyEvent;
 
-        let Some(ref mut ts) = self.terminal_session else {
-            return false;
-        };
-
-        let events = ts.pty_handle.try_recv();
-        if events.is_empty() {
-            return false;
-        }
-
         let mut got_output = false;
-        let mut exited = false;
-
-        for event in events {
-            match event {
-                PtyEvent::Output(bytes) => {
-                    ts.vt100_parser.process(&bytes);
-                    got_output = true;
-                }
-                PtyEvent::Exited => {
-                    exited = true;
+        let mut exited = Vec::new();
+
+        for (index, ts) in self.terminal_tabs.iter_mut().enumerate() {
+            for event in ts.pty_handle.try_recv() {
+                match event {
+                    PtyEvent::Output(bytes) => {
+                        ts.vt100_parser.process(&bytes);
+                        got_output = true;
+                        // Background tabs are redrawn when switched to
+                        if index == self.active_terminal_tab
+                            && self.detail_mode == DetailMode::Terminal
+                        {
+                            self.dirty = true;
+                        }
+                    }
+                    PtyEvent::Exited => {
+                        exited.push(index);
+                    }
                 }
             }
         }
 
-        if exited {
-            self.toast = Some(Toast::success("Terminal process exited".into()));
-            self.exit_terminal_mode();
-            return true;
-        }
-
-        if got_output {
-            self.dirty = true;
+        // Remove back to front so the remaining indices stay valid
+        for &index in exited.iter().rev() {
+            self.toast = Some(Toast::success(format!(
+                "Terminal exited: {}",
+                self.terminal_tabs[index].title
+            )));
+            self.remove_terminal_tab(index);
         }
 
-        got_output
+        got_output || !exited.is_empty()
     }
 
-    /// Check and handle viewport resize for embedded terminal.
+    /// Check and handle viewport resize for the shown terminal tab.
     pub(super) fn check_terminal_resize(&mut self, current_area: Rect) {
-        let Some(ref mut ts) = self.terminal_session else {
+        let Some(ts) = self.active_terminal() else {
             return;
         };
 
@@ -998,6 +1156,23 @@ impl App {
     }
 }
 
+/// Whether tab `ts` already runs the terminal that would be started for
+/// `cwd` / `session_id`: the same session, or a fresh session in the same
+/// cwd.
+fn is_same_terminal(ts: &TerminalSession, cwd: &std::path::Path, session_id: Option<&str>) -> bool {
+    match session_id {
+        Some(id) => ts.session_id.as_deref() == Some(id),
+        None => ts.session_id.is_none() && ts.cwd == cwd,
+    }
+}
+
+/// Index of the shown tab after tab `closed` was removed, leaving `len` tabs:
+/// later tabs shift left, and closing the last tab shows the one before it.
+pub(super) fn tab_index_after_close(active: usize, closed: usize, len: usize) -> usize {
+    let active = if closed < active { active - 1 } else { active };
+    active.min(len.saturating_sub(1))
+}
+
 /// Convert a crossterm KeyEvent into terminal byte sequences.
 fn key_event_to_bytes(key: &crossterm::event::KeyEvent) -> Vec<u8> {
     use crossterm::event::{KeyCode, KeyModifiers};
@@ -1057,3 +1232,39 @@ fn key_event_to_bytes(key: &crossterm::event::KeyEvent) -> Vec<u8> {
         _ => vec![],
     }
 }
+
+/// Answer selection for an ExitPlanMode plan approval, with Claude Code's
+/// four options.
+pub(super) fn plan_approval_select(pane_id: u32) -> AnswerSelectState {
+    AnswerSelectState {
+        pane_id,
+        title: "Plan Approval (ExitPlanMode)".into(),
+        prompt_kind: AnswerPromptKind::PlanApproval,
+        options: vec![
+            AnswerOption {
+                label: "Yes, clear & bypass".into(),
+                description: Some("Clear context and bypass permissions".into()),
+                keystroke: "1".into(),
+                enter_input_after: false,
+            },
+            AnswerOption {
+                label: "Yes, bypass".into(),
+                description: Some("Bypass permissions".into()),
+                keystroke: "2".into(),
+                enter_input_after: false,
+            },
+            AnswerOption {
+                label: "Yes, approve edits".into(),
+                description: Some("Manually approve edits".into()),
+                keystroke: "3".into(),
+                enter_input_after: false,
+            },
+            AnswerOption {
+                label: "Tell Claude".into(),
+                description: Some("Type feedback for Claude".into()),
+                keystroke: "4".into(),
+                enter_input_after: true,
+            },
+        ],
+    }
+}
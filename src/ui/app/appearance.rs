@@ -0,0 +1,24 @@
+use super::*;
+
+impl App {
+    /// Switch to the next built-in color theme and persist it to config.toml.
+    /// `[theme.colors]` and `syntax_theme` overrides are applied to every theme.
+    pub(super) fn cycle_theme(&mut self) {
+        let name = self.theme.name.next();
+        self.theme_config.name = name;
+        self.theme =
+            Theme::from_config(&self.theme_config).unwrap_or_else(|_| Theme::builtin(name));
+
+        // Rendered markdown carries the old theme's colors
+        self.cached_preview_lines = None;
+        self.cached_history_lines = None;
+        self.needs_full_redraw = true;
+        self.dirty = true;
+
+        let message = format!("Theme: {}", name.as_str());
+        self.toast = Some(match Config::save_theme_name(name) {
+            Ok(()) => Toast::success(message),
+            Err(e) => Toast::error(format!("{} (not saved: {})", message, e)),
+        });
+    }
+}
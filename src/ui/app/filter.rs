@@ -0,0 +1,134 @@
+use super::*;
+
+impl App {
+    /// Enter filter editing mode (`/`). Keeps any existing query so it can be refined.
+    pub(super) fn enter_filter_mode(&mut self) {
+        self.filter_editing = true;
+        self.dirty = true;
+    }
+
+    /// Stop editing the filter but keep it applied (Enter).
+    pub(super) fn accept_filter(&mut self) {
+        self.filter_editing = false;
+        if self.filter_query.trim().is_empty() {
+            self.filter_query.clear();
+        }
+        self.dirty = true;
+    }
+
+    /// Clear the filter and show all sessions again (Esc).
+    pub(super) fn clear_filter(&mut self) {
+        self.filter_editing = false;
+        if !self.filter_query.is_empty() {
+            self.filter_query.clear();
+            self.rebuild_visible_sessions();
+        }
+        self.dirty = true;
+    }
+
+    /// Append a character to the filter query and narrow the list.
+    pub(super) fn filter_push_char(&mut self, c: char) {
+        self.filter_query.push(c);
+        self.rebuild_visible_sessions();
+    }
+
+    /// Append pasted text to the filter query and narrow the list.
+    pub(super) fn filter_push_str(&mut self, text: &str) {
+        self.filter_query.push_str(text);
+        self.rebuild_visible_sessions();
+    }
+
+    /// Remove the last character of the filter query and widen the list.
+    pub(super) fn filter_backspace(&mut self) {
+        if self.filter_query.pop().is_some() {
+            self.rebuild_visible_sessions();
+        }
+    }
+
+    /// Clear the filter query without leaving filter editing mode (Ctrl+U).
+    pub(super) fn filter_clear_query(&mut self) {
+        if !self.filter_query.is_empty() {
+            self.filter_query.clear();
+            self.rebuild_visible_sessions();
+        }
+    }
+
+    /// Whether a non-empty filter is currently narrowing the session list.
+    pub(super) fn is_filter_applied(&self) -> bool {
+        !self.filter_query.trim().is_empty()
+    }
+
+    /// Recompute the visible session list from `all_sessions`, keeping the
+    /// currently selected pane selected if it is still visible.
+    pub(super) fn rebuild_visible_sessions(&mut self) {
+        let selected_pane_id = self
+            .list_state
+            .selected()
+            .and_then(|i| self.sessions.get(i))
+            .map(|s| s.pane.pane_id);
+        self.rebuild_visible_sessions_with_selection(selected_pane_id);
+    }
+
+    /// Recompute the visible session list and select `selected_pane_id`
+    /// (falls back to the first visible session).
+    pub(super) fn rebuild_visible_sessions_with_selection(
+        &mut self,
+        selected_pane_id: Option<u32>,
+    ) {
+        self.sessions = filter_sessions(&self.all_sessions, &self.filter_query);
+
+        if !self.sessions.is_empty() {
+            let new_index = selected_pane_id
+                .and_then(|id| self.sessions.iter().position(|s| s.pane.pane_id == id))
+                .unwrap_or(0);
+            self.list_state.select(Some(new_index));
+        } else {
+            self.list_state.select(None);
+        }
+
+        self.dirty = true;
+    }
+}
+
+/// Return the sessions matching `query`, preserving their order.
+/// An empty query returns all sessions.
+pub(super) fn filter_sessions(sessions: &[ClaudeSession], query: &str) -> Vec<ClaudeSession> {
+    if query.trim().is_empty() {
+        return sessions.to_vec();
+    }
+    sessions
+        .iter()
+        .filter(|s| session_matches_filter(s, query))
+        .cloned()
+        .collect()
+}
+
+/// Check whether a session matches the filter query.
+/// Each whitespace-separated term must fuzzy-match one of: workspace, cwd,
+/// git branch, git worktree, pane title, last prompt, status, label, or tags.
+pub(super) fn session_matches_filter(session: &ClaudeSession, query: &str) -> bool {
+    let mut fields: Vec<&str> = vec![
+        &session.pane.workspace,
+        &session.pane.title,
+        session.status.as_str(),
+    ];
+    let cwd = session.pane.cwd_path();
+    if let Some(cwd) = cwd.as_deref() {
+        fields.push(cwd);
+    }
+    if let Some(branch) = session.git_branch.as_deref() {
+        fields.push(branch);
+    }
+    if let Some(worktree) = session.git_worktree.as_deref() {
+        fields.push(worktree);
+    }
+    if let Some(prompt) = session.last_prompt.as_deref() {
+        fields.push(prompt);
+    }
+    if let Some(label) = session.meta.label.as_deref() {
+        fields.push(label);
+    }
+    fields.extend(session.meta.tags.iter().map(String::as_str));
+
+    matches_all_terms(query, &fields)
+}
@@ -0,0 +1,174 @@
+use super::*;
+
+impl App {
+    /// Attach stored metadata to every session (by session_id).
+    /// Returns true if any session's pinned state changed (order must be refreshed).
+    pub(super) fn apply_session_meta(&mut self) -> bool {
+        let mut pin_changed = false;
+        for session in &mut self.all_sessions {
+            let meta = session
+                .session_id
+                .as_deref()
+                .and_then(|id| self.session_meta.get(id))
+                .cloned()
+                .unwrap_or_default();
+            pin_changed |= meta.pinned != session.meta.pinned;
+            session.meta = meta;
+        }
+        pin_changed
+    }
+
+    /// Session ID and pane ID of the selected session.
+    /// Shows an error toast if the session has no ID (no statusLine bridge mapping).
+    fn selected_session_key(&mut self) -> Option<(String, u32)> {
+        let session = self
+            .list_state
+            .selected()
+            .and_then(|i| self.sessions.get(i))?;
+        match &session.session_id {
+            Some(id) => Some((id.clone(), session.pane.pane_id)),
+            None => {
+                self.toast = Some(Toast::error(
+                    "No session ID (run `wzcc install-bridge` to label sessions)".to_string(),
+                ));
+                self.dirty = true;
+                None
+            }
+        }
+    }
+
+    /// Toggle the pinned state of the selected session (`p`).
+    pub(super) fn toggle_pin_selected(&mut self) {
+        if let Some((session_id, pane_id)) = self.selected_session_key() {
+            self.update_meta(&session_id, pane_id, |m| m.pinned = !m.pinned);
+        }
+    }
+
+    /// Cycle the label color of the selected session (`C`).
+    pub(super) fn cycle_color_selected(&mut self) {
+        if let Some((session_id, pane_id)) = self.selected_session_key() {
+            self.update_meta(&session_id, pane_id, SessionMeta::cycle_color);
+        }
+    }
+
+    /// Open the label (`n`) or tags (`#`) prompt for the selected session,
+    /// pre-filled with the current value.
+    pub(super) fn start_meta_edit(&mut self, field: MetaField) {
+        let Some((session_id, pane_id)) = self.selected_session_key() else {
+            return;
+        };
+        let current = self.session_meta.get(&session_id);
+        let buffer = match field {
+            MetaField::Label => current.and_then(|m| m.label.clone()).unwrap_or_default(),
+            MetaField::Tags => current.map(|m| m.tags_input()).unwrap_or_default(),
+        };
+        self.meta_edit = Some(MetaEditState {
+            session_id,
+            pane_id,
+            field,
+            buffer,
+        });
+        // The prompt is shown in the footer, where a toast would hide it
+        self.toast = None;
+        self.dirty = true;
+    }
+
+    /// Close the label/tags prompt without saving (Esc).
+    pub(super) fn cancel_meta_edit(&mut self) {
+        self.meta_edit = None;
+        self.dirty = true;
+    }
+
+    /// Save the label/tags prompt (Enter).
+    pub(super) fn confirm_meta_edit(&mut self) {
+        let Some(edit) = self.meta_edit.take() else {
+            return;
+        };
+        match edit.field {
+            MetaField::Label => self.update_meta(&edit.session_id, edit.pane_id, |m| {
+                m.set_label(&edit.buffer)
+            }),
+            MetaField::Tags => self.update_meta(&edit.session_id, edit.pane_id, |m| {
+                m.tags = parse_tags(&edit.buffer)
+            }),
+        }
+    }
+
+    /// Edit the label/tags prompt buffer.
+    pub(super) fn meta_edit_buffer(&mut self, f: impl FnOnce(&mut String)) {
+        if let Some(edit) = &mut self.meta_edit {
+            f(&mut edit.buffer);
+            self.dirty = true;
+        }
+    }
+
+    /// Modify and persist a session's metadata, then refresh the list order
+    /// (pins) and, if enabled, the WezTerm tab title.
+    fn update_meta<F>(&mut self, session_id: &str, pane_id: u32, f: F)
+    where
+        F: FnOnce(&mut SessionMeta),
+    {
+        let previous_label = self
+            .session_meta
+            .get(session_id)
+            .and_then(|m| m.label.clone());
+
+        let result = self.session_meta.update(session_id, f);
+
+        // The in-memory store is updated even if saving failed
+        self.apply_session_meta();
+        sort_sessions(
+            &mut self.all_sessions,
+            &self.current_workspace,
+            self.list_config,
+        );
+        self.rebuild_visible_sessions();
+        self.needs_full_redraw = true;
+
+        let meta = match result {
+            Ok(meta) => meta,
+            Err(e) => {
+                self.toast = Some(Toast::error(format!("Not saved: {}", e)));
+                return;
+            }
+        };
+
+        if self.sync_tab_title && meta.label != previous_label {
+            // An empty title resets the tab to WezTerm's default
+            let title = meta.label.as_deref().unwrap_or("");
+            if let Err(e) = WeztermCli::set_tab_title(pane_id, title) {
+                self.toast = Some(Toast::error(format!("Failed to set tab title: {}", e)));
+                return;
+            }
+        }
+
+        self.toast = Some(Toast::success(meta_summary(&meta)));
+    }
+}
+
+/// Toast message describing a session's metadata after an edit.
+fn meta_summary(meta: &SessionMeta) -> String {
+    if meta.is_empty() {
+        return "Session metadata cleared".to_string();
+    }
+    let mut parts = Vec::new();
+    if meta.pinned {
+        parts.push("📌 pinned".to_string());
+    }
+    if let Some(label) = &meta.label {
+        parts.push(format!("\"{}\"", label));
+    }
+    if let Some(color) = &meta.color {
+        parts.push(color.clone());
+    }
+    if !meta.tags.is_empty() {
+        parts.push(
+            meta.tags
+                .iter()
+                .map(|t| format!("#{}", t))
+                .collect::<Vec<_>>()
+                .join(" "),
+        );
+    }
+    format!("Session: {}", parts.join(" "))
+}
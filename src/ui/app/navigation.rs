@@ -66,6 +66,19 @@ impl App {
         }
     }
 
+    /// Select the session in `pane_id`, if it is listed.
+    pub(super) fn select_pane(&mut self, pane_id: u32) {
+        let Some(i) = self.sessions.iter().position(|s| s.pane.pane_id == pane_id) else {
+            return;
+        };
+        if self.list_state.selected() != Some(i) {
+            self.list_state.select(Some(i));
+            self.summary_scroll_offset = 0;
+            self.exit_live_pane_view();
+            self.dirty = true;
+        }
+    }
+
     /// Jump to selected session
     pub fn jump_to_selected(&mut self) -> Result<()> {
         if let Some(i) = self.list_state.selected() {
@@ -86,7 +99,7 @@ impl App {
                 if switching_workspace {
                     // Small delay to allow WezTerm to complete workspace switch
                     std::thread::sleep(std::time::Duration::from_millis(100));
-                    self.refresh()?;
+                    self.refresh();
                 }
             }
         }
@@ -94,9 +107,40 @@ impl App {
         Ok(())
     }
 
+    /// Expand details panel (move divider left)
+    pub(super) fn expand_details(&mut self) {
+        if self.details_width_percent < 80 {
+            self.details_width_percent += 5;
+            self.dirty = true;
+            self.needs_full_redraw = true;
+        }
+    }
+
+    /// Shrink details panel (move divider right)
+    pub(super) fn shrink_details(&mut self) {
+        if self.details_width_percent > 20 {
+            self.details_width_percent -= 5;
+            self.dirty = true;
+            self.needs_full_redraw = true;
+        }
+    }
+
+    /// Resolve a key press to an action in `mode`, tracking two-key sequences
+    /// (any key other than the sequence's second key cancels it).
+    pub(super) fn key_action(&mut self, mode: KeyMode, key: &KeyEvent) -> Option<Action> {
+        match self.keymap.lookup(mode, self.pending_key.take(), key) {
+            KeyLookup::Action(action) => Some(action),
+            KeyLookup::Pending(first) => {
+                self.pending_key = Some(first);
+                None
+            }
+            KeyLookup::Unbound => None,
+        }
+    }
+
     /// Calculate session index from list display row
     /// Returns the session corresponding to the clicked row, considering group headers
     pub(super) fn row_to_session_index(&self, row: usize) -> Option<usize> {
-        row_to_session_index(&self.sessions, row)
+        row_to_session_index(&self.sessions, self.list_config.group, row)
     }
 }
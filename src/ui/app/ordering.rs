@@ -0,0 +1,31 @@
+use super::*;
+
+impl App {
+    /// Cycle the session list sort order (`s`) and persist it to config.toml.
+    pub(super) fn cycle_sort_order(&mut self) {
+        self.list_config.sort = self.list_config.sort.next();
+        self.apply_list_config(format!("Sort: {}", self.list_config.sort.as_str()));
+    }
+
+    /// Cycle the session list grouping (`S`) and persist it to config.toml.
+    pub(super) fn cycle_group_by(&mut self) {
+        self.list_config.group = self.list_config.group.next();
+        self.apply_list_config(format!("Group: {}", self.list_config.group.as_str()));
+    }
+
+    /// Re-sort the session list with the current settings and save them.
+    fn apply_list_config(&mut self, message: String) {
+        sort_sessions(
+            &mut self.all_sessions,
+            &self.current_workspace,
+            self.list_config,
+        );
+        self.rebuild_visible_sessions();
+        self.needs_full_redraw = true;
+
+        self.toast = Some(match Config::save_list_config(&self.list_config) {
+            Ok(()) => Toast::success(message),
+            Err(e) => Toast::error(format!("{} (not saved: {})", message, e)),
+        });
+    }
+}
@@ -0,0 +1,81 @@
+use super::*;
+use crate::ui::keymap::palette_matches;
+
+impl App {
+    /// Open the key binding help overlay for `mode` (`?`).
+    pub(super) fn open_help(&mut self, mode: KeyMode) {
+        self.help = Some(HelpState { mode, scroll: 0 });
+        self.dirty = true;
+    }
+
+    /// Close the help overlay.
+    pub(super) fn close_help(&mut self) {
+        self.help = None;
+        self.dirty = true;
+    }
+
+    /// Scroll the help overlay by `delta` lines (clamped when rendered).
+    pub(super) fn scroll_help(&mut self, delta: isize) {
+        if let Some(help) = &mut self.help {
+            help.scroll = help.scroll.saturating_add_signed(delta);
+            self.dirty = true;
+        }
+    }
+
+    /// Open the command palette with an empty query (Ctrl-P).
+    pub(super) fn open_palette(&mut self) {
+        let mut palette = PaletteState {
+            query: String::new(),
+            matches: Vec::new(),
+            list_state: ListState::default(),
+        };
+        update_palette_matches(&mut palette);
+        self.palette = Some(palette);
+        self.dirty = true;
+    }
+
+    /// Close the command palette without running anything (Esc).
+    pub(super) fn close_palette(&mut self) {
+        self.palette = None;
+        self.dirty = true;
+    }
+
+    /// Edit the palette query and re-run the search.
+    pub(super) fn palette_query(&mut self, f: impl FnOnce(&mut String)) {
+        if let Some(palette) = &mut self.palette {
+            f(&mut palette.query);
+            update_palette_matches(palette);
+            self.dirty = true;
+        }
+    }
+
+    /// Move the palette selection down (`delta` > 0) or up, without wrapping.
+    pub(super) fn palette_select(&mut self, delta: isize) {
+        if let Some(palette) = &mut self.palette {
+            if let Some(i) = palette.list_state.selected() {
+                let last = palette.matches.len().saturating_sub(1);
+                let next = i.saturating_add_signed(delta).min(last);
+                palette.list_state.select(Some(next));
+                self.dirty = true;
+            }
+        }
+    }
+
+    /// Close the palette and return the selected action (Enter).
+    pub(super) fn take_palette_action(&mut self) -> Option<Action> {
+        let palette = self.palette.take()?;
+        self.dirty = true;
+        palette
+            .list_state
+            .selected()
+            .and_then(|i| palette.matches.get(i).copied())
+    }
+}
+
+/// Re-run the palette search and select the best match.
+fn update_palette_matches(palette: &mut PaletteState) {
+    palette.matches = palette_matches(&palette.query);
+    palette
+        .list_state
+        .select((!palette.matches.is_empty()).then_some(0));
+}
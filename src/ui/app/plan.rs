@@ -0,0 +1,205 @@
+use super::actions::plan_approval_select;
+use super::*;
+use crate::transcript::WaitingPrompt;
+use std::io::Write;
+
+/// Lines scrolled by half-page keys in the plan view.
+const PLAN_HALF_PAGE: usize = 10;
+
+impl App {
+    /// Open the plan of the selected session's ExitPlanMode approval (`P`).
+    pub(super) fn open_plan_view(&mut self) {
+        let Some(session) = self
+            .list_state
+            .selected()
+            .and_then(|i| self.sessions.get(i))
+        else {
+            return;
+        };
+        let Some(WaitingPrompt::PlanApproval { plan }) = &session.waiting_prompt else {
+            self.toast = Some(Toast::error("Session has no plan awaiting approval".into()));
+            self.dirty = true;
+            return;
+        };
+        let title = session
+            .meta
+            .label
+            .clone()
+            .unwrap_or_else(|| session.pane.title.clone());
+        self.plan_view = Some(PlanViewState {
+            pane_id: session.pane.pane_id,
+            title,
+            plan: plan.clone(),
+            scroll: 0,
+            edit_requested: false,
+        });
+        self.dirty = true;
+    }
+
+    /// Close the plan view (Esc).
+    pub(super) fn close_plan_view(&mut self) {
+        self.plan_view = None;
+        self.dirty = true;
+        self.needs_full_redraw = true;
+    }
+
+    /// Scroll the plan down (`delta` > 0) or up. The renderer clamps the
+    /// offset to the rendered length.
+    pub(super) fn plan_scroll(&mut self, delta: isize) {
+        if let Some(state) = &mut self.plan_view {
+            state.scroll = state.scroll.saturating_add_signed(delta);
+            self.dirty = true;
+        }
+    }
+
+    /// Scroll half a page down (`down`) or up.
+    pub(super) fn plan_half_page(&mut self, down: bool) {
+        let delta = PLAN_HALF_PAGE as isize;
+        self.plan_scroll(if down { delta } else { -delta });
+    }
+
+    /// Jump to the top or (clamped when rendered) the bottom of the plan.
+    pub(super) fn plan_scroll_to(&mut self, bottom: bool) {
+        if let Some(state) = &mut self.plan_view {
+            state.scroll = if bottom { usize::MAX } else { 0 };
+            self.dirty = true;
+        }
+    }
+
+    /// Answer the approval with option `index` (0-based), re-checking that
+    /// the session still waits for this plan like the answer popup does.
+    pub(super) fn approve_plan(&mut self, index: usize) {
+        let Some(state) = self.plan_view.take() else {
+            return;
+        };
+        self.answer_select_pending = Some(plan_approval_select(state.pane_id));
+        self.confirm_answer_select(index);
+    }
+
+    /// Open the plan in the editor once the run loop can hand over the
+    /// terminal (`e`).
+    pub(super) fn request_plan_edit(&mut self) {
+        if let Some(state) = &mut self.plan_view {
+            state.edit_requested = true;
+        }
+    }
+
+    /// Whether the plan view is waiting for `edit_plan`.
+    pub(super) fn plan_edit_pending(&self) -> bool {
+        self.plan_view.as_ref().is_some_and(|s| s.edit_requested)
+    }
+
+    /// Suspend the TUI and edit the plan in `$VISUAL`/`$EDITOR`. An edited
+    /// plan is answered with "Tell Claude", and the feedback asking Claude
+    /// to follow the edits is left in the prompt input for review.
+    pub(super) fn edit_plan(
+        &mut self,
+        terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    ) -> Result<()> {
+        let Some(state) = &mut self.plan_view else {
+            return Ok(());
+        };
+        state.edit_requested = false;
+        let pane_id = state.pane_id;
+        let plan = state.plan.clone();
+
+        // A fresh private file (O_EXCL, mode 0600), removed when dropped
+        let file = tempfile::Builder::new()
+            .prefix(&format!("wzcc-plan-{}-", pane_id))
+            .suffix(".md")
+            .tempfile()
+            .and_then(|mut file| file.write_all(plan.as_bytes()).map(|()| file));
+        let file = match file {
+            Ok(file) => file,
+            Err(e) => {
+                self.toast = Some(Toast::error(format!("Failed to write plan: {}", e)));
+                self.dirty = true;
+                return Ok(());
+            }
+        };
+        let path = file.path();
+
+        disable_raw_mode()?;
+        execute!(
+            terminal.backend_mut(),
+            LeaveAlternateScreen,
+            DisableMouseCapture,
+            DisableBracketedPaste
+        )?;
+        let status = Command::new("sh")
+            .arg("-c")
+            .arg(format!("{} \"$1\"", editor_command()))
+            .arg("sh")
+            .arg(path)
+            .status();
+        enable_raw_mode()?;
+        execute!(
+            terminal.backend_mut(),
+            EnterAlternateScreen,
+            EnableMouseCapture,
+            EnableBracketedPaste
+        )?;
+        self.needs_full_redraw = true;
+        self.dirty = true;
+
+        // Read by path: editors may replace the file instead of writing to it
+        let edited = std::fs::read_to_string(path);
+        drop(file);
+        let error = match (status, edited) {
+            (Err(e), _) => format!("Failed to start editor: {}", e),
+            (Ok(status), _) if !status.success() => format!("Editor exited with {}", status),
+            (Ok(_), Err(e)) => format!("Failed to read plan: {}", e),
+            (Ok(_), Ok(edited)) => {
+                self.send_plan_feedback(&plan, &edited);
+                return Ok(());
+            }
+        };
+        self.toast = Some(Toast::error(error));
+        Ok(())
+    }
+
+    /// Answer the approval with "Tell Claude" and prefill the prompt input
+    /// with feedback on the edited plan.
+    fn send_plan_feedback(&mut self, plan: &str, edited: &str) {
+        let Some(feedback) = plan_feedback(plan, edited) else {
+            self.toast = Some(Toast::success("Plan unchanged, nothing sent".into()));
+            return;
+        };
+        let Some(pane_id) = self.plan_view.as_ref().map(|s| s.pane_id) else {
+            return;
+        };
+        self.approve_plan(3);
+        // Answering opens the input for the plan's session; only prefill it
+        // if that is where it was opened
+        let selected = self
+            .list_state
+            .selected()
+            .and_then(|i| self.sessions.get(i))
+            .map(|s| s.pane.pane_id);
+        if self.input_mode && selected == Some(pane_id) {
+            self.input_buffer.insert_str(&feedback);
+        }
+    }
+}
+
+/// The editor command: `$VISUAL`, then `$EDITOR`, then `vi`.
+fn editor_command() -> String {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string())
+}
+
+/// Feedback for Claude asking it to follow an edited plan. None if the plan
+/// wasn't changed or was emptied.
+pub(super) fn plan_feedback(original: &str, edited: &str) -> Option<String> {
+    let edited = edited.trim();
+    if edited.is_empty() || edited == original.trim() {
+        return None;
+    }
+    Some(format!(
+        "Please revise the plan to match my edits:\n\n{}",
+        edited
+    ))
+}
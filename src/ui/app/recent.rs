@@ -0,0 +1,176 @@
+use super::*;
+use crate::transcript::{
+    get_transcript_dir, list_recent_sessions, projects_dir, read_all_conversation_turns,
+};
+use std::path::Path;
+
+/// Maximum number of sessions shown in the recent sessions popup.
+const RECENT_SESSIONS_LIMIT: usize = 50;
+
+impl App {
+    /// Open the recent sessions popup (`R`) for the selected session's
+    /// project, or for every project if no session is selected.
+    pub(super) fn open_recent_sessions(&mut self) {
+        let project_cwd = self
+            .list_state
+            .selected()
+            .and_then(|i| self.sessions.get(i))
+            .and_then(|s| s.pane.cwd_path());
+        let all_projects = project_cwd.is_none();
+        self.recent_sessions = Some(RecentSessionsState {
+            project_cwd,
+            all_projects,
+            sessions: Vec::new(),
+            live_ids: HashSet::new(),
+            list_state: ListState::default(),
+        });
+        self.load_recent_sessions();
+    }
+
+    /// Close the recent sessions popup (Esc).
+    pub(super) fn close_recent_sessions(&mut self) {
+        self.recent_sessions = None;
+        self.dirty = true;
+    }
+
+    /// Switch between the project's sessions and all projects (`a`).
+    pub(super) fn toggle_recent_sessions_scope(&mut self) {
+        if let Some(state) = &mut self.recent_sessions {
+            if state.project_cwd.is_some() {
+                state.all_projects = !state.all_projects;
+                self.load_recent_sessions();
+            }
+        }
+    }
+
+    /// (Re)read the transcripts listed in the popup.
+    fn load_recent_sessions(&mut self) {
+        let live_ids: HashSet<String> = self
+            .all_sessions
+            .iter()
+            .filter_map(|s| s.session_id.clone())
+            .collect();
+        let Some(state) = &mut self.recent_sessions else {
+            return;
+        };
+
+        let dirs = match (&state.project_cwd, state.all_projects) {
+            (Some(cwd), false) => get_transcript_dir(cwd).map(|dir| vec![dir]),
+            _ => None,
+        };
+        let result = match projects_dir() {
+            Some(root) => list_recent_sessions(&root, dirs.as_deref(), RECENT_SESSIONS_LIMIT),
+            None => Err(anyhow::anyhow!("could not determine home directory")),
+        };
+        match result {
+            Ok(sessions) => {
+                state.list_state.select((!sessions.is_empty()).then_some(0));
+                state.sessions = sessions;
+            }
+            Err(e) => {
+                state.sessions.clear();
+                state.list_state.select(None);
+                self.toast = Some(Toast::error(format!("Failed to list sessions: {}", e)));
+            }
+        }
+        state.live_ids = live_ids;
+        self.dirty = true;
+    }
+
+    /// Move the selection down (`delta` > 0) or up, without wrapping.
+    pub(super) fn recent_sessions_select(&mut self, delta: isize) {
+        if let Some(state) = &mut self.recent_sessions {
+            let len = state.sessions.len();
+            if let Some(i) = state.list_state.selected() {
+                let next = i.saturating_add_signed(delta).min(len.saturating_sub(1));
+                state.list_state.select(Some(next));
+                self.dirty = true;
+            }
+        }
+    }
+
+    fn selected_recent_session(&self) -> Option<ArchivedSession> {
+        let state = self.recent_sessions.as_ref()?;
+        state.sessions.get(state.list_state.selected()?).cloned()
+    }
+
+    /// Open the selected session's transcript in the history view (Enter).
+    pub(super) fn open_recent_session_history(&mut self) {
+        let Some(session) = self.selected_recent_session() else {
+            return;
+        };
+        let turns = match read_all_conversation_turns(&session.transcript_path) {
+            Ok(turns) => turns,
+            Err(_) => {
+                self.toast = Some(Toast::error("Failed to read history".to_string()));
+                self.dirty = true;
+                return;
+            }
+        };
+
+        self.recent_sessions = None;
+        if self.detail_mode == DetailMode::LivePane {
+            self.exit_live_pane_view();
+        }
+        let source = format!("{} · {}", session.project_name(), session.session_id);
+        self.show_history(turns, Some(source));
+    }
+
+    /// Resume the selected session with `claude --resume` in a new WezTerm
+    /// tab (`r`).
+    pub(super) fn resume_recent_session(&mut self) {
+        let Some(session) = self.selected_recent_session() else {
+            return;
+        };
+        let Some(window_id) = self.spawn_window_id() else {
+            self.toast = Some(Toast::error(
+                "No WezTerm window to open the session in".to_string(),
+            ));
+            self.dirty = true;
+            return;
+        };
+
+        // Claude Code looks sessions up by working directory
+        let cwd = session
+            .cwd
+            .as_deref()
+            .filter(|cwd| Path::new(cwd).is_dir())
+            .map(str::to_string)
+            .or_else(|| dirs::home_dir().map(|h| h.to_string_lossy().to_string()))
+            .unwrap_or_else(|| "/".to_string());
+        let args = ["--resume".to_string(), session.session_id.clone()];
+        match WeztermCli::spawn_tab(&cwd, window_id, "claude", &args) {
+            Ok(new_pane_id) => {
+                self.recent_sessions = None;
+                self.toast = Some(Toast::success(format!(
+                    "Resumed {} in Pane {}",
+                    session.session_id, new_pane_id
+                )));
+                self.refresh();
+            }
+            Err(e) => {
+                self.toast = Some(Toast::error(format!("Failed to resume session: {}", e)));
+            }
+        }
+        self.dirty = true;
+        self.needs_full_redraw = true;
+    }
+
+    /// Window for new tabs: the selected session's, else wzcc's own.
+    fn spawn_window_id(&self) -> Option<u32> {
+        if let Some(session) = self
+            .list_state
+            .selected()
+            .and_then(|i| self.sessions.get(i))
+        {
+            return Some(session.pane.window_id);
+        }
+        let own_pane_id = std::env::var("WEZTERM_PANE").ok()?.parse::<u32>().ok()?;
+        self.pane_ds
+            .list_panes()
+            .ok()?
+            .into_iter()
+            .find(|p| p.pane_id == own_pane_id)
+            .map(|p| p.window_id)
+    }
+}
@@ -21,11 +21,8 @@ impl App {
         // Setup file watcher
         self.transcript_watcher = Some(TranscriptWatcher::new()?);
 
-        // Initial refresh
-        self.refresh()?;
-
-        // Start watching transcript directories
-        self.update_watched_dirs()?;
+        // Initial refresh (also starts watching transcript directories)
+        self.start_refresh_worker()?;
 
         // Event handler - shorter poll interval (100ms) since we're event-driven now
         // This is just for keyboard/mouse events, not for status updates
@@ -39,20 +36,14 @@ impl App {
         let result = loop {
             // Check for file changes from notify (lightweight transcript-only refresh)
             if self.drain_file_changes() && self.should_refresh_transcripts() {
-                self.refresh_transcripts();
+                self.request_refresh(RefreshRequest::Transcripts);
+            }
 
-                // Check for actual changes in output
-                let current_outputs: Vec<Option<String>> = self
-                    .sessions
-                    .iter()
-                    .map(|s| s.last_output.clone())
-                    .collect();
+            // Apply results published by the refresh worker
+            self.apply_refresh_updates()?;
 
-                if current_outputs != self.prev_last_outputs {
-                    self.needs_full_redraw = true;
-                    self.prev_last_outputs = current_outputs;
-                }
-            }
+            // Apply transcript search, usage and worktree results
+            self.apply_scan_updates();
 
             // Only draw when dirty flag is set
             if self.dirty {
@@ -65,6 +56,12 @@ impl App {
                 self.dirty = false;
             }
 
+            // Hand the terminal over to the editor for the plan view
+            if self.plan_edit_pending() {
+                self.edit_plan(&mut terminal)?;
+                continue;
+            }
+
             // Clear expired toast
             if let Some(ref toast) = self.toast {
                 if toast.is_expired() {
@@ -120,10 +117,7 @@ impl App {
                                 self.update_slash_filter();
                             }
                             KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                                self.input_buffer.clear();
-                                self.slash_complete_active = false;
-                                self.slash_filtered.clear();
-                                self.dirty = true;
+                                self.clear_input();
                             }
                             KeyCode::Char(_) if key.modifiers.contains(KeyModifiers::CONTROL) => {
                                 // Ignore other Ctrl combinations (Ctrl+a/e/h/l/o etc.)
@@ -137,83 +131,57 @@ impl App {
                         }
                     } else {
                         // Normal input mode (no autocomplete active)
-                        match key.code {
-                            KeyCode::Esc => {
+                        match self.key_action(KeyMode::Input, &key) {
+                            Some(Action::Back) => {
                                 self.exit_input_mode();
                             }
-                            KeyCode::Char('o') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                                // Ctrl+O -> newline
+                            Some(Action::Newline) => {
                                 self.dirty |= self.input_buffer.insert_char('\n');
                             }
-                            KeyCode::Enter => {
-                                // Enter -> submit
+                            Some(Action::Send) => {
                                 self.send_prompt()?;
                             }
-                            KeyCode::Backspace => {
-                                self.dirty |= self.input_buffer.backspace();
-                                self.update_slash_filter();
-                            }
-                            KeyCode::Left => {
+                            Some(Action::CursorLeft) => {
                                 self.dirty |= self.input_buffer.cursor_left();
                                 self.update_slash_filter();
                             }
-                            KeyCode::Right => {
+                            Some(Action::CursorRight) => {
                                 self.dirty |= self.input_buffer.cursor_right();
                                 self.update_slash_filter();
                             }
-                            KeyCode::Up => {
+                            Some(Action::CursorUp) => {
                                 self.dirty |= self.input_buffer.cursor_up();
                                 self.update_slash_filter();
                             }
-                            KeyCode::Down => {
+                            Some(Action::CursorDown) => {
                                 self.dirty |= self.input_buffer.cursor_down();
                                 self.update_slash_filter();
                             }
-                            KeyCode::Home => {
+                            Some(Action::LineStart) => {
                                 self.dirty |= self.input_buffer.cursor_home();
                                 self.update_slash_filter();
                             }
-                            KeyCode::End => {
+                            Some(Action::LineEnd) => {
                                 self.dirty |= self.input_buffer.cursor_end();
                                 self.update_slash_filter();
                             }
-                            KeyCode::Char('a') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                                self.dirty |= self.input_buffer.cursor_home();
-                                self.update_slash_filter();
-                            }
-                            KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                                self.dirty |= self.input_buffer.cursor_end();
-                                self.update_slash_filter();
+                            Some(Action::Clear) => {
+                                self.input_buffer.clear();
+                                self.slash_complete_active = false;
+                                self.slash_filtered.clear();
+                                self.dirty = true;
                             }
-                            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                                if !self.input_buffer.is_empty() {
-                                    self.input_buffer.clear();
-                                    self.slash_complete_active = false;
-                                    self.slash_filtered.clear();
-                                    self.dirty = true;
+                            _ => match key.code {
+                                KeyCode::Backspace => {
+                                    self.dirty |= self.input_buffer.backspace();
+                                    self.update_slash_filter();
                                 }
-                            }
-                            KeyCode::Char('h') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                                self.dirty |= self.input_buffer.cursor_left();
-                                self.update_slash_filter();
-                            }
-                            KeyCode::Char('j') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                                self.dirty |= self.input_buffer.cursor_down();
-                                self.update_slash_filter();
-                            }
-                            KeyCode::Char('k') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                                self.dirty |= self.input_buffer.cursor_up();
-                                self.update_slash_filter();
-                            }
-                            KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                                self.dirty |= self.input_buffer.cursor_right();
-                                self.update_slash_filter();
-                            }
-                            KeyCode::Char(c) => {
-                                self.dirty |= self.input_buffer.insert_char(c);
-                                self.update_slash_filter();
-                            }
-                            _ => {}
+                                KeyCode::Char(c) => {
+                                    self.dirty |= self.input_buffer.insert_char(c);
+                                    self.update_slash_filter();
+                                }
+                                _ => {}
+                            },
                         }
                     }
                 }
@@ -223,6 +191,116 @@ impl App {
                     self.dirty |= self.input_buffer.insert_str(&normalized);
                     self.update_slash_filter();
                 }
+                Event::Key(key) if self.filter_editing => {
+                    // Session list filter editing: narrow the list live as the user types
+                    match key.code {
+                        KeyCode::Esc => {
+                            self.clear_filter();
+                        }
+                        KeyCode::Enter => {
+                            self.accept_filter();
+                        }
+                        KeyCode::Backspace => {
+                            self.filter_backspace();
+                        }
+                        KeyCode::Up => {
+                            self.select_previous();
+                        }
+                        KeyCode::Down => {
+                            self.select_next();
+                        }
+                        KeyCode::Char('p') | KeyCode::Char('k')
+                            if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                        {
+                            self.select_previous();
+                        }
+                        KeyCode::Char('n') | KeyCode::Char('j')
+                            if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                        {
+                            self.select_next();
+                        }
+                        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            self.filter_clear_query();
+                        }
+                        KeyCode::Char(_) if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            // Ignore other Ctrl combinations
+                        }
+                        KeyCode::Char(c) => {
+                            self.filter_push_char(c);
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Paste(text) if self.filter_editing => {
+                    // Only the first line of pasted text is used as a filter query
+                    if let Some(line) = text.lines().next() {
+                        self.filter_push_str(line);
+                    }
+                }
+                Event::Key(key) if self.meta_edit.is_some() => {
+                    // Session label/tags prompt
+                    match key.code {
+                        KeyCode::Esc => {
+                            self.cancel_meta_edit();
+                        }
+                        KeyCode::Enter => {
+                            self.confirm_meta_edit();
+                        }
+                        KeyCode::Backspace => {
+                            self.meta_edit_buffer(|b| {
+                                b.pop();
+                            });
+                        }
+                        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            self.meta_edit_buffer(String::clear);
+                        }
+                        KeyCode::Char(_) if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            // Ignore other Ctrl combinations
+                        }
+                        KeyCode::Char(c) => {
+                            self.meta_edit_buffer(|b| b.push(c));
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Paste(text) if self.meta_edit.is_some() => {
+                    // Labels and tags are single-line
+                    if let Some(line) = text.lines().next() {
+                        self.meta_edit_buffer(|b| b.push_str(line));
+                    }
+                }
+                Event::Key(key) if self.worktree_prompt.is_some() => {
+                    // Worktree branch prompt
+                    match key.code {
+                        KeyCode::Esc => {
+                            self.cancel_worktree_prompt();
+                        }
+                        KeyCode::Enter => {
+                            self.confirm_worktree_prompt();
+                        }
+                        KeyCode::Backspace => {
+                            self.worktree_prompt_buffer(|b| {
+                                b.pop();
+                            });
+                        }
+                        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            self.worktree_prompt_buffer(String::clear);
+                        }
+                        KeyCode::Char(_) if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            // Ignore other Ctrl combinations
+                        }
+                        KeyCode::Char(c) => {
+                            self.worktree_prompt_buffer(|b| b.push(c));
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Paste(text) if self.worktree_prompt.is_some() => {
+                    // Branch names are single-line
+                    if let Some(line) = text.lines().next() {
+                        self.worktree_prompt_buffer(|b| b.push_str(line.trim()));
+                    }
+                }
                 Event::Key(key) if self.kill_confirm.is_some() => {
                     // Kill confirmation mode key handling
                     match key.code {
@@ -246,6 +324,14 @@ impl App {
                         KeyCode::Char('t') | KeyCode::Char('T') => {
                             self.select_command_or_spawn(SplitDirection::Tab)?;
                         }
+                        KeyCode::Char('w') | KeyCode::Char('W')
+                            if self
+                                .add_pane_pending
+                                .as_ref()
+                                .is_some_and(|t| t.worktree_branch.is_none()) =>
+                        {
+                            self.start_worktree_prompt();
+                        }
                         _ => {
                             self.cancel_add_pane();
                         }
@@ -325,18 +411,303 @@ impl App {
                         _ => {}
                     }
                 }
+                Event::Key(key) if self.help.is_some() => {
+                    // Key binding help overlay
+                    match key.code {
+                        KeyCode::Char('j') | KeyCode::Down => {
+                            self.scroll_help(1);
+                        }
+                        KeyCode::Char('k') | KeyCode::Up => {
+                            self.scroll_help(-1);
+                        }
+                        KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('?') => {
+                            self.close_help();
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Key(key) if self.palette.is_some() => {
+                    // Command palette: fuzzy-search actions as the user types
+                    match key.code {
+                        KeyCode::Esc => {
+                            self.close_palette();
+                        }
+                        KeyCode::Enter => {
+                            if let Some(action) = self.take_palette_action() {
+                                if self.run_sidebar_action(action)? {
+                                    break Ok(());
+                                }
+                            }
+                        }
+                        KeyCode::Backspace => {
+                            self.palette_query(|q| {
+                                q.pop();
+                            });
+                        }
+                        KeyCode::Up => {
+                            self.palette_select(-1);
+                        }
+                        KeyCode::Down => {
+                            self.palette_select(1);
+                        }
+                        KeyCode::Char('p') | KeyCode::Char('k')
+                            if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                        {
+                            self.palette_select(-1);
+                        }
+                        KeyCode::Char('n') | KeyCode::Char('j')
+                            if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                        {
+                            self.palette_select(1);
+                        }
+                        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            self.palette_query(String::clear);
+                        }
+                        KeyCode::Char(_) if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            // Ignore other Ctrl combinations
+                        }
+                        KeyCode::Char(c) => {
+                            self.palette_query(|q| q.push(c));
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Paste(text) if self.palette.is_some() => {
+                    if let Some(line) = text.lines().next() {
+                        self.palette_query(|q| q.push_str(line));
+                    }
+                }
+                Event::Key(key) if self.transcript_search.as_ref().is_some_and(|s| s.editing) => {
+                    // Transcript search query editing
+                    match key.code {
+                        KeyCode::Esc => {
+                            self.close_transcript_search();
+                        }
+                        KeyCode::Enter => {
+                            self.run_transcript_search();
+                        }
+                        KeyCode::Backspace => {
+                            self.transcript_search_query(|q| {
+                                q.pop();
+                            });
+                        }
+                        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            self.transcript_search_query(String::clear);
+                        }
+                        KeyCode::Char(_) if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            // Ignore other Ctrl combinations
+                        }
+                        KeyCode::Char(c) => {
+                            self.transcript_search_query(|q| q.push(c));
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Paste(text)
+                    if self.transcript_search.as_ref().is_some_and(|s| s.editing) =>
+                {
+                    if let Some(line) = text.lines().next() {
+                        self.transcript_search_query(|q| q.push_str(line));
+                    }
+                }
+                Event::Key(key) if self.transcript_search.is_some() => {
+                    // Transcript search results
+                    match key.code {
+                        KeyCode::Esc | KeyCode::Char('q') => {
+                            self.close_transcript_search();
+                        }
+                        KeyCode::Char('j') | KeyCode::Down => {
+                            self.transcript_search_select(1);
+                        }
+                        KeyCode::Char('k') | KeyCode::Up => {
+                            self.transcript_search_select(-1);
+                        }
+                        KeyCode::Enter => {
+                            self.open_transcript_hit();
+                        }
+                        KeyCode::Char('r') => {
+                            self.resume_transcript_hit();
+                        }
+                        KeyCode::Char('/') => {
+                            self.edit_transcript_search();
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Key(key) if self.recent_sessions.is_some() => {
+                    // Recent sessions popup
+                    match key.code {
+                        KeyCode::Esc | KeyCode::Char('q') => {
+                            self.close_recent_sessions();
+                        }
+                        KeyCode::Char('j') | KeyCode::Down => {
+                            self.recent_sessions_select(1);
+                        }
+                        KeyCode::Char('k') | KeyCode::Up => {
+                            self.recent_sessions_select(-1);
+                        }
+                        KeyCode::Enter => {
+                            self.open_recent_session_history();
+                        }
+                        KeyCode::Char('r') => {
+                            self.resume_recent_session();
+                        }
+                        KeyCode::Char('a') => {
+                            self.toggle_recent_sessions_scope();
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Key(key) if self.worktrees.as_ref().is_some_and(|w| w.confirm.is_some()) => {
+                    // Worktree removal confirmation
+                    match key.code {
+                        KeyCode::Char('y') | KeyCode::Char('Y') => {
+                            self.confirm_worktree_removal();
+                        }
+                        _ => {
+                            self.cancel_worktree_removal();
+                        }
+                    }
+                }
+                Event::Key(key) if self.worktrees.is_some() => {
+                    // Worktree cleanup popup
+                    match key.code {
+                        KeyCode::Esc | KeyCode::Char('q') => {
+                            self.close_worktrees();
+                        }
+                        KeyCode::Char('j') | KeyCode::Down => {
+                            self.worktrees_select(1);
+                        }
+                        KeyCode::Char('k') | KeyCode::Up => {
+                            self.worktrees_select(-1);
+                        }
+                        KeyCode::Char('d') => {
+                            self.request_worktree_removal(false);
+                        }
+                        KeyCode::Char('D') => {
+                            self.request_worktree_removal(true);
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Key(key) if self.stats_view.is_some() => {
+                    // Usage statistics popup
+                    match key.code {
+                        KeyCode::Esc | KeyCode::Char('q') => {
+                            self.close_stats();
+                        }
+                        KeyCode::Char('j') | KeyCode::Down => {
+                            self.stats_scroll(1);
+                        }
+                        KeyCode::Char('k') | KeyCode::Up => {
+                            self.stats_scroll(-1);
+                        }
+                        KeyCode::Tab => {
+                            self.cycle_stats_group();
+                        }
+                        KeyCode::Char('d') => {
+                            self.cycle_stats_period();
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Key(key) if self.plan_view.is_some() => {
+                    // Plan view: read the plan, approve it or edit it
+                    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+                    match key.code {
+                        KeyCode::Esc | KeyCode::Char('q') => {
+                            self.close_plan_view();
+                        }
+                        KeyCode::Char('d') if ctrl => {
+                            self.plan_half_page(true);
+                        }
+                        KeyCode::Char('u') if ctrl => {
+                            self.plan_half_page(false);
+                        }
+                        KeyCode::PageDown | KeyCode::Char(' ') => {
+                            self.plan_half_page(true);
+                        }
+                        KeyCode::PageUp => {
+                            self.plan_half_page(false);
+                        }
+                        KeyCode::Char('j') | KeyCode::Down => {
+                            self.plan_scroll(1);
+                        }
+                        KeyCode::Char('k') | KeyCode::Up => {
+                            self.plan_scroll(-1);
+                        }
+                        KeyCode::Char('g') | KeyCode::Home => {
+                            self.plan_scroll_to(false);
+                        }
+                        KeyCode::Char('G') | KeyCode::End => {
+                            self.plan_scroll_to(true);
+                        }
+                        KeyCode::Char(c @ '1'..='4') => {
+                            self.approve_plan((c as usize) - ('1' as usize));
+                        }
+                        KeyCode::Char('e') => {
+                            self.request_plan_edit();
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Key(key) if self.history_search.editing => {
+                    // History search query editing: highlight hits as the user types
+                    match key.code {
+                        KeyCode::Esc => {
+                            self.clear_history_search();
+                        }
+                        KeyCode::Enter => {
+                            self.accept_history_search();
+                        }
+                        KeyCode::Backspace => {
+                            self.history_search_query(|q| {
+                                q.pop();
+                            });
+                        }
+                        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            self.history_search_query(String::clear);
+                        }
+                        KeyCode::Char(_) if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            // Ignore other Ctrl combinations
+                        }
+                        KeyCode::Char(c) => {
+                            self.history_search_query(|q| q.push(c));
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Paste(text) if self.history_search.editing => {
+                    if let Some(line) = text.lines().next() {
+                        self.history_search_query(|q| q.push_str(line));
+                    }
+                }
                 Event::Key(key) if self.detail_mode == DetailMode::HistoryList => {
                     // History list view key handling
-                    match key.code {
-                        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('H') => {
+                    match self.key_action(KeyMode::History, &key) {
+                        Some(Action::Help) => {
+                            self.open_help(KeyMode::History);
+                        }
+                        Some(Action::Search) => {
+                            self.enter_history_search();
+                        }
+                        Some(Action::NextMatch) => {
+                            self.history_search_next(true);
+                        }
+                        Some(Action::PrevMatch) => {
+                            self.history_search_next(false);
+                        }
+                        Some(Action::Back) if self.history_search.is_active() => {
+                            // Back clears an applied search before leaving the view
+                            self.clear_history_search();
+                        }
+                        Some(Action::Back) => {
                             self.exit_history_mode();
                         }
-                        KeyCode::Enter => {
-                            self.pending_g = false;
+                        Some(Action::Open) => {
                             self.enter_history_detail();
                         }
-                        KeyCode::Char('j') | KeyCode::Down => {
-                            self.pending_g = false;
+                        Some(Action::Down) => {
                             let len = self.history_turns.len();
                             if let Some(i) = self.history_list_state.selected() {
                                 if i + 1 < len {
@@ -345,8 +716,7 @@ impl App {
                                 }
                             }
                         }
-                        KeyCode::Char('k') | KeyCode::Up => {
-                            self.pending_g = false;
+                        Some(Action::Up) => {
                             if let Some(i) = self.history_list_state.selected() {
                                 if i > 0 {
                                     self.history_list_state.select(Some(i - 1));
@@ -354,178 +724,152 @@ impl App {
                                 }
                             }
                         }
-                        KeyCode::Char('g') => {
-                            if self.pending_g {
-                                // gg -> jump to newest (first in list)
-                                self.history_list_state.select(Some(0));
-                                self.dirty = true;
-                                self.pending_g = false;
-                            } else {
-                                self.pending_g = true;
-                            }
+                        Some(Action::Top) => {
+                            // Jump to newest (first in list)
+                            self.history_list_state.select(Some(0));
+                            self.dirty = true;
                         }
-                        KeyCode::Char('G') => {
-                            // G -> jump to oldest (last in list)
-                            self.pending_g = false;
-                            if !self.history_turns.is_empty() {
-                                self.history_list_state
-                                    .select(Some(self.history_turns.len() - 1));
-                                self.dirty = true;
-                            }
+                        Some(Action::Bottom) if !self.history_turns.is_empty() => {
+                            // Jump to oldest (last in list)
+                            self.history_list_state
+                                .select(Some(self.history_turns.len() - 1));
+                            self.dirty = true;
                         }
-                        KeyCode::Char('y') => {
-                            // y -> yank selected history turn's response to clipboard
-                            self.pending_g = false;
+                        Some(Action::Yank) => {
+                            // Yank selected history turn's response to clipboard
                             if let Some(i) = self.history_list_state.selected() {
                                 self.yank_history_output(i);
                             }
                         }
-                        KeyCode::Char('h') => {
-                            self.pending_g = false;
-                            if self.details_width_percent < 80 {
-                                self.details_width_percent += 5;
-                                self.dirty = true;
-                                self.needs_full_redraw = true;
-                            }
-                        }
-                        KeyCode::Char('l') => {
-                            self.pending_g = false;
-                            if self.details_width_percent > 20 {
-                                self.details_width_percent -= 5;
-                                self.dirty = true;
-                                self.needs_full_redraw = true;
-                            }
+                        Some(Action::Expand) => {
+                            self.expand_details();
                         }
-                        _ => {
-                            self.pending_g = false;
+                        Some(Action::Shrink) => {
+                            self.shrink_details();
                         }
+                        _ => {}
                     }
                 }
                 Event::Key(key) if self.detail_mode == DetailMode::HistoryDetail => {
                     // History detail view key handling
-                    match key.code {
-                        KeyCode::Esc | KeyCode::Char('q') => {
-                            // Back to list (NOT exit history entirely)
-                            self.exit_history_detail();
+                    match self.key_action(KeyMode::History, &key) {
+                        Some(Action::Help) => {
+                            self.open_help(KeyMode::History);
+                        }
+                        Some(Action::Search) => {
+                            self.enter_history_search();
                         }
-                        KeyCode::Char('H') => {
-                            // H in detail -> back to list
+                        Some(Action::NextMatch) => {
+                            self.history_search_next(true);
+                        }
+                        Some(Action::PrevMatch) => {
+                            self.history_search_next(false);
+                        }
+                        Some(Action::Back) if self.history_search.is_active() => {
+                            // Back clears an applied search before leaving the view
+                            self.clear_history_search();
+                        }
+                        Some(Action::Back) => {
+                            // Back to list (NOT exit history entirely)
                             self.exit_history_detail();
                         }
-                        KeyCode::Char('j') | KeyCode::Down => {
+                        Some(Action::Down) => {
                             // Scroll content down line-by-line
-                            self.pending_g = false;
                             self.history_scroll_offset =
                                 self.history_scroll_offset.saturating_add(1);
                             self.dirty = true;
                         }
-                        KeyCode::Char('k') | KeyCode::Up => {
+                        Some(Action::Up) => {
                             // Scroll content up line-by-line
-                            self.pending_g = false;
                             self.history_scroll_offset =
                                 self.history_scroll_offset.saturating_sub(1);
                             self.dirty = true;
                         }
-                        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                            // Ctrl+D -> scroll down half page
-                            self.pending_g = false;
+                        Some(Action::HalfPageDown) => {
                             let half = self.viewport_half_height();
                             self.history_scroll_offset =
                                 self.history_scroll_offset.saturating_add(half);
                             self.dirty = true;
                         }
-                        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                            // Ctrl+U -> scroll up half page
-                            self.pending_g = false;
+                        Some(Action::HalfPageUp) => {
                             let half = self.viewport_half_height();
                             self.history_scroll_offset =
                                 self.history_scroll_offset.saturating_sub(half);
                             self.dirty = true;
                         }
-                        KeyCode::Char('g') => {
-                            if self.pending_g {
-                                // gg -> scroll to top
-                                self.history_scroll_offset = 0;
-                                self.dirty = true;
-                                self.pending_g = false;
-                            } else {
-                                self.pending_g = true;
-                            }
+                        Some(Action::Top) => {
+                            self.history_scroll_offset = 0;
+                            self.dirty = true;
                         }
-                        KeyCode::Char('G') => {
-                            // G -> scroll to bottom (clamped in render)
-                            self.pending_g = false;
+                        Some(Action::Bottom) => {
+                            // Scroll to bottom (clamped in render)
                             self.history_scroll_offset = usize::MAX;
                             self.dirty = true;
                         }
-                        KeyCode::Char('y') => {
-                            // y -> yank current turn's response to clipboard
-                            self.pending_g = false;
+                        Some(Action::Yank) => {
+                            // Yank current turn's response to clipboard
                             self.yank_history_output(self.history_index);
                         }
-                        KeyCode::Char('h') => {
-                            self.pending_g = false;
-                            if self.details_width_percent < 80 {
-                                self.details_width_percent += 5;
-                                self.dirty = true;
-                                self.needs_full_redraw = true;
-                            }
+                        Some(Action::Expand) => {
+                            self.expand_details();
                         }
-                        KeyCode::Char('l') => {
-                            self.pending_g = false;
-                            if self.details_width_percent > 20 {
-                                self.details_width_percent -= 5;
-                                self.dirty = true;
-                                self.needs_full_redraw = true;
-                            }
-                        }
-                        _ => {
-                            self.pending_g = false;
+                        Some(Action::Shrink) => {
+                            self.shrink_details();
                         }
+                        _ => {}
                     }
                 }
                 Event::Key(key) if self.detail_mode == DetailMode::Terminal => {
                     // Embedded terminal mode key handling
                     if self.focus_pane == FocusPane::Terminal {
-                        // Terminal has focus: forward all keys except escape combo
+                        // Terminal has focus: forward all keys except the focus toggle
+                        // and tab switching bound with Ctrl/Alt (plain keys are typed)
                         // NOTE: Ctrl+\ (0x1c) is NOT reliably decoded by crossterm in
                         // legacy mode (only 0x01-0x1a = Ctrl+A..Z are mapped).
-                        // Use Ctrl+G (0x07, BEL) instead — unused by Claude Code.
-                        if key.code == KeyCode::Char('g')
-                            && key.modifiers.contains(KeyModifiers::CONTROL)
-                        {
-                            // Ctrl+G -> toggle focus back to sidebar
-                            self.toggle_terminal_focus();
-                        } else {
-                            self.send_key_to_pty(&key);
+                        // The default is Ctrl+G (0x07, BEL) — unused by Claude Code.
+                        let modified = key
+                            .modifiers
+                            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+                        match self.key_action(KeyMode::Terminal, &key) {
+                            Some(Action::FocusSidebar) => self.toggle_terminal_focus(),
+                            Some(Action::NextTab) if modified => self.switch_terminal_tab(1),
+                            Some(Action::PrevTab) if modified => self.switch_terminal_tab(-1),
+                            _ => self.send_key_to_pty(&key),
                         }
                     } else {
                         // Sidebar has focus while terminal is running
-                        match key.code {
-                            KeyCode::Tab => {
+                        match self.key_action(KeyMode::Terminal, &key) {
+                            Some(Action::Help) => {
+                                self.open_help(KeyMode::Terminal);
+                            }
+                            Some(Action::FocusTerminal) => {
                                 self.toggle_terminal_focus();
                             }
-                            KeyCode::Esc | KeyCode::Char('q') => {
+                            Some(Action::NextTab) => {
+                                self.switch_terminal_tab(1);
+                            }
+                            Some(Action::PrevTab) => {
+                                self.switch_terminal_tab(-1);
+                            }
+                            Some(Action::CloseTab) => {
+                                self.close_terminal_tab();
+                            }
+                            Some(Action::Terminal) => {
+                                self.enter_terminal_mode();
+                            }
+                            Some(Action::Back) => {
                                 self.exit_terminal_mode();
                             }
-                            KeyCode::Char('h') => {
-                                if self.details_width_percent < 80 {
-                                    self.details_width_percent += 5;
-                                    self.dirty = true;
-                                    self.needs_full_redraw = true;
-                                }
+                            Some(Action::Expand) => {
+                                self.expand_details();
                             }
-                            KeyCode::Char('l') => {
-                                if self.details_width_percent > 20 {
-                                    self.details_width_percent -= 5;
-                                    self.dirty = true;
-                                    self.needs_full_redraw = true;
-                                }
+                            Some(Action::Shrink) => {
+                                self.shrink_details();
                             }
-                            _ if is_down_key(&key) => {
+                            Some(Action::Down) => {
                                 self.select_next();
                             }
-                            _ if is_up_key(&key) => {
+                            Some(Action::Up) => {
                                 self.select_previous();
                             }
                             _ => {}
@@ -540,194 +884,78 @@ impl App {
                 }
                 Event::Key(key) if self.detail_mode == DetailMode::LivePane => {
                     // Live pane view key handling
-                    match key.code {
-                        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('v') => {
+                    match self.key_action(KeyMode::LivePane, &key) {
+                        Some(Action::Help) => {
+                            self.open_help(KeyMode::LivePane);
+                        }
+                        Some(Action::Back) => {
                             self.exit_live_pane_view();
                         }
-                        KeyCode::Char('j') | KeyCode::Down => {
-                            self.pending_g = false;
+                        Some(Action::Down) => {
                             self.live_pane_follow_tail = false;
                             self.live_pane_scroll_offset =
                                 self.live_pane_scroll_offset.saturating_add(1);
                             self.dirty = true;
                         }
-                        KeyCode::Char('k') | KeyCode::Up => {
-                            self.pending_g = false;
+                        Some(Action::Up) => {
                             self.live_pane_follow_tail = false;
                             self.live_pane_scroll_offset =
                                 self.live_pane_scroll_offset.saturating_sub(1);
                             self.dirty = true;
                         }
-                        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                            self.pending_g = false;
+                        Some(Action::HalfPageDown) => {
                             self.live_pane_follow_tail = false;
                             let half_page = self.viewport_half_height();
                             self.live_pane_scroll_offset =
                                 self.live_pane_scroll_offset.saturating_add(half_page);
                             self.dirty = true;
                         }
-                        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                            self.pending_g = false;
+                        Some(Action::HalfPageUp) => {
                             self.live_pane_follow_tail = false;
                             let half_page = self.viewport_half_height();
                             self.live_pane_scroll_offset =
                                 self.live_pane_scroll_offset.saturating_sub(half_page);
                             self.dirty = true;
                         }
-                        KeyCode::Char('g') => {
-                            if self.pending_g {
-                                self.live_pane_follow_tail = false;
-                                self.live_pane_scroll_offset = 0;
-                                self.dirty = true;
-                                self.pending_g = false;
-                            } else {
-                                self.pending_g = true;
-                            }
+                        Some(Action::Top) => {
+                            self.live_pane_follow_tail = false;
+                            self.live_pane_scroll_offset = 0;
+                            self.dirty = true;
                         }
-                        KeyCode::Char('G') => {
-                            self.pending_g = false;
+                        Some(Action::Bottom) => {
                             self.live_pane_follow_tail = true;
                             self.live_pane_scroll_offset = usize::MAX;
                             self.dirty = true;
                         }
-                        KeyCode::Char('y') => {
-                            self.pending_g = false;
+                        Some(Action::Yank) => {
                             self.yank_live_pane_content();
                         }
-                        KeyCode::Char('h') => {
-                            self.pending_g = false;
-                            if self.details_width_percent < 80 {
-                                self.details_width_percent += 5;
-                                self.dirty = true;
-                                self.needs_full_redraw = true;
-                            }
-                        }
-                        KeyCode::Char('l') => {
-                            self.pending_g = false;
-                            if self.details_width_percent > 20 {
-                                self.details_width_percent -= 5;
-                                self.dirty = true;
-                                self.needs_full_redraw = true;
-                            }
+                        Some(Action::Expand) => {
+                            self.expand_details();
                         }
-                        _ => {
-                            self.pending_g = false;
+                        Some(Action::Shrink) => {
+                            self.shrink_details();
                         }
+                        _ => {}
                     }
                 }
+                Event::Key(key) if key.code == KeyCode::Esc && self.is_filter_applied() => {
+                    // Esc clears an applied filter before quitting
+                    self.pending_key = None;
+                    self.clear_filter();
+                }
                 Event::Key(key) => {
                     // Normal mode key handling
-                    // Handle gg sequence
-                    if self.pending_g {
-                        self.pending_g = false;
-                        if key.code == KeyCode::Char('g') {
-                            // gg -> jump to first
-                            self.select_first();
-                            continue;
-                        }
-                        // Reset pending if different key comes after g
-                    }
-
-                    if self.detail_mode == DetailMode::Summary {
-                        if key.code == KeyCode::Char('j')
-                            && key.modifiers.contains(KeyModifiers::CONTROL)
-                        {
-                            self.summary_scroll_offset =
-                                self.summary_scroll_offset.saturating_add(1);
-                            self.dirty = true;
-                            continue;
-                        } else if key.code == KeyCode::Char('k')
-                            && key.modifiers.contains(KeyModifiers::CONTROL)
-                        {
-                            self.summary_scroll_offset =
-                                self.summary_scroll_offset.saturating_sub(1);
-                            self.dirty = true;
-                            continue;
-                        } else if key.code == KeyCode::Char('d')
-                            && key.modifiers.contains(KeyModifiers::CONTROL)
-                        {
-                            let half = self.viewport_half_height();
-                            self.summary_scroll_offset =
-                                self.summary_scroll_offset.saturating_add(half);
-                            self.dirty = true;
-                            continue;
-                        } else if key.code == KeyCode::Char('u')
-                            && key.modifiers.contains(KeyModifiers::CONTROL)
-                        {
-                            let half = self.viewport_half_height();
-                            self.summary_scroll_offset =
-                                self.summary_scroll_offset.saturating_sub(half);
-                            self.dirty = true;
-                            continue;
-                        }
-                    }
-
-                    if is_quit_key(&key) {
-                        break Ok(());
-                    } else if is_down_key(&key) {
-                        self.select_next();
-                    } else if is_up_key(&key) {
-                        self.select_previous();
-                    } else if key.code == KeyCode::Char('g') {
-                        // First g -> set pending state
-                        self.pending_g = true;
-                    } else if key.code == KeyCode::Char('G') {
-                        // G -> jump to last
-                        self.select_last();
-                    } else if is_enter_key(&key) {
-                        // Try to jump (TUI continues)
-                        let _ = self.jump_to_selected();
-                    } else if key.code == KeyCode::Char('h') {
-                        // Expand details panel (move divider left)
-                        if self.details_width_percent < 80 {
-                            self.details_width_percent += 5;
-                            self.dirty = true;
-                            self.needs_full_redraw = true;
+                    match self.key_action(KeyMode::Sidebar, &key) {
+                        Some(action) => {
+                            if self.run_sidebar_action(action)? {
+                                break Ok(());
+                            }
                         }
-                    } else if key.code == KeyCode::Char('l') {
-                        // Shrink details panel (move divider right)
-                        if self.details_width_percent > 20 {
-                            self.details_width_percent -= 5;
-                            self.dirty = true;
-                            self.needs_full_redraw = true;
-                        }
-                    } else if key.code == KeyCode::Char('i') {
-                        // Enter input mode
-                        self.enter_input_mode();
-                    } else if key.code == KeyCode::Char('x') {
-                        // Request kill for selected session (shows confirmation)
-                        self.request_kill_selected();
-                    } else if key.code == KeyCode::Char('y') {
-                        // Yank selected session's last output to clipboard
-                        self.yank_selected_output();
-                    } else if key.code == KeyCode::Char('H') {
-                        // Enter history browsing mode
-                        self.enter_history_mode();
-                    } else if key.code == KeyCode::Char('t') {
-                        // Enter embedded terminal mode
-                        self.enter_terminal_mode();
-                    } else if key.code == KeyCode::Char('v') {
-                        // Enter live pane view
-                        self.enter_live_pane_view();
-                    } else if key.code == KeyCode::Char('a') {
-                        // Enter add-pane mode (split direction selection)
-                        self.request_add_pane();
-                    } else if key.code == KeyCode::Char('o') {
-                        // Open answer selection for WaitingForUser session
-                        self.open_answer_select();
-                    } else if is_refresh_key(&key) {
-                        // Show refreshing indicator then update
-                        self.refreshing = true;
-                        self.dirty = true;
-                        terminal.draw(|f| self.render(f))?;
-                        self.git_branch_cache.clear();
-                        self.refresh()?;
-                        self.refreshing = false;
-                    } else if let KeyCode::Char(c) = key.code {
-                        // Quick select with number keys [1-9]
-                        if let Some(digit) = c.to_digit(10) {
-                            if (1..=9).contains(&digit) {
-                                let index = (digit - 1) as usize;
+                        None => {
+                            // Quick select with number keys [1-9]
+                            if let KeyCode::Char(c @ '1'..='9') = key.code {
+                                let index = (c as usize) - ('1' as usize);
                                 if index < self.sessions.len() {
                                     self.list_state.select(Some(index));
                                     self.summary_scroll_offset = 0;
@@ -744,9 +972,16 @@ impl App {
                     if self.input_mode
                         || self.detail_mode != DetailMode::Summary
                         || self.command_select_pending.is_some()
-                        || self.answer_select_pending.is_some() =>
+                        || self.answer_select_pending.is_some()
+                        || self.help.is_some()
+                        || self.palette.is_some()
+                        || self.transcript_search.is_some()
+                        || self.recent_sessions.is_some()
+                        || self.worktrees.is_some()
+                        || self.stats_view.is_some()
+                        || self.plan_view.is_some() =>
                 {
-                    // Ignore mouse in input mode, history mode, live pane mode, command selection, and answer selection
+                    // Ignore mouse in input mode, history mode, live pane mode, popups and overlays
                     let _ = mouse;
                 }
                 Event::Mouse(mouse) => {
@@ -811,11 +1046,17 @@ impl App {
                     self.animation_frame = (self.animation_frame + 1) % 4;
 
                     // Trigger redraw if any session is Processing (for animation)
-                    let has_processing = self
+                    let has_processing = self.sessions.iter().any(|s| s.status.is_active());
+                    if has_processing {
+                        self.dirty = true;
+                    }
+
+                    // Keep "waiting for" durations ticking
+                    let has_waiting = self
                         .sessions
                         .iter()
-                        .any(|s| matches!(s.status, crate::transcript::SessionStatus::Processing));
-                    if has_processing {
+                        .any(|s| matches!(s.status, SessionStatus::WaitingForUser { .. }));
+                    if has_waiting && self.animation_frame == 0 {
                         self.dirty = true;
                     }
 
@@ -824,38 +1065,15 @@ impl App {
                         && self.last_transcript_refresh.elapsed()
                             >= Duration::from_millis(TRANSCRIPT_DEBOUNCE_MS)
                     {
-                        self.refresh_transcripts();
+                        self.request_refresh(RefreshRequest::Transcripts);
                         self.pending_transcript_refresh = false;
                         self.last_transcript_refresh = Instant::now();
-
-                        let current_outputs: Vec<Option<String>> = self
-                            .sessions
-                            .iter()
-                            .map(|s| s.last_output.clone())
-                            .collect();
-                        if current_outputs != self.prev_last_outputs {
-                            self.needs_full_redraw = true;
-                            self.prev_last_outputs = current_outputs;
-                        }
                     }
 
                     // Periodic full refresh for new session detection (every 5 seconds)
                     if last_full_refresh.elapsed() >= full_refresh_interval {
-                        self.refresh()?;
-                        self.update_watched_dirs()?;
+                        self.refresh();
                         last_full_refresh = std::time::Instant::now();
-
-                        // Check for actual changes in output
-                        let current_outputs: Vec<Option<String>> = self
-                            .sessions
-                            .iter()
-                            .map(|s| s.last_output.clone())
-                            .collect();
-
-                        if current_outputs != self.prev_last_outputs {
-                            self.needs_full_redraw = true;
-                            self.prev_last_outputs = current_outputs;
-                        }
                     }
                 }
             }
@@ -870,10 +1088,145 @@ impl App {
             DisableBracketedPaste
         )?;
         terminal.show_cursor()?;
+        self.save_status_timelines();
 
         result
     }
 
+    /// Run a session list action (from its key binding or the command palette)
+    /// against the selected session. Returns true if wzcc should quit.
+    fn run_sidebar_action(&mut self, action: Action) -> Result<bool> {
+        match action {
+            Action::Quit => return Ok(true),
+            Action::Down => {
+                self.select_next();
+            }
+            Action::Up => {
+                self.select_previous();
+            }
+            Action::Top => {
+                self.select_first();
+            }
+            Action::Bottom => {
+                self.select_last();
+            }
+            Action::Jump => {
+                // Try to jump (TUI continues)
+                let _ = self.jump_to_selected();
+            }
+            Action::ScrollDown => {
+                self.summary_scroll_offset = self.summary_scroll_offset.saturating_add(1);
+                self.dirty = true;
+            }
+            Action::ScrollUp => {
+                self.summary_scroll_offset = self.summary_scroll_offset.saturating_sub(1);
+                self.dirty = true;
+            }
+            Action::HalfPageDown => {
+                let half = self.viewport_half_height();
+                self.summary_scroll_offset = self.summary_scroll_offset.saturating_add(half);
+                self.dirty = true;
+            }
+            Action::HalfPageUp => {
+                let half = self.viewport_half_height();
+                self.summary_scroll_offset = self.summary_scroll_offset.saturating_sub(half);
+                self.dirty = true;
+            }
+            Action::Expand => {
+                self.expand_details();
+            }
+            Action::Shrink => {
+                self.shrink_details();
+            }
+            Action::Prompt => {
+                self.enter_input_mode();
+            }
+            Action::Kill => {
+                // Request kill for selected session (shows confirmation)
+                self.request_kill_selected();
+            }
+            Action::Yank => {
+                // Yank selected session's last output to clipboard
+                self.yank_selected_output();
+            }
+            Action::History => {
+                self.enter_history_mode();
+            }
+            Action::Terminal => {
+                self.enter_terminal_mode();
+            }
+            Action::ShowTerminal => {
+                self.show_terminal();
+            }
+            Action::LivePane => {
+                self.enter_live_pane_view();
+            }
+            Action::AddPane => {
+                // Enter add-pane mode (split direction selection)
+                self.request_add_pane();
+            }
+            Action::Answer => {
+                // Open answer selection for WaitingForUser session
+                self.open_answer_select();
+            }
+            Action::ViewPlan => {
+                self.open_plan_view();
+            }
+            Action::Filter => {
+                self.enter_filter_mode();
+            }
+            Action::CycleSort => {
+                self.cycle_sort_order();
+            }
+            Action::CycleGroup => {
+                self.cycle_group_by();
+            }
+            Action::Pin => {
+                self.toggle_pin_selected();
+            }
+            Action::Label => {
+                self.start_meta_edit(MetaField::Label);
+            }
+            Action::Tags => {
+                self.start_meta_edit(MetaField::Tags);
+            }
+            Action::CycleColor => {
+                self.cycle_color_selected();
+            }
+            Action::Refresh => {
+                // Show refreshing indicator until the worker publishes the result
+                self.refreshing = true;
+                self.dirty = true;
+                self.request_refresh(RefreshRequest::Full {
+                    clear_git_cache: true,
+                });
+            }
+            Action::CycleTheme => {
+                self.cycle_theme();
+            }
+            Action::Help => {
+                self.open_help(KeyMode::Sidebar);
+            }
+            Action::Palette => {
+                self.open_palette();
+            }
+            Action::SearchAll => {
+                self.open_transcript_search();
+            }
+            Action::RecentSessions => {
+                self.open_recent_sessions();
+            }
+            Action::Worktrees => {
+                self.open_worktrees();
+            }
+            Action::Stats => {
+                self.open_stats();
+            }
+            _ => {}
+        }
+        Ok(false)
+    }
+
     /// Render
     fn render(&mut self, f: &mut ratatui::Frame) {
         let size = f.area();
@@ -898,15 +1251,20 @@ impl App {
             .split(main_area);
 
         // Render list (update list_area)
-        self.list_area = render_list(
-            f,
-            chunks[0],
-            &self.sessions,
-            &mut self.list_state,
-            self.refreshing,
-            self.animation_frame,
-            &self.current_workspace,
-        );
+        let list_ctx = ListRenderCtx {
+            sessions: &self.sessions,
+            refreshing: self.refreshing,
+            animation_frame: self.animation_frame,
+            current_workspace: &self.current_workspace,
+            list_config: self.list_config,
+            filter: (self.filter_editing || self.is_filter_applied()).then(|| ListFilterCtx {
+                query: &self.filter_query,
+                editing: self.filter_editing,
+                total: self.all_sessions.len(),
+            }),
+            theme: &self.theme,
+        };
+        self.list_area = render_list(f, chunks[0], &list_ctx, &mut self.list_state);
 
         // Render details
         let mut ctx = DetailsRenderCtx {
@@ -922,6 +1280,8 @@ impl App {
             history_list_state: &mut self.history_list_state,
             history_timestamps: &self.history_timestamps,
             cached_history_lines: &mut self.cached_history_lines,
+            history_search: &mut self.history_search,
+            history_source: self.history_source.as_deref(),
             cached_preview_lines: &mut self.cached_preview_lines,
             summary_scroll_offset: &mut self.summary_scroll_offset,
             live_pane_bytes: self.live_pane_bytes.as_deref(),
@@ -929,16 +1289,21 @@ impl App {
             live_pane_scroll_offset: &mut self.live_pane_scroll_offset,
             cached_live_pane_lines: &mut self.cached_live_pane_lines,
             live_pane_error: self.live_pane_poll_failures > 0,
+            theme: &self.theme,
         };
         // Build terminal render context if in terminal mode
-        let terminal_ctx =
-            self.terminal_session
-                .as_ref()
-                .map(|ts| crate::ui::render::TerminalRenderCtx {
-                    screen: ts.vt100_parser.screen(),
-                    focused: self.focus_pane == FocusPane::Terminal,
-                    title: &ts.title,
-                });
+        let terminal_ctx = self.terminal_tabs.get(self.active_terminal_tab).map(|ts| {
+            crate::ui::render::TerminalRenderCtx {
+                screen: ts.vt100_parser.screen(),
+                focused: self.focus_pane == FocusPane::Terminal,
+                titles: self
+                    .terminal_tabs
+                    .iter()
+                    .map(|t| t.title.as_str())
+                    .collect(),
+                active: self.active_terminal_tab,
+            }
+        });
 
         render_details(f, chunks[1], &mut ctx, terminal_ctx);
 
@@ -957,14 +1322,42 @@ impl App {
             f,
             footer_area,
             self.input_mode,
+            self.filter_editing,
+            self.meta_edit
+                .as_ref()
+                .map(|e| {
+                    let prompt = match e.field {
+                        MetaField::Label => "Label",
+                        MetaField::Tags => "Tags",
+                    };
+                    (prompt, e.buffer.as_str(), "Save")
+                })
+                .or_else(|| {
+                    self.worktree_prompt
+                        .as_ref()
+                        .map(|p| ("Worktree branch", p.buffer.as_str(), "Create"))
+                }),
             self.detail_mode,
             self.toast.as_ref(),
             self.kill_confirm.as_ref(),
-            self.add_pane_pending.as_ref(),
+            self.add_pane_pending
+                .as_ref()
+                .map(|t| t.worktree_branch.as_deref()),
             self.command_select_pending.is_some(),
             self.slash_complete_active,
             self.answer_select_pending.is_some(),
+            self.help.is_some(),
+            self.palette.is_some(),
+            self.transcript_search.as_ref().map(|s| s.editing),
+            self.recent_sessions.is_some(),
+            self.worktrees.is_some(),
+            self.stats_view.is_some(),
+            self.plan_view.is_some(),
+            &self.history_search,
             has_waiting_session,
+            self.terminal_tabs.len(),
+            &self.keymap,
+            &self.theme,
         );
 
         // Render slash command autocomplete popup (anchored to details area)
@@ -975,6 +1368,7 @@ impl App {
                 &self.slash_commands,
                 &self.slash_filtered,
                 &mut self.slash_complete_state,
+                &self.theme,
             );
         }
 
@@ -985,12 +1379,38 @@ impl App {
                 size,
                 &self.resolved_commands,
                 &mut self.command_select_state,
+                &self.theme,
             );
         }
 
         // Render answer selection popup overlay (on top of everything)
         if let Some(ref state) = self.answer_select_pending {
-            render_answer_select(f, size, state, &mut self.answer_select_state);
+            render_answer_select(f, size, state, &mut self.answer_select_state, &self.theme);
+        }
+
+        // Render transcript search popup overlay (on top of everything)
+        if let Some(ref mut state) = self.transcript_search {
+            render_transcript_search(f, size, state, &self.theme);
+        }
+        if let Some(ref mut state) = self.recent_sessions {
+            render_recent_sessions(f, size, state, &self.theme);
+        }
+        if let Some(ref mut state) = self.worktrees {
+            render_worktrees(f, size, state, &self.theme);
+        }
+        if let Some(ref state) = self.stats_view {
+            render_stats(f, size, state, &self.theme);
+        }
+        if let Some(ref mut state) = self.plan_view {
+            render_plan(f, size, state, &self.theme);
+        }
+
+        // Render command palette / key binding help overlays (on top of everything)
+        if let Some(ref mut state) = self.palette {
+            render_palette(f, size, state, &self.keymap, &self.theme);
+        }
+        if let Some(ref state) = self.help {
+            render_help(f, size, state, &self.keymap, &self.theme);
         }
     }
 }
@@ -0,0 +1,145 @@
+use super::*;
+use crate::ui::history_search::{find_turn, HitJump, SEARCH_CONTEXT_LINES};
+
+impl App {
+    /// Start editing the history search query (`/`). Keeps any existing
+    /// query so it can be refined.
+    pub(super) fn enter_history_search(&mut self) {
+        self.history_search.editing = true;
+        self.pending_key = None;
+        self.dirty = true;
+    }
+
+    /// Edit the search query and move to the first hit as the user types.
+    pub(super) fn history_search_query(&mut self, f: impl FnOnce(&mut String)) {
+        f(&mut self.history_search.query);
+        self.history_search.reset_position();
+        if self.history_search.is_active() {
+            match self.detail_mode {
+                DetailMode::HistoryList => {
+                    let start = self.history_list_state.selected().unwrap_or(0);
+                    if let Some(i) =
+                        find_turn(&self.history_turns, &self.history_search, start, true)
+                    {
+                        self.history_list_state.select(Some(i));
+                    }
+                }
+                DetailMode::HistoryDetail => {
+                    self.history_search.pending = Some(HitJump::First);
+                }
+                _ => {}
+            }
+        }
+        self.dirty = true;
+    }
+
+    /// Stop editing but keep the search applied (Enter).
+    pub(super) fn accept_history_search(&mut self) {
+        self.history_search.editing = false;
+        if !self.history_search.is_active() {
+            self.history_search.query.clear();
+        } else if !self
+            .history_turns
+            .iter()
+            .any(|t| self.history_search.turn_matches(t))
+        {
+            self.toast_no_history_matches();
+        }
+        self.dirty = true;
+    }
+
+    /// Clear the search and its highlights (Esc).
+    pub(super) fn clear_history_search(&mut self) {
+        self.history_search.query.clear();
+        self.history_search.editing = false;
+        self.history_search.reset_position();
+        self.dirty = true;
+    }
+
+    /// Move to the next (`n`) or previous (`N`) search hit. In the list this
+    /// selects the next matching turn; in the detail view it steps through
+    /// hits in the open turn, then continues in the next matching turn.
+    pub(super) fn history_search_next(&mut self, forward: bool) {
+        if !self.history_search.is_active() || self.history_turns.is_empty() {
+            return;
+        }
+        self.dirty = true;
+
+        let len = self.history_turns.len();
+        let step = |i: usize| {
+            if forward {
+                (i + 1) % len
+            } else {
+                (i + len - 1) % len
+            }
+        };
+
+        if self.detail_mode == DetailMode::HistoryList {
+            let current = self.history_list_state.selected().unwrap_or(0);
+            match find_turn(
+                &self.history_turns,
+                &self.history_search,
+                step(current),
+                forward,
+            ) {
+                Some(i) => self.history_list_state.select(Some(i)),
+                None => self.toast_no_history_matches(),
+            }
+            return;
+        }
+
+        // Next hit in the open turn, relative to the current hit (or the
+        // scroll position if none has been selected yet)
+        let search = &self.history_search;
+        let hit = match (search.current_line, forward) {
+            (Some(c), true) => search.hit_lines.iter().find(|&&l| l > c),
+            (Some(c), false) => search.hit_lines.iter().rev().find(|&&l| l < c),
+            (None, true) => search
+                .hit_lines
+                .iter()
+                .find(|&&l| l >= self.history_scroll_offset),
+            (None, false) => search
+                .hit_lines
+                .iter()
+                .rev()
+                .find(|&&l| l < self.history_scroll_offset),
+        }
+        .copied();
+
+        if let Some(line) = hit {
+            self.history_search.current_line = Some(line);
+            self.history_scroll_offset = line.saturating_sub(SEARCH_CONTEXT_LINES);
+            return;
+        }
+
+        // Otherwise open the next matching turn (this one again if it is the
+        // only match) at its first or last hit
+        match find_turn(
+            &self.history_turns,
+            &self.history_search,
+            step(self.history_index),
+            forward,
+        ) {
+            Some(i) => {
+                self.history_index = i;
+                self.history_list_state.select(Some(i));
+                self.history_scroll_offset = 0;
+                self.history_search.reset_position();
+                self.history_search.pending = Some(if forward {
+                    HitJump::First
+                } else {
+                    HitJump::Last
+                });
+                self.needs_full_redraw = true;
+            }
+            None => self.toast_no_history_matches(),
+        }
+    }
+
+    fn toast_no_history_matches(&mut self) {
+        self.toast = Some(Toast::error(format!(
+            "No matches for '{}'",
+            self.history_search.query
+        )));
+    }
+}
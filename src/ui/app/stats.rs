@@ -0,0 +1,99 @@
+use super::*;
+use crate::transcript::{period_start, UsageStats};
+use chrono::NaiveDate;
+
+/// Periods cycled with `d` in the usage statistics popup (None: all history).
+const STATS_PERIODS: [Option<u32>; 3] = [Some(7), Some(30), None];
+
+impl App {
+    /// Open the usage statistics popup (`U`). Transcripts are aggregated on
+    /// the scan worker while the loading indicator is shown.
+    pub(super) fn open_stats(&mut self) {
+        self.stats_view = Some(StatsViewState {
+            group: StatsGroup::Project,
+            days: STATS_PERIODS[0],
+            stats: None,
+            scroll: 0,
+        });
+        self.request_stats();
+        self.dirty = true;
+    }
+
+    /// Close the usage statistics popup (Esc).
+    pub(super) fn close_stats(&mut self) {
+        self.cancel_scan(ScanKind::Stats);
+        self.stats_view = None;
+        self.dirty = true;
+    }
+
+    /// Aggregate usage over the popup's period on the scan worker.
+    /// The result is shown by `apply_stats`.
+    fn request_stats(&mut self) {
+        if let Some(state) = &self.stats_view {
+            let since = state.days.map(period_start);
+            self.request_scan(ScanRequest::Stats { since });
+        }
+    }
+
+    /// Show usage aggregated by the scan worker, unless the popup has moved
+    /// on to another period.
+    pub(super) fn apply_stats(
+        &mut self,
+        since: Option<NaiveDate>,
+        stats: Result<UsageStats, String>,
+    ) {
+        let Some(state) = &mut self.stats_view else {
+            return;
+        };
+        if state.stats.is_some() || state.days.map(period_start) != since {
+            return;
+        }
+        match stats {
+            Ok(stats) => state.stats = Some(stats),
+            Err(e) => {
+                self.stats_view = None;
+                self.toast = Some(Toast::error(format!("Failed to read usage: {}", e)));
+            }
+        }
+        self.dirty = true;
+    }
+
+    /// Group rows by the next of project, day and model (Tab).
+    pub(super) fn cycle_stats_group(&mut self) {
+        if let Some(state) = &mut self.stats_view {
+            state.group = state.group.next();
+            state.scroll = 0;
+            self.dirty = true;
+        }
+    }
+
+    /// Switch between the last 7 days, 30 days and all history (`d`).
+    pub(super) fn cycle_stats_period(&mut self) {
+        if let Some(state) = &mut self.stats_view {
+            let i = STATS_PERIODS
+                .iter()
+                .position(|p| *p == state.days)
+                .unwrap_or(0);
+            state.days = STATS_PERIODS[(i + 1) % STATS_PERIODS.len()];
+            state.stats = None;
+            state.scroll = 0;
+            self.request_stats();
+            self.dirty = true;
+        }
+    }
+
+    /// Scroll the rows down (`delta` > 0) or up.
+    pub(super) fn stats_scroll(&mut self, delta: isize) {
+        if let Some(state) = &mut self.stats_view {
+            let rows = state
+                .stats
+                .as_ref()
+                .map_or(0, |s| s.groups(state.group).len());
+            state.scroll = state
+                .scroll
+                .saturating_add_signed(delta)
+                .min(rows.saturating_sub(1));
+            self.dirty = true;
+        }
+    }
+}
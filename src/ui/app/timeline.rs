@@ -0,0 +1,38 @@
+use super::*;
+
+impl App {
+    /// Record the status of every session and attach its timeline.
+    pub(super) fn apply_status_timelines(&mut self) {
+        let now = chrono::Utc::now();
+        for session in &mut self.all_sessions {
+            let key = timeline_key(session);
+            self.status_timelines
+                .record(&key, session.status.kind(), now);
+            session.timeline = self.status_timelines.get(&key).cloned().unwrap_or_default();
+        }
+        if let Err(e) = self.status_timelines.save_if_due() {
+            self.toast = Some(Toast::error(format!(
+                "Failed to save status timelines: {}",
+                e
+            )));
+        }
+    }
+
+    /// Write the status timelines on exit, so the time between the last
+    /// periodic save and the exit isn't counted as unobserved.
+    pub(super) fn save_status_timelines(&mut self) {
+        // The terminal is restored by now, so report errors on stderr
+        if let Err(e) = self.status_timelines.save() {
+            eprintln!("Failed to save status timelines: {}", e);
+        }
+    }
+}
+
+/// Timeline key of a session: its session ID, or its pane (not persisted)
+/// without the statusLine bridge.
+fn timeline_key(session: &ClaudeSession) -> String {
+    match &session.session_id {
+        Some(id) => id.clone(),
+        None => format!("{}{}", PANE_KEY_PREFIX, session.pane.pane_id),
+    }
+}
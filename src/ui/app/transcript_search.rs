@@ -0,0 +1,178 @@
+use super::*;
+use crate::transcript::read_all_conversation_turns;
+use std::path::PathBuf;
+
+/// Maximum number of hits shown in the transcript search popup.
+const TRANSCRIPT_SEARCH_LIMIT: usize = 100;
+
+impl App {
+    /// Open the transcript search popup (`F`), keeping the last query and
+    /// results so a search can be revisited.
+    pub(super) fn open_transcript_search(&mut self) {
+        let state = self
+            .transcript_search
+            .get_or_insert_with(|| TranscriptSearchState {
+                query: String::new(),
+                editing: true,
+                searching: false,
+                results: None,
+                list_state: ListState::default(),
+            });
+        state.editing = state.results.is_none();
+        self.dirty = true;
+    }
+
+    /// Close the transcript search popup (Esc).
+    pub(super) fn close_transcript_search(&mut self) {
+        if let Some(state) = &mut self.transcript_search {
+            if state.editing && state.results.is_some() {
+                // Back to the previous results rather than closing
+                state.editing = false;
+            } else {
+                if state.searching {
+                    self.cancel_scan(ScanKind::Search);
+                }
+                self.transcript_search = None;
+            }
+            self.dirty = true;
+        }
+    }
+
+    /// Go back to editing the query (`/`), abandoning a running search.
+    pub(super) fn edit_transcript_search(&mut self) {
+        if let Some(state) = &mut self.transcript_search {
+            state.editing = true;
+            if state.searching {
+                state.searching = false;
+                self.cancel_scan(ScanKind::Search);
+            }
+            self.dirty = true;
+        }
+    }
+
+    /// Edit the transcript search query.
+    pub(super) fn transcript_search_query(&mut self, f: impl FnOnce(&mut String)) {
+        if let Some(state) = &mut self.transcript_search {
+            f(&mut state.query);
+            self.dirty = true;
+        }
+    }
+
+    /// Search every transcript for the query on the scan worker (Enter).
+    /// The hits are shown by `apply_transcript_search`.
+    pub(super) fn run_transcript_search(&mut self) {
+        let Some(state) = &mut self.transcript_search else {
+            return;
+        };
+        if state.query.trim().is_empty() {
+            return;
+        }
+        state.searching = true;
+        state.editing = false;
+        state.results = None;
+        state.list_state.select(None);
+        let request = ScanRequest::Search {
+            query: state.query.clone(),
+            limit: TRANSCRIPT_SEARCH_LIMIT,
+        };
+        self.request_scan(request);
+        self.dirty = true;
+    }
+
+    /// Show the hits of a finished search, unless it was abandoned.
+    pub(super) fn apply_transcript_search(
+        &mut self,
+        query: String,
+        hits: Result<Vec<SearchHit>, String>,
+    ) {
+        let Some(state) = &mut self.transcript_search else {
+            return;
+        };
+        if !state.searching || state.query != query {
+            return;
+        }
+        state.searching = false;
+        match hits {
+            Ok(hits) => {
+                state.list_state.select((!hits.is_empty()).then_some(0));
+                state.results = Some(hits);
+            }
+            Err(e) => {
+                state.editing = true;
+                self.toast = Some(Toast::error(format!("Search failed: {}", e)));
+            }
+        }
+        self.dirty = true;
+    }
+
+    /// Move the result selection down (`delta` > 0) or up, without wrapping.
+    pub(super) fn transcript_search_select(&mut self, delta: isize) {
+        if let Some(state) = &mut self.transcript_search {
+            let len = state.results.as_ref().map_or(0, Vec::len);
+            if let Some(i) = state.list_state.selected() {
+                let next = i.saturating_add_signed(delta).min(len.saturating_sub(1));
+                state.list_state.select(Some(next));
+                self.dirty = true;
+            }
+        }
+    }
+
+    fn selected_transcript_hit(&self) -> Option<(SearchHit, String)> {
+        let state = self.transcript_search.as_ref()?;
+        let hit = state.results.as_ref()?.get(state.list_state.selected()?)?;
+        Some((hit.clone(), state.query.clone()))
+    }
+
+    /// Open the selected hit's transcript in the history view, at the
+    /// matching turn with the query highlighted (Enter).
+    pub(super) fn open_transcript_hit(&mut self) {
+        let Some((hit, query)) = self.selected_transcript_hit() else {
+            return;
+        };
+        let turns = match read_all_conversation_turns(&hit.transcript_path) {
+            Ok(turns) if hit.turn_index < turns.len() => turns,
+            Ok(_) | Err(_) => {
+                self.toast = Some(Toast::error("Failed to read history".to_string()));
+                self.dirty = true;
+                return;
+            }
+        };
+
+        self.transcript_search = None;
+        if self.detail_mode == DetailMode::LivePane {
+            self.exit_live_pane_view();
+        }
+        let source = format!("{} · {}", hit.project_name(), hit.session_id);
+        self.show_history(turns, Some(source));
+        self.history_list_state.select(Some(hit.turn_index));
+        // Open the turn with the query highlighted (jumps to the first hit)
+        self.history_search.query = query;
+        self.enter_history_detail();
+    }
+
+    /// Resume the selected hit's session in the embedded terminal with
+    /// `claude --resume` (`r`).
+    pub(super) fn resume_transcript_hit(&mut self) {
+        let Some((hit, _)) = self.selected_transcript_hit() else {
+            return;
+        };
+        if self.detail_mode == DetailMode::Terminal {
+            self.exit_terminal_mode();
+        } else if self.detail_mode != DetailMode::Summary {
+            self.exit_history_mode();
+        }
+
+        // Claude Code looks sessions up by working directory
+        let cwd = hit
+            .cwd
+            .as_deref()
+            .map(PathBuf::from)
+            .filter(|p| p.is_dir())
+            .or_else(dirs::home_dir)
+            .unwrap_or_else(|| PathBuf::from("/"));
+        let title = hit.project_name().to_string();
+
+        self.transcript_search = None;
+        self.spawn_terminal(cwd, title, Some(hit.session_id));
+    }
+}
@@ -0,0 +1,215 @@
+use super::*;
+use crate::datasource::git::{delete_branch, remove_worktree};
+use crate::ui::scan_worker::WorktreeStatus;
+use std::collections::BTreeSet;
+use std::path::Path;
+
+impl App {
+    /// Open the worktree cleanup popup (`W`) listing the linked worktrees of
+    /// every repository that has a session. The rows are loaded on the scan
+    /// worker.
+    pub(super) fn open_worktrees(&mut self) {
+        self.worktrees = Some(WorktreesState {
+            rows: Vec::new(),
+            list_state: ListState::default(),
+            confirm: None,
+            loading: true,
+        });
+        self.request_worktree_scan();
+        self.dirty = true;
+    }
+
+    /// Close the worktree cleanup popup (Esc).
+    pub(super) fn close_worktrees(&mut self) {
+        self.cancel_scan(ScanKind::Worktrees);
+        self.worktrees = None;
+        self.dirty = true;
+    }
+
+    /// (Re)load the worktrees of the repositories of all sessions.
+    fn request_worktree_scan(&mut self) {
+        let repo_roots: BTreeSet<String> = self
+            .all_sessions
+            .iter()
+            .filter_map(|s| s.git_repo_root.clone())
+            .collect();
+        self.request_scan(ScanRequest::Worktrees {
+            repo_roots: repo_roots.into_iter().collect(),
+        });
+    }
+
+    /// Show the worktrees loaded by the scan worker, keeping the selection.
+    pub(super) fn apply_worktree_scan(
+        &mut self,
+        worktrees: Vec<WorktreeStatus>,
+        errors: Vec<String>,
+    ) {
+        let rows: Vec<WorktreeRow> = worktrees
+            .into_iter()
+            .map(|w| WorktreeRow {
+                session_pane: self.worktree_session_pane(&w.worktree.path),
+                repo_root: w.repo_root,
+                worktree: w.worktree,
+                merged: w.merged,
+                dirty: w.dirty,
+            })
+            .collect();
+        let Some(state) = &mut self.worktrees else {
+            return;
+        };
+        let selected = state
+            .list_state
+            .selected()
+            .unwrap_or(0)
+            .min(rows.len().saturating_sub(1));
+        state
+            .list_state
+            .select((!rows.is_empty()).then_some(selected));
+        // A pending confirmation may point at a different row now
+        state.confirm = None;
+        state.rows = rows;
+        state.loading = false;
+        if !errors.is_empty() {
+            self.toast = Some(Toast::error(format!(
+                "Failed to list worktrees: {}",
+                errors.join("; ")
+            )));
+        }
+        self.dirty = true;
+    }
+
+    /// Pane of a live session working in the worktree at `path`.
+    pub(super) fn worktree_session_pane(&self, path: &Path) -> Option<u32> {
+        self.all_sessions
+            .iter()
+            .find(|s| {
+                s.pane
+                    .cwd_path()
+                    .is_some_and(|cwd| is_inside_worktree(&cwd, path))
+            })
+            .map(|s| s.pane.pane_id)
+    }
+
+    /// Move the selection down (`delta` > 0) or up, without wrapping.
+    pub(super) fn worktrees_select(&mut self, delta: isize) {
+        if let Some(state) = &mut self.worktrees {
+            let len = state.rows.len();
+            if let Some(i) = state.list_state.selected() {
+                let next = i.saturating_add_signed(delta).min(len.saturating_sub(1));
+                state.list_state.select(Some(next));
+                self.dirty = true;
+            }
+        }
+    }
+
+    /// Ask to remove the selected worktree (`d`), and its branch too (`D`).
+    /// Worktrees with a live session can't be removed.
+    pub(super) fn request_worktree_removal(&mut self, delete_branch: bool) {
+        let Some(state) = &mut self.worktrees else {
+            return;
+        };
+        let Some(index) = state.list_state.selected() else {
+            return;
+        };
+        let Some(row) = state.rows.get(index) else {
+            return;
+        };
+        if let Some(pane_id) = row.session_pane {
+            self.toast = Some(Toast::error(format!(
+                "Worktree is in use by Pane {}",
+                pane_id
+            )));
+        } else {
+            state.confirm = Some(WorktreeRemoval {
+                index,
+                delete_branch: delete_branch && row.worktree.branch.is_some(),
+            });
+        }
+        self.dirty = true;
+    }
+
+    /// Cancel the pending removal.
+    pub(super) fn cancel_worktree_removal(&mut self) {
+        if let Some(state) = &mut self.worktrees {
+            state.confirm = None;
+            self.dirty = true;
+        }
+    }
+
+    /// Remove the worktree (and branch) awaiting confirmation (`y`), then
+    /// reload the list.
+    pub(super) fn confirm_worktree_removal(&mut self) {
+        let Some(state) = &mut self.worktrees else {
+            return;
+        };
+        let Some(removal) = state.confirm.take() else {
+            return;
+        };
+        let Some(path) = state
+            .rows
+            .get(removal.index)
+            .map(|row| row.worktree.path.clone())
+        else {
+            return;
+        };
+        // A session may have started in the worktree since the list was loaded
+        if let Some(pane_id) = self.worktree_session_pane(&path) {
+            self.toast = Some(Toast::error(format!(
+                "Worktree is in use by Pane {}",
+                pane_id
+            )));
+            self.dirty = true;
+            return;
+        }
+        let Some(row) = self
+            .worktrees
+            .as_ref()
+            .and_then(|s| s.rows.get(removal.index))
+        else {
+            return;
+        };
+
+        // Uncommitted changes were shown in the confirmation
+        let force = row.dirty || row.worktree.prunable;
+        let result =
+            remove_worktree(&row.repo_root, &row.worktree.path, force).and_then(|()| {
+                match (&row.worktree.branch, removal.delete_branch) {
+                    (Some(branch), true) => delete_branch(&row.repo_root, branch),
+                    _ => Ok(()),
+                }
+            });
+        let name = row.worktree.path.display().to_string();
+        let removed = result.is_ok();
+        self.toast = Some(match result {
+            Ok(()) => Toast::success(format!("Removed worktree {}", name)),
+            Err(e) => Toast::error(format!("Failed to remove worktree: {}", e)),
+        });
+
+        if let Some(state) = &mut self.worktrees {
+            if removed {
+                // Drop the row right away; the reload catches up with the rest
+                state.rows.remove(removal.index);
+                let selected = state
+                    .list_state
+                    .selected()
+                    .map(|i| i.min(state.rows.len().saturating_sub(1)));
+                state
+                    .list_state
+                    .select(selected.filter(|_| !state.rows.is_empty()));
+            }
+            state.loading = true;
+        }
+        self.request_worktree_scan();
+        self.dirty = true;
+    }
+}
+
+/// Whether `cwd` is inside the worktree at `path`. Both are resolved first,
+/// so symlinked paths (e.g. `/tmp` on macOS) match; worktrees may live
+/// anywhere, not only under the main worktree.
+fn is_inside_worktree(cwd: &str, path: &Path) -> bool {
+    match (Path::new(cwd).canonicalize(), path.canonicalize()) {
+        (Ok(cwd), Ok(path)) => cwd.starts_with(path),
+        _ => Path::new(cwd).starts_with(path),
+    }
+}
@@ -1,7 +1,5 @@
 use anyhow::Result;
-use crossterm::event::{
-    self, Event as CrosstermEvent, KeyCode, KeyEvent, KeyModifiers, MouseEvent,
-};
+use crossterm::event::{self, Event as CrosstermEvent, KeyEvent, MouseEvent};
 use std::time::Duration;
 
 /// TUI event
@@ -49,27 +47,3 @@ impl EventHandler {
         }
     }
 }
-
-/// Key event helper
-pub fn is_quit_key(key: &KeyEvent) -> bool {
-    matches!(
-        key.code,
-        KeyCode::Char('q') | KeyCode::Esc | KeyCode::Char('c')
-    ) || (key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL))
-}
-
-pub fn is_up_key(key: &KeyEvent) -> bool {
-    matches!(key.code, KeyCode::Up | KeyCode::Char('k'))
-}
-
-pub fn is_down_key(key: &KeyEvent) -> bool {
-    matches!(key.code, KeyCode::Down | KeyCode::Char('j'))
-}
-
-pub fn is_enter_key(key: &KeyEvent) -> bool {
-    matches!(key.code, KeyCode::Enter)
-}
-
-pub fn is_refresh_key(key: &KeyEvent) -> bool {
-    matches!(key.code, KeyCode::Char('r'))
-}
@@ -0,0 +1,145 @@
+//! Lightweight fuzzy matching for interactive filters.
+//!
+//! Matching is case-insensitive subsequence matching: every character of the
+//! pattern must appear in the candidate in order. Matches are scored so that
+//! consecutive runs and word-boundary hits rank higher than scattered ones.
+
+/// Bonus for a matched character directly following the previous match.
+const CONSECUTIVE_BONUS: i64 = 8;
+/// Bonus for a match at the start of a word (after `/`, `-`, `_`, space, etc.).
+const WORD_START_BONUS: i64 = 6;
+/// Penalty per skipped candidate character between two matches.
+const GAP_PENALTY: i64 = 1;
+
+/// Score `pattern` against `candidate`.
+///
+/// Returns `None` if `pattern` is not a (case-insensitive) subsequence of
+/// `candidate`. An empty pattern matches everything with a score of 0.
+pub fn fuzzy_score(pattern: &str, candidate: &str) -> Option<i64> {
+    let pattern: Vec<char> = pattern.chars().flat_map(char::to_lowercase).collect();
+    if pattern.is_empty() {
+        return Some(0);
+    }
+
+    let candidate: Vec<char> = candidate.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0;
+    let mut pi = 0;
+    let mut last_match: Option<usize> = None;
+
+    for (ci, &c) in candidate.iter().enumerate() {
+        if pi == pattern.len() {
+            break;
+        }
+        if c != pattern[pi] {
+            continue;
+        }
+
+        score += 1;
+        let at_word_start = ci == 0 || !candidate[ci - 1].is_alphanumeric();
+        if at_word_start {
+            score += WORD_START_BONUS;
+        }
+        match last_match {
+            Some(prev) if prev + 1 == ci => score += CONSECUTIVE_BONUS,
+            Some(prev) => score -= GAP_PENALTY * (ci - prev - 1) as i64,
+            None => {}
+        }
+
+        last_match = Some(ci);
+        pi += 1;
+    }
+
+    (pi == pattern.len()).then_some(score)
+}
+
+/// Check whether every whitespace-separated term of `query` fuzzy-matches at
+/// least one of `fields`.
+pub fn matches_all_terms<S: AsRef<str>>(query: &str, fields: &[S]) -> bool {
+    score_all_terms(query, fields).is_some()
+}
+
+/// Score `query` against `fields`: the sum of each whitespace-separated
+/// term's best score over the fields. Returns `None` if any term matches
+/// no field. An empty query scores 0.
+pub fn score_all_terms<S: AsRef<str>>(query: &str, fields: &[S]) -> Option<i64> {
+    query.split_whitespace().try_fold(0, |total, term| {
+        let best = fields
+            .iter()
+            .filter_map(|field| fuzzy_score(term, field.as_ref()))
+            .max()?;
+        Some(total + best)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_pattern_matches() {
+        assert_eq!(fuzzy_score("", "anything"), Some(0));
+        assert_eq!(fuzzy_score("", ""), Some(0));
+    }
+
+    #[test]
+    fn test_subsequence_match() {
+        assert!(fuzzy_score("wzc", "wezterm-claude-code").is_some());
+        assert!(fuzzy_score("fbr", "feature/branch").is_some());
+    }
+
+    #[test]
+    fn test_non_subsequence_rejected() {
+        assert_eq!(fuzzy_score("xyz", "wezterm"), None);
+        // Order matters
+        assert_eq!(fuzzy_score("ba", "ab"), None);
+        // Pattern longer than candidate
+        assert_eq!(fuzzy_score("abc", "ab"), None);
+    }
+
+    #[test]
+    fn test_case_insensitive() {
+        assert!(fuzzy_score("WAIT", "Waiting").is_some());
+        assert!(fuzzy_score("main", "MAIN").is_some());
+    }
+
+    #[test]
+    fn test_consecutive_scores_higher_than_scattered() {
+        let consecutive = fuzzy_score("api", "api-server").unwrap();
+        let scattered = fuzzy_score("api", "a-project-index").unwrap();
+        assert!(consecutive > scattered);
+    }
+
+    #[test]
+    fn test_word_start_scores_higher() {
+        let word_start = fuzzy_score("b", "feature/branch").unwrap();
+        let mid_word = fuzzy_score("b", "feature/xbranch").unwrap();
+        assert!(word_start > mid_word);
+    }
+
+    #[test]
+    fn test_matches_all_terms() {
+        let fields = ["work", "/home/user/api-server", "main"];
+        assert!(matches_all_terms("api main", &fields));
+        assert!(matches_all_terms("  wrk  ", &fields));
+        assert!(!matches_all_terms("api develop", &fields));
+    }
+
+    #[test]
+    fn test_matches_all_terms_empty_query() {
+        let fields: [&str; 0] = [];
+        assert!(matches_all_terms("", &fields));
+        assert!(matches_all_terms("   ", &["x"]));
+    }
+
+    #[test]
+    fn test_score_all_terms() {
+        assert_eq!(score_all_terms("", &["anything"]), Some(0));
+        assert_eq!(score_all_terms("live xyz", &["Open live pane view"]), None);
+        // Each term uses its best-matching field
+        let both = score_all_terms("kill pane", &["kill", "pane"]).unwrap();
+        assert_eq!(
+            both,
+            fuzzy_score("kill", "kill").unwrap() + fuzzy_score("pane", "pane").unwrap()
+        );
+    }
+}
@@ -0,0 +1,215 @@
+//! Search inside conversation history (`/` in the history views).
+//!
+//! Queries are plain substrings matched smart-case: case-insensitive unless
+//! the query contains an uppercase letter. Hits are highlighted in the
+//! rendered (markdown) lines, and `n`/`N` step through them across turns.
+
+use crate::transcript::{contains_match, find_matches, ConversationTurn};
+use crate::ui::theme::Theme;
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+
+/// Lines of context kept above a hit when scrolling to it.
+pub const SEARCH_CONTEXT_LINES: usize = 2;
+
+/// Which hit to select once a newly opened turn has been rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HitJump {
+    First,
+    Last,
+}
+
+/// History search state, shared by the history list and detail views.
+#[derive(Debug, Default)]
+pub struct HistorySearch {
+    /// Search query
+    pub query: String,
+    /// Whether the query is being edited (shows a cursor)
+    pub editing: bool,
+    /// Rendered line indices of hits in the open turn (set while drawing it)
+    pub hit_lines: Vec<usize>,
+    /// Rendered line index of the current hit
+    pub current_line: Option<usize>,
+    /// Hit to move to once the open turn has been drawn
+    pub pending: Option<HitJump>,
+}
+
+impl HistorySearch {
+    /// Whether a non-blank query is applied.
+    pub fn is_active(&self) -> bool {
+        !self.query.trim().is_empty()
+    }
+
+    /// Whether the turn's prompt or response contains the query.
+    pub fn turn_matches(&self, turn: &ConversationTurn) -> bool {
+        self.is_active()
+            && (contains_match(&turn.user_prompt, &self.query)
+                || contains_match(&turn.assistant_response, &self.query))
+    }
+
+    /// Forget hit positions (query changed or another turn was opened).
+    pub fn reset_position(&mut self) {
+        self.hit_lines.clear();
+        self.current_line = None;
+        self.pending = None;
+    }
+}
+
+/// Index of the first turn matching the search, checking `start` first and
+/// then moving down (`forward`, towards older turns) or up, wrapping around.
+pub fn find_turn(
+    turns: &[ConversationTurn],
+    search: &HistorySearch,
+    start: usize,
+    forward: bool,
+) -> Option<usize> {
+    let len = turns.len();
+    (0..len)
+        .map(|k| {
+            if forward {
+                (start + k) % len
+            } else {
+                (start + len - k) % len
+            }
+        })
+        .find(|&i| search.turn_matches(&turns[i]))
+}
+
+/// Indices of `lines` whose text contains the query.
+pub fn hit_lines(lines: &[Line<'_>], query: &str) -> Vec<usize> {
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| contains_match(&line_text(line), query))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Style for a highlighted hit; the current hit stands out from the rest.
+pub fn hit_style(theme: &Theme, current: bool) -> Style {
+    if current {
+        Style::default()
+            .fg(theme.attention)
+            .add_modifier(Modifier::REVERSED | Modifier::BOLD)
+    } else {
+        Style::default()
+            .fg(theme.highlight)
+            .add_modifier(Modifier::REVERSED)
+    }
+}
+
+/// Highlight every occurrence of `query` in `line`, splitting spans at hit
+/// boundaries so hits that cross styled spans keep each span's style.
+pub fn highlight_line(line: &Line<'_>, query: &str, style: Style) -> Line<'static> {
+    let text = line_text(line);
+    let hits = find_matches(&text, query);
+
+    let mut spans = Vec::new();
+    let mut offset = 0;
+    for span in &line.spans {
+        let span_range = offset..offset + span.content.len();
+        let mut pos = span_range.start;
+        for hit in hits
+            .iter()
+            .filter(|h| h.start < span_range.end && h.end > span_range.start)
+        {
+            let start = hit.start.max(span_range.start);
+            let end = hit.end.min(span_range.end);
+            if start > pos {
+                spans.push(Span::styled(text[pos..start].to_string(), span.style));
+            }
+            spans.push(Span::styled(
+                text[start..end].to_string(),
+                span.style.patch(style),
+            ));
+            pos = end;
+        }
+        if pos < span_range.end {
+            spans.push(Span::styled(
+                text[pos..span_range.end].to_string(),
+                span.style,
+            ));
+        }
+        offset = span_range.end;
+    }
+
+    Line {
+        spans,
+        style: line.style,
+        alignment: line.alignment,
+    }
+}
+
+fn line_text(line: &Line<'_>) -> String {
+    line.spans.iter().map(|s| s.content.as_ref()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Color;
+
+    fn turn(prompt: &str, response: &str) -> ConversationTurn {
+        ConversationTurn {
+            user_prompt: prompt.to_string(),
+            assistant_response: response.to_string(),
+            timestamp: None,
+        }
+    }
+
+    fn search(query: &str) -> HistorySearch {
+        HistorySearch {
+            query: query.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_find_turn_wraps_in_both_directions() {
+        let turns = vec![
+            turn("fix the parser", ""),
+            turn("unrelated", "nothing here"),
+            turn("hello", "the PARSER now works"),
+        ];
+        let s = search("parser");
+        assert_eq!(find_turn(&turns, &s, 1, true), Some(2));
+        assert_eq!(find_turn(&turns, &s, 1, false), Some(0));
+        assert_eq!(find_turn(&turns, &s, 0, true), Some(0));
+        assert_eq!(find_turn(&turns, &search("zzz"), 0, true), None);
+    }
+
+    #[test]
+    fn test_highlight_line_splits_spans() {
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let line = Line::from(vec![Span::raw("say hel"), Span::styled("lo world", bold)]);
+        let hit = Style::default().fg(Color::Yellow);
+        let highlighted = highlight_line(&line, "hello", hit);
+
+        let parts: Vec<(&str, Style)> = highlighted
+            .spans
+            .iter()
+            .map(|s| (s.content.as_ref(), s.style))
+            .collect();
+        assert_eq!(
+            parts,
+            vec![
+                ("say ", Style::default()),
+                ("hel", hit),
+                ("lo", bold.patch(hit)),
+                (" world", bold),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hit_lines() {
+        let lines = vec![
+            Line::from("first match"),
+            Line::from("nothing"),
+            Line::from(vec![Span::raw("mat"), Span::raw("ch across spans")]),
+        ];
+        assert_eq!(hit_lines(&lines, "match"), vec![0, 2]);
+    }
+}
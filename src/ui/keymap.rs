@@ -0,0 +1,974 @@
+//! Key bindings: actions per mode, their default keys, and `[keys]` overrides
+//! from config.toml.
+//!
+//! Keys are written as `"j"`, `"G"`, `"ctrl+u"`, `"alt+x"`, `"shift+tab"`,
+//! named keys (`"enter"`, `"esc"`, `"up"`, `"pagedown"`, `"f1"`, ...) or a
+//! two-key sequence separated by a space (`"g g"`).
+
+use crate::config::{KeyList, KeysConfig};
+use crate::ui::fuzzy::score_all_terms;
+use anyhow::{bail, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::BTreeMap;
+
+/// A key binding context. Each mode has its own set of actions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyMode {
+    /// Session list (normal mode)
+    Sidebar,
+    /// Prompt input
+    Input,
+    /// History list and detail views
+    History,
+    /// Live pane view
+    LivePane,
+    /// Embedded terminal
+    Terminal,
+}
+
+impl KeyMode {
+    pub const ALL: [KeyMode; 5] = [
+        KeyMode::Sidebar,
+        KeyMode::Input,
+        KeyMode::History,
+        KeyMode::LivePane,
+        KeyMode::Terminal,
+    ];
+
+    /// Table name under `[keys]` in config.toml.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KeyMode::Sidebar => "sidebar",
+            KeyMode::Input => "input",
+            KeyMode::History => "history",
+            KeyMode::LivePane => "live_pane",
+            KeyMode::Terminal => "terminal",
+        }
+    }
+
+    fn overrides<'a>(&self, keys: &'a KeysConfig) -> &'a BTreeMap<String, KeyList> {
+        match self {
+            KeyMode::Sidebar => &keys.sidebar,
+            KeyMode::Input => &keys.input,
+            KeyMode::History => &keys.history,
+            KeyMode::LivePane => &keys.live_pane,
+            KeyMode::Terminal => &keys.terminal,
+        }
+    }
+}
+
+/// A bindable action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    // Shared navigation
+    Quit,
+    Back,
+    Down,
+    Up,
+    Top,
+    Bottom,
+    HalfPageDown,
+    HalfPageUp,
+    Expand,
+    Shrink,
+    Yank,
+    // Session list
+    Jump,
+    ScrollDown,
+    ScrollUp,
+    Prompt,
+    Kill,
+    History,
+    Terminal,
+    ShowTerminal,
+    LivePane,
+    AddPane,
+    Answer,
+    ViewPlan,
+    Filter,
+    SearchAll,
+    RecentSessions,
+    Worktrees,
+    Stats,
+    CycleSort,
+    CycleGroup,
+    Pin,
+    Label,
+    Tags,
+    CycleColor,
+    CycleTheme,
+    Refresh,
+    Help,
+    Palette,
+    // History
+    Open,
+    Search,
+    NextMatch,
+    PrevMatch,
+    // Prompt input
+    Send,
+    Newline,
+    CursorLeft,
+    CursorRight,
+    CursorUp,
+    CursorDown,
+    LineStart,
+    LineEnd,
+    Clear,
+    // Embedded terminal
+    FocusSidebar,
+    FocusTerminal,
+    NextTab,
+    PrevTab,
+    CloseTab,
+}
+
+impl Action {
+    /// Action name used in config.toml.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Back => "back",
+            Action::Down => "down",
+            Action::Up => "up",
+            Action::Top => "top",
+            Action::Bottom => "bottom",
+            Action::HalfPageDown => "half_page_down",
+            Action::HalfPageUp => "half_page_up",
+            Action::Expand => "expand",
+            Action::Shrink => "shrink",
+            Action::Yank => "yank",
+            Action::Jump => "jump",
+            Action::ScrollDown => "scroll_down",
+            Action::ScrollUp => "scroll_up",
+            Action::Prompt => "prompt",
+            Action::Kill => "kill",
+            Action::History => "history",
+            Action::Terminal => "terminal",
+            Action::ShowTerminal => "show_terminal",
+            Action::LivePane => "live_pane",
+            Action::AddPane => "add_pane",
+            Action::Answer => "answer",
+            Action::ViewPlan => "view_plan",
+            Action::Filter => "filter",
+            Action::SearchAll => "search_all",
+            Action::RecentSessions => "recent_sessions",
+            Action::Worktrees => "worktrees",
+            Action::Stats => "stats",
+            Action::CycleSort => "cycle_sort",
+            Action::CycleGroup => "cycle_group",
+            Action::Pin => "pin",
+            Action::Label => "label",
+            Action::Tags => "tags",
+            Action::CycleColor => "cycle_color",
+            Action::CycleTheme => "cycle_theme",
+            Action::Refresh => "refresh",
+            Action::Help => "help",
+            Action::Palette => "palette",
+            Action::Open => "open",
+            Action::Search => "search",
+            Action::NextMatch => "next_match",
+            Action::PrevMatch => "prev_match",
+            Action::Send => "send",
+            Action::Newline => "newline",
+            Action::CursorLeft => "cursor_left",
+            Action::CursorRight => "cursor_right",
+            Action::CursorUp => "cursor_up",
+            Action::CursorDown => "cursor_down",
+            Action::LineStart => "line_start",
+            Action::LineEnd => "line_end",
+            Action::Clear => "clear",
+            Action::FocusSidebar => "focus_sidebar",
+            Action::FocusTerminal => "focus_terminal",
+            Action::NextTab => "next_tab",
+            Action::PrevTab => "prev_tab",
+            Action::CloseTab => "close_tab",
+        }
+    }
+
+    /// Human-readable description shown in the help overlay and command palette.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Action::Quit => "Quit wzcc",
+            Action::Back => "Go back / cancel",
+            Action::Down => "Move down",
+            Action::Up => "Move up",
+            Action::Top => "Go to top",
+            Action::Bottom => "Go to bottom",
+            Action::HalfPageDown => "Scroll half a page down",
+            Action::HalfPageUp => "Scroll half a page up",
+            Action::Expand => "Widen the details panel",
+            Action::Shrink => "Narrow the details panel",
+            Action::Yank => "Copy output to clipboard",
+            Action::Jump => "Jump to session pane",
+            Action::ScrollDown => "Scroll details down",
+            Action::ScrollUp => "Scroll details up",
+            Action::Prompt => "Send a prompt to the session",
+            Action::Kill => "Kill session pane",
+            Action::History => "Browse conversation history",
+            Action::Terminal => "Open the session in a terminal tab",
+            Action::ShowTerminal => "Show the open terminal tabs",
+            Action::LivePane => "Open live pane view",
+            Action::AddPane => "Add pane (split right/down or new tab)",
+            Action::Answer => "Answer the waiting question",
+            Action::ViewPlan => "View the plan awaiting approval",
+            Action::Filter => "Filter sessions",
+            Action::SearchAll => "Search all transcripts",
+            Action::RecentSessions => "Browse and resume recent sessions",
+            Action::Worktrees => "Clean up git worktrees",
+            Action::Stats => "Show usage statistics",
+            Action::CycleSort => "Cycle sort order",
+            Action::CycleGroup => "Cycle grouping",
+            Action::Pin => "Pin / unpin session",
+            Action::Label => "Set session label",
+            Action::Tags => "Set session tags",
+            Action::CycleColor => "Cycle label color",
+            Action::CycleTheme => "Switch color theme",
+            Action::Refresh => "Refresh session list",
+            Action::Help => "Show key bindings",
+            Action::Palette => "Open command palette",
+            Action::Open => "Open selected turn",
+            Action::Search => "Search prompts and responses",
+            Action::NextMatch => "Next search match",
+            Action::PrevMatch => "Previous search match",
+            Action::Send => "Send prompt",
+            Action::Newline => "Insert newline",
+            Action::CursorLeft => "Cursor left",
+            Action::CursorRight => "Cursor right",
+            Action::CursorUp => "Cursor up",
+            Action::CursorDown => "Cursor down",
+            Action::LineStart => "Cursor to line start",
+            Action::LineEnd => "Cursor to line end",
+            Action::Clear => "Clear input",
+            Action::FocusSidebar => "Focus the session list",
+            Action::FocusTerminal => "Focus the terminal",
+            Action::NextTab => "Next terminal tab",
+            Action::PrevTab => "Previous terminal tab",
+            Action::CloseTab => "Close the terminal tab",
+        }
+    }
+}
+
+/// Session list actions offered by the command palette, in display order.
+/// They run against the selected session exactly as their key bindings do.
+pub const PALETTE_ACTIONS: [Action; 27] = [
+    Action::Jump,
+    Action::Prompt,
+    Action::Answer,
+    Action::ViewPlan,
+    Action::History,
+    Action::LivePane,
+    Action::Terminal,
+    Action::ShowTerminal,
+    Action::AddPane,
+    Action::Yank,
+    Action::Kill,
+    Action::Filter,
+    Action::SearchAll,
+    Action::RecentSessions,
+    Action::Worktrees,
+    Action::Stats,
+    Action::CycleSort,
+    Action::CycleGroup,
+    Action::Pin,
+    Action::Label,
+    Action::Tags,
+    Action::CycleColor,
+    Action::CycleTheme,
+    Action::Refresh,
+    Action::Expand,
+    Action::Shrink,
+    Action::Quit,
+];
+
+/// Palette actions matching `query` (fuzzy, against the description and
+/// config name), best match first. Ties keep `PALETTE_ACTIONS` order.
+pub fn palette_matches(query: &str) -> Vec<Action> {
+    let mut scored: Vec<(i64, Action)> = PALETTE_ACTIONS
+        .iter()
+        .filter_map(|action| {
+            score_all_terms(query, &[action.description(), action.as_str()])
+                .map(|score| (score, *action))
+        })
+        .collect();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().map(|(_, action)| action).collect()
+}
+
+/// Default bindings per mode, in display order.
+fn default_bindings(mode: KeyMode) -> &'static [(Action, &'static [&'static str])] {
+    match mode {
+        KeyMode::Sidebar => &[
+            (Action::Down, &["j", "down"]),
+            (Action::Up, &["k", "up"]),
+            (Action::Top, &["g g"]),
+            (Action::Bottom, &["G"]),
+            (Action::Jump, &["enter"]),
+            (Action::ScrollDown, &["ctrl+j"]),
+            (Action::ScrollUp, &["ctrl+k"]),
+            (Action::HalfPageDown, &["ctrl+d"]),
+            (Action::HalfPageUp, &["ctrl+u"]),
+            (Action::Expand, &["h"]),
+            (Action::Shrink, &["l"]),
+            (Action::Prompt, &["i"]),
+            (Action::Kill, &["x"]),
+            (Action::Yank, &["y"]),
+            (Action::History, &["H"]),
+            (Action::Terminal, &["t"]),
+            (Action::ShowTerminal, &["T"]),
+            (Action::LivePane, &["v"]),
+            (Action::AddPane, &["a"]),
+            (Action::Answer, &["o"]),
+            (Action::ViewPlan, &["P"]),
+            (Action::Filter, &["/"]),
+            (Action::SearchAll, &["F"]),
+            (Action::RecentSessions, &["R"]),
+            (Action::Worktrees, &["W"]),
+            (Action::Stats, &["U"]),
+            (Action::CycleSort, &["s"]),
+            (Action::CycleGroup, &["S"]),
+            (Action::Pin, &["p"]),
+            (Action::Label, &["n"]),
+            (Action::Tags, &["#"]),
+            (Action::CycleColor, &["C"]),
+            (Action::CycleTheme, &[]),
+            (Action::Refresh, &["r"]),
+            (Action::Help, &["?"]),
+            (Action::Palette, &["ctrl+p"]),
+            (Action::Quit, &["q", "esc", "c", "ctrl+c"]),
+        ],
+        KeyMode::Input => &[
+            (Action::Send, &["enter"]),
+            (Action::Newline, &["ctrl+o"]),
+            (Action::CursorLeft, &["ctrl+h", "left"]),
+            (Action::CursorDown, &["ctrl+j", "down"]),
+            (Action::CursorUp, &["ctrl+k", "up"]),
+            (Action::CursorRight, &["ctrl+l", "right"]),
+            (Action::LineStart, &["ctrl+a", "home"]),
+            (Action::LineEnd, &["ctrl+e", "end"]),
+            (Action::Clear, &["ctrl+u"]),
+            (Action::Back, &["esc"]),
+        ],
+        KeyMode::History => &[
+            (Action::Down, &["j", "down"]),
+            (Action::Up, &["k", "up"]),
+            (Action::Open, &["enter"]),
+            (Action::HalfPageDown, &["ctrl+d"]),
+            (Action::HalfPageUp, &["ctrl+u"]),
+            (Action::Yank, &["y"]),
+            (Action::Top, &["g g"]),
+            (Action::Bottom, &["G"]),
+            (Action::Expand, &["h"]),
+            (Action::Shrink, &["l"]),
+            (Action::Search, &["/"]),
+            (Action::NextMatch, &["n"]),
+            (Action::PrevMatch, &["N"]),
+            (Action::Help, &["?"]),
+            (Action::Back, &["esc", "q", "H"]),
+        ],
+        KeyMode::LivePane => &[
+            (Action::Down, &["j", "down"]),
+            (Action::Up, &["k", "up"]),
+            (Action::HalfPageDown, &["ctrl+d"]),
+            (Action::HalfPageUp, &["ctrl+u"]),
+            (Action::Yank, &["y"]),
+            (Action::Top, &["g g"]),
+            (Action::Bottom, &["G"]),
+            (Action::Expand, &["h"]),
+            (Action::Shrink, &["l"]),
+            (Action::Help, &["?"]),
+            (Action::Back, &["esc", "q", "v"]),
+        ],
+        KeyMode::Terminal => &[
+            (Action::FocusTerminal, &["tab"]),
+            (Action::FocusSidebar, &["ctrl+g"]),
+            (Action::NextTab, &["alt+right", "]"]),
+            (Action::PrevTab, &["alt+left", "["]),
+            (Action::CloseTab, &["x"]),
+            (Action::Terminal, &["t"]),
+            (Action::Down, &["j", "down"]),
+            (Action::Up, &["k", "up"]),
+            (Action::Expand, &["h"]),
+            (Action::Shrink, &["l"]),
+            (Action::Help, &["?"]),
+            (Action::Back, &["esc", "q"]),
+        ],
+    }
+}
+
+/// A single key press (code + Ctrl/Alt modifiers).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        // Shift is implied by the character itself (`G`, `#`) or by BackTab;
+        // Ctrl+letter is matched case-insensitively.
+        let modifiers = modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT);
+        let code = match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::CONTROL) => {
+                KeyCode::Char(c.to_ascii_lowercase())
+            }
+            code => code,
+        };
+        Self { code, modifiers }
+    }
+
+    /// Binding for a key event.
+    pub fn from_event(key: &KeyEvent) -> Self {
+        Self::new(key.code, key.modifiers)
+    }
+
+    /// Parse a single key (e.g. `"j"`, `"ctrl+u"`, `"shift+tab"`, `"pagedown"`).
+    pub fn parse(s: &str) -> Result<Self> {
+        // A lone character is always a literal key (including "+")
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Ok(Self::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+
+        let mut parts: Vec<&str> = s.split('+').collect();
+        let mut name = parts.pop().unwrap_or_default();
+        if name.is_empty() && parts.last() == Some(&"") {
+            // "ctrl++" -> the "+" key
+            parts.pop();
+            name = "+";
+        }
+
+        let mut modifiers = KeyModifiers::NONE;
+        let mut shift = false;
+        for part in parts {
+            match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => modifiers |= KeyModifiers::CONTROL,
+                "alt" | "meta" => modifiers |= KeyModifiers::ALT,
+                "shift" => shift = true,
+                _ => bail!("unknown modifier '{}' in key '{}'", part, s),
+            }
+        }
+
+        let mut name_chars = name.chars();
+        let code = match (name_chars.next(), name_chars.next()) {
+            (Some(c), None) if shift => KeyCode::Char(c.to_ascii_uppercase()),
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match name.to_ascii_lowercase().as_str() {
+                "tab" if shift => KeyCode::BackTab,
+                _ if shift => bail!("shift is only supported with characters and tab: '{}'", s),
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "space" => KeyCode::Char(' '),
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                lower => match lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n) if (1..=12).contains(&n) => KeyCode::F(n),
+                    _ => bail!("unknown key '{}'", s),
+                },
+            },
+        };
+
+        Ok(Self::new(code, modifiers))
+    }
+
+    /// Short display form for the footer (e.g. `j`, `^U`, `Enter`, `↓`).
+    pub fn display(&self) -> String {
+        let key = match self.code {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) if self.modifiers.contains(KeyModifiers::CONTROL) => {
+                c.to_ascii_uppercase().to_string()
+            }
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::Enter => "Enter".to_string(),
+            KeyCode::Esc => "Esc".to_string(),
+            KeyCode::Tab => "Tab".to_string(),
+            KeyCode::BackTab => "S-Tab".to_string(),
+            KeyCode::Backspace => "BS".to_string(),
+            KeyCode::Delete => "Del".to_string(),
+            KeyCode::Up => "↑".to_string(),
+            KeyCode::Down => "↓".to_string(),
+            KeyCode::Left => "←".to_string(),
+            KeyCode::Right => "→".to_string(),
+            KeyCode::Home => "Home".to_string(),
+            KeyCode::End => "End".to_string(),
+            KeyCode::PageUp => "PgUp".to_string(),
+            KeyCode::PageDown => "PgDn".to_string(),
+            KeyCode::F(n) => format!("F{}", n),
+            other => format!("{:?}", other),
+        };
+        let mut prefix = String::new();
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            prefix.push('^');
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            prefix.push_str("M-");
+        }
+        prefix + &key
+    }
+}
+
+/// One key or a two-key sequence (e.g. `g g`).
+type KeySequence = Vec<KeyBinding>;
+
+/// Actions of one mode with their key sequences, in display order.
+type ModeBindings = Vec<(Action, Vec<KeySequence>)>;
+
+fn parse_sequence(s: &str) -> Result<KeySequence> {
+    let keys = s
+        .split_whitespace()
+        .map(KeyBinding::parse)
+        .collect::<Result<KeySequence>>()?;
+    if keys.is_empty() || keys.len() > 2 {
+        bail!("key '{}' must be a single key or a two-key sequence", s);
+    }
+    Ok(keys)
+}
+
+fn display_sequence(seq: &KeySequence) -> String {
+    seq.iter().map(KeyBinding::display).collect()
+}
+
+/// Result of looking up a key press.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyLookup {
+    /// The key (or completed sequence) triggers this action.
+    Action(Action),
+    /// The key starts a sequence; wait for the next key.
+    Pending(KeyBinding),
+    /// The key is not bound in this mode.
+    Unbound,
+}
+
+/// Resolved key bindings for every mode.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    modes: Vec<(KeyMode, ModeBindings)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::from_config(&KeysConfig::default()).expect("default key bindings are valid")
+    }
+}
+
+impl Keymap {
+    /// Build the keymap from defaults plus `[keys]` overrides.
+    ///
+    /// An override replaces all default keys of that action (an empty list
+    /// unbinds it). Returns Err for unknown actions, invalid keys, or keys
+    /// bound to more than one action in the same mode.
+    pub fn from_config(keys: &KeysConfig) -> Result<Self> {
+        let mut modes = Vec::new();
+
+        for mode in KeyMode::ALL {
+            let defaults = default_bindings(mode);
+            let overrides = mode.overrides(keys);
+
+            for name in overrides.keys() {
+                if !defaults.iter().any(|(a, _)| a.as_str() == name) {
+                    bail!("unknown action '{}' in [keys.{}]", name, mode.as_str());
+                }
+            }
+
+            let mut bindings = Vec::new();
+            for (action, default_keys) in defaults {
+                let key_strs = match overrides.get(action.as_str()) {
+                    Some(list) => list.keys(),
+                    None => default_keys.to_vec(),
+                };
+                let seqs = key_strs
+                    .into_iter()
+                    .map(|k| {
+                        parse_sequence(k).map_err(|e| {
+                            anyhow::anyhow!("[keys.{}] {}: {}", mode.as_str(), action.as_str(), e)
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                bindings.push((*action, seqs));
+            }
+
+            check_conflicts(mode, &bindings)?;
+            modes.push((mode, bindings));
+        }
+
+        Ok(Self { modes })
+    }
+
+    fn bindings(&self, mode: KeyMode) -> &[(Action, Vec<KeySequence>)] {
+        self.modes
+            .iter()
+            .find(|(m, _)| *m == mode)
+            .map(|(_, b)| b.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Look up a key press in `mode`. `pending` is the first key of a
+    /// sequence returned as `KeyLookup::Pending` by the previous lookup.
+    pub fn lookup(&self, mode: KeyMode, pending: Option<KeyBinding>, key: &KeyEvent) -> KeyLookup {
+        let key = KeyBinding::from_event(key);
+        let bindings = self.bindings(mode);
+        let find = |target: &[KeyBinding]| {
+            bindings
+                .iter()
+                .find(|(_, seqs)| seqs.iter().any(|s| s.as_slice() == target))
+                .map(|(action, _)| *action)
+        };
+
+        // Complete a pending sequence; otherwise the pending key is dropped
+        if let Some(action) = pending.and_then(|first| find(&[first, key])) {
+            return KeyLookup::Action(action);
+        }
+        if let Some(action) = find(&[key]) {
+            return KeyLookup::Action(action);
+        }
+        let starts_sequence = bindings
+            .iter()
+            .flat_map(|(_, seqs)| seqs)
+            .any(|s| s.len() == 2 && s[0] == key);
+        if starts_sequence {
+            KeyLookup::Pending(key)
+        } else {
+            KeyLookup::Unbound
+        }
+    }
+
+    /// Footer label for `actions` in `mode` (e.g. `j/k`, `Esc/q`).
+    ///
+    /// A single action shows up to two of its keys; several actions show the
+    /// first key of each. Returns None if none of the actions are bound.
+    pub fn hint(&self, mode: KeyMode, actions: &[Action]) -> Option<String> {
+        let bindings = self.bindings(mode);
+        let keys_of = |action: &Action| {
+            bindings
+                .iter()
+                .find(|(a, _)| a == action)
+                .map(|(_, seqs)| seqs.as_slice())
+                .unwrap_or_default()
+        };
+
+        let labels: Vec<String> = match actions {
+            [action] => keys_of(action)
+                .iter()
+                .take(2)
+                .map(display_sequence)
+                .collect(),
+            _ => actions
+                .iter()
+                .filter_map(|a| keys_of(a).first().map(display_sequence))
+                .collect(),
+        };
+
+        (!labels.is_empty()).then(|| labels.join("/"))
+    }
+
+    /// Every action of `mode` with all of its keys (e.g. `j, ↓`), in display
+    /// order. Unbound actions have an empty key list.
+    pub fn help_entries(&self, mode: KeyMode) -> Vec<(Action, String)> {
+        self.bindings(mode)
+            .iter()
+            .map(|(action, seqs)| {
+                let keys: Vec<String> = seqs.iter().map(display_sequence).collect();
+                (*action, keys.join(", "))
+            })
+            .collect()
+    }
+}
+
+/// Reject keys bound to two actions, and single keys that are also the
+/// first key of a sequence (the sequence could never be typed).
+fn check_conflicts(mode: KeyMode, bindings: &[(Action, Vec<KeySequence>)]) -> Result<()> {
+    let mut seen: Vec<(&KeySequence, Action)> = Vec::new();
+    for (action, seqs) in bindings {
+        for seq in seqs {
+            if let Some((_, other)) = seen.iter().find(|(s, a)| *s == seq && a != action) {
+                bail!(
+                    "key '{}' is bound to both '{}' and '{}' in [keys.{}]",
+                    display_sequence(seq),
+                    other.as_str(),
+                    action.as_str(),
+                    mode.as_str()
+                );
+            }
+            seen.push((seq, *action));
+        }
+    }
+
+    for (single, action) in seen.iter().filter(|(s, _)| s.len() == 1) {
+        if let Some((seq, other)) = seen.iter().find(|(s, _)| s.len() == 2 && s[0] == single[0]) {
+            bail!(
+                "key '{}' ('{}') conflicts with sequence '{}' ('{}') in [keys.{}]",
+                display_sequence(single),
+                action.as_str(),
+                display_sequence(seq),
+                other.as_str(),
+                mode.as_str()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    fn char_key(c: char) -> KeyEvent {
+        key(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+
+    fn keys_config(mode: &str, entries: &[(&str, &[&str])]) -> KeysConfig {
+        let mut toml_str = format!("[{}]\n", mode);
+        for (action, keys) in entries {
+            let list: Vec<String> = keys.iter().map(|k| format!("{:?}", k)).collect();
+            toml_str.push_str(&format!("{} = [{}]\n", action, list.join(", ")));
+        }
+        toml::from_str(&toml_str).unwrap()
+    }
+
+    #[test]
+    fn test_parse_keys() {
+        assert_eq!(
+            KeyBinding::parse("j").unwrap(),
+            KeyBinding::new(KeyCode::Char('j'), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            KeyBinding::parse("ctrl+U").unwrap(),
+            KeyBinding::new(KeyCode::Char('u'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(
+            KeyBinding::parse("shift+g").unwrap(),
+            KeyBinding::parse("G").unwrap()
+        );
+        assert_eq!(
+            KeyBinding::parse("shift+tab").unwrap(),
+            KeyBinding::new(KeyCode::BackTab, KeyModifiers::NONE)
+        );
+        assert_eq!(
+            KeyBinding::parse("PageDown").unwrap(),
+            KeyBinding::new(KeyCode::PageDown, KeyModifiers::NONE)
+        );
+        assert_eq!(
+            KeyBinding::parse("+").unwrap(),
+            KeyBinding::new(KeyCode::Char('+'), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            KeyBinding::parse("alt++").unwrap(),
+            KeyBinding::new(KeyCode::Char('+'), KeyModifiers::ALT)
+        );
+        assert_eq!(
+            KeyBinding::parse("f5").unwrap(),
+            KeyBinding::new(KeyCode::F(5), KeyModifiers::NONE)
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_keys() {
+        assert!(KeyBinding::parse("hyper+j").is_err());
+        assert!(KeyBinding::parse("notakey").is_err());
+        assert!(KeyBinding::parse("shift+enter").is_err());
+        assert!(KeyBinding::parse("f13").is_err());
+        assert!(parse_sequence("a b c").is_err());
+        assert!(parse_sequence("  ").is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(KeyBinding::parse("ctrl+u").unwrap().display(), "^U");
+        assert_eq!(KeyBinding::parse("alt+x").unwrap().display(), "M-x");
+        assert_eq!(KeyBinding::parse("down").unwrap().display(), "↓");
+        assert_eq!(display_sequence(&parse_sequence("g g").unwrap()), "gg");
+    }
+
+    #[test]
+    fn test_default_keymap_lookup() {
+        let keymap = Keymap::default();
+        assert_eq!(
+            keymap.lookup(KeyMode::Sidebar, None, &char_key('j')),
+            KeyLookup::Action(Action::Down)
+        );
+        assert_eq!(
+            keymap.lookup(
+                KeyMode::Sidebar,
+                None,
+                &key(KeyCode::Down, KeyModifiers::NONE)
+            ),
+            KeyLookup::Action(Action::Down)
+        );
+        assert_eq!(
+            keymap.lookup(
+                KeyMode::Sidebar,
+                None,
+                &key(KeyCode::Char('j'), KeyModifiers::CONTROL)
+            ),
+            KeyLookup::Action(Action::ScrollDown)
+        );
+        // Shift is implied by the character
+        assert_eq!(
+            keymap.lookup(
+                KeyMode::Sidebar,
+                None,
+                &key(KeyCode::Char('G'), KeyModifiers::SHIFT)
+            ),
+            KeyLookup::Action(Action::Bottom)
+        );
+        assert_eq!(
+            keymap.lookup(KeyMode::Sidebar, None, &char_key('z')),
+            KeyLookup::Unbound
+        );
+        // Same key, different mode
+        assert_eq!(
+            keymap.lookup(KeyMode::LivePane, None, &char_key('v')),
+            KeyLookup::Action(Action::Back)
+        );
+    }
+
+    #[test]
+    fn test_sequence_lookup() {
+        let keymap = Keymap::default();
+        let g = KeyBinding::parse("g").unwrap();
+        assert_eq!(
+            keymap.lookup(KeyMode::History, None, &char_key('g')),
+            KeyLookup::Pending(g)
+        );
+        assert_eq!(
+            keymap.lookup(KeyMode::History, Some(g), &char_key('g')),
+            KeyLookup::Action(Action::Top)
+        );
+        // A different key after the prefix is handled on its own
+        assert_eq!(
+            keymap.lookup(KeyMode::History, Some(g), &char_key('j')),
+            KeyLookup::Action(Action::Down)
+        );
+    }
+
+    #[test]
+    fn test_override_replaces_defaults() {
+        let config = keys_config("sidebar", &[("down", &["ctrl+n"]), ("kill", &[])]);
+        let keymap = Keymap::from_config(&config).unwrap();
+        assert_eq!(
+            keymap.lookup(KeyMode::Sidebar, None, &char_key('j')),
+            KeyLookup::Unbound
+        );
+        assert_eq!(
+            keymap.lookup(
+                KeyMode::Sidebar,
+                None,
+                &key(KeyCode::Char('n'), KeyModifiers::CONTROL)
+            ),
+            KeyLookup::Action(Action::Down)
+        );
+        assert_eq!(
+            keymap.lookup(KeyMode::Sidebar, None, &char_key('x')),
+            KeyLookup::Unbound
+        );
+        assert_eq!(keymap.hint(KeyMode::Sidebar, &[Action::Kill]), None);
+    }
+
+    #[test]
+    fn test_unknown_action_rejected() {
+        let config = keys_config("history", &[("jump", &["enter"])]);
+        let err = Keymap::from_config(&config).unwrap_err().to_string();
+        assert!(
+            err.contains("unknown action 'jump' in [keys.history]"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_invalid_key_rejected() {
+        let config = keys_config("input", &[("send", &["ctrl+enterr"])]);
+        let err = Keymap::from_config(&config).unwrap_err().to_string();
+        assert!(err.contains("[keys.input] send"), "{}", err);
+    }
+
+    #[test]
+    fn test_conflict_detected() {
+        let config = keys_config("sidebar", &[("quit", &["x"])]);
+        let err = Keymap::from_config(&config).unwrap_err().to_string();
+        assert!(
+            err.contains("'x' is bound to both 'kill' and 'quit' in [keys.sidebar]"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_prefix_conflict_detected() {
+        let config = keys_config("live_pane", &[("yank", &["g"])]);
+        let err = Keymap::from_config(&config).unwrap_err().to_string();
+        assert!(err.contains("conflicts with sequence 'gg'"), "{}", err);
+    }
+
+    #[test]
+    fn test_same_key_in_different_modes_allowed() {
+        let config = keys_config("history", &[("back", &["x"])]);
+        assert!(Keymap::from_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_hint() {
+        let keymap = Keymap::default();
+        assert_eq!(
+            keymap
+                .hint(KeyMode::Sidebar, &[Action::Down, Action::Up])
+                .as_deref(),
+            Some("j/k")
+        );
+        assert_eq!(
+            keymap.hint(KeyMode::History, &[Action::Back]).as_deref(),
+            Some("Esc/q")
+        );
+        assert_eq!(
+            keymap.hint(KeyMode::History, &[Action::Top]).as_deref(),
+            Some("gg")
+        );
+    }
+
+    #[test]
+    fn test_help_entries() {
+        let keymap = Keymap::default();
+        let entries = keymap.help_entries(KeyMode::Sidebar);
+        assert_eq!(entries[0], (Action::Down, "j, ↓".to_string()));
+        assert!(entries.contains(&(Action::Palette, "^P".to_string())));
+        // Unbound actions are listed without keys
+        assert!(entries.contains(&(Action::CycleTheme, String::new())));
+        assert!(keymap
+            .help_entries(KeyMode::History)
+            .contains(&(Action::Help, "?".to_string())));
+    }
+
+    #[test]
+    fn test_palette_matches() {
+        assert_eq!(palette_matches("").len(), PALETTE_ACTIONS.len());
+        assert_eq!(palette_matches("")[0], Action::Jump);
+        assert_eq!(palette_matches("live")[0], Action::LivePane);
+        assert_eq!(palette_matches("theme")[0], Action::CycleTheme);
+        assert_eq!(palette_matches("split")[0], Action::AddPane);
+        assert!(palette_matches("kill pane").contains(&Action::Kill));
+        assert!(palette_matches("zzzz").is_empty());
+    }
+
+    #[test]
+    fn test_palette_actions_are_sidebar_actions() {
+        let sidebar = default_bindings(KeyMode::Sidebar);
+        for action in PALETTE_ACTIONS {
+            assert!(sidebar.iter().any(|(a, _)| *a == action), "{:?}", action);
+        }
+    }
+}
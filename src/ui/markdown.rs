@@ -1,14 +1,11 @@
+use super::theme::Theme;
 use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
 use ratatui::{
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
 };
 use std::sync::OnceLock;
-use syntect::{
-    easy::HighlightLines,
-    highlighting::{Theme, ThemeSet},
-    parsing::SyntaxSet,
-};
+use syntect::{easy::HighlightLines, highlighting::ThemeSet, parsing::SyntaxSet};
 
 // ---------------------------------------------------------------------------
 // Syntect resource cache (loaded once, reused across renders)
@@ -16,34 +13,43 @@ use syntect::{
 
 struct HighlightResources {
     syntax_set: SyntaxSet,
-    theme: Theme,
+    theme_set: ThemeSet,
 }
 
-/// Returns the shared highlight resources, or `None` if loading failed.
-fn highlight_resources() -> Option<&'static HighlightResources> {
-    static RESOURCES: OnceLock<Option<HighlightResources>> = OnceLock::new();
-    RESOURCES
-        .get_or_init(|| {
-            let syntax_set = SyntaxSet::load_defaults_newlines();
-            let theme_set = ThemeSet::load_defaults();
-            let theme = theme_set.themes.get("base16-ocean.dark")?.clone();
-            Some(HighlightResources { syntax_set, theme })
-        })
-        .as_ref()
+/// Returns the shared highlight resources.
+fn highlight_resources() -> &'static HighlightResources {
+    static RESOURCES: OnceLock<HighlightResources> = OnceLock::new();
+    RESOURCES.get_or_init(|| HighlightResources {
+        syntax_set: SyntaxSet::load_defaults_newlines(),
+        theme_set: ThemeSet::load_defaults(),
+    })
+}
+
+/// Names of the bundled syntect themes (valid `syntax_theme` values).
+pub fn syntax_theme_names() -> Vec<String> {
+    highlight_resources()
+        .theme_set
+        .themes
+        .keys()
+        .cloned()
+        .collect()
 }
 
 // ---------------------------------------------------------------------------
 // Code block highlighting
 // ---------------------------------------------------------------------------
 
-/// Syntax-highlight a code block. Falls back to plain gray text on any error.
-fn highlight_code_block(code: &str, lang: Option<&str>) -> Vec<Line<'static>> {
-    if let Some(res) = highlight_resources() {
+/// Syntax-highlight a code block with the theme's syntect theme.
+/// Falls back to plain text on any error.
+fn highlight_code_block(code: &str, lang: Option<&str>, theme: &Theme) -> Vec<Line<'static>> {
+    let res = highlight_resources();
+    let plain = Style::default().fg(theme.text_secondary);
+    if let Some(syntax_theme) = res.theme_set.themes.get(&theme.syntax_theme) {
         let syntax = lang
             .and_then(|l| res.syntax_set.find_syntax_by_token(l))
             .unwrap_or_else(|| res.syntax_set.find_syntax_plain_text());
 
-        let mut highlighter = HighlightLines::new(syntax, &res.theme);
+        let mut highlighter = HighlightLines::new(syntax, syntax_theme);
         let mut lines = Vec::new();
 
         for line_str in code.lines() {
@@ -55,32 +61,22 @@ fn highlight_code_block(code: &str, lang: Option<&str>) -> Vec<Line<'static>> {
                             let text_owned = seg.1.to_string();
                             match syntect_tui::into_span(seg) {
                                 Ok(s) => Span::styled(text_owned, s.style),
-                                Err(_) => {
-                                    Span::styled(text_owned, Style::default().fg(Color::Gray))
-                                }
+                                Err(_) => Span::styled(text_owned, plain),
                             }
                         })
                         .collect();
                     lines.push(Line::from(spans));
                 }
                 Err(_) => {
-                    lines.push(Line::from(Span::styled(
-                        line_str.to_string(),
-                        Style::default().fg(Color::Gray),
-                    )));
+                    lines.push(Line::from(Span::styled(line_str.to_string(), plain)));
                 }
             }
         }
         lines
     } else {
-        // Syntect unavailable — plain text fallback
+        // Syntect theme unavailable — plain text fallback
         code.lines()
-            .map(|l| {
-                Line::from(Span::styled(
-                    l.to_string(),
-                    Style::default().fg(Color::Gray),
-                ))
-            })
+            .map(|l| Line::from(Span::styled(l.to_string(), plain)))
             .collect()
     }
 }
@@ -90,12 +86,12 @@ fn highlight_code_block(code: &str, lang: Option<&str>) -> Vec<Line<'static>> {
 // ---------------------------------------------------------------------------
 
 /// Simple newline-split fallback used when markdown parsing encounters issues.
-fn plain_text_lines(text: &str) -> Vec<Line<'static>> {
+fn plain_text_lines(text: &str, theme: &Theme) -> Vec<Line<'static>> {
     text.lines()
         .map(|l| {
             Line::from(Span::styled(
                 l.to_string(),
-                Style::default().fg(Color::Gray),
+                Style::default().fg(theme.text_secondary),
             ))
         })
         .collect()
@@ -110,15 +106,15 @@ fn plain_text_lines(text: &str) -> Vec<Line<'static>> {
 /// visual row, so `lines.len()` gives an accurate scroll height.
 ///
 /// This function never fails — on any internal error it falls back to
-/// plain gray text split by newlines.
-pub fn markdown_to_lines(text: &str, width: usize) -> Vec<Line<'static>> {
+/// plain text split by newlines.
+pub fn markdown_to_lines(text: &str, width: usize, theme: &Theme) -> Vec<Line<'static>> {
     // Guard: catch panics from pulldown-cmark (shouldn't happen, but be safe)
     match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-        let logical = markdown_to_lines_inner(text, width);
+        let logical = markdown_to_lines_inner(text, width, theme);
         wrap_lines(logical, width)
     })) {
         Ok(lines) => lines,
-        Err(_) => plain_text_lines(text),
+        Err(_) => plain_text_lines(text, theme),
     }
 }
 
@@ -128,18 +124,19 @@ pub fn markdown_to_lines_truncated(
     text: &str,
     width: usize,
     max_lines: usize,
+    theme: &Theme,
 ) -> Vec<Line<'static>> {
     if max_lines == 0 {
         return Vec::new();
     }
-    let all_lines = markdown_to_lines(text, width);
+    let all_lines = markdown_to_lines(text, width, theme);
     if all_lines.len() <= max_lines {
         all_lines
     } else {
         let mut truncated: Vec<Line<'static>> = all_lines.into_iter().take(max_lines).collect();
         truncated.push(Line::from(Span::styled(
             "...",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         )));
         truncated
     }
@@ -209,13 +206,13 @@ fn wrap_lines(lines: Vec<Line<'static>>, width: usize) -> Vec<Line<'static>> {
 // Internal implementation
 // ---------------------------------------------------------------------------
 
-fn markdown_to_lines_inner(text: &str, width: usize) -> Vec<Line<'static>> {
+fn markdown_to_lines_inner(text: &str, width: usize, theme: &Theme) -> Vec<Line<'static>> {
     let options = Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TABLES;
     let parser = Parser::new_ext(text, options);
 
     let mut lines: Vec<Line<'static>> = Vec::new();
     let mut current_spans: Vec<Span<'static>> = Vec::new();
-    let mut style_stack: Vec<Style> = vec![Style::default().fg(Color::Gray)];
+    let mut style_stack: Vec<Style> = vec![Style::default().fg(theme.text_secondary)];
 
     let mut in_code_block = false;
     let mut code_block_lang: Option<String> = None;
@@ -238,7 +235,7 @@ fn markdown_to_lines_inner(text: &str, width: usize) -> Vec<Line<'static>> {
             // -- Block-level events --
             Event::Start(Tag::Heading { level, .. }) => {
                 flush_line(&mut current_spans, &mut lines);
-                style_stack.push(heading_style(level));
+                style_stack.push(heading_style(level, theme));
             }
             Event::End(TagEnd::Heading(_)) => {
                 flush_line(&mut current_spans, &mut lines);
@@ -270,10 +267,10 @@ fn markdown_to_lines_inner(text: &str, width: usize) -> Vec<Line<'static>> {
             }
             Event::End(TagEnd::CodeBlock) => {
                 let highlighted =
-                    highlight_code_block(&code_block_content, code_block_lang.as_deref());
-                lines.push(code_block_separator());
+                    highlight_code_block(&code_block_content, code_block_lang.as_deref(), theme);
+                lines.push(code_block_separator(theme));
                 lines.extend(highlighted);
-                lines.push(code_block_separator());
+                lines.push(code_block_separator(theme));
                 lines.push(Line::from(""));
                 in_code_block = false;
                 code_block_lang = None;
@@ -300,7 +297,7 @@ fn markdown_to_lines_inner(text: &str, width: usize) -> Vec<Line<'static>> {
                 } else {
                     format!("{}- ", indent)
                 };
-                current_spans.push(Span::styled(bullet, Style::default().fg(Color::Cyan)));
+                current_spans.push(Span::styled(bullet, Style::default().fg(theme.accent)));
             }
             Event::End(TagEnd::Item) => {
                 flush_line(&mut current_spans, &mut lines);
@@ -308,7 +305,7 @@ fn markdown_to_lines_inner(text: &str, width: usize) -> Vec<Line<'static>> {
 
             Event::Start(Tag::BlockQuote(_)) => {
                 flush_line(&mut current_spans, &mut lines);
-                current_spans.push(Span::styled("│ ", Style::default().fg(Color::DarkGray)));
+                current_spans.push(Span::styled("│ ", Style::default().fg(theme.muted)));
             }
             Event::End(TagEnd::BlockQuote(_)) => {
                 flush_line(&mut current_spans, &mut lines);
@@ -319,7 +316,7 @@ fn markdown_to_lines_inner(text: &str, width: usize) -> Vec<Line<'static>> {
                 flush_line(&mut current_spans, &mut lines);
                 lines.push(Line::from(Span::styled(
                     "────────────────────────────────",
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme.muted),
                 )));
                 lines.push(Line::from(""));
             }
@@ -363,12 +360,12 @@ fn markdown_to_lines_inner(text: &str, width: usize) -> Vec<Line<'static>> {
                         if in_table {
                             current_cell_spans.push(Span::styled(
                                 format!(" ({})", url),
-                                Style::default().fg(Color::DarkGray),
+                                Style::default().fg(theme.muted),
                             ));
                         } else {
                             current_spans.push(Span::styled(
                                 format!(" ({})", url),
-                                Style::default().fg(Color::DarkGray),
+                                Style::default().fg(theme.muted),
                             ));
                         }
                     }
@@ -379,12 +376,12 @@ fn markdown_to_lines_inner(text: &str, width: usize) -> Vec<Line<'static>> {
                 if in_table {
                     current_cell_spans.push(Span::styled(
                         format!(" {} ", code_text),
-                        Style::default().fg(Color::Yellow).bg(Color::DarkGray),
+                        Style::default().fg(theme.highlight).bg(theme.code_bg),
                     ));
                 } else {
                     current_spans.push(Span::styled(
                         format!(" {} ", code_text),
-                        Style::default().fg(Color::Yellow).bg(Color::DarkGray),
+                        Style::default().fg(theme.highlight).bg(theme.code_bg),
                     ));
                 }
             }
@@ -434,17 +431,17 @@ fn markdown_to_lines_inner(text: &str, width: usize) -> Vec<Line<'static>> {
                 table_rows.clear();
             }
             Event::End(TagEnd::Table) => {
-                render_table(&table_header, &table_rows, width, &mut lines);
+                render_table(&table_header, &table_rows, width, theme, &mut lines);
                 in_table = false;
                 lines.push(Line::from(""));
             }
             Event::Start(Tag::TableHead) => {
                 in_table_header = true;
                 current_row.clear();
-                // Header cells inherit Cyan+Bold as their base style
+                // Header cells inherit accent+Bold as their base style
                 style_stack.push(
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(theme.accent)
                         .add_modifier(Modifier::BOLD),
                 );
             }
@@ -509,26 +506,24 @@ fn current_style(stack: &[Style]) -> Style {
 }
 
 /// Style for heading levels.
-fn heading_style(level: HeadingLevel) -> Style {
+fn heading_style(level: HeadingLevel, theme: &Theme) -> Style {
     match level {
         HeadingLevel::H1 => Style::default()
-            .fg(Color::Magenta)
+            .fg(theme.attention)
             .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
         HeadingLevel::H2 => Style::default()
-            .fg(Color::Cyan)
+            .fg(theme.accent)
             .add_modifier(Modifier::BOLD),
         HeadingLevel::H3 => Style::default()
-            .fg(Color::Green)
-            .add_modifier(Modifier::BOLD),
-        _ => Style::default()
-            .fg(Color::White)
+            .fg(theme.success)
             .add_modifier(Modifier::BOLD),
+        _ => Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
     }
 }
 
 /// Visual separator line for code blocks.
-fn code_block_separator() -> Line<'static> {
-    Line::from(Span::styled("───", Style::default().fg(Color::DarkGray)))
+fn code_block_separator(theme: &Theme) -> Line<'static> {
+    Line::from(Span::styled("───", Style::default().fg(theme.muted)))
 }
 
 // ---------------------------------------------------------------------------
@@ -542,6 +537,7 @@ fn render_table(
     header: &[Vec<Span<'static>>],
     rows: &[Vec<Vec<Span<'static>>>],
     max_width: usize,
+    theme: &Theme,
     lines: &mut Vec<Line<'static>>,
 ) {
     let num_cols = header
@@ -571,9 +567,9 @@ fn render_table(
     if total_content + overhead > max_width {
         if max_width > overhead {
             let available = max_width - overhead;
-            if total_content > 0 {
-                for w in &mut col_widths {
-                    *w = (*w * available / total_content).max(1);
+            for w in &mut col_widths {
+                if let Some(scaled) = (*w * available).checked_div(total_content) {
+                    *w = scaled.max(1);
                 }
             }
             // max(1) can cause the sum to exceed available; trim the widest
@@ -597,7 +593,7 @@ fn render_table(
         }
     }
 
-    let border = Style::default().fg(Color::DarkGray);
+    let border = Style::default().fg(theme.muted);
 
     // ┌───┬───┐
     lines.push(table_border_line(&col_widths, "┌", "┬", "┐", border));
@@ -714,25 +710,27 @@ fn unicode_display_width(s: &str) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::ThemeName;
+    use ratatui::style::Color;
 
     const W: usize = 200; // generous width so wrapping doesn't interfere
 
     #[test]
     fn test_empty_input() {
-        let lines = markdown_to_lines("", W);
+        let lines = markdown_to_lines("", W, &Theme::default());
         assert!(lines.is_empty());
     }
 
     #[test]
     fn test_plain_text() {
-        let lines = markdown_to_lines("hello world", W);
+        let lines = markdown_to_lines("hello world", W, &Theme::default());
         assert_eq!(lines.len(), 1);
         assert_eq!(lines[0].spans[0].content.as_ref(), "hello world");
     }
 
     #[test]
     fn test_heading() {
-        let lines = markdown_to_lines("# Title", W);
+        let lines = markdown_to_lines("# Title", W, &Theme::default());
         assert!(!lines.is_empty());
         let first = &lines[0];
         assert_eq!(first.spans[0].content.as_ref(), "Title");
@@ -741,7 +739,7 @@ mod tests {
 
     #[test]
     fn test_bold_text() {
-        let lines = markdown_to_lines("**bold**", W);
+        let lines = markdown_to_lines("**bold**", W, &Theme::default());
         assert!(!lines.is_empty());
         let span = &lines[0].spans[0];
         assert_eq!(span.content.as_ref(), "bold");
@@ -750,7 +748,7 @@ mod tests {
 
     #[test]
     fn test_italic_text() {
-        let lines = markdown_to_lines("*italic*", W);
+        let lines = markdown_to_lines("*italic*", W, &Theme::default());
         assert!(!lines.is_empty());
         let span = &lines[0].spans[0];
         assert_eq!(span.content.as_ref(), "italic");
@@ -759,7 +757,7 @@ mod tests {
 
     #[test]
     fn test_nested_bold_italic() {
-        let lines = markdown_to_lines("***bold italic***", W);
+        let lines = markdown_to_lines("***bold italic***", W, &Theme::default());
         assert!(!lines.is_empty());
         let span = &lines[0].spans[0];
         assert_eq!(span.content.as_ref(), "bold italic");
@@ -769,7 +767,7 @@ mod tests {
 
     #[test]
     fn test_inline_code() {
-        let lines = markdown_to_lines("`code`", W);
+        let lines = markdown_to_lines("`code`", W, &Theme::default());
         assert!(!lines.is_empty());
         let span = &lines[0].spans[0];
         assert_eq!(span.content.as_ref(), " code ");
@@ -780,7 +778,7 @@ mod tests {
     #[test]
     fn test_code_block_no_lang() {
         let input = "```\nfn main() {}\n```";
-        let lines = markdown_to_lines(input, W);
+        let lines = markdown_to_lines(input, W, &Theme::default());
         // Should have separator + code + separator
         assert!(lines.len() >= 3);
     }
@@ -788,7 +786,7 @@ mod tests {
     #[test]
     fn test_code_block_unknown_lang() {
         let input = "```xyzunknown\nsome code\n```";
-        let lines = markdown_to_lines(input, W);
+        let lines = markdown_to_lines(input, W, &Theme::default());
         // Should not panic, should produce lines
         assert!(!lines.is_empty());
     }
@@ -796,7 +794,7 @@ mod tests {
     #[test]
     fn test_bullet_list() {
         let input = "- item1\n- item2";
-        let lines = markdown_to_lines(input, W);
+        let lines = markdown_to_lines(input, W, &Theme::default());
         assert!(lines.len() >= 2);
         // First non-empty line should contain a bullet
         let first_content: String = lines[0].spans.iter().map(|s| s.content.as_ref()).collect();
@@ -807,7 +805,7 @@ mod tests {
     #[test]
     fn test_truncation() {
         let input = "line1\n\nline2\n\nline3\n\nline4\n\nline5";
-        let lines = markdown_to_lines_truncated(input, W, 3);
+        let lines = markdown_to_lines_truncated(input, W, 3, &Theme::default());
         assert_eq!(lines.len(), 4); // 3 lines + "..."
         assert_eq!(lines.last().unwrap().spans[0].content.as_ref(), "...");
     }
@@ -815,14 +813,14 @@ mod tests {
     #[test]
     fn test_truncation_no_truncation_needed() {
         let input = "short";
-        let lines = markdown_to_lines_truncated(input, W, 100);
+        let lines = markdown_to_lines_truncated(input, W, 100, &Theme::default());
         assert_eq!(lines.len(), 1);
     }
 
     #[test]
     fn test_wrap_long_line() {
         let long = "a".repeat(160);
-        let lines = markdown_to_lines(&long, 80);
+        let lines = markdown_to_lines(&long, 80, &Theme::default());
         // 160 chars at width 80 => 2 visual rows
         assert_eq!(lines.len(), 2);
     }
@@ -831,7 +829,7 @@ mod tests {
     fn test_wrap_preserves_styles() {
         // Bold text wider than viewport should wrap while keeping bold style
         let input = format!("**{}**", "b".repeat(100));
-        let lines = markdown_to_lines(&input, 50);
+        let lines = markdown_to_lines(&input, 50, &Theme::default());
         assert!(lines.len() >= 2);
         for line in &lines {
             for span in &line.spans {
@@ -843,7 +841,7 @@ mod tests {
     #[test]
     fn test_cjk_in_code_block() {
         let input = "```\nlet x = \"日本語\";\n```";
-        let lines = markdown_to_lines(input, W);
+        let lines = markdown_to_lines(input, W, &Theme::default());
         // Should not panic and should produce content
         assert!(!lines.is_empty());
     }
@@ -851,7 +849,7 @@ mod tests {
     #[test]
     fn test_horizontal_rule() {
         let input = "above\n\n---\n\nbelow";
-        let lines = markdown_to_lines(input, W);
+        let lines = markdown_to_lines(input, W, &Theme::default());
         let has_rule = lines
             .iter()
             .any(|l| l.spans.iter().any(|s| s.content.contains('─')));
@@ -861,7 +859,7 @@ mod tests {
     #[test]
     fn test_nested_ordered_list() {
         let input = "1. outer1\n   1. inner1\n   2. inner2\n2. outer2";
-        let lines = markdown_to_lines(input, W);
+        let lines = markdown_to_lines(input, W, &Theme::default());
         let all_text: String = lines
             .iter()
             .flat_map(|l| l.spans.iter().map(|s| s.content.as_ref()))
@@ -876,7 +874,7 @@ mod tests {
     #[test]
     fn test_link_shows_url() {
         let input = "[click here](https://example.com)";
-        let lines = markdown_to_lines(input, W);
+        let lines = markdown_to_lines(input, W, &Theme::default());
         let all_text: String = lines
             .iter()
             .flat_map(|l| l.spans.iter().map(|s| s.content.as_ref()))
@@ -894,14 +892,14 @@ mod tests {
     #[test]
     fn test_truncation_zero_max_lines() {
         let input = "some text";
-        let lines = markdown_to_lines_truncated(input, W, 0);
+        let lines = markdown_to_lines_truncated(input, W, 0, &Theme::default());
         assert!(lines.is_empty());
     }
 
     #[test]
     fn test_table_rendering() {
         let input = "| H1 | H2 |\n|---|---|\n| a | b |\n| c | d |";
-        let lines = markdown_to_lines(input, W);
+        let lines = markdown_to_lines(input, W, &Theme::default());
         let all_text: String = lines
             .iter()
             .flat_map(|l| l.spans.iter().map(|s| s.content.as_ref()))
@@ -926,7 +924,7 @@ mod tests {
     #[test]
     fn test_table_with_cjk() {
         let input = "| 名前 | 値 |\n|---|---|\n| テスト | 123 |";
-        let lines = markdown_to_lines(input, W);
+        let lines = markdown_to_lines(input, W, &Theme::default());
         let all_text: String = lines
             .iter()
             .flat_map(|l| l.spans.iter().map(|s| s.content.as_ref()))
@@ -938,7 +936,7 @@ mod tests {
     #[test]
     fn test_table_with_inline_code() {
         let input = "| Col |\n|---|\n| `code` |";
-        let lines = markdown_to_lines(input, W);
+        let lines = markdown_to_lines(input, W, &Theme::default());
         let all_text: String = lines
             .iter()
             .flat_map(|l| l.spans.iter().map(|s| s.content.as_ref()))
@@ -952,7 +950,7 @@ mod tests {
     #[test]
     fn test_table_preserves_inline_styles() {
         let input = "| Col |\n|---|\n| **bold** and `code` |";
-        let lines = markdown_to_lines(input, W);
+        let lines = markdown_to_lines(input, W, &Theme::default());
         let has_bold = lines.iter().any(|l| {
             l.spans.iter().any(|s| {
                 s.content.contains("bold") && s.style.add_modifier.contains(Modifier::BOLD)
@@ -973,7 +971,7 @@ mod tests {
     #[test]
     fn test_table_header_style() {
         let input = "| Header |\n|---|\n| body |";
-        let lines = markdown_to_lines(input, W);
+        let lines = markdown_to_lines(input, W, &Theme::default());
         let has_cyan_bold = lines.iter().any(|l| {
             l.spans.iter().any(|s| {
                 s.content.contains("Header")
@@ -988,7 +986,7 @@ mod tests {
     fn test_table_narrow_width() {
         let input = "| Header1 | Header2 |\n|---|---|\n| cell1 | cell2 |";
         // Very narrow width — columns must shrink to fit
-        let lines = markdown_to_lines(input, 20);
+        let lines = markdown_to_lines(input, 20, &Theme::default());
         assert!(
             !lines.is_empty(),
             "should produce lines even at narrow width"
@@ -1019,13 +1017,13 @@ mod tests {
     fn test_table_extremely_narrow_width() {
         // 2 columns: overhead = 3*2+1 = 7.  width=8 → only 1 char available.
         let input = "| AB | CD |\n|---|---|\n| ef | gh |";
-        let lines = markdown_to_lines(input, 8);
+        let lines = markdown_to_lines(input, 8, &Theme::default());
         assert!(!lines.is_empty(), "should not be empty at width 8");
         // width=4 → narrower than overhead; should still not panic.
-        let lines2 = markdown_to_lines(input, 4);
+        let lines2 = markdown_to_lines(input, 4, &Theme::default());
         assert!(!lines2.is_empty(), "should not be empty at width 4");
         // width=1 → extreme edge; must not panic.
-        let lines3 = markdown_to_lines(input, 1);
+        let lines3 = markdown_to_lines(input, 1, &Theme::default());
         assert!(!lines3.is_empty(), "should not be empty at width 1");
     }
 
@@ -1033,10 +1031,35 @@ mod tests {
     fn test_table_many_columns_narrow() {
         // 5 columns: overhead = 3*5+1 = 16.  width=15 → less than overhead.
         let input = "| a | b | c | d | e |\n|---|---|---|---|---|\n| 1 | 2 | 3 | 4 | 5 |";
-        let lines = markdown_to_lines(input, 15);
+        let lines = markdown_to_lines(input, 15, &Theme::default());
         assert!(
             !lines.is_empty(),
             "many-column table at narrow width should not panic"
         );
     }
+
+    #[test]
+    fn test_light_theme_colors() {
+        let theme = Theme::builtin(ThemeName::Light);
+        let lines = markdown_to_lines("text `code`", W, &theme);
+        let code = lines[0]
+            .spans
+            .iter()
+            .find(|s| s.content.contains("code"))
+            .unwrap();
+        assert_eq!(code.style.fg, Some(theme.highlight));
+        assert_eq!(code.style.bg, Some(theme.code_bg));
+        assert_eq!(lines[0].spans[0].style.fg, Some(theme.text_secondary));
+    }
+
+    #[test]
+    fn test_unknown_syntax_theme_falls_back_to_plain() {
+        let theme = Theme {
+            syntax_theme: "missing".to_string(),
+            ..Theme::default()
+        };
+        let lines = highlight_code_block("fn main() {}", Some("rust"), &theme);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].spans[0].style.fg, Some(theme.text_secondary));
+    }
 }
@@ -1,11 +1,17 @@
 pub mod app;
 pub mod event;
+pub mod fuzzy;
+pub mod history_search;
 pub mod input_buffer;
+pub mod keymap;
 pub mod markdown;
+pub mod refresh_worker;
 pub mod render;
+pub mod scan_worker;
 pub mod session;
 pub mod slash_commands;
 pub mod terminal_session;
+pub mod theme;
 pub mod toast;
 
 pub use app::App;
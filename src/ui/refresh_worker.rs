@@ -0,0 +1,186 @@
+//! Background session discovery and transcript parsing.
+//!
+//! Session discovery (`crate::discovery`) lists panes, builds the process
+//! tree, looks up git info and reads transcripts; here it runs on a worker
+//! thread, which publishes the results to the UI over a channel. Requests
+//! queued while the worker is busy are coalesced into one, and a full
+//! refresh cancels whatever refresh is in progress.
+
+use crate::discovery::{ClaudeSession, Discovery};
+use crate::transcript::{DetectionConfig, SessionInfo, TranscriptTails};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+
+/// Work for the refresh worker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefreshRequest {
+    /// Rediscover sessions (panes, processes, git info) and read their transcripts.
+    Full { clear_git_cache: bool },
+    /// Re-read the transcripts of the sessions found by the last full refresh.
+    Transcripts,
+}
+
+impl RefreshRequest {
+    /// One request covering both `self` and `other`.
+    fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Full { clear_git_cache: a }, Self::Full { clear_git_cache: b }) => Self::Full {
+                clear_git_cache: a || b,
+            },
+            (full @ Self::Full { .. }, _) | (_, full @ Self::Full { .. }) => full,
+            (Self::Transcripts, Self::Transcripts) => Self::Transcripts,
+        }
+    }
+}
+
+/// Result of a refresh, published to the UI.
+pub enum RefreshUpdate {
+    /// Detected sessions (metadata not yet attached, unsorted) and the
+    /// current workspace, if it could be determined.
+    Sessions {
+        workspace: Option<String>,
+        sessions: Vec<ClaudeSession>,
+    },
+    /// Fresh transcript info of known sessions, by pane ID.
+    Transcripts(Vec<(u32, SessionInfo)>),
+    /// A full refresh failed.
+    Error(String),
+}
+
+/// Handle to the refresh worker thread. Dropping it stops the worker.
+pub struct RefreshWorker {
+    tx: Sender<RefreshRequest>,
+    rx: Receiver<RefreshUpdate>,
+    /// Tells the worker to abandon the refresh in progress
+    cancel: Arc<AtomicBool>,
+}
+
+impl RefreshWorker {
+    /// Spawn the worker. Transcripts are read through `tails`, which the UI
+    /// shares for reading history, and their status detected with `detection`.
+    pub fn spawn(tails: Arc<Mutex<TranscriptTails>>, detection: DetectionConfig) -> Self {
+        let (request_tx, request_rx) = mpsc::channel();
+        let (update_tx, update_rx) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+
+        let mut discovery = Discovery::new(tails, detection);
+        let worker_cancel = cancel.clone();
+        std::thread::spawn(move || {
+            run(&mut discovery, &request_rx, &update_tx, &worker_cancel);
+        });
+
+        Self {
+            tx: request_tx,
+            rx: update_rx,
+            cancel,
+        }
+    }
+
+    /// Queue a refresh. A full refresh cancels the one in progress.
+    pub fn request(&self, request: RefreshRequest) {
+        // Cancel before sending, so a cancelled worker always finds the new request
+        if matches!(request, RefreshRequest::Full { .. }) {
+            self.cancel.store(true, Ordering::Relaxed);
+        }
+        let _ = self.tx.send(request);
+    }
+
+    /// Published updates, oldest first, without blocking.
+    pub fn updates(&self) -> Vec<RefreshUpdate> {
+        self.rx.try_iter().collect()
+    }
+
+    /// Wait for the next update (used for the initial refresh).
+    pub fn wait(&self) -> Option<RefreshUpdate> {
+        self.rx.recv().ok()
+    }
+}
+
+impl Drop for RefreshWorker {
+    fn drop(&mut self) {
+        // The request channel closes with `tx`, ending the worker loop
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Worker loop: serve requests until the UI drops its handle.
+fn run(
+    discovery: &mut Discovery,
+    requests: &Receiver<RefreshRequest>,
+    updates: &Sender<RefreshUpdate>,
+    cancel: &AtomicBool,
+) {
+    let mut request = match requests.recv() {
+        Ok(request) => request,
+        Err(_) => return,
+    };
+    loop {
+        // Coalesce everything queued behind it
+        request = requests.try_iter().fold(request, RefreshRequest::merge);
+        cancel.store(false, Ordering::Relaxed);
+
+        let update = match request {
+            RefreshRequest::Full { clear_git_cache } => discovery
+                .full_refresh(clear_git_cache, cancel)
+                .map(|result| match result {
+                    Ok((workspace, sessions)) => RefreshUpdate::Sessions {
+                        workspace,
+                        sessions,
+                    },
+                    Err(e) => RefreshUpdate::Error(e.to_string()),
+                }),
+            RefreshRequest::Transcripts => discovery
+                .transcript_refresh(cancel)
+                .map(RefreshUpdate::Transcripts),
+        };
+
+        request = match update {
+            Some(update) => {
+                if updates.send(update).is_err() {
+                    return;
+                }
+                match requests.recv() {
+                    Ok(next) => next,
+                    Err(_) => return,
+                }
+            }
+            // Cancelled: a newer request is queued (or the UI is gone)
+            None => match requests.recv() {
+                Ok(next) => request.merge(next),
+                Err(_) => return,
+            },
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_full_wins() {
+        let full = RefreshRequest::Full {
+            clear_git_cache: false,
+        };
+        assert_eq!(full.merge(RefreshRequest::Transcripts), full);
+        assert_eq!(RefreshRequest::Transcripts.merge(full), full);
+        assert_eq!(
+            RefreshRequest::Transcripts.merge(RefreshRequest::Transcripts),
+            RefreshRequest::Transcripts
+        );
+    }
+
+    #[test]
+    fn test_merge_keeps_git_cache_clear() {
+        let clear = RefreshRequest::Full {
+            clear_git_cache: true,
+        };
+        let keep = RefreshRequest::Full {
+            clear_git_cache: false,
+        };
+        assert_eq!(keep.merge(clear), clear);
+        assert_eq!(clear.merge(keep), clear);
+        assert_eq!(clear.merge(RefreshRequest::Transcripts), clear);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::config::ListConfig;
 use crate::transcript::ConversationTurn;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
@@ -7,7 +8,9 @@ use ratatui::{
 use std::sync::Arc;
 use std::time::SystemTime;
 
+use super::history_search::HistorySearch;
 use super::session::ClaudeSession;
+use super::theme::Theme;
 
 #[path = "render/answer_select.rs"]
 mod answer_select;
@@ -15,16 +18,30 @@ mod answer_select;
 mod command_select;
 #[path = "render/footer.rs"]
 mod footer;
+#[path = "render/help.rs"]
+mod help;
 #[path = "render/history.rs"]
 mod history;
 #[path = "render/live.rs"]
 mod live;
+#[path = "render/palette.rs"]
+mod palette;
+#[path = "render/plan.rs"]
+mod plan;
+#[path = "render/recent.rs"]
+mod recent;
 #[path = "render/slash_complete.rs"]
 mod slash_complete;
+#[path = "render/stats.rs"]
+mod stats;
 #[path = "render/summary.rs"]
 mod summary;
 #[path = "render/terminal.rs"]
 pub(super) mod terminal;
+#[path = "render/transcript_search.rs"]
+mod transcript_search;
+#[path = "render/worktrees.rs"]
+mod worktrees;
 
 /// Cache entry for history detail view: ((text_hash, width), rendered_lines).
 pub type HistoryLinesCache = Option<((u64, usize), Vec<Line<'static>>)>;
@@ -51,9 +68,33 @@ pub enum DetailMode {
 
 /// Rendering context for the embedded terminal panel.
 pub struct TerminalRenderCtx<'a> {
+    /// Screen of the shown tab
     pub screen: &'a vt100::Screen,
     pub focused: bool,
-    pub title: &'a str,
+    /// Titles of all tabs, in tab bar order
+    pub titles: Vec<&'a str>,
+    /// Index of the shown tab in `titles`
+    pub active: usize,
+}
+
+/// Rendering context for the session list.
+pub struct ListRenderCtx<'a> {
+    pub sessions: &'a [ClaudeSession],
+    pub refreshing: bool,
+    pub animation_frame: u8,
+    pub current_workspace: &'a str,
+    pub list_config: ListConfig,
+    pub filter: Option<ListFilterCtx<'a>>,
+    pub theme: &'a Theme,
+}
+
+/// Session list filter state shown in the list block.
+pub struct ListFilterCtx<'a> {
+    pub query: &'a str,
+    /// Whether the query is currently being edited (shows a cursor).
+    pub editing: bool,
+    /// Number of sessions before filtering.
+    pub total: usize,
 }
 
 /// Rendering context for the details panel.
@@ -70,6 +111,10 @@ pub struct DetailsRenderCtx<'a> {
     pub history_list_state: &'a mut ListState,
     pub history_timestamps: &'a [Option<SystemTime>],
     pub cached_history_lines: &'a mut HistoryLinesCache,
+    pub history_search: &'a mut HistorySearch,
+    /// Label of a transcript opened from transcript search (replaces the
+    /// selected session's header in history mode)
+    pub history_source: Option<&'a str>,
     pub cached_preview_lines: &'a mut PreviewLinesCache,
     pub summary_scroll_offset: &'a mut usize,
     pub live_pane_bytes: Option<&'a [u8]>,
@@ -77,27 +122,17 @@ pub struct DetailsRenderCtx<'a> {
     pub live_pane_scroll_offset: &'a mut usize,
     pub cached_live_pane_lines: &'a mut LivePaneLinesCache,
     pub live_pane_error: bool,
+    pub theme: &'a Theme,
 }
 
 /// Render the session list.
 pub fn render_list(
     f: &mut ratatui::Frame,
     area: Rect,
-    sessions: &[ClaudeSession],
+    ctx: &ListRenderCtx<'_>,
     list_state: &mut ListState,
-    refreshing: bool,
-    animation_frame: u8,
-    current_workspace: &str,
 ) -> Option<Rect> {
-    summary::render_list(
-        f,
-        area,
-        sessions,
-        list_state,
-        refreshing,
-        animation_frame,
-        current_workspace,
-    )
+    summary::render_list(f, area, ctx, list_state)
 }
 
 /// Render the details panel.
@@ -109,7 +144,15 @@ pub fn render_details(
 ) {
     if ctx.detail_mode == DetailMode::Terminal {
         if let Some(tctx) = terminal_ctx {
-            terminal::render_terminal_pane(f, area, tctx.screen, tctx.focused, tctx.title);
+            terminal::render_terminal_pane(
+                f,
+                area,
+                tctx.screen,
+                tctx.focused,
+                &tctx.titles,
+                tctx.active,
+                ctx.theme,
+            );
         }
         return;
     }
@@ -120,7 +163,7 @@ pub fn render_details(
                 .direction(Direction::Vertical)
                 .constraints([Constraint::Length(2), Constraint::Min(0)])
                 .split(area);
-            summary::render_session_info_header(f, chunks[0], session);
+            summary::render_session_info_header(f, chunks[0], session, ctx.theme);
             chunks[1]
         } else {
             area
@@ -134,6 +177,7 @@ pub fn render_details(
             ctx.live_pane_scroll_offset,
             ctx.cached_live_pane_lines,
             ctx.live_pane_error,
+            ctx.theme,
         );
         return;
     }
@@ -143,12 +187,21 @@ pub fn render_details(
         DetailMode::HistoryList | DetailMode::HistoryDetail
     ) && !ctx.history_turns.is_empty()
     {
-        let content_area = if let Some(session) = ctx.selected.and_then(|i| ctx.sessions.get(i)) {
+        let session = ctx.selected.and_then(|i| ctx.sessions.get(i));
+        let content_area = if ctx.history_source.is_some() || session.is_some() {
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
                 .constraints([Constraint::Length(2), Constraint::Min(0)])
                 .split(area);
-            summary::render_session_info_header(f, chunks[0], session);
+            match (ctx.history_source, session) {
+                (Some(source), _) => {
+                    history::render_history_source_header(f, chunks[0], source, ctx.theme)
+                }
+                (None, Some(session)) => {
+                    summary::render_session_info_header(f, chunks[0], session, ctx.theme)
+                }
+                (None, None) => {}
+            }
             chunks[1]
         } else {
             area
@@ -162,6 +215,8 @@ pub fn render_details(
                     ctx.history_turns,
                     ctx.history_list_state,
                     ctx.history_timestamps,
+                    ctx.history_search,
+                    ctx.theme,
                 );
             }
             DetailMode::HistoryDetail => {
@@ -172,6 +227,8 @@ pub fn render_details(
                     ctx.history_index,
                     ctx.history_scroll_offset,
                     ctx.cached_history_lines,
+                    ctx.history_search,
+                    ctx.theme,
                 );
             }
             _ => unreachable!(),
@@ -188,19 +245,34 @@ pub fn render_footer(
     f: &mut ratatui::Frame,
     area: Rect,
     input_mode: bool,
+    filter_editing: bool,
+    text_prompt: Option<(&str, &str, &str)>,
     detail_mode: DetailMode,
     toast: Option<&super::toast::Toast>,
     kill_confirm: Option<&(u32, String)>,
-    add_pane_pending: Option<&(u32, String, u32)>,
+    add_pane_pending: Option<Option<&str>>,
     command_select_active: bool,
     slash_complete_active: bool,
     answer_select_active: bool,
+    help_active: bool,
+    palette_active: bool,
+    transcript_search_editing: Option<bool>,
+    recent_sessions_active: bool,
+    worktrees_active: bool,
+    stats_active: bool,
+    plan_active: bool,
+    history_search: &HistorySearch,
     has_waiting_session: bool,
+    terminal_tabs: usize,
+    keymap: &super::keymap::Keymap,
+    theme: &Theme,
 ) {
     footer::render_footer(
         f,
         area,
         input_mode,
+        filter_editing,
+        text_prompt,
         detail_mode,
         toast,
         kill_confirm,
@@ -208,7 +280,18 @@ pub fn render_footer(
         command_select_active,
         slash_complete_active,
         answer_select_active,
+        help_active,
+        palette_active,
+        transcript_search_editing,
+        recent_sessions_active,
+        worktrees_active,
+        stats_active,
+        plan_active,
+        history_search,
         has_waiting_session,
+        terminal_tabs,
+        keymap,
+        theme,
     );
 }
 
@@ -218,8 +301,9 @@ pub fn render_command_select(
     area: Rect,
     commands: &[crate::config::SpawnCommand],
     list_state: &mut ListState,
+    theme: &Theme,
 ) {
-    command_select::render_command_select(f, area, commands, list_state);
+    command_select::render_command_select(f, area, commands, list_state, theme);
 }
 
 /// Render the answer selection popup overlay.
@@ -228,8 +312,9 @@ pub(super) fn render_answer_select(
     area: Rect,
     state: &crate::ui::app::AnswerSelectState,
     list_state: &mut ListState,
+    theme: &Theme,
 ) {
-    answer_select::render_answer_select(f, area, state, list_state);
+    answer_select::render_answer_select(f, area, state, list_state, theme);
 }
 
 /// Render the slash command autocomplete popup overlay.
@@ -239,6 +324,79 @@ pub fn render_slash_complete(
     commands: &[crate::ui::slash_commands::SlashCommand],
     filtered: &[usize],
     list_state: &mut ListState,
+    theme: &Theme,
+) {
+    slash_complete::render_slash_complete(f, details_area, commands, filtered, list_state, theme);
+}
+
+/// Render the key binding help overlay.
+pub(super) fn render_help(
+    f: &mut ratatui::Frame,
+    area: Rect,
+    state: &crate::ui::app::HelpState,
+    keymap: &super::keymap::Keymap,
+    theme: &Theme,
+) {
+    help::render_help(f, area, state.mode, state.scroll, keymap, theme);
+}
+
+/// Render the command palette popup overlay.
+pub(super) fn render_palette(
+    f: &mut ratatui::Frame,
+    area: Rect,
+    state: &mut crate::ui::app::PaletteState,
+    keymap: &super::keymap::Keymap,
+    theme: &Theme,
+) {
+    palette::render_palette(f, area, state, keymap, theme);
+}
+
+/// Render the cross-session transcript search popup overlay.
+pub(super) fn render_transcript_search(
+    f: &mut ratatui::Frame,
+    area: Rect,
+    state: &mut crate::ui::app::TranscriptSearchState,
+    theme: &Theme,
+) {
+    transcript_search::render_transcript_search(f, area, state, theme);
+}
+
+/// Render the recent sessions popup overlay.
+pub(super) fn render_recent_sessions(
+    f: &mut ratatui::Frame,
+    area: Rect,
+    state: &mut crate::ui::app::RecentSessionsState,
+    theme: &Theme,
+) {
+    recent::render_recent_sessions(f, area, state, theme);
+}
+
+/// Render the worktree cleanup popup overlay.
+pub(super) fn render_worktrees(
+    f: &mut ratatui::Frame,
+    area: Rect,
+    state: &mut crate::ui::app::WorktreesState,
+    theme: &Theme,
+) {
+    worktrees::render_worktrees(f, area, state, theme);
+}
+
+/// Render the plan view overlay.
+pub(super) fn render_plan(
+    f: &mut ratatui::Frame,
+    area: Rect,
+    state: &mut crate::ui::app::PlanViewState,
+    theme: &Theme,
+) {
+    plan::render_plan(f, area, state, theme);
+}
+
+/// Render the usage statistics popup overlay.
+pub(super) fn render_stats(
+    f: &mut ratatui::Frame,
+    area: Rect,
+    state: &crate::ui::app::StatsViewState,
+    theme: &Theme,
 ) {
-    slash_complete::render_slash_complete(f, details_area, commands, filtered, list_state);
+    stats::render_stats(f, area, state, theme);
 }
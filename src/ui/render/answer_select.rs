@@ -1,11 +1,12 @@
 use ratatui::{
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, List, ListItem, ListState},
 };
 
 use crate::ui::app::AnswerSelectState;
+use crate::ui::theme::Theme;
 
 /// Render the answer selection popup overlay.
 pub(super) fn render_answer_select(
@@ -13,6 +14,7 @@ pub(super) fn render_answer_select(
     area: Rect,
     state: &AnswerSelectState,
     list_state: &mut ListState,
+    theme: &Theme,
 ) {
     // Calculate popup dimensions: 50% of terminal width (wider for question text)
     let popup_width = (area.width * 50 / 100)
@@ -40,7 +42,7 @@ pub(super) fn render_answer_select(
                 Span::styled(
                     format!("[{}] ", i + 1),
                     Style::default()
-                        .fg(Color::Magenta)
+                        .fg(theme.attention)
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::raw(&opt.label),
@@ -48,7 +50,7 @@ pub(super) fn render_answer_select(
             if let Some(ref desc) = opt.description {
                 spans.push(Span::styled(
                     format!(" - {}", desc),
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme.muted),
                 ));
             }
             ListItem::new(Line::from(spans))
@@ -74,11 +76,11 @@ pub(super) fn render_answer_select(
             Block::default()
                 .borders(Borders::ALL)
                 .title(title)
-                .border_style(Style::default().fg(Color::Magenta)),
+                .border_style(Style::default().fg(theme.attention)),
         )
         .highlight_style(
             Style::default()
-                .bg(Color::DarkGray)
+                .bg(theme.selection_bg)
                 .add_modifier(Modifier::BOLD),
         )
         .highlight_symbol(">> ");
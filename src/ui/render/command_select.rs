@@ -1,11 +1,12 @@
 use ratatui::{
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, List, ListItem, ListState},
 };
 
 use crate::config::SpawnCommand;
+use crate::ui::theme::Theme;
 
 /// Render the command selection popup overlay.
 pub(super) fn render_command_select(
@@ -13,6 +14,7 @@ pub(super) fn render_command_select(
     area: Rect,
     commands: &[SpawnCommand],
     list_state: &mut ListState,
+    theme: &Theme,
 ) {
     // Calculate popup dimensions: 40% of terminal width, clamped to area
     let popup_width = (area.width * 40 / 100)
@@ -42,11 +44,11 @@ pub(super) fn render_command_select(
             Block::default()
                 .borders(Borders::ALL)
                 .title(" Select Command ")
-                .border_style(Style::default().fg(Color::Green)),
+                .border_style(Style::default().fg(theme.success)),
         )
         .highlight_style(
             Style::default()
-                .bg(Color::DarkGray)
+                .bg(theme.selection_bg)
                 .add_modifier(Modifier::BOLD),
         )
         .highlight_symbol(">> ");
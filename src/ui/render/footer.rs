@@ -5,6 +5,9 @@ use ratatui::{
     widgets::Paragraph,
 };
 
+use crate::ui::history_search::HistorySearch;
+use crate::ui::keymap::{Action, KeyMode, Keymap};
+use crate::ui::theme::Theme;
 use crate::ui::toast::{Toast, ToastType};
 
 use super::DetailMode;
@@ -15,19 +18,32 @@ pub(super) fn render_footer(
     f: &mut ratatui::Frame,
     area: Rect,
     input_mode: bool,
+    filter_editing: bool,
+    text_prompt: Option<(&str, &str, &str)>,
     detail_mode: DetailMode,
     toast: Option<&Toast>,
     kill_confirm: Option<&(u32, String)>,
-    add_pane_pending: Option<&(u32, String, u32)>,
+    add_pane_pending: Option<Option<&str>>,
     command_select_active: bool,
     slash_complete_active: bool,
     answer_select_active: bool,
+    help_active: bool,
+    palette_active: bool,
+    transcript_search_editing: Option<bool>,
+    recent_sessions_active: bool,
+    worktrees_active: bool,
+    stats_active: bool,
+    plan_active: bool,
+    history_search: &HistorySearch,
     has_waiting_session: bool,
+    terminal_tabs: usize,
+    keymap: &Keymap,
+    theme: &Theme,
 ) {
     if let Some(toast) = toast {
         let (color, prefix) = match toast.toast_type {
-            ToastType::Success => (Color::Green, "✓"),
-            ToastType::Error => (Color::Red, "✗"),
+            ToastType::Success => (theme.success, "✓"),
+            ToastType::Error => (theme.error, "✗"),
         };
         let toast_text = Line::from(vec![
             Span::styled(format!("{} ", prefix), Style::default().fg(color)),
@@ -40,20 +56,20 @@ pub(super) fn render_footer(
 
     if let Some((_pane_id, label)) = kill_confirm {
         let confirm_text = Line::from(vec![
-            Span::styled("Kill ", Style::default().fg(Color::Red)),
+            Span::styled("Kill ", Style::default().fg(theme.error)),
             Span::styled(
                 label.as_str(),
-                Style::default()
-                    .fg(Color::White)
-                    .add_modifier(Modifier::BOLD),
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
             ),
-            Span::styled("? ", Style::default().fg(Color::Red)),
+            Span::styled("? ", Style::default().fg(theme.error)),
             Span::styled(
                 "[y]",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                Style::default()
+                    .fg(theme.error)
+                    .add_modifier(Modifier::BOLD),
             ),
             Span::raw("es / "),
-            Span::styled("[any]", Style::default().fg(Color::Cyan)),
+            Span::styled("[any]", Style::default().fg(theme.accent)),
             Span::raw("cancel"),
         ]);
         let paragraph = Paragraph::new(confirm_text);
@@ -61,34 +77,57 @@ pub(super) fn render_footer(
         return;
     }
 
+    if let Some((prompt, buffer, confirm)) = text_prompt {
+        let prompt_text = Line::from(vec![
+            Span::styled(
+                format!("{}: ", prompt),
+                Style::default().fg(theme.highlight),
+            ),
+            Span::styled(
+                format!("{}▏", buffer),
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("  "),
+            Span::styled("[Enter]", Style::default().fg(theme.accent)),
+            Span::raw(format!("{} ", confirm)),
+            Span::styled("[^U]", Style::default().fg(theme.accent)),
+            Span::raw("Clear "),
+            Span::styled("[Esc]", Style::default().fg(theme.accent)),
+            Span::raw("Cancel"),
+        ]);
+        let paragraph = Paragraph::new(prompt_text).style(Style::default().fg(theme.muted));
+        f.render_widget(paragraph, area);
+        return;
+    }
+
     if answer_select_active {
         let prompt_text = Line::from(vec![
-            Span::styled("Answer: ", Style::default().fg(Color::Magenta)),
+            Span::styled("Answer: ", Style::default().fg(theme.attention)),
             Span::styled(
                 "[jk]",
                 Style::default()
-                    .fg(Color::Magenta)
+                    .fg(theme.attention)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw("Select "),
             Span::styled(
                 "[1-9]",
                 Style::default()
-                    .fg(Color::Magenta)
+                    .fg(theme.attention)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw("Quick "),
             Span::styled(
                 "[Enter]",
                 Style::default()
-                    .fg(Color::Magenta)
+                    .fg(theme.attention)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw("Send "),
             Span::styled(
                 "[Esc]",
                 Style::default()
-                    .fg(Color::Magenta)
+                    .fg(theme.attention)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw("Cancel"),
@@ -100,25 +139,25 @@ pub(super) fn render_footer(
 
     if command_select_active {
         let prompt_text = Line::from(vec![
-            Span::styled("Select command: ", Style::default().fg(Color::Green)),
+            Span::styled("Select command: ", Style::default().fg(theme.success)),
             Span::styled(
                 "[jk]",
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(theme.success)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw("Select "),
             Span::styled(
                 "[Enter]",
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(theme.success)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw("Confirm "),
             Span::styled(
                 "[Esc]",
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(theme.success)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw("Cancel"),
@@ -128,175 +167,493 @@ pub(super) fn render_footer(
         return;
     }
 
-    if let Some((_pane_id, _cwd, _window_id)) = add_pane_pending {
-        let prompt_text = Line::from(vec![
-            Span::styled("Add pane: ", Style::default().fg(Color::Green)),
-            Span::styled(
-                "[r]",
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD),
-            ),
+    if let Some(worktree_branch) = add_pane_pending {
+        let key_style = Style::default()
+            .fg(theme.success)
+            .add_modifier(Modifier::BOLD);
+        let mut spans = match worktree_branch {
+            Some(branch) => vec![
+                Span::styled("Open worktree ", Style::default().fg(theme.success)),
+                Span::styled(
+                    branch,
+                    Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(": ", Style::default().fg(theme.success)),
+            ],
+            None => vec![Span::styled(
+                "Add pane: ",
+                Style::default().fg(theme.success),
+            )],
+        };
+        spans.extend([
+            Span::styled("[r]", key_style),
             Span::raw("ight / "),
-            Span::styled(
-                "[d]",
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD),
-            ),
+            Span::styled("[d]", key_style),
             Span::raw("own / "),
-            Span::styled(
-                "[t]",
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD),
-            ),
+            Span::styled("[t]", key_style),
             Span::raw("ab / "),
-            Span::styled("[any]", Style::default().fg(Color::Cyan)),
+        ]);
+        if worktree_branch.is_none() {
+            spans.extend([Span::styled("[w]", key_style), Span::raw("orktree / ")]);
+        }
+        spans.extend([
+            Span::styled("[any]", Style::default().fg(theme.accent)),
             Span::raw("cancel"),
         ]);
-        let paragraph = Paragraph::new(prompt_text);
+        let paragraph = Paragraph::new(Line::from(spans));
         f.render_widget(paragraph, area);
         return;
     }
 
-    let help_text = if slash_complete_active {
+    let help_text = if help_active {
+        Line::from(vec![
+            Span::styled("Help: ", Style::default().fg(theme.accent)),
+            Span::styled("[jk]", Style::default().fg(theme.accent)),
+            Span::raw("Scroll "),
+            Span::styled("[Esc/?]", Style::default().fg(theme.accent)),
+            Span::raw("Close"),
+        ])
+    } else if palette_active {
+        Line::from(vec![
+            Span::styled("Commands: ", Style::default().fg(theme.accent)),
+            Span::styled("[↑↓]", Style::default().fg(theme.accent)),
+            Span::raw("Select "),
+            Span::styled("[Enter]", Style::default().fg(theme.accent)),
+            Span::raw("Run "),
+            Span::styled("[^U]", Style::default().fg(theme.accent)),
+            Span::raw("Clear "),
+            Span::styled("[Esc]", Style::default().fg(theme.accent)),
+            Span::raw("Close"),
+        ])
+    } else if recent_sessions_active {
+        Line::from(vec![
+            Span::styled("Recent sessions: ", Style::default().fg(theme.highlight)),
+            Span::styled("[jk]", Style::default().fg(theme.accent)),
+            Span::raw("Select "),
+            Span::styled("[Enter]", Style::default().fg(theme.accent)),
+            Span::raw("History "),
+            Span::styled("[r]", Style::default().fg(theme.accent)),
+            Span::raw("Resume "),
+            Span::styled("[a]", Style::default().fg(theme.accent)),
+            Span::raw("Project/All "),
+            Span::styled("[Esc]", Style::default().fg(theme.accent)),
+            Span::raw("Close"),
+        ])
+    } else if worktrees_active {
+        Line::from(vec![
+            Span::styled("Worktrees: ", Style::default().fg(theme.highlight)),
+            Span::styled("[jk]", Style::default().fg(theme.accent)),
+            Span::raw("Select "),
+            Span::styled("[d]", Style::default().fg(theme.accent)),
+            Span::raw("Remove "),
+            Span::styled("[D]", Style::default().fg(theme.accent)),
+            Span::raw("Remove+Branch "),
+            Span::styled("[Esc]", Style::default().fg(theme.accent)),
+            Span::raw("Close"),
+        ])
+    } else if plan_active {
+        Line::from(vec![
+            Span::styled("Plan: ", Style::default().fg(theme.highlight)),
+            Span::styled("[1-3]", Style::default().fg(theme.accent)),
+            Span::raw("Approve "),
+            Span::styled("[4]", Style::default().fg(theme.accent)),
+            Span::raw("Tell Claude "),
+            Span::styled("[e]", Style::default().fg(theme.accent)),
+            Span::raw("Edit in $EDITOR "),
+            Span::styled("[jk]", Style::default().fg(theme.accent)),
+            Span::raw("Scroll "),
+            Span::styled("[Esc]", Style::default().fg(theme.accent)),
+            Span::raw("Close"),
+        ])
+    } else if stats_active {
+        Line::from(vec![
+            Span::styled("Usage: ", Style::default().fg(theme.highlight)),
+            Span::styled("[Tab]", Style::default().fg(theme.accent)),
+            Span::raw("Group "),
+            Span::styled("[d]", Style::default().fg(theme.accent)),
+            Span::raw("Period "),
+            Span::styled("[jk]", Style::default().fg(theme.accent)),
+            Span::raw("Scroll "),
+            Span::styled("[Esc]", Style::default().fg(theme.accent)),
+            Span::raw("Close"),
+        ])
+    } else if let Some(editing) = transcript_search_editing {
+        if editing {
+            Line::from(vec![
+                Span::styled("Search transcripts: ", Style::default().fg(theme.highlight)),
+                Span::styled("[Enter]", Style::default().fg(theme.accent)),
+                Span::raw("Search "),
+                Span::styled("[^U]", Style::default().fg(theme.accent)),
+                Span::raw("Clear "),
+                Span::styled("[Esc]", Style::default().fg(theme.accent)),
+                Span::raw("Close"),
+            ])
+        } else {
+            Line::from(vec![
+                Span::styled("Results: ", Style::default().fg(theme.highlight)),
+                Span::styled("[jk]", Style::default().fg(theme.accent)),
+                Span::raw("Select "),
+                Span::styled("[Enter]", Style::default().fg(theme.accent)),
+                Span::raw("Open "),
+                Span::styled("[r]", Style::default().fg(theme.accent)),
+                Span::raw("Resume "),
+                Span::styled("[/]", Style::default().fg(theme.accent)),
+                Span::raw("Edit "),
+                Span::styled("[Esc]", Style::default().fg(theme.accent)),
+                Span::raw("Close"),
+            ])
+        }
+    } else if history_search.editing {
+        Line::from(vec![
+            Span::styled("Search: ", Style::default().fg(theme.highlight)),
+            Span::styled("[Enter]", Style::default().fg(theme.accent)),
+            Span::raw("Find "),
+            Span::styled("[^U]", Style::default().fg(theme.accent)),
+            Span::raw("Clear "),
+            Span::styled("[Esc]", Style::default().fg(theme.accent)),
+            Span::raw("Cancel"),
+        ])
+    } else if slash_complete_active {
         Line::from(vec![
             Span::styled(
                 "[↑↓]",
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.accent)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw("Select "),
             Span::styled(
                 "[Tab/Enter]",
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.accent)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw("Accept "),
             Span::styled(
                 "[Esc]",
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.accent)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw("Dismiss"),
         ])
     } else if detail_mode == DetailMode::HistoryList {
-        Line::from(vec![
-            Span::styled("[jk]", Style::default().fg(Color::Yellow)),
-            Span::raw("Select "),
-            Span::styled("[Enter]", Style::default().fg(Color::Yellow)),
-            Span::raw("Open "),
-            Span::styled("[y]", Style::default().fg(Color::Yellow)),
-            Span::raw("Yank "),
-            Span::styled("[gg]", Style::default().fg(Color::Yellow)),
-            Span::raw("Newest "),
-            Span::styled("[G]", Style::default().fg(Color::Yellow)),
-            Span::raw("Oldest "),
-            Span::styled("[h/l]", Style::default().fg(Color::Yellow)),
-            Span::raw("Resize "),
-            Span::styled("[Esc/q]", Style::default().fg(Color::Yellow)),
-            Span::raw("Back"),
-        ])
+        let mode = KeyMode::History;
+        let mut spans = Vec::new();
+        let c = theme.highlight;
+        push_hint(
+            &mut spans,
+            keymap,
+            mode,
+            &[Action::Down, Action::Up],
+            "Select",
+            c,
+        );
+        push_hint(&mut spans, keymap, mode, &[Action::Open], "Open", c);
+        push_hint(&mut spans, keymap, mode, &[Action::Yank], "Yank", c);
+        push_hint(&mut spans, keymap, mode, &[Action::Top], "Newest", c);
+        push_hint(&mut spans, keymap, mode, &[Action::Bottom], "Oldest", c);
+        push_hint(
+            &mut spans,
+            keymap,
+            mode,
+            &[Action::Expand, Action::Shrink],
+            "Resize",
+            c,
+        );
+        push_search_hints(&mut spans, keymap, history_search, c);
+        push_hint(&mut spans, keymap, mode, &[Action::Help], "Help", c);
+        push_hint(&mut spans, keymap, mode, &[Action::Back], "Back", c);
+        Line::from(spans)
     } else if detail_mode == DetailMode::HistoryDetail {
-        Line::from(vec![
-            Span::styled("[jk]", Style::default().fg(Color::Yellow)),
-            Span::raw("Scroll "),
-            Span::styled("[^D/^U]", Style::default().fg(Color::Yellow)),
-            Span::raw("HalfPage "),
-            Span::styled("[y]", Style::default().fg(Color::Yellow)),
-            Span::raw("Yank "),
-            Span::styled("[gg]", Style::default().fg(Color::Yellow)),
-            Span::raw("Top "),
-            Span::styled("[G]", Style::default().fg(Color::Yellow)),
-            Span::raw("Bottom "),
-            Span::styled("[h/l]", Style::default().fg(Color::Yellow)),
-            Span::raw("Resize "),
-            Span::styled("[Esc/q]", Style::default().fg(Color::Yellow)),
-            Span::raw("Back"),
-        ])
+        let mode = KeyMode::History;
+        let mut spans = Vec::new();
+        let c = theme.highlight;
+        push_hint(
+            &mut spans,
+            keymap,
+            mode,
+            &[Action::Down, Action::Up],
+            "Scroll",
+            c,
+        );
+        push_hint(
+            &mut spans,
+            keymap,
+            mode,
+            &[Action::HalfPageDown, Action::HalfPageUp],
+            "HalfPage",
+            c,
+        );
+        push_hint(&mut spans, keymap, mode, &[Action::Yank], "Yank", c);
+        push_hint(&mut spans, keymap, mode, &[Action::Top], "Top", c);
+        push_hint(&mut spans, keymap, mode, &[Action::Bottom], "Bottom", c);
+        push_hint(
+            &mut spans,
+            keymap,
+            mode,
+            &[Action::Expand, Action::Shrink],
+            "Resize",
+            c,
+        );
+        push_search_hints(&mut spans, keymap, history_search, c);
+        push_hint(&mut spans, keymap, mode, &[Action::Help], "Help", c);
+        push_hint(&mut spans, keymap, mode, &[Action::Back], "Back", c);
+        Line::from(spans)
     } else if detail_mode == DetailMode::LivePane {
-        Line::from(vec![
-            Span::styled("[jk]", Style::default().fg(Color::Green)),
-            Span::raw("Scroll "),
-            Span::styled("[^D/^U]", Style::default().fg(Color::Green)),
-            Span::raw("HalfPage "),
-            Span::styled("[y]", Style::default().fg(Color::Green)),
-            Span::raw("Yank "),
-            Span::styled("[gg]", Style::default().fg(Color::Green)),
-            Span::raw("Top "),
-            Span::styled("[G]", Style::default().fg(Color::Green)),
-            Span::raw("Bottom "),
-            Span::styled("[h/l]", Style::default().fg(Color::Green)),
-            Span::raw("Resize "),
-            Span::styled("[Esc/q]", Style::default().fg(Color::Green)),
-            Span::raw("Back"),
-        ])
+        let mode = KeyMode::LivePane;
+        let mut spans = Vec::new();
+        let c = theme.success;
+        push_hint(
+            &mut spans,
+            keymap,
+            mode,
+            &[Action::Down, Action::Up],
+            "Scroll",
+            c,
+        );
+        push_hint(
+            &mut spans,
+            keymap,
+            mode,
+            &[Action::HalfPageDown, Action::HalfPageUp],
+            "HalfPage",
+            c,
+        );
+        push_hint(&mut spans, keymap, mode, &[Action::Yank], "Yank", c);
+        push_hint(&mut spans, keymap, mode, &[Action::Top], "Top", c);
+        push_hint(&mut spans, keymap, mode, &[Action::Bottom], "Bottom", c);
+        push_hint(
+            &mut spans,
+            keymap,
+            mode,
+            &[Action::Expand, Action::Shrink],
+            "Resize",
+            c,
+        );
+        push_hint(&mut spans, keymap, mode, &[Action::Help], "Help", c);
+        push_hint(&mut spans, keymap, mode, &[Action::Back], "Back", c);
+        Line::from(spans)
     } else if detail_mode == DetailMode::Terminal {
+        let mode = KeyMode::Terminal;
+        let mut spans = Vec::new();
+        let c = theme.success;
+        push_hint(
+            &mut spans,
+            keymap,
+            mode,
+            &[Action::FocusTerminal],
+            "Focus",
+            c,
+        );
+        push_hint(
+            &mut spans,
+            keymap,
+            mode,
+            &[Action::FocusSidebar],
+            "Sidebar",
+            c,
+        );
+        push_hint(
+            &mut spans,
+            keymap,
+            mode,
+            &[Action::PrevTab, Action::NextTab],
+            "Tabs",
+            c,
+        );
+        push_hint(&mut spans, keymap, mode, &[Action::CloseTab], "Close", c);
+        push_hint(
+            &mut spans,
+            keymap,
+            mode,
+            &[Action::Expand, Action::Shrink],
+            "Resize",
+            c,
+        );
+        push_hint(&mut spans, keymap, mode, &[Action::Help], "Help", c);
+        push_hint(&mut spans, keymap, mode, &[Action::Back], "Hide", c);
+        Line::from(spans)
+    } else if filter_editing {
         Line::from(vec![
-            Span::styled("[Tab]", Style::default().fg(Color::Green)),
-            Span::raw("Focus "),
-            Span::styled("[^G]", Style::default().fg(Color::Green)),
-            Span::raw("Sidebar "),
-            Span::styled("[h/l]", Style::default().fg(Color::Green)),
-            Span::raw("Resize "),
-            Span::styled("[Esc/q]", Style::default().fg(Color::Green)),
-            Span::raw("Exit"),
+            Span::styled("Filter: ", Style::default().fg(theme.highlight)),
+            Span::styled("[↑↓]", Style::default().fg(theme.accent)),
+            Span::raw("Select "),
+            Span::styled("[Enter]", Style::default().fg(theme.accent)),
+            Span::raw("Apply "),
+            Span::styled("[^U]", Style::default().fg(theme.accent)),
+            Span::raw("Clear "),
+            Span::styled("[Esc]", Style::default().fg(theme.accent)),
+            Span::raw("Cancel"),
         ])
     } else if input_mode {
-        Line::from(vec![
-            Span::styled("[Enter]", Style::default().fg(Color::Cyan)),
-            Span::raw("Send "),
-            Span::styled("[^O]", Style::default().fg(Color::Cyan)),
-            Span::raw("Newline "),
-            Span::styled("[^hjkl]", Style::default().fg(Color::Cyan)),
-            Span::raw("Move "),
-            Span::styled("[Esc]", Style::default().fg(Color::Cyan)),
-            Span::raw("Cancel "),
-            Span::styled("[^U]", Style::default().fg(Color::Cyan)),
-            Span::raw("Clear"),
-        ])
+        let mode = KeyMode::Input;
+        let mut spans = Vec::new();
+        let c = theme.accent;
+        push_hint(&mut spans, keymap, mode, &[Action::Send], "Send", c);
+        push_hint(&mut spans, keymap, mode, &[Action::Newline], "Newline", c);
+        push_hint(
+            &mut spans,
+            keymap,
+            mode,
+            &[
+                Action::CursorLeft,
+                Action::CursorDown,
+                Action::CursorUp,
+                Action::CursorRight,
+            ],
+            "Move",
+            c,
+        );
+        push_hint(&mut spans, keymap, mode, &[Action::Back], "Cancel", c);
+        push_hint(&mut spans, keymap, mode, &[Action::Clear], "Clear", c);
+        Line::from(spans)
     } else {
-        let mut spans = vec![
-            Span::styled("[↑↓/jk]", Style::default().fg(Color::Cyan)),
-            Span::raw("Select "),
-            Span::styled("[Enter]", Style::default().fg(Color::Cyan)),
-            Span::raw("Focus "),
-            Span::styled("[y]", Style::default().fg(Color::Cyan)),
-            Span::raw("Yank "),
-            Span::styled("[^J/^K/^D/^U]", Style::default().fg(Color::Cyan)),
-            Span::raw("Scroll "),
-            Span::styled("[i]", Style::default().fg(Color::Cyan)),
-            Span::raw("Prompt "),
-            Span::styled("[1-9]", Style::default().fg(Color::Cyan)),
-            Span::raw("Quick "),
-            Span::styled("[h/l]", Style::default().fg(Color::Cyan)),
-            Span::raw("Resize "),
-            Span::styled("[H]", Style::default().fg(Color::Cyan)),
-            Span::raw("History "),
-            Span::styled("[v]", Style::default().fg(Color::Cyan)),
-            Span::raw("Live "),
-            Span::styled("[t]", Style::default().fg(Color::Cyan)),
-            Span::raw("Term "),
-            Span::styled("[r]", Style::default().fg(Color::Cyan)),
-            Span::raw("Refresh "),
-            Span::styled("[x]", Style::default().fg(Color::Cyan)),
-            Span::raw("Kill "),
-            Span::styled("[a]", Style::default().fg(Color::Cyan)),
-            Span::raw("Add "),
-        ];
+        let mode = KeyMode::Sidebar;
+        let mut spans = Vec::new();
+        let c = theme.accent;
+        push_hint(
+            &mut spans,
+            keymap,
+            mode,
+            &[Action::Down, Action::Up],
+            "Select",
+            c,
+        );
+        push_hint(&mut spans, keymap, mode, &[Action::Jump], "Focus", c);
+        push_hint(&mut spans, keymap, mode, &[Action::Yank], "Yank", c);
+        push_hint(
+            &mut spans,
+            keymap,
+            mode,
+            &[
+                Action::ScrollDown,
+                Action::ScrollUp,
+                Action::HalfPageDown,
+                Action::HalfPageUp,
+            ],
+            "Scroll",
+            c,
+        );
+        push_hint(&mut spans, keymap, mode, &[Action::Prompt], "Prompt", c);
+        spans.push(Span::styled("[1-9]", Style::default().fg(c)));
+        spans.push(Span::raw("Quick "));
+        push_hint(&mut spans, keymap, mode, &[Action::Filter], "Filter", c);
+        push_hint(&mut spans, keymap, mode, &[Action::SearchAll], "Search", c);
+        push_hint(
+            &mut spans,
+            keymap,
+            mode,
+            &[Action::RecentSessions],
+            "Recent",
+            c,
+        );
+        push_hint(
+            &mut spans,
+            keymap,
+            mode,
+            &[Action::Worktrees],
+            "Worktrees",
+            c,
+        );
+        push_hint(&mut spans, keymap, mode, &[Action::Stats], "Stats", c);
+        push_hint(
+            &mut spans,
+            keymap,
+            mode,
+            &[Action::CycleSort, Action::CycleGroup],
+            "Sort/Group",
+            c,
+        );
+        push_hint(
+            &mut spans,
+            keymap,
+            mode,
+            &[Action::Pin, Action::Label, Action::Tags, Action::CycleColor],
+            "Pin/Label/Tag/Color",
+            c,
+        );
+        push_hint(
+            &mut spans,
+            keymap,
+            mode,
+            &[Action::Expand, Action::Shrink],
+            "Resize",
+            c,
+        );
+        push_hint(&mut spans, keymap, mode, &[Action::History], "History", c);
+        push_hint(&mut spans, keymap, mode, &[Action::LivePane], "Live", c);
+        push_hint(&mut spans, keymap, mode, &[Action::Terminal], "Term", c);
+        if terminal_tabs > 0 {
+            push_hint(
+                &mut spans,
+                keymap,
+                mode,
+                &[Action::ShowTerminal],
+                &format!("Tabs ({})", terminal_tabs),
+                c,
+            );
+        }
+        push_hint(&mut spans, keymap, mode, &[Action::Refresh], "Refresh", c);
+        push_hint(&mut spans, keymap, mode, &[Action::Kill], "Kill", c);
+        push_hint(&mut spans, keymap, mode, &[Action::AddPane], "Add", c);
         if has_waiting_session {
-            spans.push(Span::styled("[o]", Style::default().fg(Color::Magenta)));
-            spans.push(Span::raw("Answer "));
+            push_hint(
+                &mut spans,
+                keymap,
+                mode,
+                &[Action::Answer],
+                "Answer",
+                theme.attention,
+            );
         }
-        spans.push(Span::styled("[q]", Style::default().fg(Color::Cyan)));
-        spans.push(Span::raw("Quit"));
+        push_hint(&mut spans, keymap, mode, &[Action::Help], "Help", c);
+        push_hint(&mut spans, keymap, mode, &[Action::Palette], "Commands", c);
+        push_hint(&mut spans, keymap, mode, &[Action::Quit], "Quit", c);
         Line::from(spans)
     };
 
-    let paragraph = Paragraph::new(help_text).style(Style::default().fg(Color::DarkGray));
+    let paragraph = Paragraph::new(help_text).style(Style::default().fg(theme.muted));
     f.render_widget(paragraph, area);
 }
+
+/// Append a `[keys]Label ` hint for `actions` using the configured key bindings.
+/// Nothing is added if none of the actions are bound.
+fn push_hint(
+    spans: &mut Vec<Span<'static>>,
+    keymap: &Keymap,
+    mode: KeyMode,
+    actions: &[Action],
+    label: &str,
+    color: Color,
+) {
+    if let Some(keys) = keymap.hint(mode, actions) {
+        spans.push(Span::styled(
+            format!("[{}]", keys),
+            Style::default().fg(color),
+        ));
+        spans.push(Span::raw(format!("{} ", label)));
+    }
+}
+
+/// Append the history search hints: `[/]Search`, plus `[n/N]Match` while a
+/// search is applied.
+fn push_search_hints(
+    spans: &mut Vec<Span<'static>>,
+    keymap: &Keymap,
+    search: &HistorySearch,
+    color: Color,
+) {
+    let mode = KeyMode::History;
+    push_hint(spans, keymap, mode, &[Action::Search], "Search", color);
+    if search.is_active() {
+        push_hint(
+            spans,
+            keymap,
+            mode,
+            &[Action::NextMatch, Action::PrevMatch],
+            "Match",
+            color,
+        );
+    }
+}
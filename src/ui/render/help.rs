@@ -0,0 +1,80 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::ui::keymap::{KeyMode, Keymap};
+use crate::ui::theme::Theme;
+
+/// Render the key binding help overlay for `mode`.
+pub(super) fn render_help(
+    f: &mut ratatui::Frame,
+    area: Rect,
+    mode: KeyMode,
+    scroll: usize,
+    keymap: &Keymap,
+    theme: &Theme,
+) {
+    let entries = keymap.help_entries(mode);
+    let key_width = entries
+        .iter()
+        .map(|(_, keys)| keys.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(9);
+
+    let lines: Vec<Line> = entries
+        .iter()
+        .map(|(action, keys)| {
+            let keys = if keys.is_empty() {
+                Span::styled(
+                    format!("{:<width$}", "(unbound)", width = key_width),
+                    Style::default().fg(theme.muted),
+                )
+            } else {
+                Span::styled(
+                    format!("{:<width$}", keys, width = key_width),
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                )
+            };
+            Line::from(vec![
+                Span::raw(" "),
+                keys,
+                Span::raw("  "),
+                Span::styled(action.description(), Style::default().fg(theme.text)),
+            ])
+        })
+        .collect();
+
+    // 60% of terminal width, tall enough for every binding if possible
+    let popup_width = (area.width * 60 / 100)
+        .max(40)
+        .min(area.width.saturating_sub(4));
+    // +2 for top/bottom borders
+    let popup_height = ((lines.len() as u16) + 2).min(area.height.saturating_sub(4));
+
+    let x = area.x + (area.width.saturating_sub(popup_width)) / 2;
+    let y = area.y + (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
+    // Clamp scroll so the last binding stays at the bottom edge
+    let visible = popup_height.saturating_sub(2) as usize;
+    let scroll = scroll.min(lines.len().saturating_sub(visible));
+
+    f.render_widget(Clear, popup_area);
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" Key Bindings: {} ", mode.as_str()))
+                .border_style(Style::default().fg(theme.accent)),
+        )
+        .scroll((scroll as u16, 0));
+
+    f.render_widget(paragraph, popup_area);
+}
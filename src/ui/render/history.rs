@@ -1,9 +1,11 @@
 use crate::transcript::ConversationTurn;
+use crate::ui::history_search::{self, HistorySearch, SEARCH_CONTEXT_LINES};
 use crate::ui::markdown;
 use crate::ui::session::wrap_text_lines;
+use crate::ui::theme::Theme;
 use ratatui::{
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
 };
@@ -66,8 +68,11 @@ pub(super) fn render_history_list(
     turns: &[ConversationTurn],
     list_state: &mut ListState,
     timestamps: &[Option<SystemTime>],
+    search: &HistorySearch,
+    theme: &Theme,
 ) {
     let inner_width = history_list_inner_width(area.width, HISTORY_LIST_HIGHLIGHT_SYMBOL);
+    let mut match_count = 0;
 
     let items: Vec<ListItem> = turns
         .iter()
@@ -84,8 +89,8 @@ pub(super) fn render_history_list(
             let time_color = timestamps
                 .get(i)
                 .and_then(|ts| ts.as_ref())
-                .map(elapsed_time_color)
-                .unwrap_or(Color::DarkGray);
+                .map(|t| elapsed_time_color(t, theme))
+                .unwrap_or(theme.muted);
 
             let first_line = turn.user_prompt.lines().next().unwrap_or("");
 
@@ -93,32 +98,49 @@ pub(super) fn render_history_list(
             let truncated_prompt =
                 truncate_history_prompt(first_line, turn_num, &time_display, inner_width);
 
-            let line = Line::from(vec![
-                Span::styled(
-                    format!("{} ", num_str),
-                    Style::default().fg(Color::DarkGray),
-                ),
+            // While searching, dim turns without hits and highlight prompt hits
+            let prompt = if !search.is_active() {
+                Line::from(truncated_prompt)
+            } else if search.turn_matches(turn) {
+                match_count += 1;
+                history_search::highlight_line(
+                    &Line::from(truncated_prompt),
+                    &search.query,
+                    history_search::hit_style(theme, false),
+                )
+            } else {
+                Line::styled(truncated_prompt, Style::default().fg(theme.muted))
+            };
+
+            let mut spans = vec![
+                Span::styled(format!("{} ", num_str), Style::default().fg(theme.muted)),
                 Span::styled(
                     format!("{} ", time_display),
                     Style::default().fg(time_color),
                 ),
-                Span::raw(truncated_prompt),
-            ]);
-            ListItem::new(line)
+            ];
+            spans.extend(prompt.spans);
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
     let title = format!(" History ({} turns) ", turns.len());
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(Style::default().fg(theme.highlight));
+    if search.editing || search.is_active() {
+        block = block.title_bottom(search_title(
+            search,
+            format!("{}/{} turns", match_count, turns.len()),
+            theme,
+        ));
+    }
     let list = List::new(items)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(title)
-                .border_style(Style::default().fg(Color::Yellow)),
-        )
+        .block(block)
         .highlight_style(
             Style::default()
-                .bg(Color::DarkGray)
+                .bg(theme.selection_bg)
                 .add_modifier(Modifier::BOLD),
         )
         .highlight_symbol(HISTORY_LIST_HIGHLIGHT_SYMBOL);
@@ -126,58 +148,24 @@ pub(super) fn render_history_list(
     f.render_stateful_widget(list, area, list_state);
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_history_list_inner_width_matches_highlight_symbol_width() {
-        assert_eq!(history_list_inner_width(20, ">> "), 15);
-    }
-
-    #[test]
-    fn test_history_list_inner_width_uses_unicode_display_width() {
-        // Full-width greater-than signs are width=2 each.
-        assert_eq!(history_list_inner_width(20, "＞＞ "), 13);
-    }
-
-    #[test]
-    fn test_history_list_inner_width_saturates_at_zero() {
-        assert_eq!(history_list_inner_width(4, ">> "), 0);
-    }
-
-    #[test]
-    fn test_truncate_history_prompt_no_truncation() {
-        let prompt = truncate_history_prompt("hello world", 12, "10m", 40);
-        assert_eq!(prompt, "hello world");
-    }
-
-    #[test]
-    fn test_truncate_history_prompt_narrow_width() {
-        let prompt = truncate_history_prompt("abcdefghij", 999, "1h", 8);
-        assert_eq!(prompt, "...");
-    }
-
-    #[test]
-    fn test_clamp_scroll_offset_for_render_normal_range() {
-        let clamped = clamp_scroll_offset_for_render(120, 500, 30);
-        assert_eq!(clamped, 120);
-    }
-
-    #[test]
-    fn test_clamp_scroll_offset_for_render_limited_by_content() {
-        let clamped = clamp_scroll_offset_for_render(9_999, 120, 20);
-        assert_eq!(clamped, 100);
-    }
-
-    #[test]
-    fn test_clamp_scroll_offset_for_render_limited_by_u16_max() {
-        let clamped = clamp_scroll_offset_for_render(usize::MAX, 200_000, 10);
-        assert_eq!(clamped, u16::MAX as usize);
-    }
+/// Header shown above history opened from transcript search, in place of
+/// the selected session's info header.
+pub(super) fn render_history_source_header(
+    f: &mut ratatui::Frame,
+    area: Rect,
+    source: &str,
+    theme: &Theme,
+) {
+    let line = Line::from(vec![
+        Span::raw(" "),
+        Span::styled("🔎 ", Style::default().fg(theme.highlight)),
+        Span::styled(source.to_string(), Style::default().fg(theme.highlight)),
+    ]);
+    f.render_widget(Paragraph::new(line), area);
 }
 
 /// Render details panel in history browsing mode.
+#[allow(clippy::too_many_arguments)]
 pub(super) fn render_history_details(
     f: &mut ratatui::Frame,
     area: Rect,
@@ -185,6 +173,8 @@ pub(super) fn render_history_details(
     index: usize,
     scroll_offset: &mut usize,
     cached_history_lines: &mut HistoryLinesCache,
+    search: &mut HistorySearch,
+    theme: &Theme,
 ) {
     let turn = &turns[index];
     let total = turns.len();
@@ -199,29 +189,31 @@ pub(super) fn render_history_details(
         "💬 Prompt:",
         Style::default()
             .add_modifier(Modifier::BOLD)
-            .fg(Color::Cyan),
+            .fg(theme.accent),
     )]));
 
-    let prompt_lines = wrap_text_lines(&turn.user_prompt, inner_width, max_lines, Color::White);
+    let prompt_lines = wrap_text_lines(&turn.user_prompt, inner_width, max_lines, theme.text);
+    let prompt_range = lines.len()..lines.len() + prompt_lines.len();
     lines.extend(prompt_lines);
 
     lines.push(Line::from(""));
     lines.push(Line::from(vec![Span::styled(
         "─".repeat(inner_width),
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme.muted),
     )]));
 
     lines.push(Line::from(vec![Span::styled(
         "🤖 Response:",
         Style::default()
             .add_modifier(Modifier::BOLD)
-            .fg(Color::Green),
+            .fg(theme.success),
     )]));
 
+    let response_start = lines.len();
     if turn.assistant_response.is_empty() {
         lines.push(Line::from(Span::styled(
             "(no response yet)",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         )));
     } else {
         let text_hash = hash_str(&turn.assistant_response);
@@ -230,18 +222,39 @@ pub(super) fn render_history_details(
             if *cached_key == cache_key {
                 cached.clone()
             } else {
-                let rendered = markdown::markdown_to_lines(&turn.assistant_response, inner_width);
+                let rendered =
+                    markdown::markdown_to_lines(&turn.assistant_response, inner_width, theme);
                 *cached_history_lines = Some((cache_key, rendered.clone()));
                 rendered
             }
         } else {
-            let rendered = markdown::markdown_to_lines(&turn.assistant_response, inner_width);
+            let rendered =
+                markdown::markdown_to_lines(&turn.assistant_response, inner_width, theme);
             *cached_history_lines = Some((cache_key, rendered.clone()));
             rendered
         };
         lines.extend(response_lines);
     }
 
+    let search_status = if search.is_active() {
+        let response_range = response_start..lines.len();
+        highlight_search_hits(
+            &mut lines,
+            &[prompt_range, response_range],
+            search,
+            scroll_offset,
+            theme,
+        );
+        let position = search
+            .current_line
+            .and_then(|l| search.hit_lines.iter().position(|&h| h == l))
+            .map_or("-".to_string(), |i| (i + 1).to_string());
+        Some(format!("line {}/{}", position, search.hit_lines.len()))
+    } else {
+        search.hit_lines.clear();
+        search.editing.then(String::new)
+    };
+
     let content_height = lines.len();
     let viewport_height = area.height.saturating_sub(2) as usize;
     *scroll_offset =
@@ -249,14 +262,126 @@ pub(super) fn render_history_details(
     let clamped_offset = *scroll_offset as u16;
 
     let title = format!(" History ({}/{}) ", turn_num, total);
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(Style::default().fg(theme.highlight));
+    if let Some(status) = search_status {
+        block = block.title_bottom(search_title(search, status, theme));
+    }
     let paragraph = Paragraph::new(lines)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(title)
-                .border_style(Style::default().fg(Color::Yellow)),
-        )
+        .block(block)
         .scroll((clamped_offset, 0));
 
     f.render_widget(paragraph, area);
 }
+
+/// Find and highlight search hits in the searchable `ranges` of `lines`
+/// (prompt and response, not the headers), record them in `search`, and
+/// scroll to the hit requested by a pending `n`/`N` jump.
+fn highlight_search_hits(
+    lines: &mut [Line<'static>],
+    ranges: &[std::ops::Range<usize>],
+    search: &mut HistorySearch,
+    scroll_offset: &mut usize,
+    theme: &Theme,
+) {
+    search.hit_lines = ranges
+        .iter()
+        .flat_map(|range| {
+            history_search::hit_lines(&lines[range.clone()], &search.query)
+                .into_iter()
+                .map(move |i| range.start + i)
+        })
+        .collect();
+
+    if let Some(jump) = search.pending.take() {
+        search.current_line = match jump {
+            history_search::HitJump::First => search.hit_lines.first().copied(),
+            history_search::HitJump::Last => search.hit_lines.last().copied(),
+        };
+        if let Some(line) = search.current_line {
+            *scroll_offset = line.saturating_sub(SEARCH_CONTEXT_LINES);
+        }
+    }
+
+    for &i in &search.hit_lines {
+        let style = history_search::hit_style(theme, search.current_line == Some(i));
+        lines[i] = history_search::highlight_line(&lines[i], &search.query, style);
+    }
+}
+
+/// Bottom title showing the search query (with a cursor while editing)
+/// followed by `status`.
+fn search_title(search: &HistorySearch, status: String, theme: &Theme) -> Line<'static> {
+    let cursor = if search.editing { "▏" } else { "" };
+    let query_style = if search.editing {
+        Style::default()
+            .fg(theme.highlight)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(theme.highlight)
+    };
+    let mut spans = vec![
+        Span::styled(" /", Style::default().fg(theme.muted)),
+        Span::styled(format!("{}{} ", search.query, cursor), query_style),
+    ];
+    if !status.is_empty() {
+        spans.push(Span::styled(
+            format!("{} ", status),
+            Style::default().fg(theme.muted),
+        ));
+    }
+    Line::from(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_list_inner_width_matches_highlight_symbol_width() {
+        assert_eq!(history_list_inner_width(20, ">> "), 15);
+    }
+
+    #[test]
+    fn test_history_list_inner_width_uses_unicode_display_width() {
+        // Full-width greater-than signs are width=2 each.
+        assert_eq!(history_list_inner_width(20, "＞＞ "), 13);
+    }
+
+    #[test]
+    fn test_history_list_inner_width_saturates_at_zero() {
+        assert_eq!(history_list_inner_width(4, ">> "), 0);
+    }
+
+    #[test]
+    fn test_truncate_history_prompt_no_truncation() {
+        let prompt = truncate_history_prompt("hello world", 12, "10m", 40);
+        assert_eq!(prompt, "hello world");
+    }
+
+    #[test]
+    fn test_truncate_history_prompt_narrow_width() {
+        let prompt = truncate_history_prompt("abcdefghij", 999, "1h", 8);
+        assert_eq!(prompt, "...");
+    }
+
+    #[test]
+    fn test_clamp_scroll_offset_for_render_normal_range() {
+        let clamped = clamp_scroll_offset_for_render(120, 500, 30);
+        assert_eq!(clamped, 120);
+    }
+
+    #[test]
+    fn test_clamp_scroll_offset_for_render_limited_by_content() {
+        let clamped = clamp_scroll_offset_for_render(9_999, 120, 20);
+        assert_eq!(clamped, 100);
+    }
+
+    #[test]
+    fn test_clamp_scroll_offset_for_render_limited_by_u16_max() {
+        let clamped = clamp_scroll_offset_for_render(usize::MAX, 200_000, 10);
+        assert_eq!(clamped, u16::MAX as usize);
+    }
+}
@@ -7,8 +7,10 @@ use ratatui::{
 use std::sync::Arc;
 
 use super::LivePaneLinesCache;
+use crate::ui::theme::Theme;
 
 /// Render the live pane view in the details panel area.
+#[allow(clippy::too_many_arguments)]
 pub(super) fn render_live_pane(
     f: &mut ratatui::Frame,
     area: Rect,
@@ -17,6 +19,7 @@ pub(super) fn render_live_pane(
     scroll_offset: &mut usize,
     cached_lines: &mut LivePaneLinesCache,
     has_error: bool,
+    theme: &Theme,
 ) {
     let inner_width = area.width.saturating_sub(2) as usize;
 
@@ -39,7 +42,7 @@ pub(super) fn render_live_pane(
     } else {
         Arc::new(vec![Line::from(Span::styled(
             "Loading...",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         ))])
     };
 
@@ -50,7 +53,7 @@ pub(super) fn render_live_pane(
         let mut lines = (*lines_arc).clone();
         lines.push(Line::from(Span::styled(
             "⚠ Pane unavailable (retrying...)",
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(theme.highlight),
         )));
         _owned = lines;
         lines_ref = &_owned;
@@ -66,9 +69,9 @@ pub(super) fn render_live_pane(
     let clamped_offset = (*scroll_offset).min(u16::MAX as usize) as u16;
 
     let border_color = if has_error {
-        Color::Yellow
+        theme.highlight
     } else {
-        Color::Green
+        theme.success
     };
     let paragraph = Paragraph::new(lines_ref.clone())
         .block(
@@ -335,6 +338,7 @@ mod tests {
                     &mut offset,
                     &mut cache,
                     false,
+                    &Theme::default(),
                 );
             })
             .unwrap();
@@ -382,7 +386,16 @@ mod tests {
         terminal
             .draw(|f| {
                 let area = Rect::new(0, 0, 80, 10);
-                render_live_pane(f, area, Some(content), hash, &mut offset, &mut cache, false);
+                render_live_pane(
+                    f,
+                    area,
+                    Some(content),
+                    hash,
+                    &mut offset,
+                    &mut cache,
+                    false,
+                    &Theme::default(),
+                );
             })
             .unwrap();
         assert!(cache.is_some());
@@ -393,7 +406,16 @@ mod tests {
         terminal
             .draw(|f| {
                 let area = Rect::new(0, 0, 80, 10);
-                render_live_pane(f, area, Some(content), hash, &mut offset, &mut cache, false);
+                render_live_pane(
+                    f,
+                    area,
+                    Some(content),
+                    hash,
+                    &mut offset,
+                    &mut cache,
+                    false,
+                    &Theme::default(),
+                );
             })
             .unwrap();
         let (cached_key2, _) = cache.as_ref().unwrap();
@@ -416,7 +438,16 @@ mod tests {
         terminal
             .draw(|f| {
                 let area = Rect::new(0, 0, 80, 10);
-                render_live_pane(f, area, Some(content), 100, &mut offset, &mut cache, false);
+                render_live_pane(
+                    f,
+                    area,
+                    Some(content),
+                    100,
+                    &mut offset,
+                    &mut cache,
+                    false,
+                    &Theme::default(),
+                );
             })
             .unwrap();
         let (key1, _) = cache.as_ref().unwrap();
@@ -426,7 +457,16 @@ mod tests {
         terminal
             .draw(|f| {
                 let area = Rect::new(0, 0, 80, 10);
-                render_live_pane(f, area, Some(content), 200, &mut offset, &mut cache, false);
+                render_live_pane(
+                    f,
+                    area,
+                    Some(content),
+                    200,
+                    &mut offset,
+                    &mut cache,
+                    false,
+                    &Theme::default(),
+                );
             })
             .unwrap();
         let (key2, _) = cache.as_ref().unwrap();
@@ -0,0 +1,88 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+};
+
+use crate::ui::app::PaletteState;
+use crate::ui::keymap::{KeyMode, Keymap, PALETTE_ACTIONS};
+use crate::ui::theme::Theme;
+
+/// Render the command palette popup: a query line above the matching actions.
+pub(super) fn render_palette(
+    f: &mut ratatui::Frame,
+    area: Rect,
+    state: &mut PaletteState,
+    keymap: &Keymap,
+    theme: &Theme,
+) {
+    let popup_width = (area.width * 50 / 100)
+        .max(30)
+        .min(area.width.saturating_sub(4));
+    // Room for every action so the popup doesn't resize while typing;
+    // +3 for borders and the query line
+    let popup_height = ((PALETTE_ACTIONS.len() as u16) + 3).min(area.height.saturating_sub(4));
+
+    let x = area.x + (area.width.saturating_sub(popup_width)) / 2;
+    let y = area.y + (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Commands ")
+        .border_style(Style::default().fg(theme.accent));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .split(inner);
+
+    let query = Paragraph::new(Line::from(vec![
+        Span::styled("> ", Style::default().fg(theme.accent)),
+        Span::styled(state.query.as_str(), Style::default().fg(theme.highlight)),
+        Span::styled("▏", Style::default().fg(theme.highlight)),
+    ]));
+    f.render_widget(query, chunks[0]);
+
+    if state.matches.is_empty() {
+        let empty = Paragraph::new(Span::styled(
+            "  No matching commands",
+            Style::default().fg(theme.muted),
+        ));
+        f.render_widget(empty, chunks[1]);
+        return;
+    }
+
+    let items: Vec<ListItem> = state
+        .matches
+        .iter()
+        .map(|action| {
+            let mut spans = vec![Span::styled(
+                action.description(),
+                Style::default().fg(theme.text),
+            )];
+            if let Some(keys) = keymap.hint(KeyMode::Sidebar, &[*action]) {
+                spans.push(Span::styled(
+                    format!("  {}", keys),
+                    Style::default().fg(theme.muted),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(theme.selection_bg)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(">> ");
+
+    f.render_stateful_widget(list, chunks[1], &mut state.list_state);
+}
@@ -0,0 +1,80 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::ui::app::PlanViewState;
+use crate::ui::markdown::markdown_to_lines;
+use crate::ui::theme::Theme;
+
+/// Render the plan view: the ExitPlanMode plan as markdown, scrolled to
+/// `state.scroll` (clamped so the last line stays at the bottom edge).
+pub(super) fn render_plan(
+    f: &mut ratatui::Frame,
+    area: Rect,
+    state: &mut PlanViewState,
+    theme: &Theme,
+) {
+    let popup_width = (area.width * 90 / 100)
+        .max(40)
+        .min(area.width.saturating_sub(2));
+    let popup_height = (area.height * 90 / 100)
+        .max(8)
+        .min(area.height.saturating_sub(2));
+
+    let x = area.x + (area.width.saturating_sub(popup_width)) / 2;
+    let y = area.y + (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
+    f.render_widget(Clear, popup_area);
+
+    let inner_width = popup_width.saturating_sub(2) as usize;
+    let lines = if state.plan.trim().is_empty() {
+        vec![Line::from(Span::styled(
+            "(The plan is empty)",
+            Style::default().fg(theme.muted),
+        ))]
+    } else {
+        markdown_to_lines(&state.plan, inner_width, theme)
+    };
+
+    let visible = popup_height.saturating_sub(2) as usize;
+    let max_scroll = lines.len().saturating_sub(visible);
+    state.scroll = state.scroll.min(max_scroll);
+    let position = if max_scroll == 0 {
+        String::new()
+    } else {
+        format!(
+            " {}/{} ",
+            state.scroll + visible.min(lines.len()),
+            lines.len()
+        )
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Line::from(vec![
+            Span::styled(
+                " 📋 Plan ",
+                Style::default()
+                    .fg(theme.attention)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("· {} ", state.title),
+                Style::default().fg(theme.muted),
+            ),
+        ]))
+        .title_bottom(Line::from(Span::styled(
+            position,
+            Style::default().fg(theme.muted),
+        )))
+        .border_style(Style::default().fg(theme.attention));
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .scroll((state.scroll as u16, 0));
+    f.render_widget(paragraph, popup_area);
+}
@@ -0,0 +1,111 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+};
+
+use super::summary::format_relative_time;
+use crate::ui::app::RecentSessionsState;
+use crate::ui::theme::Theme;
+
+/// Render the recent sessions popup: one row per transcript with its last
+/// activity, project, turns, cost and first prompt.
+pub(super) fn render_recent_sessions(
+    f: &mut ratatui::Frame,
+    area: Rect,
+    state: &mut RecentSessionsState,
+    theme: &Theme,
+) {
+    let popup_width = (area.width * 80 / 100)
+        .max(40)
+        .min(area.width.saturating_sub(4));
+    let popup_height = (area.height * 70 / 100)
+        .max(8)
+        .min(area.height.saturating_sub(4));
+
+    let x = area.x + (area.width.saturating_sub(popup_width)) / 2;
+    let y = area.y + (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
+    f.render_widget(Clear, popup_area);
+
+    let scope = match (&state.project_cwd, state.all_projects) {
+        (Some(cwd), false) => cwd.clone(),
+        _ => "all projects".to_string(),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Recent Sessions · {} ", scope))
+        .title_bottom(Line::from(Span::styled(
+            format!(" {} sessions ", state.sessions.len()),
+            Style::default().fg(theme.muted),
+        )))
+        .border_style(Style::default().fg(theme.highlight));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    if state.sessions.is_empty() {
+        let empty = Paragraph::new(Span::styled(
+            "  No transcripts found",
+            Style::default().fg(theme.muted),
+        ));
+        f.render_widget(empty, inner);
+        return;
+    }
+
+    let items: Vec<ListItem> = state
+        .sessions
+        .iter()
+        .map(|session| {
+            let mut header = vec![
+                Span::styled(
+                    format!("{:>4}  ", format_relative_time(&session.last_activity)),
+                    Style::default().fg(theme.muted),
+                ),
+                Span::styled(
+                    session.project_name().to_string(),
+                    Style::default().fg(theme.accent),
+                ),
+                Span::styled(
+                    format!(
+                        "  {} turns{}",
+                        session.turns,
+                        session
+                            .cost_usd
+                            .map(|c| format!("  ~${:.2}", c))
+                            .unwrap_or_default()
+                    ),
+                    Style::default().fg(theme.text_secondary),
+                ),
+                Span::styled(
+                    format!("  {}", session.session_id),
+                    Style::default().fg(theme.muted),
+                ),
+            ];
+            if state.live_ids.contains(&session.session_id) {
+                header.push(Span::styled("  ● live", Style::default().fg(theme.success)));
+            }
+            let prompt = session
+                .first_prompt
+                .as_deref()
+                .map(|p| p.split_whitespace().collect::<Vec<_>>().join(" "))
+                .unwrap_or_default();
+            let prompt = Line::from(Span::styled(
+                format!("  {}", prompt),
+                Style::default().fg(theme.text),
+            ));
+            ListItem::new(vec![Line::from(header), prompt])
+        })
+        .collect();
+
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(theme.selection_bg)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(">> ");
+
+    f.render_stateful_widget(list, inner, &mut state.list_state);
+}
@@ -1,11 +1,12 @@
 use ratatui::{
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, List, ListItem, ListState},
 };
 
 use crate::ui::slash_commands::SlashCommand;
+use crate::ui::theme::Theme;
 
 /// Render the slash command autocomplete popup overlay.
 ///
@@ -16,6 +17,7 @@ pub(super) fn render_slash_complete(
     commands: &[SlashCommand],
     filtered: &[usize],
     list_state: &mut ListState,
+    theme: &Theme,
 ) {
     if filtered.is_empty() {
         return;
@@ -51,21 +53,21 @@ pub(super) fn render_slash_complete(
             let mut spans = vec![Span::styled(
                 format!("/{}", cmd.name),
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.accent)
                     .add_modifier(Modifier::BOLD),
             )];
 
             if let Some(hint) = &cmd.argument_hint {
                 spans.push(Span::styled(
                     format!(" {}", hint),
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme.muted),
                 ));
             }
 
             if !cmd.description.is_empty() {
                 spans.push(Span::styled(
                     format!("  {}", cmd.description),
-                    Style::default().fg(Color::Gray),
+                    Style::default().fg(theme.text_secondary),
                 ));
             }
 
@@ -78,11 +80,11 @@ pub(super) fn render_slash_complete(
             Block::default()
                 .borders(Borders::ALL)
                 .title(" / Commands ")
-                .border_style(Style::default().fg(Color::Green)),
+                .border_style(Style::default().fg(theme.success)),
         )
         .highlight_style(
             Style::default()
-                .bg(Color::DarkGray)
+                .bg(theme.selection_bg)
                 .add_modifier(Modifier::BOLD),
         )
         .highlight_symbol(">> ");
@@ -0,0 +1,154 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+use crate::transcript::{UsageTotals, STATS_COLUMNS};
+use crate::ui::app::StatsViewState;
+use crate::ui::theme::Theme;
+
+/// Render the usage statistics popup: a table of turns, tool calls, tokens,
+/// cost and active time per group, the total, and tool calls by type.
+pub(super) fn render_stats(
+    f: &mut ratatui::Frame,
+    area: Rect,
+    state: &StatsViewState,
+    theme: &Theme,
+) {
+    let popup_width = (area.width * 80 / 100)
+        .max(40)
+        .min(area.width.saturating_sub(4));
+    let popup_height = (area.height * 70 / 100)
+        .max(8)
+        .min(area.height.saturating_sub(4));
+
+    let x = area.x + (area.width.saturating_sub(popup_width)) / 2;
+    let y = area.y + (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
+    f.render_widget(Clear, popup_area);
+
+    let period = match state.days {
+        Some(days) => format!("last {} days", days),
+        None => "all history".to_string(),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            " Usage · {} · by {} ",
+            period,
+            state.group.as_str()
+        ))
+        .title_bottom(Line::from(Span::styled(
+            " Costs are estimates at list prices ",
+            Style::default().fg(theme.muted),
+        )))
+        .border_style(Style::default().fg(theme.highlight));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let Some(stats) = &state.stats else {
+        let loading = Paragraph::new(Span::styled(
+            "  Reading transcripts…",
+            Style::default().fg(theme.muted),
+        ));
+        f.render_widget(loading, inner);
+        return;
+    };
+    let groups = stats.groups(state.group);
+    if groups.is_empty() {
+        let empty = Paragraph::new(Span::styled(
+            "  No usage in this period",
+            Style::default().fg(theme.muted),
+        ));
+        f.render_widget(empty, inner);
+        return;
+    }
+
+    let rows: Vec<(&str, [String; 7])> = groups
+        .iter()
+        .map(|(key, totals)| (key.as_str(), totals.cells()))
+        .collect();
+    let total = stats.total.cells();
+    let mut widths = STATS_COLUMNS.map(str::len);
+    for cells in rows.iter().map(|(_, c)| c).chain([&total]) {
+        for (width, cell) in widths.iter_mut().zip(cells) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    // The key column gets whatever the numbers leave
+    let numbers_width: usize = widths.iter().map(|w| w + 2).sum();
+    let key_width = (inner.width as usize)
+        .saturating_sub(numbers_width + 1)
+        .clamp(8, 40);
+
+    let row_line = |key: &str, cells: &[String; 7], style: Style| {
+        let mut text = format!(" {}", fit(key, key_width));
+        for (cell, w) in cells.iter().zip(widths) {
+            text.push_str(&format!("  {:>w$}", cell));
+        }
+        Line::from(Span::styled(text, style))
+    };
+
+    // Header, rows, rule, total, blank line and tool calls
+    let header_cells = STATS_COLUMNS.map(str::to_string);
+    let mut lines = vec![row_line(
+        state.group.as_str(),
+        &header_cells,
+        Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD),
+    )];
+    let visible = (inner.height as usize).saturating_sub(5).max(1);
+    lines.extend(
+        rows.iter()
+            .skip(state.scroll)
+            .take(visible)
+            .map(|(key, cells)| row_line(key, cells, Style::default().fg(theme.text))),
+    );
+    lines.push(Line::from(Span::styled(
+        format!(" {}", "─".repeat(key_width + numbers_width)),
+        Style::default().fg(theme.muted),
+    )));
+    lines.push(row_line(
+        "Total",
+        &total,
+        Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+    ));
+    lines.push(Line::default());
+    lines.push(tool_calls_line(&stats.total, theme));
+
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+}
+
+/// Tool calls by type, most used first.
+fn tool_calls_line(total: &UsageTotals, theme: &Theme) -> Line<'static> {
+    let mut tools: Vec<(&String, &usize)> = total.tool_calls.iter().collect();
+    tools.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+    let mut spans = vec![Span::styled(" Tools: ", Style::default().fg(theme.accent))];
+    if tools.is_empty() {
+        spans.push(Span::styled("none", Style::default().fg(theme.muted)));
+    }
+    for (i, (name, count)) in tools.into_iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::styled(" · ", Style::default().fg(theme.muted)));
+        }
+        spans.push(Span::styled(
+            format!("{} {}", name, count),
+            Style::default().fg(theme.text_secondary),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// Pad or truncate `s` to exactly `width` columns.
+fn fit(s: &str, width: usize) -> String {
+    if s.chars().count() > width {
+        let cut: String = s.chars().take(width.saturating_sub(1)).collect();
+        format!("{}…", cut)
+    } else {
+        format!("{:<width$}", s)
+    }
+}
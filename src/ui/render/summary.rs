@@ -1,22 +1,37 @@
-use crate::transcript::SessionStatus;
+use crate::config::{GroupBy, ListConfig, SortOrder};
 use crate::transcript::WaitingPrompt;
+use crate::transcript::{
+    format_duration, todo_progress, SessionStatus, StatusSource, Subagent, SubagentState, Todo,
+    TodoStatus,
+};
 use crate::ui::markdown;
-use crate::ui::session::{status_display, ClaudeSession};
+use crate::ui::session::{
+    first_new_header_level, git_status_span, meta_spans, status_display, ClaudeSession, GroupHeader,
+};
+use crate::ui::theme::Theme;
+use chrono::{DateTime, Utc};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Position, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
 };
+use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 use std::time::SystemTime;
 use unicode_width::UnicodeWidthChar;
 
-use super::DetailsRenderCtx;
+use super::{DetailsRenderCtx, ListRenderCtx};
 
 /// Animation frames for Processing status (rotating dots)
 const PROCESSING_FRAMES: [&str; 4] = ["◐", "◓", "◑", "◒"];
 
+/// Sessions waiting longer than this are highlighted in the list.
+const WAITING_ALERT_SECS: u64 = 60;
+
+/// Window of the status timeline in the details panel.
+const TIMELINE_WINDOW_SECS: i64 = 60 * 60;
+
 /// Compute a fast hash of a string for cache key comparison.
 fn hash_str(s: &str) -> u64 {
     let mut hasher = std::hash::DefaultHasher::new();
@@ -24,157 +39,373 @@ fn hash_str(s: &str) -> u64 {
     hasher.finish()
 }
 
-/// Format a duration as a relative time string (e.g., "5s", "2m", "1h", "3d").
-fn format_duration(duration: std::time::Duration) -> String {
-    let secs = duration.as_secs();
-    if secs < 60 {
-        format!("{}s", secs)
-    } else if secs < 3600 {
-        format!("{}m", secs / 60)
-    } else if secs < 86400 {
-        format!("{}h", secs / 3600)
-    } else {
-        format!("{}d", secs / 86400)
-    }
-}
-
 /// Format relative time (e.g., "5s", "2m", "1h")
 pub(super) fn format_relative_time(time: &SystemTime) -> String {
     let now = SystemTime::now();
     match now.duration_since(*time) {
-        Ok(d) => format_duration(d),
+        Ok(d) => format_duration(d, 1),
         Err(_) => "now".to_string(),
     }
 }
 
+/// Where the status came from (e.g., "via hook: Stop · 5s ago").
+fn status_source_text(source: &StatusSource) -> String {
+    match source {
+        StatusSource::Hook { event, at } => match format_relative_time(&(*at).into()).as_str() {
+            "now" => format!("via hook: {} · just now", event),
+            ago => format!("via hook: {} · {} ago", event, ago),
+        },
+        StatusSource::Transcript => "via transcript".to_string(),
+        StatusSource::Cwd => "via transcript (matched by cwd)".to_string(),
+    }
+}
+
 /// Get color for a given elapsed duration.
-/// - < 5 minutes: Green (fresh/active)
-/// - 5-30 minutes: Yellow (slightly stale)
-/// - > 30 minutes: Red (inactive/stale)
-fn color_for_elapsed(duration: std::time::Duration) -> Color {
+/// - < 5 minutes: success (fresh/active)
+/// - 5-30 minutes: highlight (slightly stale)
+/// - > 30 minutes: error (inactive/stale)
+fn color_for_elapsed(duration: std::time::Duration, theme: &Theme) -> Color {
     let secs = duration.as_secs();
     if secs < 300 {
-        Color::Green
+        theme.success
     } else if secs < 1800 {
-        Color::Yellow
+        theme.highlight
     } else {
-        Color::Red
+        theme.error
     }
 }
 
 /// Get color for elapsed time display based on a SystemTime.
-pub(super) fn elapsed_time_color(time: &SystemTime) -> Color {
+pub(super) fn elapsed_time_color(time: &SystemTime, theme: &Theme) -> Color {
     let now = SystemTime::now();
     match now.duration_since(*time) {
-        Ok(d) => color_for_elapsed(d),
-        Err(_) => Color::Green,
+        Ok(d) => color_for_elapsed(d, theme),
+        Err(_) => theme.success,
     }
 }
 
-/// Render the session list.
-pub(super) fn render_list(
-    f: &mut ratatui::Frame,
-    area: Rect,
-    sessions: &[ClaudeSession],
-    list_state: &mut ListState,
-    refreshing: bool,
-    animation_frame: u8,
+/// Build the list item for a group header row.
+fn group_header_item(
+    header: &GroupHeader,
+    level: usize,
+    count: usize,
+    innermost: bool,
     current_workspace: &str,
-) -> Option<Rect> {
-    // Count sessions per (workspace, cwd)
-    let mut cwd_info: std::collections::HashMap<(String, String), usize> =
-        std::collections::HashMap::new();
-    for session in sessions {
-        let ws = session.pane.workspace.clone();
-        if let Some(cwd) = session.pane.cwd_path() {
-            *cwd_info.entry((ws, cwd)).or_insert(0) += 1;
-        }
-    }
-
-    // Build list items (workspace header + cwd header + sessions)
-    let mut items: Vec<ListItem> = Vec::new();
-    let mut session_indices: Vec<usize> = Vec::new(); // ListItem index -> session index mapping
-    let mut current_ws: Option<String> = None;
-    let mut current_cwd: Option<String> = None;
-
-    for (session_idx, session) in sessions.iter().enumerate() {
-        let pane = &session.pane;
-        let ws = &pane.workspace;
-        let cwd = pane.cwd_path().unwrap_or_default();
-
-        // Add workspace header for new workspace
-        if current_ws.as_ref() != Some(ws) {
-            current_ws = Some(ws.clone());
-            current_cwd = None; // Reset cwd tracking for new workspace
+    theme: &Theme,
+) -> ListItem<'static> {
+    let indent = "  ".repeat(level);
+    // Show session count on the innermost header if multiple sessions
+    let count_suffix = if innermost && count > 1 {
+        format!(" ({} sessions)", count)
+    } else {
+        String::new()
+    };
 
+    let line = match header {
+        GroupHeader::Pinned => Line::from(vec![Span::styled(
+            format!("{}📌 Pinned{}", indent, count_suffix),
+            Style::default().add_modifier(Modifier::BOLD),
+        )]),
+        GroupHeader::Workspace(ws) => {
             // Visual distinction for current vs other workspace (subtle colors)
             let (ws_icon, ws_style) = if ws == current_workspace {
                 (
                     "🏠",
                     Style::default()
-                        .fg(Color::DarkGray)
+                        .fg(theme.muted)
                         .add_modifier(Modifier::BOLD),
                 )
             } else {
-                ("📍", Style::default().fg(Color::DarkGray))
+                ("📍", Style::default().fg(theme.muted))
             };
-
-            let ws_header = Line::from(vec![Span::styled(
-                format!("{} Workspace: {}", ws_icon, ws),
+            Line::from(vec![Span::styled(
+                format!("{}{} Workspace: {}{}", indent, ws_icon, ws, count_suffix),
                 ws_style,
-            )]);
-            items.push(ListItem::new(ws_header));
-            session_indices.push(usize::MAX); // Header is not a session
+            )])
         }
-
-        // Get group info
-        let count = cwd_info
-            .get(&(ws.clone(), cwd.clone()))
-            .copied()
-            .unwrap_or(1);
-
-        // Add header for new CWD (within the same workspace)
-        if current_cwd.as_ref() != Some(&cwd) {
-            current_cwd = Some(cwd.clone());
-
+        GroupHeader::Cwd(cwd) => {
             // Get directory name from cwd
-            let dir_name = std::path::Path::new(&cwd)
+            let dir_name = std::path::Path::new(cwd)
                 .file_name()
                 .and_then(|n| n.to_str())
-                .unwrap_or(&cwd)
-                .to_string();
+                .unwrap_or(cwd);
+            Line::from(vec![Span::raw(format!(
+                "{}📂 {}{}",
+                indent, dir_name, count_suffix
+            ))])
+        }
+        GroupHeader::Repo(root) => {
+            let name = root
+                .as_deref()
+                .map(|r| {
+                    std::path::Path::new(r)
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .unwrap_or(r)
+                        .to_string()
+                })
+                .unwrap_or_else(|| "(no repository)".to_string());
+            Line::from(vec![Span::raw(format!(
+                "{}📦 {}{}",
+                indent, name, count_suffix
+            ))])
+        }
+        GroupHeader::Agent(agent) => Line::from(vec![Span::raw(format!(
+            "{}🤖 {}{}",
+            indent, agent, count_suffix
+        ))]),
+    };
 
-            // Show session count if multiple sessions
-            let header_text = if count > 1 {
-                format!("  📂 {} ({} sessions)", dir_name, count)
-            } else {
-                format!("  📂 {}", dir_name)
-            };
+    ListItem::new(line)
+}
 
-            let header_line = Line::from(vec![Span::raw(header_text)]);
-            items.push(ListItem::new(header_line));
-            session_indices.push(usize::MAX); // Header is not a session
-        }
+/// Compact progress bar of a todo list (e.g., "▰▰▰▱▱ 4/7"). None if the
+/// list is empty.
+fn todo_bar(todos: &[Todo]) -> Option<String> {
+    const CELLS: usize = 5;
+    let (done, total) = todo_progress(todos);
+    if total == 0 {
+        return None;
+    }
+    // Rounded, but only full once every item is done
+    let filled = ((done * CELLS + total / 2) / total).min(CELLS - usize::from(done < total));
+    Some(format!(
+        "{}{} {}/{}",
+        "▰".repeat(filled),
+        "▱".repeat(CELLS - filled),
+        done,
+        total
+    ))
+}
+
+/// Checklist line of a todo item: ✓ completed, ▶ in progress (by its
+/// active form), ○ pending.
+fn todo_line<'a>(todo: &'a Todo, theme: &Theme) -> Line<'a> {
+    let (icon, text, style) = match todo.status {
+        TodoStatus::Completed => ("✓ ", &todo.content, Style::default().fg(theme.muted)),
+        TodoStatus::InProgress => (
+            "▶ ",
+            todo.active_form.as_ref().unwrap_or(&todo.content),
+            Style::default()
+                .fg(theme.highlight)
+                .add_modifier(Modifier::BOLD),
+        ),
+        TodoStatus::Pending => ("○ ", &todo.content, Style::default().fg(theme.text)),
+    };
+    Line::from(vec![
+        Span::raw("  "),
+        Span::styled(icon, style),
+        Span::styled(text.as_str(), style),
+    ])
+}
+
+/// Tool count and running time of a subagent (e.g., " · 4 tools · 1m").
+fn subagent_details(subagent: &Subagent, now: DateTime<Utc>) -> String {
+    let mut details = format!(
+        " · {} tool{}",
+        subagent.tool_count,
+        if subagent.tool_count == 1 { "" } else { "s" }
+    );
+    if let Some(elapsed) = subagent.elapsed(now) {
+        details.push_str(&format!(" · {}", format_duration(elapsed, 1)));
+    }
+    details
+}
+
+/// Tree branch of a subagent beneath its session
+/// (e.g., "├─ ◐ Find parsers (Explore) · 4 tools · 1m").
+fn subagent_spans(
+    subagent: &Subagent,
+    last: bool,
+    running_icon: &'static str,
+    theme: &Theme,
+) -> Vec<Span<'static>> {
+    let (icon, color) = match subagent.state {
+        SubagentState::Running => (running_icon, theme.status_subagent),
+        SubagentState::Completed => ("✓", theme.success),
+        SubagentState::Failed => ("✗", theme.status_error),
+    };
+    let mut label = subagent.description.clone();
+    if let Some(agent_type) = &subagent.agent_type {
+        label.push_str(&format!(" ({})", agent_type));
+    }
+    vec![
+        Span::styled(
+            if last { "└─ " } else { "├─ " },
+            Style::default().fg(theme.muted),
+        ),
+        Span::styled(format!("{} ", icon), Style::default().fg(color)),
+        Span::raw(label),
+        Span::styled(
+            subagent_details(subagent, Utc::now()),
+            Style::default().fg(theme.muted),
+        ),
+    ]
+}
+
+/// Render the session list.
+/// Status label of a list row: waiting sessions show for how long (e.g.
+/// "Waiting for 4m12s"). Also returns whether the wait is long enough to
+/// highlight.
+fn list_status_label(session: &ClaudeSession, now: DateTime<Utc>) -> (String, bool) {
+    match (&session.status, session.status_elapsed(now)) {
+        (SessionStatus::WaitingForUser { .. }, Some(elapsed)) => (
+            format!("Waiting for {}", format_duration(elapsed, 2)),
+            elapsed.as_secs() >= WAITING_ALERT_SECS,
+        ),
+        (status, _) => (status.as_str().to_string(), false),
+    }
+}
 
-        // Status icon and color (Processing uses animated spinner)
-        let (status_icon, status_color) = match &session.status {
-            SessionStatus::Ready => ("◇", Color::Cyan),
-            SessionStatus::Processing => (
-                PROCESSING_FRAMES[animation_frame as usize % 4],
-                Color::Yellow,
+/// Timeline section of the details panel: time in the current status, the
+/// statuses of the last hour as colored cells, and the time spent in each.
+fn timeline_lines(
+    session: &ClaudeSession,
+    width: usize,
+    now: DateTime<Utc>,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    let timeline = &session.timeline;
+    if timeline.since().is_none() {
+        return Vec::new();
+    }
+    let from = now - chrono::Duration::seconds(TIMELINE_WINDOW_SECS);
+    let status_color = theme.status_color(&session.status);
+
+    let mut header = vec![Span::styled(
+        "⏱ Timeline",
+        Style::default().add_modifier(Modifier::BOLD),
+    )];
+    if let Some(elapsed) = session.status_elapsed(now) {
+        header.push(Span::styled(
+            format!(
+                " · {} for {}",
+                session.status.as_str(),
+                format_duration(elapsed, 2)
             ),
-            SessionStatus::Idle => ("○", Color::Green),
-            SessionStatus::WaitingForUser { .. } => ("◐", Color::Magenta),
-            SessionStatus::Unknown => ("?", Color::DarkGray),
+            Style::default().fg(status_color),
+        ));
+    }
+
+    let cells = width.saturating_sub(8).clamp(10, 60);
+    let mut bar = vec![Span::styled("-1h ", Style::default().fg(theme.muted))];
+    bar.extend(
+        timeline
+            .samples(from, now, cells)
+            .into_iter()
+            .map(|kind| match kind {
+                Some(kind) => Span::styled("▆", Style::default().fg(theme.status_kind_color(kind))),
+                None => Span::styled("·", Style::default().fg(theme.muted)),
+            }),
+    );
+    bar.push(Span::styled(" now", Style::default().fg(theme.muted)));
+
+    let mut totals = Vec::new();
+    for (i, (kind, total)) in timeline.durations(from, now).into_iter().enumerate() {
+        if i > 0 {
+            totals.push(Span::styled(" · ", Style::default().fg(theme.muted)));
+        }
+        totals.push(Span::styled(
+            kind.as_str(),
+            Style::default().fg(theme.status_kind_color(kind)),
+        ));
+        totals.push(Span::styled(
+            format!(" {}", format_duration(total, 2)),
+            Style::default().fg(theme.muted),
+        ));
+    }
+
+    vec![Line::from(header), Line::from(bar), Line::from(totals)]
+}
+
+pub(super) fn render_list(
+    f: &mut ratatui::Frame,
+    area: Rect,
+    ctx: &ListRenderCtx<'_>,
+    list_state: &mut ListState,
+) -> Option<Rect> {
+    let sessions = ctx.sessions;
+    let theme = ctx.theme;
+    let group_by = ctx.list_config.group;
+    let session_headers: Vec<Vec<GroupHeader>> =
+        sessions.iter().map(|s| s.group_headers(group_by)).collect();
+
+    // Count sessions under each header path (for "(N sessions)" display)
+    let mut group_counts: HashMap<&[GroupHeader], usize> = HashMap::new();
+    for headers in &session_headers {
+        for depth in 1..=headers.len() {
+            *group_counts.entry(&headers[..depth]).or_insert(0) += 1;
+        }
+    }
+
+    // Build list items (group headers + sessions)
+    let mut items: Vec<ListItem> = Vec::new();
+    let mut session_indices: Vec<usize> = Vec::new(); // ListItem index -> session index mapping
+    let mut prev_headers: Option<&[GroupHeader]> = None;
+    let now = Utc::now();
+
+    for (session_idx, session) in sessions.iter().enumerate() {
+        let pane = &session.pane;
+        let headers = session_headers[session_idx].as_slice();
+
+        // Add header rows for each group level that changed
+        let first_level = first_new_header_level(prev_headers, headers);
+        for (level, header) in headers.iter().enumerate().skip(first_level) {
+            let count = group_counts.get(&headers[..=level]).copied().unwrap_or(1);
+            items.push(group_header_item(
+                header,
+                level,
+                count,
+                level + 1 == headers.len(),
+                ctx.current_workspace,
+                theme,
+            ));
+            session_indices.push(usize::MAX); // Header is not a session
+        }
+        prev_headers = Some(headers);
+
+        // Status icon and color (working statuses use animated spinner)
+        let status_icon = match &session.status {
+            status if status.is_active() => PROCESSING_FRAMES[ctx.animation_frame as usize % 4],
+            SessionStatus::Ready => "◇",
+            SessionStatus::Idle => "○",
+            SessionStatus::WaitingForUser { .. } => "◐",
+            SessionStatus::RateLimited { .. } => "⏸",
+            SessionStatus::Errored { .. } => "✗",
+            _ => "?",
+        };
+        let status_color = theme.status_color(&session.status);
+        let (status_label, waiting_long) = list_status_label(session, now);
+        let status_style = if waiting_long {
+            Style::default()
+                .fg(status_color)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED)
+        } else {
+            Style::default().fg(status_color)
         };
 
-        // Title (max 35 chars)
-        let title = if pane.title.chars().count() > 35 {
-            let truncated: String = pane.title.chars().take(32).collect();
+        // Title (max 35 chars), replaced by the user-assigned label if any
+        let title = session.meta.label.as_ref().unwrap_or(&pane.title);
+        let title = if title.chars().count() > 35 {
+            let truncated: String = title.chars().take(32).collect();
             format!("{}...", truncated)
         } else {
-            pane.title.clone()
+            title.clone()
         };
+        let title_style = if session.meta.label.is_some() {
+            Style::default()
+                .fg(theme.label_color(session.meta.color.as_deref()))
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        let pin_marker = if session.meta.pinned { "📌 " } else { "" };
+        let tags: String = session
+            .meta
+            .tags
+            .iter()
+            .map(|t| format!(" #{}", t))
+            .collect();
 
         // Quick select number (1-9, or space if > 9)
         let quick_num = if session_idx < 9 {
@@ -190,30 +421,65 @@ pub(super) fn render_list(
             .map(|t| {
                 (
                     format!(" {}", format_relative_time(t)),
-                    elapsed_time_color(t),
+                    elapsed_time_color(t, theme),
                 )
             })
-            .unwrap_or((String::new(), Color::DarkGray));
+            .unwrap_or((String::new(), theme.muted));
 
-        // Indent (all sessions are indented under workspace + cwd headers)
-        let line = Line::from(vec![
-            Span::raw("    "), // Extra indent for hierarchy
-            Span::styled(format!("{} ", quick_num), Style::default().fg(Color::White)),
+        // Uncommitted changes, ahead/behind and stashes
+        let git_status = session
+            .git_status
+            .as_ref()
+            .and_then(|status| git_status_span(status, theme));
+
+        // Indent sessions under their group headers
+        let mut line = Line::from(vec![
+            Span::raw("  ".repeat(headers.len())), // Extra indent for hierarchy
+            Span::styled(format!("{} ", quick_num), Style::default().fg(theme.text)),
             Span::styled(
                 format!("{} ", status_icon),
                 Style::default()
                     .fg(status_color)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::raw(title),
-            Span::styled(
-                format!(" [{}]", session.status.as_str()),
-                Style::default().fg(status_color),
-            ),
+            Span::raw(pin_marker),
+            Span::styled(title, title_style),
+            Span::styled(tags, Style::default().fg(theme.muted)),
+            Span::styled(format!(" [{}]", status_label), status_style),
             Span::styled(time_display, Style::default().fg(time_color)),
         ]);
+        if let Some(bar) = todo_bar(&session.todos) {
+            let (done, total) = todo_progress(&session.todos);
+            let color = if done == total {
+                theme.success
+            } else {
+                theme.accent
+            };
+            line.spans.push(Span::styled(
+                format!(" {}", bar),
+                Style::default().fg(color),
+            ));
+        }
+        if let Some(span) = git_status {
+            line.spans.extend([Span::raw(" "), span]);
+        }
 
-        items.push(ListItem::new(line));
+        // Running subagents as a tree beneath the session
+        let spinner = PROCESSING_FRAMES[ctx.animation_frame as usize % 4];
+        let running: Vec<_> = session.running_subagents().collect();
+        let mut lines = vec![line];
+        for (i, subagent) in running.iter().enumerate() {
+            let mut spans = vec![Span::raw("  ".repeat(headers.len() + 2))];
+            spans.extend(subagent_spans(
+                subagent,
+                i + 1 == running.len(),
+                spinner,
+                theme,
+            ));
+            lines.push(Line::from(spans));
+        }
+
+        items.push(ListItem::new(lines));
         session_indices.push(session_idx);
     }
 
@@ -225,18 +491,42 @@ pub(super) fn render_list(
     let mut render_state = ListState::default();
     render_state.select(list_index);
 
-    // Title (show indicator while refreshing)
-    let title = if refreshing {
+    // Title (show indicator while refreshing, matched/total while filtering)
+    let count = match &ctx.filter {
+        Some(filter) => format!("{}/{}", sessions.len(), filter.total),
+        None => sessions.len().to_string(),
+    };
+    let title = if ctx.refreshing {
         " ⌛ Claude Code Sessions - Refreshing... ".to_string()
     } else {
-        format!(" Claude Code Sessions ({}) ", sessions.len())
+        format!(
+            " Claude Code Sessions ({}){} ",
+            count,
+            list_config_label(&ctx.list_config)
+        )
     };
 
+    let mut block = Block::default().borders(Borders::ALL).title(title);
+    if let Some(filter) = &ctx.filter {
+        let cursor = if filter.editing { "▏" } else { "" };
+        let query_style = if filter.editing {
+            Style::default()
+                .fg(theme.highlight)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.highlight)
+        };
+        block = block.title_bottom(Line::from(vec![
+            Span::styled(" /", Style::default().fg(theme.muted)),
+            Span::styled(format!("{}{} ", filter.query, cursor), query_style),
+        ]));
+    }
+
     let list = List::new(items)
-        .block(Block::default().borders(Borders::ALL).title(title))
+        .block(block)
         .highlight_style(
             Style::default()
-                .bg(Color::DarkGray)
+                .bg(theme.selection_bg)
                 .add_modifier(Modifier::BOLD),
         )
         .highlight_symbol(">> ");
@@ -246,11 +536,29 @@ pub(super) fn render_list(
     Some(area)
 }
 
+/// Short label for non-default sort/group settings shown in the list title
+/// (e.g. " [sort:status group:flat]"). Empty when both are defaults.
+fn list_config_label(list: &ListConfig) -> String {
+    let mut parts = Vec::new();
+    if list.sort != SortOrder::default() {
+        parts.push(format!("sort:{}", list.sort.as_str()));
+    }
+    if list.group != GroupBy::default() {
+        parts.push(format!("group:{}", list.group.as_str()));
+    }
+    if parts.is_empty() {
+        String::new()
+    } else {
+        format!(" [{}]", parts.join(" "))
+    }
+}
+
 pub(super) fn render_summary_details(
     f: &mut ratatui::Frame,
     area: Rect,
     ctx: &mut DetailsRenderCtx<'_>,
 ) {
+    let theme = ctx.theme;
     let text = if let Some(i) = ctx.selected {
         if let Some(session) = ctx.sessions.get(i) {
             let pane = &session.pane;
@@ -265,67 +573,148 @@ pub(super) fn render_summary_details(
             // -- Compact badge-style metadata header --
 
             // Line 1: Pane ID [quick] │ ● Status │ ⎇ Branch
-            let (status_color, status_text) = status_display(&session.status);
+            let (status_color, status_text) = status_display(&session.status, theme);
             let mut header_spans: Vec<Span<'_>> = vec![
                 Span::styled(
                     format!("#{}", pane.pane_id),
                     Style::default().add_modifier(Modifier::BOLD),
                 ),
-                Span::styled(quick_num_display, Style::default().fg(Color::DarkGray)),
-                Span::styled("  │ ", Style::default().fg(Color::DarkGray)),
+                Span::styled(quick_num_display, Style::default().fg(theme.muted)),
+                Span::styled("  │ ", Style::default().fg(theme.muted)),
                 Span::styled("● ", Style::default().fg(status_color)),
                 Span::styled(status_text, Style::default().fg(status_color)),
             ];
             if let Some(branch) = &session.git_branch {
-                header_spans.push(Span::styled("  │ ", Style::default().fg(Color::DarkGray)));
-                header_spans.push(Span::styled("⎇ ", Style::default().fg(Color::Cyan)));
+                header_spans.push(Span::styled("  │ ", Style::default().fg(theme.muted)));
+                header_spans.push(Span::styled("⎇ ", Style::default().fg(theme.accent)));
                 header_spans.push(Span::styled(
                     branch.as_str(),
-                    Style::default().fg(Color::Cyan),
+                    Style::default().fg(theme.accent),
                 ));
             }
+            if let Some(span) = session
+                .git_status
+                .as_ref()
+                .and_then(|status| git_status_span(status, theme))
+            {
+                header_spans.push(Span::raw(" "));
+                header_spans.push(span);
+            }
             if let Some(worktree) = &session.git_worktree {
-                header_spans.push(Span::styled("  │ ", Style::default().fg(Color::DarkGray)));
-                header_spans.push(Span::styled("🌳 ", Style::default().fg(Color::Green)));
+                header_spans.push(Span::styled("  │ ", Style::default().fg(theme.muted)));
+                header_spans.push(Span::styled("🌳 ", Style::default().fg(theme.success)));
                 header_spans.push(Span::styled(
                     worktree.as_str(),
-                    Style::default().fg(Color::Green),
+                    Style::default().fg(theme.success),
                 ));
             }
             let mut lines = vec![Line::from(header_spans)];
 
+            // User-assigned label/tags (if any)
+            let meta = meta_spans(&session.meta, theme);
+            if !meta.is_empty() {
+                lines.push(Line::from(meta));
+            }
+
             // Line 2: Workspace │ TTY
             let mut info_spans: Vec<Span<'_>> = vec![Span::styled(
                 &pane.workspace,
-                Style::default().fg(Color::Yellow),
+                Style::default().fg(theme.highlight),
             )];
             if let Some(tty) = &pane.tty_name {
-                info_spans.push(Span::styled("  │ ", Style::default().fg(Color::DarkGray)));
-                info_spans.push(Span::styled(tty, Style::default().fg(Color::DarkGray)));
+                info_spans.push(Span::styled("  │ ", Style::default().fg(theme.muted)));
+                info_spans.push(Span::styled(tty, Style::default().fg(theme.muted)));
             }
+            info_spans.push(Span::styled("  │ ", Style::default().fg(theme.muted)));
+            info_spans.push(Span::styled(
+                status_source_text(&session.status_source),
+                Style::default().fg(theme.muted),
+            ));
             lines.push(Line::from(info_spans));
 
             // Line 3: CWD (if present)
             if let Some(cwd) = pane.cwd_path() {
                 lines.push(Line::from(Span::styled(
                     cwd,
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme.muted),
                 )));
             }
 
-            // Show hint when session is waiting for user input
-            if session.waiting_prompt.is_some() {
+            // Line 4: Last commit subject (if in a repository)
+            if let Some(subject) = session
+                .git_status
+                .as_ref()
+                .and_then(|status| status.last_commit.as_deref())
+            {
+                lines.push(Line::from(vec![
+                    Span::styled("✎ ", Style::default().fg(theme.muted)),
+                    Span::styled(subject, Style::default().fg(theme.muted)),
+                ]));
+            }
+
+            // Status changes of the last hour
+            lines.extend(timeline_lines(
+                session,
+                area.width.saturating_sub(2) as usize,
+                Utc::now(),
+                theme,
+            ));
+
+            // Subagents of the current prompt, finished ones included
+            if !session.subagents.is_empty() {
+                let running = session.running_subagents().count();
                 lines.push(Line::from(vec![
-                    Span::styled("⚡ ", Style::default().fg(Color::Magenta)),
-                    Span::styled("Press ", Style::default().fg(Color::Magenta)),
+                    Span::styled("⑂ Subagents", Style::default().add_modifier(Modifier::BOLD)),
                     Span::styled(
-                        "o",
-                        Style::default()
-                            .fg(Color::Magenta)
-                            .add_modifier(Modifier::BOLD),
+                        format!(
+                            " ({} running, {} done)",
+                            running,
+                            session.subagents.len() - running
+                        ),
+                        Style::default().fg(theme.muted),
                     ),
-                    Span::styled(" to respond", Style::default().fg(Color::Magenta)),
                 ]));
+                for (i, subagent) in session.subagents.iter().enumerate() {
+                    lines.push(Line::from(subagent_spans(
+                        subagent,
+                        i + 1 == session.subagents.len(),
+                        "◐",
+                        theme,
+                    )));
+                }
+            }
+
+            // Latest TodoWrite checklist
+            if let Some(bar) = todo_bar(&session.todos) {
+                lines.push(Line::from(vec![
+                    Span::styled("☑ Todos ", Style::default().add_modifier(Modifier::BOLD)),
+                    Span::styled(bar, Style::default().fg(theme.muted)),
+                ]));
+                lines.extend(session.todos.iter().map(|todo| todo_line(todo, theme)));
+            }
+
+            // Show hint when session is waiting for user input
+            if session.waiting_prompt.is_some() {
+                let key_style = Style::default()
+                    .fg(theme.attention)
+                    .add_modifier(Modifier::BOLD);
+                let mut hint = vec![
+                    Span::styled("⚡ ", Style::default().fg(theme.attention)),
+                    Span::styled("Press ", Style::default().fg(theme.attention)),
+                    Span::styled("o", key_style),
+                    Span::styled(" to respond", Style::default().fg(theme.attention)),
+                ];
+                if matches!(
+                    session.waiting_prompt,
+                    Some(WaitingPrompt::PlanApproval { .. })
+                ) {
+                    hint.extend([
+                        Span::styled(", ", Style::default().fg(theme.attention)),
+                        Span::styled("P", key_style),
+                        Span::styled(" to view the plan", Style::default().fg(theme.attention)),
+                    ]);
+                }
+                lines.push(Line::from(hint));
             }
 
             // Display last prompt and last output preview
@@ -338,7 +727,7 @@ pub(super) fn render_summary_details(
                 lines.push(Line::from(""));
                 lines.push(Line::from(vec![Span::styled(
                     "─".repeat(inner_width),
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme.muted),
                 )]));
 
                 // When PlanApproval with plan content, show plan instead of last prompt/output
@@ -357,20 +746,21 @@ pub(super) fn render_summary_details(
 
                     let text_hash = hash_str(plan);
                     let cache_key = (text_hash, inner_width);
-                    let output_lines =
-                        if let Some((cached_key, cached)) = ctx.cached_preview_lines.as_ref() {
-                            if *cached_key == cache_key {
-                                cached.clone()
-                            } else {
-                                let rendered = markdown::markdown_to_lines(plan, inner_width);
-                                *ctx.cached_preview_lines = Some((cache_key, rendered.clone()));
-                                rendered
-                            }
+                    let output_lines = if let Some((cached_key, cached)) =
+                        ctx.cached_preview_lines.as_ref()
+                    {
+                        if *cached_key == cache_key {
+                            cached.clone()
                         } else {
-                            let rendered = markdown::markdown_to_lines(plan, inner_width);
+                            let rendered = markdown::markdown_to_lines(plan, inner_width, theme);
                             *ctx.cached_preview_lines = Some((cache_key, rendered.clone()));
                             rendered
-                        };
+                        }
+                    } else {
+                        let rendered = markdown::markdown_to_lines(plan, inner_width, theme);
+                        *ctx.cached_preview_lines = Some((cache_key, rendered.clone()));
+                        rendered
+                    };
                     lines.extend(output_lines);
                 } else {
                     if let Some(prompt) = &session.last_prompt {
@@ -382,7 +772,7 @@ pub(super) fn render_summary_details(
                             prompt,
                             inner_width,
                             usize::MAX,
-                            Color::Cyan,
+                            theme.accent,
                         );
                         lines.extend(prompt_lines);
                     }
@@ -392,7 +782,7 @@ pub(super) fn render_summary_details(
                             lines.push(Line::from(""));
                             lines.push(Line::from(vec![Span::styled(
                                 "─".repeat(inner_width),
-                                Style::default().fg(Color::DarkGray),
+                                Style::default().fg(theme.muted),
                             )]));
                         }
 
@@ -403,20 +793,22 @@ pub(super) fn render_summary_details(
 
                         let text_hash = hash_str(output);
                         let cache_key = (text_hash, inner_width);
-                        let output_lines =
-                            if let Some((cached_key, cached)) = ctx.cached_preview_lines.as_ref() {
-                                if *cached_key == cache_key {
-                                    cached.clone()
-                                } else {
-                                    let rendered = markdown::markdown_to_lines(output, inner_width);
-                                    *ctx.cached_preview_lines = Some((cache_key, rendered.clone()));
-                                    rendered
-                                }
+                        let output_lines = if let Some((cached_key, cached)) =
+                            ctx.cached_preview_lines.as_ref()
+                        {
+                            if *cached_key == cache_key {
+                                cached.clone()
                             } else {
-                                let rendered = markdown::markdown_to_lines(output, inner_width);
+                                let rendered =
+                                    markdown::markdown_to_lines(output, inner_width, theme);
                                 *ctx.cached_preview_lines = Some((cache_key, rendered.clone()));
                                 rendered
-                            };
+                            }
+                        } else {
+                            let rendered = markdown::markdown_to_lines(output, inner_width, theme);
+                            *ctx.cached_preview_lines = Some((cache_key, rendered.clone()));
+                            rendered
+                        };
                         lines.extend(output_lines);
                     }
                 }
@@ -451,12 +843,12 @@ pub(super) fn render_summary_details(
                 }
                 visual_lines.push(Line::from(Span::styled(
                     prefix_str.to_string(),
-                    Style::default().fg(Color::Cyan),
+                    Style::default().fg(theme.accent),
                 )));
             } else if text_width == 0 {
                 visual_lines.push(Line::from(Span::styled(
                     prefix_str.to_string(),
-                    Style::default().fg(Color::Cyan),
+                    Style::default().fg(theme.accent),
                 )));
             } else {
                 let mut col_w = 0usize;
@@ -470,7 +862,7 @@ pub(super) fn render_summary_details(
                         let chunk = &logical_line[chunk_start..ci];
                         let pfx = if is_first_visual { prefix_str } else { "  " };
                         visual_lines.push(Line::from(vec![
-                            Span::styled(pfx.to_string(), Style::default().fg(Color::Cyan)),
+                            Span::styled(pfx.to_string(), Style::default().fg(theme.accent)),
                             Span::raw(chunk.to_string()),
                         ]));
                         chunk_start = ci;
@@ -490,7 +882,7 @@ pub(super) fn render_summary_details(
                 let chunk = &logical_line[chunk_start..];
                 let pfx = if is_first_visual { prefix_str } else { "  " };
                 visual_lines.push(Line::from(vec![
-                    Span::styled(pfx.to_string(), Style::default().fg(Color::Cyan)),
+                    Span::styled(pfx.to_string(), Style::default().fg(theme.accent)),
                     Span::raw(chunk.to_string()),
                 ]));
 
@@ -541,7 +933,7 @@ pub(super) fn render_summary_details(
                 Block::default()
                     .borders(Borders::ALL)
                     .title(format!(" Send prompt to Pane {} ", pane_id))
-                    .border_style(Style::default().fg(Color::Cyan)),
+                    .border_style(Style::default().fg(theme.accent)),
             )
             .scroll((scroll_offset, 0));
         f.render_widget(input_paragraph, chunks[1]);
@@ -570,30 +962,44 @@ pub(super) fn render_session_info_header(
     f: &mut ratatui::Frame,
     area: Rect,
     session: &ClaudeSession,
+    theme: &Theme,
 ) {
     let pane = &session.pane;
-    let (status_color, status_text) = status_display(&session.status);
+    let (status_color, status_text) = status_display(&session.status, theme);
 
-    let mut spans: Vec<Span<'_>> = vec![
-        Span::raw(" "),
-        Span::styled(&pane.workspace, Style::default().fg(Color::Yellow)),
-        Span::styled(" │ ", Style::default().fg(Color::DarkGray)),
+    let mut spans: Vec<Span<'_>> = vec![Span::raw(" ")];
+    let meta = meta_spans(&session.meta, theme);
+    if !meta.is_empty() {
+        spans.extend(meta);
+        spans.push(Span::styled(" │ ", Style::default().fg(theme.muted)));
+    }
+    spans.extend([
+        Span::styled(&pane.workspace, Style::default().fg(theme.highlight)),
+        Span::styled(" │ ", Style::default().fg(theme.muted)),
         Span::styled(status_text, Style::default().fg(status_color)),
-    ];
+    ]);
 
     if let Some(branch) = &session.git_branch {
-        spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
+        spans.push(Span::styled(" │ ", Style::default().fg(theme.muted)));
         spans.push(Span::styled(
             branch.as_str(),
-            Style::default().fg(Color::Cyan),
+            Style::default().fg(theme.accent),
         ));
     }
+    if let Some(span) = session
+        .git_status
+        .as_ref()
+        .and_then(|status| git_status_span(status, theme))
+    {
+        spans.push(Span::raw(" "));
+        spans.push(span);
+    }
     if let Some(worktree) = &session.git_worktree {
-        spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
-        spans.push(Span::styled("🌳 ", Style::default().fg(Color::Green)));
+        spans.push(Span::styled(" │ ", Style::default().fg(theme.muted)));
+        spans.push(Span::styled("🌳 ", Style::default().fg(theme.success)));
         spans.push(Span::styled(
             worktree.as_str(),
-            Style::default().fg(Color::Green),
+            Style::default().fg(theme.success),
         ));
     }
 
@@ -602,7 +1008,7 @@ pub(super) fn render_session_info_header(
     if let Some(cwd) = pane.cwd_path() {
         lines.push(Line::from(vec![
             Span::raw(" "),
-            Span::styled(cwd, Style::default().fg(Color::DarkGray)),
+            Span::styled(cwd, Style::default().fg(theme.muted)),
         ]));
     }
 
@@ -616,89 +1022,153 @@ mod tests {
     use std::time::Duration;
 
     #[test]
-    fn test_format_duration_seconds() {
-        assert_eq!(format_duration(Duration::from_secs(0)), "0s");
-        assert_eq!(format_duration(Duration::from_secs(1)), "1s");
-        assert_eq!(format_duration(Duration::from_secs(30)), "30s");
-        assert_eq!(format_duration(Duration::from_secs(59)), "59s");
+    fn test_format_relative_time_recent() {
+        let time = SystemTime::now() - Duration::from_secs(5);
+        let result = format_relative_time(&time);
+        assert!(result.ends_with('s'));
     }
 
     #[test]
-    fn test_format_duration_minutes() {
-        assert_eq!(format_duration(Duration::from_secs(60)), "1m");
-        assert_eq!(format_duration(Duration::from_secs(90)), "1m");
-        assert_eq!(format_duration(Duration::from_secs(300)), "5m");
-        assert_eq!(format_duration(Duration::from_secs(3599)), "59m");
+    fn test_format_relative_time_future() {
+        let time = SystemTime::now() + Duration::from_secs(100);
+        assert_eq!(format_relative_time(&time), "now");
     }
 
     #[test]
-    fn test_format_duration_hours() {
-        assert_eq!(format_duration(Duration::from_secs(3600)), "1h");
-        assert_eq!(format_duration(Duration::from_secs(7200)), "2h");
-        assert_eq!(format_duration(Duration::from_secs(86399)), "23h");
+    fn test_status_source_text() {
+        let at = chrono::Utc::now() - chrono::Duration::minutes(3);
+        assert_eq!(
+            status_source_text(&StatusSource::Hook {
+                event: "PreToolUse Bash".to_string(),
+                at,
+            }),
+            "via hook: PreToolUse Bash · 3m ago"
+        );
+        assert_eq!(
+            status_source_text(&StatusSource::Cwd),
+            "via transcript (matched by cwd)"
+        );
     }
 
     #[test]
-    fn test_format_duration_days() {
-        assert_eq!(format_duration(Duration::from_secs(86400)), "1d");
-        assert_eq!(format_duration(Duration::from_secs(172800)), "2d");
+    fn test_todo_bar() {
+        let todos = |done: usize, total: usize| -> Vec<Todo> {
+            (0..total)
+                .map(|i| Todo {
+                    content: format!("item {}", i),
+                    status: if i < done {
+                        TodoStatus::Completed
+                    } else {
+                        TodoStatus::Pending
+                    },
+                    active_form: None,
+                })
+                .collect()
+        };
+        assert_eq!(todo_bar(&[]), None);
+        assert_eq!(todo_bar(&todos(0, 7)).as_deref(), Some("▱▱▱▱▱ 0/7"));
+        assert_eq!(todo_bar(&todos(4, 7)).as_deref(), Some("▰▰▰▱▱ 4/7"));
+        assert_eq!(todo_bar(&todos(9, 10)).as_deref(), Some("▰▰▰▰▱ 9/10"));
+        assert_eq!(todo_bar(&todos(7, 7)).as_deref(), Some("▰▰▰▰▰ 7/7"));
     }
 
     #[test]
-    fn test_format_relative_time_recent() {
-        let time = SystemTime::now() - Duration::from_secs(5);
-        let result = format_relative_time(&time);
-        assert!(result.ends_with('s'));
-    }
+    fn test_subagent_details() {
+        let started = chrono::Utc::now() - chrono::Duration::seconds(90);
+        let mut subagent = Subagent {
+            id: "t1".to_string(),
+            description: "Find parsers".to_string(),
+            agent_type: Some("Explore".to_string()),
+            state: SubagentState::Running,
+            tool_count: 1,
+            started_at: Some(started),
+            duration: None,
+        };
+        let now = started + chrono::Duration::seconds(90);
+        assert_eq!(subagent_details(&subagent, now), " · 1 tool · 1m");
 
-    #[test]
-    fn test_format_relative_time_future() {
-        let time = SystemTime::now() + Duration::from_secs(100);
-        assert_eq!(format_relative_time(&time), "now");
+        subagent.state = SubagentState::Completed;
+        subagent.tool_count = 4;
+        subagent.duration = Some(Duration::from_secs(42));
+        assert_eq!(subagent_details(&subagent, now), " · 4 tools · 42s");
     }
 
     #[test]
     fn test_color_for_elapsed_green() {
-        assert_eq!(color_for_elapsed(Duration::from_secs(0)), Color::Green);
-        assert_eq!(color_for_elapsed(Duration::from_secs(60)), Color::Green);
-        assert_eq!(color_for_elapsed(Duration::from_secs(299)), Color::Green);
+        let theme = Theme::default();
+        assert_eq!(
+            color_for_elapsed(Duration::from_secs(0), &theme),
+            theme.success
+        );
+        assert_eq!(
+            color_for_elapsed(Duration::from_secs(60), &theme),
+            theme.success
+        );
+        assert_eq!(
+            color_for_elapsed(Duration::from_secs(299), &theme),
+            theme.success
+        );
     }
 
     #[test]
     fn test_color_for_elapsed_yellow() {
-        assert_eq!(color_for_elapsed(Duration::from_secs(300)), Color::Yellow);
-        assert_eq!(color_for_elapsed(Duration::from_secs(900)), Color::Yellow);
-        assert_eq!(color_for_elapsed(Duration::from_secs(1799)), Color::Yellow);
+        let theme = Theme::default();
+        assert_eq!(
+            color_for_elapsed(Duration::from_secs(300), &theme),
+            theme.highlight
+        );
+        assert_eq!(
+            color_for_elapsed(Duration::from_secs(900), &theme),
+            theme.highlight
+        );
+        assert_eq!(
+            color_for_elapsed(Duration::from_secs(1799), &theme),
+            theme.highlight
+        );
     }
 
     #[test]
     fn test_color_for_elapsed_red() {
-        assert_eq!(color_for_elapsed(Duration::from_secs(1800)), Color::Red);
-        assert_eq!(color_for_elapsed(Duration::from_secs(3600)), Color::Red);
-        assert_eq!(color_for_elapsed(Duration::from_secs(86400)), Color::Red);
+        let theme = Theme::default();
+        assert_eq!(
+            color_for_elapsed(Duration::from_secs(1800), &theme),
+            theme.error
+        );
+        assert_eq!(
+            color_for_elapsed(Duration::from_secs(3600), &theme),
+            theme.error
+        );
+        assert_eq!(
+            color_for_elapsed(Duration::from_secs(86400), &theme),
+            theme.error
+        );
     }
 
     #[test]
     fn test_elapsed_time_color_recent() {
+        let theme = Theme::default();
         let time = SystemTime::now() - Duration::from_secs(10);
-        assert_eq!(elapsed_time_color(&time), Color::Green);
+        assert_eq!(elapsed_time_color(&time, &theme), theme.success);
     }
 
     #[test]
     fn test_elapsed_time_color_stale() {
+        let theme = Theme::default();
         let time = SystemTime::now() - Duration::from_secs(600);
-        assert_eq!(elapsed_time_color(&time), Color::Yellow);
+        assert_eq!(elapsed_time_color(&time, &theme), theme.highlight);
     }
 
     #[test]
     fn test_elapsed_time_color_very_stale() {
+        let theme = Theme::default();
         let time = SystemTime::now() - Duration::from_secs(3600);
-        assert_eq!(elapsed_time_color(&time), Color::Red);
+        assert_eq!(elapsed_time_color(&time, &theme), theme.error);
     }
 
     #[test]
     fn test_elapsed_time_color_future() {
+        let theme = Theme::default();
         let time = SystemTime::now() + Duration::from_secs(100);
-        assert_eq!(elapsed_time_color(&time), Color::Green);
+        assert_eq!(elapsed_time_color(&time, &theme), theme.success);
     }
 }
@@ -2,10 +2,13 @@ use ratatui::{
     buffer::Buffer,
     layout::Rect,
     style::{Color, Modifier, Style},
+    text::{Line, Span},
     widgets::{Block, Borders},
     Frame,
 };
 
+use crate::ui::theme::Theme;
+
 /// Convert a vt100::Color to a ratatui Color.
 fn convert_color(color: vt100::Color) -> Color {
     match color {
@@ -38,24 +41,43 @@ fn cell_style(cell: &vt100::Cell) -> Style {
     style
 }
 
-/// Render the embedded terminal pane.
+/// Tab bar shown as the pane title (e.g. ` 1 api │ 2 web `), with the
+/// shown tab highlighted.
+fn tab_bar(titles: &[&str], active: usize, color: Color, theme: &Theme) -> Line<'static> {
+    let mut spans = vec![Span::raw(" ")];
+    for (i, title) in titles.iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::styled(" │ ", Style::default().fg(theme.muted)));
+        }
+        let style = if i == active {
+            Style::default()
+                .fg(color)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED)
+        } else {
+            Style::default().fg(theme.muted)
+        };
+        spans.push(Span::styled(format!(" {} {} ", i + 1, title), style));
+    }
+    spans.push(Span::raw(" "));
+    Line::from(spans)
+}
+
+/// Render the embedded terminal pane with a tab bar of all terminal tabs.
 pub fn render_terminal_pane(
     f: &mut Frame,
     area: Rect,
     screen: &vt100::Screen,
     focused: bool,
-    title: &str,
+    titles: &[&str],
+    active: usize,
+    theme: &Theme,
 ) {
-    let border_color = if focused {
-        Color::Green
-    } else {
-        Color::DarkGray
-    };
+    let border_color = if focused { theme.success } else { theme.muted };
 
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(border_color))
-        .title(format!(" {} ", title));
+        .title(tab_bar(titles, active, theme.success, theme));
 
     let inner = block.inner(area);
     f.render_widget(block, area);
@@ -0,0 +1,133 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+};
+
+use crate::ui::app::TranscriptSearchState;
+use crate::ui::history_search;
+use crate::ui::theme::Theme;
+
+/// Render the cross-session transcript search popup: a query line above the
+/// matching turns (date, project, session and snippet).
+pub(super) fn render_transcript_search(
+    f: &mut ratatui::Frame,
+    area: Rect,
+    state: &mut TranscriptSearchState,
+    theme: &Theme,
+) {
+    let popup_width = (area.width * 80 / 100)
+        .max(40)
+        .min(area.width.saturating_sub(4));
+    let popup_height = (area.height * 70 / 100)
+        .max(8)
+        .min(area.height.saturating_sub(4));
+
+    let x = area.x + (area.width.saturating_sub(popup_width)) / 2;
+    let y = area.y + (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
+    f.render_widget(Clear, popup_area);
+
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Search All Transcripts ")
+        .border_style(Style::default().fg(theme.highlight));
+    let status = if state.searching {
+        Some(" Searching… ".to_string())
+    } else {
+        state
+            .results
+            .as_ref()
+            .map(|results| format!(" {} matching turns ", results.len()))
+    };
+    if let Some(status) = status {
+        block = block.title_bottom(Line::from(Span::styled(
+            status,
+            Style::default().fg(theme.muted),
+        )));
+    }
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(0)])
+        .split(inner);
+
+    let cursor = if state.editing { "▏" } else { "" };
+    let query = Paragraph::new(Line::from(vec![
+        Span::styled("> ", Style::default().fg(theme.accent)),
+        Span::styled(
+            format!("{}{}", state.query, cursor),
+            Style::default().fg(theme.highlight),
+        ),
+    ]));
+    f.render_widget(query, chunks[0]);
+
+    let results = match &state.results {
+        Some(results) if !results.is_empty() => results,
+        Some(_) => {
+            let empty = Paragraph::new(Span::styled(
+                "  No matching turns",
+                Style::default().fg(theme.muted),
+            ));
+            f.render_widget(empty, chunks[1]);
+            return;
+        }
+        None => {
+            let hint = if state.searching {
+                "  Searching ~/.claude/projects…"
+            } else {
+                "  Type a query and press Enter to search ~/.claude/projects"
+            };
+            let hint = Paragraph::new(Span::styled(hint, Style::default().fg(theme.muted)));
+            f.render_widget(hint, chunks[1]);
+            return;
+        }
+    };
+
+    let hit_style = history_search::hit_style(theme, false);
+    let items: Vec<ListItem> = results
+        .iter()
+        .map(|hit| {
+            let header = Line::from(vec![
+                Span::styled(
+                    format!("{}  ", hit.local_time()),
+                    Style::default().fg(theme.muted),
+                ),
+                Span::styled(
+                    hit.project_name().to_string(),
+                    Style::default().fg(theme.accent),
+                ),
+                Span::styled(
+                    format!("  {}", hit.session_id),
+                    Style::default().fg(theme.muted),
+                ),
+            ]);
+            let snippet = history_search::highlight_line(
+                &Line::styled(
+                    format!("  {}", hit.snippet),
+                    Style::default().fg(theme.text_secondary),
+                ),
+                &state.query,
+                hit_style,
+            );
+            ListItem::new(vec![header, snippet])
+        })
+        .collect();
+
+    let highlight = if state.editing {
+        Style::default()
+    } else {
+        Style::default()
+            .bg(theme.selection_bg)
+            .add_modifier(Modifier::BOLD)
+    };
+    let list = List::new(items)
+        .highlight_style(highlight)
+        .highlight_symbol(">> ");
+
+    f.render_stateful_widget(list, chunks[1], &mut state.list_state);
+}
@@ -0,0 +1,149 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+};
+use std::path::Path;
+
+use crate::ui::app::{WorktreeRow, WorktreesState};
+use crate::ui::theme::Theme;
+
+/// Render the worktree cleanup popup: one row per linked worktree with its
+/// branch, location and state (live session, merged, dirty).
+pub(super) fn render_worktrees(
+    f: &mut ratatui::Frame,
+    area: Rect,
+    state: &mut WorktreesState,
+    theme: &Theme,
+) {
+    let popup_width = (area.width * 80 / 100)
+        .max(40)
+        .min(area.width.saturating_sub(4));
+    let popup_height = (area.height * 70 / 100)
+        .max(8)
+        .min(area.height.saturating_sub(4));
+
+    let x = area.x + (area.width.saturating_sub(popup_width)) / 2;
+    let y = area.y + (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
+    f.render_widget(Clear, popup_area);
+
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Worktrees ")
+        .border_style(Style::default().fg(theme.highlight));
+    let confirm = state
+        .confirm
+        .as_ref()
+        .and_then(|c| state.rows.get(c.index).map(|row| (row, c.delete_branch)));
+    block = block.title_bottom(match confirm {
+        Some((row, delete_branch)) => confirm_line(row, delete_branch, theme),
+        None if state.loading => {
+            Line::from(Span::styled(" Loading… ", Style::default().fg(theme.muted)))
+        }
+        None => Line::from(Span::styled(
+            format!(" {} linked worktrees ", state.rows.len()),
+            Style::default().fg(theme.muted),
+        )),
+    });
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    if state.rows.is_empty() {
+        let text = if state.loading {
+            "  Loading worktrees…"
+        } else {
+            "  No linked worktrees in the repositories of your sessions"
+        };
+        let empty = Paragraph::new(Span::styled(text, Style::default().fg(theme.muted)));
+        f.render_widget(empty, inner);
+        return;
+    }
+
+    let items: Vec<ListItem> = state
+        .rows
+        .iter()
+        .map(|row| {
+            let branch = row.worktree.branch.as_deref().unwrap_or("(detached)");
+            let mut header = vec![Span::styled(
+                branch.to_string(),
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+            )];
+            if let Some(pane_id) = row.session_pane {
+                header.push(Span::styled(
+                    format!("  ● Pane {}", pane_id),
+                    Style::default().fg(theme.success),
+                ));
+            }
+            if row.worktree.prunable {
+                header.push(Span::styled("  missing", Style::default().fg(theme.error)));
+            } else if row.dirty {
+                header.push(Span::styled(
+                    "  dirty",
+                    Style::default().fg(theme.attention),
+                ));
+            }
+            if row.worktree.branch.is_some() {
+                header.push(if row.merged {
+                    Span::styled("  merged", Style::default().fg(theme.success))
+                } else {
+                    Span::styled("  unmerged", Style::default().fg(theme.muted))
+                });
+            }
+            let location = Line::from(Span::styled(
+                format!("  {}", display_path(row)),
+                Style::default().fg(theme.text_secondary),
+            ));
+            ListItem::new(vec![Line::from(header), location])
+        })
+        .collect();
+
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(theme.selection_bg)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(">> ");
+
+    f.render_stateful_widget(list, inner, &mut state.list_state);
+}
+
+/// Worktree path as `<repo>/<path in repo>`, or absolute if outside the repo.
+fn display_path(row: &WorktreeRow) -> String {
+    let root = Path::new(&row.repo_root);
+    let repo = root.file_name().unwrap_or_default().to_string_lossy();
+    match row.worktree.path.strip_prefix(root) {
+        Ok(rel) => format!("{}/{}", repo, rel.display()),
+        Err(_) => row.worktree.path.display().to_string(),
+    }
+}
+
+/// Confirmation prompt for a pending removal, with what would be lost.
+fn confirm_line(row: &WorktreeRow, delete_branch: bool, theme: &Theme) -> Line<'static> {
+    let mut text = format!(" Remove {}", display_path(row));
+    if row.dirty {
+        text.push_str(" (uncommitted changes will be lost)");
+    }
+    if let (true, Some(branch)) = (delete_branch, &row.worktree.branch) {
+        text.push_str(&format!(" and delete branch {}", branch));
+        if !row.merged {
+            text.push_str(" (not merged)");
+        }
+    }
+    text.push_str("? ");
+    Line::from(vec![
+        Span::styled(text, Style::default().fg(theme.error)),
+        Span::styled(
+            "[y]",
+            Style::default()
+                .fg(theme.error)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw("es / "),
+        Span::styled("[any]", Style::default().fg(theme.accent)),
+        Span::raw("cancel "),
+    ])
+}
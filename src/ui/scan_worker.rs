@@ -0,0 +1,228 @@
+//! Background scans that read many files or run many git commands.
+//!
+//! Searching and aggregating usage over every transcript under
+//! `~/.claude/projects`, and inspecting the linked worktrees of every
+//! repository, run on a worker thread which publishes the results to the UI
+//! over a channel. Requests queued while the worker is busy are coalesced
+//! into the latest one of each kind, and a new request cancels the scan of
+//! the same kind in progress.
+
+use crate::datasource::git::{
+    get_default_branch, get_git_status, is_branch_merged, list_worktrees, Worktree,
+};
+use crate::transcript::{
+    collect_usage_stats, projects_dir, search_transcripts, SearchHit, UsageStats,
+};
+use chrono::NaiveDate;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+
+/// Work for the scan worker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanRequest {
+    /// Search the prompts and responses of every transcript.
+    Search { query: String, limit: usize },
+    /// Aggregate usage from `since` (local date) on (None: all history).
+    Stats { since: Option<NaiveDate> },
+    /// List the linked worktrees of these repositories (main worktree roots).
+    Worktrees { repo_roots: Vec<String> },
+}
+
+/// Kinds of scans, each cancelled independently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanKind {
+    Search,
+    Stats,
+    Worktrees,
+}
+
+impl ScanKind {
+    const COUNT: usize = 3;
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl ScanRequest {
+    pub fn kind(&self) -> ScanKind {
+        match self {
+            Self::Search { .. } => ScanKind::Search,
+            Self::Stats { .. } => ScanKind::Stats,
+            Self::Worktrees { .. } => ScanKind::Worktrees,
+        }
+    }
+}
+
+/// A linked worktree and its state, as shown by the worktree cleanup popup.
+pub struct WorktreeStatus {
+    /// Main worktree root of the repository
+    pub repo_root: String,
+    pub worktree: Worktree,
+    /// Whether the branch is merged into the repository's default branch
+    pub merged: bool,
+    /// Whether the worktree has uncommitted changes or untracked files
+    pub dirty: bool,
+}
+
+/// Result of a scan, published to the UI.
+pub enum ScanUpdate {
+    /// Hits for `query`, newest first, or why the search failed.
+    Search {
+        query: String,
+        hits: Result<Vec<SearchHit>, String>,
+    },
+    /// Usage from `since` on, or why it couldn't be read.
+    Stats {
+        since: Option<NaiveDate>,
+        stats: Result<UsageStats, String>,
+    },
+    /// Linked worktrees, and the repositories whose worktrees couldn't be
+    /// listed.
+    Worktrees {
+        worktrees: Vec<WorktreeStatus>,
+        errors: Vec<String>,
+    },
+}
+
+/// Cancel flags, one per `ScanKind`.
+type CancelFlags = [AtomicBool; ScanKind::COUNT];
+
+/// Handle to the scan worker thread. Dropping it stops the worker.
+pub struct ScanWorker {
+    tx: Sender<ScanRequest>,
+    rx: Receiver<ScanUpdate>,
+    /// Tell the worker to abandon the scan of a kind in progress
+    cancel: Arc<CancelFlags>,
+}
+
+impl ScanWorker {
+    /// Spawn the worker.
+    pub fn spawn() -> Self {
+        let (request_tx, request_rx) = mpsc::channel();
+        let (update_tx, update_rx) = mpsc::channel();
+        let cancel: Arc<CancelFlags> = Arc::new(Default::default());
+
+        let worker_cancel = cancel.clone();
+        std::thread::spawn(move || {
+            run(&request_rx, &update_tx, &worker_cancel);
+        });
+
+        Self {
+            tx: request_tx,
+            rx: update_rx,
+            cancel,
+        }
+    }
+
+    /// Queue a scan, cancelling the one of the same kind in progress.
+    pub fn request(&self, request: ScanRequest) {
+        // Cancel before sending, so a cancelled worker always finds the new request
+        self.cancel(request.kind());
+        let _ = self.tx.send(request);
+    }
+
+    /// Abandon the scan of `kind` in progress; its result is not published.
+    pub fn cancel(&self, kind: ScanKind) {
+        self.cancel[kind.index()].store(true, Ordering::Relaxed);
+    }
+
+    /// Published updates, oldest first, without blocking.
+    pub fn updates(&self) -> Vec<ScanUpdate> {
+        self.rx.try_iter().collect()
+    }
+}
+
+impl Drop for ScanWorker {
+    fn drop(&mut self) {
+        // The request channel closes with `tx`, ending the worker loop
+        for flag in self.cancel.iter() {
+            flag.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Worker loop: run requests until the UI drops its handle.
+fn run(requests: &Receiver<ScanRequest>, updates: &Sender<ScanUpdate>, cancel: &CancelFlags) {
+    let mut pending: Vec<ScanRequest> = Vec::new();
+    loop {
+        if pending.is_empty() {
+            match requests.recv() {
+                Ok(request) => pending.push(request),
+                Err(_) => break,
+            }
+        }
+        // Only the latest request of each kind matters
+        for request in requests.try_iter() {
+            pending.retain(|p| p.kind() != request.kind());
+            pending.push(request);
+        }
+        let request = pending.remove(0);
+        let cancel = &cancel[request.kind().index()];
+        cancel.store(false, Ordering::Relaxed);
+
+        let update = match request {
+            ScanRequest::Search { query, limit } => {
+                let hits = match projects_dir() {
+                    Some(dir) => {
+                        search_transcripts(&dir, &query, limit, cancel).map_err(|e| e.to_string())
+                    }
+                    None => Err("could not determine home directory".to_string()),
+                };
+                ScanUpdate::Search { query, hits }
+            }
+            ScanRequest::Stats { since } => {
+                let stats = match projects_dir() {
+                    Some(dir) => {
+                        collect_usage_stats(&dir, since, cancel).map_err(|e| e.to_string())
+                    }
+                    None => Err("could not determine home directory".to_string()),
+                };
+                ScanUpdate::Stats { since, stats }
+            }
+            ScanRequest::Worktrees { repo_roots } => scan_worktrees(&repo_roots, cancel),
+        };
+
+        if cancel.load(Ordering::Relaxed) {
+            continue;
+        }
+        if updates.send(update).is_err() {
+            break;
+        }
+    }
+}
+
+/// List the linked worktrees of `repo_roots` with their merged/dirty state.
+fn scan_worktrees(repo_roots: &[String], cancel: &AtomicBool) -> ScanUpdate {
+    let mut worktrees = Vec::new();
+    let mut errors = Vec::new();
+    for repo_root in repo_roots {
+        if cancel.load(Ordering::Relaxed) {
+            break;
+        }
+        let listed = match list_worktrees(repo_root) {
+            Ok(listed) => listed,
+            Err(e) => {
+                errors.push(format!("{}: {}", repo_root, e));
+                continue;
+            }
+        };
+        let base = get_default_branch(repo_root);
+        for worktree in listed {
+            let merged = worktree
+                .branch
+                .as_deref()
+                .is_some_and(|b| is_branch_merged(repo_root, b, &base));
+            let dirty = !worktree.prunable
+                && get_git_status(&worktree.path.to_string_lossy()).is_some_and(|s| s.is_dirty());
+            worktrees.push(WorktreeStatus {
+                repo_root: repo_root.clone(),
+                worktree,
+                merged,
+                dirty,
+            });
+        }
+    }
+    ScanUpdate::Worktrees { worktrees, errors }
+}
@@ -1,30 +1,81 @@
-use crate::detector::DetectionReason;
-use crate::models::Pane;
-use crate::transcript::{SessionStatus, WaitingPrompt};
+use crate::config::GroupBy;
+use crate::datasource::git::GitStatus;
+use crate::session_meta::SessionMeta;
+use crate::transcript::{format_duration, SessionStatus};
+use crate::ui::theme::Theme;
 use ratatui::{
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
 };
-use std::path::PathBuf;
-use std::time::SystemTime;
 use unicode_width::UnicodeWidthChar;
 
+pub use crate::discovery::ClaudeSession;
+
 /// Get display color and text for a SessionStatus.
-pub fn status_display(status: &SessionStatus) -> (Color, String) {
-    match status {
-        SessionStatus::Ready => (Color::Cyan, "Ready".to_string()),
-        SessionStatus::Processing => (Color::Yellow, "Processing".to_string()),
-        SessionStatus::Idle => (Color::Green, "Idle".to_string()),
+pub fn status_display(status: &SessionStatus, theme: &Theme) -> (Color, String) {
+    let text = match status {
+        SessionStatus::Ready => "Ready".to_string(),
+        SessionStatus::Processing => "Processing".to_string(),
+        SessionStatus::RunningTool { name, elapsed } => {
+            format!("Running {} ({})", name, format_duration(*elapsed, 1))
+        }
+        SessionStatus::Idle => "Idle".to_string(),
         SessionStatus::WaitingForUser { tools } => {
-            let text = if tools.is_empty() {
+            if tools.is_empty() {
                 "Approval".to_string()
             } else {
                 format!("Approval ({})", tools.join(", "))
-            };
-            (Color::Magenta, text)
+            }
         }
-        SessionStatus::Unknown => (Color::DarkGray, "Unknown".to_string()),
+        SessionStatus::SubagentRunning { description } => match description {
+            Some(description) => format!("Subagent ({})", description),
+            None => "Subagent".to_string(),
+        },
+        SessionStatus::Compacting => "Compacting".to_string(),
+        SessionStatus::RateLimited { message } => format!("Rate limited ({})", message),
+        SessionStatus::Errored { message } => format!("Error ({})", message),
+        SessionStatus::Unknown => "Unknown".to_string(),
+    };
+    (theme.status_color(status), text)
+}
+
+/// Spans for user-assigned session metadata: pin marker, colored label and
+/// `#tags`. Empty when no metadata is set.
+pub fn meta_spans(meta: &SessionMeta, theme: &Theme) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    if meta.pinned {
+        spans.push(Span::raw("📌 "));
+    }
+    if let Some(label) = &meta.label {
+        spans.push(Span::styled(
+            label.clone(),
+            Style::default()
+                .fg(theme.label_color(meta.color.as_deref()))
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    for tag in &meta.tags {
+        spans.push(Span::styled(
+            format!(" #{}", tag),
+            Style::default().fg(theme.muted),
+        ));
     }
+    spans
+}
+
+/// Compact git status span (e.g. `+1 !2 ?3 ⇡1`), highlighted when there are
+/// uncommitted changes. None for a clean tree in sync with its upstream.
+pub fn git_status_span(status: &GitStatus, theme: &Theme) -> Option<Span<'static>> {
+    let summary = status.summary();
+    if summary.is_empty() {
+        return None;
+    }
+    let color = if status.is_dirty() {
+        theme.attention
+    } else {
+        theme.muted
+    };
+    Some(Span::styled(summary, Style::default().fg(color)))
 }
 
 /// Wrap text into lines with a given display width.
@@ -146,33 +197,80 @@ mod tests {
             lines.len()
         );
     }
+
+    #[test]
+    fn test_first_new_header_level() {
+        let ws = |s: &str| GroupHeader::Workspace(s.to_string());
+        let cwd = |s: &str| GroupHeader::Cwd(s.to_string());
+
+        assert_eq!(first_new_header_level(None, &[ws("a"), cwd("/x")]), 0);
+        assert_eq!(
+            first_new_header_level(Some(&[ws("a"), cwd("/x")]), &[ws("a"), cwd("/x")]),
+            2
+        );
+        assert_eq!(
+            first_new_header_level(Some(&[ws("a"), cwd("/x")]), &[ws("a"), cwd("/y")]),
+            1
+        );
+        assert_eq!(
+            first_new_header_level(Some(&[ws("a"), cwd("/x")]), &[ws("b"), cwd("/x")]),
+            0
+        );
+        // Flat grouping: no headers at all
+        assert_eq!(first_new_header_level(Some(&[]), &[]), 0);
+        assert_eq!(first_new_header_level(None, &[]), 0);
+    }
 }
 
-/// Claude Code session information
-#[derive(Debug, Clone)]
-pub struct ClaudeSession {
-    pub pane: Pane,
-    pub detected: bool,
-    #[allow(dead_code)]
-    pub reason: DetectionReason,
-    /// Session status (Processing/Idle/WaitingForUser/Unknown)
-    pub status: SessionStatus,
-    /// Git branch name
-    pub git_branch: Option<String>,
-    /// Git worktree relative path (only set for linked worktrees)
-    pub git_worktree: Option<String>,
-    /// Last user prompt (from transcript)
-    pub last_prompt: Option<String>,
-    /// Last assistant output text (from transcript)
-    pub last_output: Option<String>,
-    /// Session ID from statusLine bridge (if available)
-    pub session_id: Option<String>,
-    /// Transcript path from statusLine bridge (if available)
-    pub transcript_path: Option<PathBuf>,
-    /// Last updated time (from transcript file modification time)
-    pub updated_at: Option<SystemTime>,
-    /// Warning message to display in details
-    pub warning: Option<String>,
-    /// Parsed waiting prompt data when status is WaitingForUser.
-    pub waiting_prompt: Option<WaitingPrompt>,
+/// A group header level shown above sessions in the session list.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GroupHeader {
+    /// Pinned sessions (always listed first).
+    Pinned,
+    /// Workspace name.
+    Workspace(String),
+    /// Working directory (nested under a workspace header).
+    Cwd(String),
+    /// Git repository root (`None` for sessions outside a repository).
+    Repo(Option<String>),
+    /// Detected agent name.
+    Agent(String),
+}
+
+impl ClaudeSession {
+    /// Rows the session takes up in the session list.
+    pub fn list_rows(&self) -> usize {
+        1 + self.running_subagents().count()
+    }
+
+    /// Group header levels for this session (outermost first) under `group_by`.
+    /// Pinned sessions share a single "Pinned" header unless the list is flat.
+    pub fn group_headers(&self, group_by: GroupBy) -> Vec<GroupHeader> {
+        if self.meta.pinned && group_by != GroupBy::Flat {
+            return vec![GroupHeader::Pinned];
+        }
+        match group_by {
+            GroupBy::Workspace => vec![
+                GroupHeader::Workspace(self.pane.workspace.clone()),
+                GroupHeader::Cwd(self.pane.cwd_path().unwrap_or_default()),
+            ],
+            GroupBy::Repo => vec![GroupHeader::Repo(self.git_repo_root.clone())],
+            GroupBy::Agent => vec![GroupHeader::Agent(self.reason.agent_name())],
+            GroupBy::Flat => Vec::new(),
+        }
+    }
+}
+
+/// Index of the first header level that differs from the previous session's
+/// headers. Header rows are emitted from this level down; a value equal to
+/// `current.len()` means no new header rows are needed.
+pub fn first_new_header_level(previous: Option<&[GroupHeader]>, current: &[GroupHeader]) -> usize {
+    match previous {
+        None => 0,
+        Some(prev) => current
+            .iter()
+            .zip(prev.iter())
+            .position(|(c, p)| c != p)
+            .unwrap_or(current.len().min(prev.len())),
+    }
 }
@@ -2,12 +2,14 @@ use crate::pty::PtyHandle;
 use ratatui::layout::Rect;
 use std::path::PathBuf;
 
-/// An embedded terminal session, independent from sidebar selection.
+/// An embedded terminal session (one tab), independent from sidebar selection.
 pub struct TerminalSession {
     /// Working directory where the session was started.
     pub cwd: PathBuf,
-    /// Display title for the terminal.
+    /// Display title for the terminal (shown in the tab bar).
     pub title: String,
+    /// Claude Code session resumed in the terminal (`--resume`), if any.
+    pub session_id: Option<String>,
     /// PTY handle for the child process.
     pub pty_handle: PtyHandle,
     /// Terminal state machine (screen buffer + parser).
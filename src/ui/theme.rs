@@ -0,0 +1,351 @@
+//! Color themes: semantic colors used by all rendering code, built-in
+//! dark/light/high-contrast palettes, and `[theme]` overrides from config.toml.
+//!
+//! Colors in config are written as names (`"cyan"`, `"darkgray"`,
+//! `"light-red"`), 256-color indices (`"244"`) or hex (`"#5f87af"`).
+
+use crate::config::{ThemeConfig, ThemeName};
+use crate::session_meta::LABEL_COLORS;
+use crate::transcript::{SessionStatus, StatusKind};
+use anyhow::{bail, Result};
+use ratatui::style::Color;
+
+/// Semantic colors used across the UI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    /// Built-in theme this was built from
+    pub name: ThemeName,
+    /// Primary text (quick-select numbers, prompts being edited)
+    pub text: Color,
+    /// Body text (markdown output, previews)
+    pub text_secondary: Color,
+    /// De-emphasized text: separators, group headers, paths, hints
+    pub muted: Color,
+    /// Background of the selected row in lists and popups
+    pub selection_bg: Color,
+    /// Background of inline code spans
+    pub code_bg: Color,
+    /// Key hints, branches, prompt prefixes, focused borders
+    pub accent: Color,
+    /// Workspace names, filter query, history/live views, inline code
+    pub highlight: Color,
+    /// Success toasts, worktrees, fresh activity
+    pub success: Color,
+    /// Errors, kill confirmation, stale activity
+    pub error: Color,
+    /// Sessions waiting for the user (approval, answers)
+    pub attention: Color,
+    pub status_ready: Color,
+    pub status_processing: Color,
+    pub status_idle: Color,
+    pub status_waiting: Color,
+    pub status_unknown: Color,
+    pub status_subagent: Color,
+    pub status_compacting: Color,
+    pub status_rate_limited: Color,
+    pub status_error: Color,
+    /// Session label colors, in `LABEL_COLORS` order
+    pub labels: [Color; 6],
+    /// Syntect theme used to highlight code blocks
+    pub syntax_theme: String,
+}
+
+/// Override keys accepted in `[theme.colors]` (plus `label_<color>`).
+pub const COLOR_ROLES: [&str; 19] = [
+    "text",
+    "text_secondary",
+    "muted",
+    "selection_bg",
+    "code_bg",
+    "accent",
+    "highlight",
+    "success",
+    "error",
+    "attention",
+    "status_ready",
+    "status_processing",
+    "status_idle",
+    "status_waiting",
+    "status_unknown",
+    "status_subagent",
+    "status_compacting",
+    "status_rate_limited",
+    "status_error",
+];
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::builtin(ThemeName::Dark)
+    }
+}
+
+impl Theme {
+    /// One of the built-in palettes.
+    pub fn builtin(name: ThemeName) -> Self {
+        match name {
+            ThemeName::Dark => Self {
+                name,
+                text: Color::White,
+                text_secondary: Color::Gray,
+                muted: Color::DarkGray,
+                selection_bg: Color::DarkGray,
+                code_bg: Color::DarkGray,
+                accent: Color::Cyan,
+                highlight: Color::Yellow,
+                success: Color::Green,
+                error: Color::Red,
+                attention: Color::Magenta,
+                status_ready: Color::Cyan,
+                status_processing: Color::Yellow,
+                status_idle: Color::Green,
+                status_waiting: Color::Magenta,
+                status_unknown: Color::DarkGray,
+                status_subagent: Color::Blue,
+                status_compacting: Color::LightBlue,
+                status_rate_limited: Color::LightRed,
+                status_error: Color::Red,
+                labels: [
+                    Color::Red,
+                    Color::Green,
+                    Color::Yellow,
+                    Color::Blue,
+                    Color::Magenta,
+                    Color::Cyan,
+                ],
+                syntax_theme: "base16-ocean.dark".to_string(),
+            },
+            // 256-color indices: the 16 ANSI colors are remapped by many
+            // light terminal schemes, and yellow/cyan are unreadable on white.
+            ThemeName::Light => Self {
+                name,
+                text: Color::Black,
+                text_secondary: Color::Indexed(237),
+                muted: Color::Indexed(243),
+                selection_bg: Color::Indexed(252),
+                code_bg: Color::Indexed(254),
+                accent: Color::Indexed(25),
+                highlight: Color::Indexed(130),
+                success: Color::Indexed(28),
+                error: Color::Indexed(160),
+                attention: Color::Indexed(127),
+                status_ready: Color::Indexed(31),
+                status_processing: Color::Indexed(130),
+                status_idle: Color::Indexed(28),
+                status_waiting: Color::Indexed(127),
+                status_unknown: Color::Indexed(243),
+                status_subagent: Color::Indexed(25),
+                status_compacting: Color::Indexed(67),
+                status_rate_limited: Color::Indexed(166),
+                status_error: Color::Indexed(160),
+                labels: [
+                    Color::Indexed(160),
+                    Color::Indexed(28),
+                    Color::Indexed(136),
+                    Color::Indexed(25),
+                    Color::Indexed(127),
+                    Color::Indexed(31),
+                ],
+                syntax_theme: "InspiredGitHub".to_string(),
+            },
+            ThemeName::HighContrast => Self {
+                name,
+                text: Color::White,
+                text_secondary: Color::White,
+                muted: Color::Gray,
+                selection_bg: Color::Blue,
+                code_bg: Color::Black,
+                accent: Color::LightCyan,
+                highlight: Color::LightYellow,
+                success: Color::LightGreen,
+                error: Color::LightRed,
+                attention: Color::LightMagenta,
+                status_ready: Color::LightCyan,
+                status_processing: Color::LightYellow,
+                status_idle: Color::LightGreen,
+                status_waiting: Color::LightMagenta,
+                status_unknown: Color::Gray,
+                status_subagent: Color::LightBlue,
+                status_compacting: Color::White,
+                status_rate_limited: Color::Indexed(208),
+                status_error: Color::LightRed,
+                labels: [
+                    Color::LightRed,
+                    Color::LightGreen,
+                    Color::LightYellow,
+                    Color::LightBlue,
+                    Color::LightMagenta,
+                    Color::LightCyan,
+                ],
+                syntax_theme: "base16-eighties.dark".to_string(),
+            },
+        }
+    }
+
+    /// Build the theme from `[theme]` in config.toml: the named built-in
+    /// palette with `syntax_theme` and `[theme.colors]` overrides applied.
+    ///
+    /// Returns Err for unknown roles, unparsable colors or an unknown
+    /// syntax theme.
+    pub fn from_config(config: &ThemeConfig) -> Result<Self> {
+        let mut theme = Self::builtin(config.name);
+
+        if let Some(name) = &config.syntax_theme {
+            let available = crate::ui::markdown::syntax_theme_names();
+            if !available.contains(name) {
+                bail!(
+                    "unknown syntax_theme '{}' in [theme] (available: {})",
+                    name,
+                    available.join(", ")
+                );
+            }
+            theme.syntax_theme = name.clone();
+        }
+
+        for (role, value) in &config.colors {
+            let color: Color = value.parse().map_err(|_| {
+                anyhow::anyhow!("[theme.colors] {}: invalid color '{}'", role, value)
+            })?;
+            match theme.role_mut(role) {
+                Some(slot) => *slot = color,
+                None => bail!(
+                    "unknown color '{}' in [theme.colors] (expected {} or label_<color>)",
+                    role,
+                    COLOR_ROLES.join(", ")
+                ),
+            }
+        }
+
+        Ok(theme)
+    }
+
+    fn role_mut(&mut self, role: &str) -> Option<&mut Color> {
+        if let Some(label) = role.strip_prefix("label_") {
+            let i = LABEL_COLORS.iter().position(|c| *c == label)?;
+            return Some(&mut self.labels[i]);
+        }
+        Some(match role {
+            "text" => &mut self.text,
+            "text_secondary" => &mut self.text_secondary,
+            "muted" => &mut self.muted,
+            "selection_bg" => &mut self.selection_bg,
+            "code_bg" => &mut self.code_bg,
+            "accent" => &mut self.accent,
+            "highlight" => &mut self.highlight,
+            "success" => &mut self.success,
+            "error" => &mut self.error,
+            "attention" => &mut self.attention,
+            "status_ready" => &mut self.status_ready,
+            "status_processing" => &mut self.status_processing,
+            "status_idle" => &mut self.status_idle,
+            "status_waiting" => &mut self.status_waiting,
+            "status_unknown" => &mut self.status_unknown,
+            "status_subagent" => &mut self.status_subagent,
+            "status_compacting" => &mut self.status_compacting,
+            "status_rate_limited" => &mut self.status_rate_limited,
+            "status_error" => &mut self.status_error,
+            _ => return None,
+        })
+    }
+
+    /// Color for a session status.
+    pub fn status_color(&self, status: &SessionStatus) -> Color {
+        self.status_kind_color(status.kind())
+    }
+
+    /// Color for a session status kind (status timelines).
+    pub fn status_kind_color(&self, kind: StatusKind) -> Color {
+        match kind {
+            StatusKind::Ready => self.status_ready,
+            StatusKind::Processing | StatusKind::RunningTool => self.status_processing,
+            StatusKind::Idle => self.status_idle,
+            StatusKind::Waiting => self.status_waiting,
+            StatusKind::Unknown => self.status_unknown,
+            StatusKind::Subagent => self.status_subagent,
+            StatusKind::Compacting => self.status_compacting,
+            StatusKind::RateLimited => self.status_rate_limited,
+            StatusKind::Errored => self.status_error,
+        }
+    }
+
+    /// Color for a session label color name (unknown or unset: `text`).
+    pub fn label_color(&self, name: Option<&str>) -> Color {
+        name.and_then(|n| LABEL_COLORS.iter().position(|c| *c == n))
+            .map(|i| self.labels[i])
+            .unwrap_or(self.text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn theme_config(toml_str: &str) -> ThemeConfig {
+        toml::from_str(toml_str).unwrap()
+    }
+
+    #[test]
+    fn test_dark_is_default() {
+        let theme = Theme::from_config(&ThemeConfig::default()).unwrap();
+        assert_eq!(theme, Theme::default());
+        assert_eq!(theme.muted, Color::DarkGray);
+        assert_eq!(theme.syntax_theme, "base16-ocean.dark");
+    }
+
+    #[test]
+    fn test_builtin_syntax_themes_exist() {
+        let available = crate::ui::markdown::syntax_theme_names();
+        for name in ThemeName::ALL {
+            assert!(available.contains(&Theme::builtin(name).syntax_theme));
+        }
+    }
+
+    #[test]
+    fn test_color_overrides() {
+        let theme = Theme::from_config(&theme_config(
+            r##"
+name = "light"
+[colors]
+accent = "blue"
+muted = "#808080"
+status_idle = "34"
+label_yellow = "light-yellow"
+"##,
+        ))
+        .unwrap();
+        assert_eq!(theme.name, ThemeName::Light);
+        assert_eq!(theme.accent, Color::Blue);
+        assert_eq!(theme.muted, Color::Rgb(0x80, 0x80, 0x80));
+        assert_eq!(theme.status_idle, Color::Indexed(34));
+        assert_eq!(theme.label_color(Some("yellow")), Color::LightYellow);
+        // Untouched roles keep the built-in value
+        assert_eq!(theme.error, Theme::builtin(ThemeName::Light).error);
+    }
+
+    #[test]
+    fn test_every_role_is_overridable() {
+        let mut theme = Theme::default();
+        for role in COLOR_ROLES {
+            assert!(theme.role_mut(role).is_some(), "{}", role);
+        }
+        for color in LABEL_COLORS {
+            assert!(theme.role_mut(&format!("label_{}", color)).is_some());
+        }
+    }
+
+    #[test]
+    fn test_invalid_overrides_error() {
+        assert!(Theme::from_config(&theme_config("[colors]\naccnt = \"blue\"\n")).is_err());
+        assert!(Theme::from_config(&theme_config("[colors]\nlabel_orange = \"red\"\n")).is_err());
+        assert!(Theme::from_config(&theme_config("[colors]\naccent = \"bleu\"\n")).is_err());
+        assert!(Theme::from_config(&theme_config("syntax_theme = \"nope\"\n")).is_err());
+        assert!(Theme::from_config(&theme_config("syntax_theme = \"InspiredGitHub\"\n")).is_ok());
+    }
+
+    #[test]
+    fn test_label_color_falls_back_to_text() {
+        let theme = Theme::builtin(ThemeName::Light);
+        assert_eq!(theme.label_color(None), Color::Black);
+        assert_eq!(theme.label_color(Some("chartreuse")), Color::Black);
+        assert_eq!(theme.label_color(Some("red")), Color::Indexed(160));
+    }
+}